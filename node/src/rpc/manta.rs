@@ -28,6 +28,10 @@ use pallet_manta_sbt::{
     rpc::{SBTPull, SBTPullApiServer},
     runtime::SBTPullLedgerDiffApi,
 };
+use pallet_name_service::{
+    rpc::{NameService, NameServiceRpcServer},
+    runtime::NameServiceApi,
+};
 use zenlink_protocol::AssetId as ZenlinkAssetId;
 use zenlink_protocol_rpc::{ZenlinkProtocol, ZenlinkProtocolApiServer};
 use zenlink_protocol_runtime_api::ZenlinkProtocolApi as ZenlinkProtocolRuntimeApi;
@@ -46,7 +50,8 @@ where
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BlockBuilder<Block>,
     C::Api: SBTPullLedgerDiffApi<Block>,
-    C::Api: LotteryApi<Block>,
+    C::Api: LotteryApi<Block, AccountId>,
+    C::Api: NameServiceApi<Block, AccountId>,
     C::Api: FarmingRuntimeApi<Block, AccountId, MantaAssetId, PoolId>,
     C::Api: ZenlinkProtocolRuntimeApi<Block, AccountId, ZenlinkAssetId>,
     P: TransactionPool + Sync + Send + 'static,
@@ -83,7 +88,11 @@ where
         .map_err(|e| sc_service::Error::Other(e.to_string()))?;
 
     module
-        .merge(Lottery::new(client).into_rpc())
+        .merge(Lottery::new(client.clone()).into_rpc())
+        .map_err(|e| sc_service::Error::Other(e.to_string()))?;
+
+    module
+        .merge(NameService::new(client).into_rpc())
         .map_err(|e| sc_service::Error::Other(e.to_string()))?;
 
     Ok(module)
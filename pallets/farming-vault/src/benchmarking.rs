@@ -0,0 +1,126 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{Pallet as FarmingVault, *};
+use frame_benchmarking::{benchmarks, vec, whitelisted_caller};
+use frame_support::{assert_ok, sp_runtime::traits::UniqueSaturatedFrom};
+use frame_system::{Pallet as System, RawOrigin};
+use pallet_farming::{EmissionCurve, Pallet as Farming};
+
+pub const LIQUIDITY: u128 = 1_000_000_000_000_000_000u128;
+pub const REWARD_PER_BLOCK: u128 = 1_000_000_000_000u128;
+
+/// Creates a farming pool over a new LP token, rewarded in the first asset of the pair.
+fn create_pool<T: Config>(caller: &T::AccountId) -> PoolId {
+    let liquidity = BalanceOf::<T>::unique_saturated_from(LIQUIDITY);
+    let lp_token = T::BenchmarkHelper::create_lp_pair(caller, liquidity);
+    let (asset_0, _) = T::LiquidityPool::lp_pair(lp_token).expect("the LP pair is created");
+
+    let pool_id = Farming::<T>::pool_next_id();
+    assert_ok!(Farming::<T>::create_farming_pool(
+        RawOrigin::Root.into(),
+        vec![(lp_token, Perbill::from_percent(100))],
+        vec![(
+            asset_0,
+            BalanceOf::<T>::unique_saturated_from(REWARD_PER_BLOCK)
+        )],
+        None,
+        Zero::zero(),
+        Zero::zero(),
+        Zero::zero(),
+        Zero::zero(),
+        5,
+        None,
+        EmissionCurve::Constant
+    ));
+    assert_ok!(T::MultiCurrency::deposit(asset_0, caller, liquidity));
+    assert_ok!(Farming::<T>::charge(
+        RawOrigin::Signed(caller.clone()).into(),
+        pool_id,
+        vec![(asset_0, liquidity)]
+    ));
+    pool_id
+}
+
+/// Creates a vault and deposits half of the LP tokens of `caller` into it.
+fn create_vault<T: Config>(caller: &T::AccountId) -> VaultId {
+    let pool_id = create_pool::<T>(caller);
+    let vault_id = FarmingVault::<T>::next_vault_id();
+    assert_ok!(FarmingVault::<T>::create_vault(
+        RawOrigin::Root.into(),
+        pool_id
+    ));
+    let lp_token = FarmingVault::<T>::vaults(vault_id)
+        .expect("the vault is created")
+        .lp_token;
+    let lp_amount = T::MultiCurrency::free_balance(lp_token, caller) / 2u32.into();
+    assert_ok!(FarmingVault::<T>::deposit(
+        RawOrigin::Signed(caller.clone()).into(),
+        vault_id,
+        lp_amount
+    ));
+    vault_id
+}
+
+benchmarks! {
+    create_vault {
+        let caller: T::AccountId = whitelisted_caller();
+        let pool_id = create_pool::<T>(&caller);
+        let vault_id = FarmingVault::<T>::next_vault_id();
+    }: _(RawOrigin::Root, pool_id)
+    verify {
+        assert!(Vaults::<T>::contains_key(vault_id));
+    }
+
+    deposit {
+        let caller: T::AccountId = whitelisted_caller();
+        let vault_id = create_vault::<T>(&caller);
+        let vault = FarmingVault::<T>::vaults(vault_id).expect("the vault is created");
+        let lp_amount = T::MultiCurrency::free_balance(vault.lp_token, &caller);
+    }: _(RawOrigin::Signed(caller.clone()), vault_id, lp_amount)
+    verify {
+        let total_lp = FarmingVault::<T>::vaults(vault_id).expect("the vault is created").total_lp;
+        assert_eq!(total_lp, vault.total_lp.saturating_add(lp_amount));
+    }
+
+    withdraw {
+        let caller: T::AccountId = whitelisted_caller();
+        let vault_id = create_vault::<T>(&caller);
+        let vault = FarmingVault::<T>::vaults(vault_id).expect("the vault is created");
+        let shares = T::MultiCurrency::free_balance(vault.share_token, &caller) / 2u32.into();
+    }: _(RawOrigin::Signed(caller.clone()), vault_id, shares)
+    verify {
+        let total_lp = FarmingVault::<T>::vaults(vault_id).expect("the vault is created").total_lp;
+        assert!(total_lp < vault.total_lp);
+    }
+
+    compound {
+        let caller: T::AccountId = whitelisted_caller();
+        let vault_id = create_vault::<T>(&caller);
+        System::<T>::set_block_number(System::<T>::block_number() + 10u32.into());
+        FarmingVault::<T>::sample_prices(vault_id);
+        // The reference prices are fresh once sampled twice
+        System::<T>::set_block_number(System::<T>::block_number() + One::one());
+        FarmingVault::<T>::sample_prices(vault_id);
+    }: _(RawOrigin::Root, vault_id)
+    verify {
+        let vault = FarmingVault::<T>::vaults(vault_id).expect("the vault is created");
+        assert_eq!(vault.last_compound_block, System::<T>::block_number());
+    }
+}
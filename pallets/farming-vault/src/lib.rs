@@ -0,0 +1,793 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Farming Vault Module
+//!
+//! ## Overview
+//!
+//! A vault holds the LP tokens of its depositors in a [`pallet_farming`] pool of a Zenlink LP token.
+//! Every [`Config::CompoundInterval`] blocks the vault claims its farming rewards, swaps them into
+//! the two assets of the LP pair, adds them as liquidity and deposits the minted LP tokens back
+//! into the farming pool. At most [`Config::MaxCompoundsPerBlock`] vaults are compounded per
+//! block, the others follow in the next blocks.
+//!
+//! The swaps and the added liquidity are bounded by reference prices, which follow the prices
+//! of the AMM over [`Config::CompoundInterval`] as the vaults are sampled one per block. A vault
+//! whose prices deviate more than [`Config::MaxPriceDeviation`] from them, or whose reference
+//! prices are not fresh, is not compounded, the rewards wait in the vault until the next
+//! compounding. A reference price is fresh once it was sampled twice less than
+//! [`Config::CompoundInterval`] apart, and up to [`Config::CompoundInterval`] after its last
+//! sample, which bounds the number of vaults below [`Config::CompoundInterval`].
+//!
+//! Depositors receive vault shares, an asset registered in asset-manager for every vault. The LP
+//! tokens backing one share only ever grow by compounding, which is the yield of the vault.
+//!
+//! Vaults only wrap farming pools with a single LP token and no withdraw limit time, so that
+//! withdrawals from the vault are paid out instantly. [`pallet_farming`] does not edit, reset,
+//! retire or kill the pools backing a vault.
+//!
+//! ### User Dispatchable Functions
+//! * [`Call::deposit`]: Deposits LP tokens into a vault in exchange for vault shares
+//! * [`Call::withdraw`]: Burns vault shares in exchange for their LP tokens
+//!
+//! ### Control Dispatchable Functions
+//! * [`Call::create_vault`]: Creates a vault over a farming pool
+//! * [`Call::compound`]: Immediately compounds the rewards of a vault
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{pallet_prelude::*, storage::with_storage_layer, PalletId};
+use frame_system::pallet_prelude::*;
+use manta_primitives::types::PoolId;
+use orml_traits::MultiCurrency;
+use pallet_farming::{AccountIdOf, CurrencyIdOf};
+use sp_core::U256;
+use sp_runtime::{
+    traits::{AccountIdConversion, One, Saturating, Zero},
+    FixedPointNumber, FixedU128, Perbill, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::vec::Vec;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+pub type VaultId = u32;
+
+#[allow(type_alias_bounds)]
+pub type BalanceOf<T: Config> =
+    <<T as pallet_farming::Config>::MultiCurrency as MultiCurrency<AccountIdOf<T>>>::Balance;
+
+#[allow(type_alias_bounds)]
+type VaultInfoOf<T: Config> = VaultInfo<BalanceOf<T>, CurrencyIdOf<T>, BlockNumberFor<T>>;
+
+#[allow(type_alias_bounds)]
+type PriceObservationOf<T: Config> = PriceObservation<BlockNumberFor<T>>;
+
+/// The reference prices are sampled with this many existential deposits of the swapped asset.
+const PRICE_PROBE_UNITS: u32 = 1_000;
+
+/// A Zenlink-like AMM the vaults swap rewards and add liquidity through.
+pub trait LiquidityPool<AccountId, CurrencyId, Balance> {
+    /// The two assets of the pair `lp_token` is the liquidity token of.
+    fn lp_pair(lp_token: CurrencyId) -> Option<(CurrencyId, CurrencyId)>;
+
+    /// The amount of `asset_out` swapping `amount_in` of `asset_in` receives at the current
+    /// reserves, zero without a pair.
+    fn quote(asset_in: CurrencyId, asset_out: CurrencyId, amount_in: Balance) -> Balance;
+
+    /// Swaps exactly `amount_in` of `asset_in` of `who` into at least `amount_out_min` of
+    /// `asset_out`, returns the amount of `asset_out` received.
+    fn swap_exact_in(
+        who: &AccountId,
+        asset_in: CurrencyId,
+        asset_out: CurrencyId,
+        amount_in: Balance,
+        amount_out_min: Balance,
+    ) -> Result<Balance, DispatchError>;
+
+    /// Adds at most `amount_0` of `asset_0` and `amount_1` of `asset_1` of `who` as liquidity,
+    /// and at least `amount_0_min` and `amount_1_min`, returns the amount of LP tokens minted.
+    #[allow(clippy::too_many_arguments)]
+    fn add_liquidity(
+        who: &AccountId,
+        asset_0: CurrencyId,
+        asset_1: CurrencyId,
+        amount_0: Balance,
+        amount_1: Balance,
+        amount_0_min: Balance,
+        amount_1_min: Balance,
+    ) -> Result<Balance, DispatchError>;
+}
+
+/// Registers the share asset of a new vault.
+pub trait ShareAssetRegistry<CurrencyId> {
+    /// Registers the share asset of a vault over `lp_token`, returns its id.
+    fn register_share_asset(lp_token: CurrencyId) -> Result<CurrencyId, DispatchError>;
+}
+
+/// Sets up the AMM the vaults are benchmarked against.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId, CurrencyId, Balance> {
+    /// Creates a pair of two new assets and adds `amount` of both as liquidity of `who`,
+    /// returns the LP token of the pair.
+    fn create_lp_pair(who: &AccountId, amount: Balance) -> CurrencyId;
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VaultInfo<BalanceOf, CurrencyIdOf, BlockNumberFor> {
+    /// The farming pool the LP tokens are deposited into
+    pub pool_id: PoolId,
+    /// The LP token of the farming pool
+    pub lp_token: CurrencyIdOf,
+    /// The two assets of the LP pair
+    pub assets: (CurrencyIdOf, CurrencyIdOf),
+    /// The vault shares minted to depositors
+    pub share_token: CurrencyIdOf,
+    /// The LP tokens deposited into the farming pool
+    pub total_lp: BalanceOf,
+    /// The block the rewards were last compounded at
+    pub last_compound_block: BlockNumberFor,
+}
+
+/// A reference price of swapping one asset into another.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PriceObservation<BlockNumberFor> {
+    /// The amount of the asset out per asset in
+    pub price: FixedU128,
+    /// The block the price was last sampled at
+    pub block: BlockNumberFor,
+    /// The block the price has been sampled at least once every `CompoundInterval` since
+    pub since: BlockNumberFor,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_farming::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// The AMM rewards are swapped and added as liquidity through
+        type LiquidityPool: LiquidityPool<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+
+        /// Registers the share asset of every vault
+        type ShareAssetRegistry: ShareAssetRegistry<CurrencyIdOf<Self>>;
+
+        /// Origin that can create vaults and compound them manually
+        type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// The vault accounts are sub-accounts of this pallet id
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// Time in blocks between the automatic compounding of all vaults
+        #[pallet::constant]
+        type CompoundInterval: Get<BlockNumberFor<Self>>;
+
+        /// The most vaults compounded automatically in one block, the remaining vaults are
+        /// compounded in the following blocks
+        #[pallet::constant]
+        type MaxCompoundsPerBlock: Get<u32>;
+
+        /// The largest deviation from the reference prices the compounding swaps and adds
+        /// liquidity at
+        #[pallet::constant]
+        type MaxPriceDeviation: Get<Perbill>;
+
+        /// Creates the LP pairs of the vaults in the benchmarks
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        VaultCreated {
+            vault_id: VaultId,
+            pid: PoolId,
+            share_token: CurrencyIdOf<T>,
+        },
+        Deposited {
+            who: AccountIdOf<T>,
+            vault_id: VaultId,
+            lp_amount: BalanceOf<T>,
+            shares: BalanceOf<T>,
+        },
+        Withdrawn {
+            who: AccountIdOf<T>,
+            vault_id: VaultId,
+            lp_amount: BalanceOf<T>,
+            shares: BalanceOf<T>,
+        },
+        Compounded {
+            vault_id: VaultId,
+            lp_amount: BalanceOf<T>,
+        },
+        CompoundSkipped {
+            vault_id: VaultId,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        VaultNotExist,
+        PoolDoesNotExist,
+        /// The farming pool does not farm a single LP token without withdraw limit time, or
+        /// does not allow withdrawals
+        InvalidPool,
+        /// The farming pool already backs a vault
+        PoolHasVault,
+        /// Every vault can not be sampled within `CompoundInterval` with one more vault
+        TooManyVaults,
+        /// The token of the farming pool is not a liquidity token
+        NotLiquidityToken,
+        ZeroAmount,
+        NoShares,
+        InsufficientShares,
+    }
+
+    #[pallet::storage]
+    #[pallet::getter(fn next_vault_id)]
+    pub type NextVaultId<T: Config> = StorageValue<_, VaultId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn vaults)]
+    pub type Vaults<T: Config> = StorageMap<_, Twox64Concat, VaultId, VaultInfoOf<T>>;
+
+    /// The vault backed by each farming pool.
+    #[pallet::storage]
+    #[pallet::getter(fn vault_pools)]
+    pub type VaultPools<T: Config> = StorageMap<_, Twox64Concat, PoolId, VaultId>;
+
+    /// The reference price of swapping the first asset into the second.
+    ///
+    /// double_map (CurrencyId, CurrencyId) => PriceObservation
+    #[pallet::storage]
+    #[pallet::getter(fn reference_prices)]
+    pub type ReferencePrices<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        CurrencyIdOf<T>,
+        Twox64Concat,
+        CurrencyIdOf<T>,
+        PriceObservationOf<T>,
+    >;
+
+    /// The vault compounded next, while an automatic compounding of all vaults is in progress.
+    #[pallet::storage]
+    #[pallet::getter(fn compound_cursor)]
+    pub type CompoundCursor<T: Config> = StorageValue<_, VaultId>;
+
+    /// The vault whose prices are sampled next.
+    #[pallet::storage]
+    #[pallet::getter(fn next_sampled_vault)]
+    pub type NextSampledVault<T: Config> = StorageValue<_, VaultId, ValueQuery>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            // The prices are sampled after compounding, which only relies on earlier samples
+            let weight = Self::compound_vaults(n);
+            weight.saturating_add(Self::sample_next_vault())
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// `ControlOrigin` creates a vault over the farming pool `pool_id`.
+        ///
+        /// The pool must farm a single LP token, must not have a withdraw limit time and must
+        /// allow withdrawals. Every vault is sampled once every `NextVaultId` blocks, so there
+        /// are fewer vaults than `CompoundInterval` blocks.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_vault())]
+        pub fn create_vault(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
+            <T as Config>::ControlOrigin::ensure_origin(origin)?;
+            ensure!(
+                !VaultPools::<T>::contains_key(pool_id),
+                Error::<T>::PoolHasVault
+            );
+            let vault_id = Self::next_vault_id();
+            ensure!(
+                vault_id.saturating_add(1).saturated_into::<u128>()
+                    < T::CompoundInterval::get().saturated_into::<u128>(),
+                Error::<T>::TooManyVaults
+            );
+
+            let pool_info = pallet_farming::Pallet::<T>::pool_infos(pool_id)
+                .ok_or(Error::<T>::PoolDoesNotExist)?;
+            let lp_token = match pool_info.tokens_proportion.iter().next() {
+                Some((token, proportion))
+                    if pool_info.tokens_proportion.len() == 1
+                        && *proportion == Perbill::from_percent(100)
+                        && pool_info.withdraw_limit_time.is_zero()
+                        && pool_info.withdraw_limit_count > 0 =>
+                {
+                    *token
+                }
+                _ => return Err(Error::<T>::InvalidPool.into()),
+            };
+            let assets =
+                T::LiquidityPool::lp_pair(lp_token).ok_or(Error::<T>::NotLiquidityToken)?;
+            let share_token = T::ShareAssetRegistry::register_share_asset(lp_token)?;
+
+            Vaults::<T>::insert(
+                vault_id,
+                VaultInfo {
+                    pool_id,
+                    lp_token,
+                    assets,
+                    share_token,
+                    total_lp: Zero::zero(),
+                    last_compound_block: frame_system::Pallet::<T>::block_number(),
+                },
+            );
+            VaultPools::<T>::insert(pool_id, vault_id);
+            NextVaultId::<T>::put(vault_id.saturating_add(1));
+
+            Self::deposit_event(Event::VaultCreated {
+                vault_id,
+                pid: pool_id,
+                share_token,
+            });
+            Ok(())
+        }
+
+        /// Deposits `lp_amount` LP tokens into the vault `vault_id` in exchange for shares.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::deposit())]
+        pub fn deposit(
+            origin: OriginFor<T>,
+            vault_id: VaultId,
+            lp_amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!lp_amount.is_zero(), Error::<T>::ZeroAmount);
+            let mut vault = Self::vaults(vault_id).ok_or(Error::<T>::VaultNotExist)?;
+
+            let share_supply = T::MultiCurrency::total_issuance(vault.share_token);
+            let shares = if share_supply.is_zero() || vault.total_lp.is_zero() {
+                lp_amount
+            } else {
+                Self::proportion(lp_amount, share_supply, vault.total_lp)
+            };
+            ensure!(!shares.is_zero(), Error::<T>::NoShares);
+
+            let account = Self::vault_account(vault_id);
+            T::MultiCurrency::transfer(vault.lp_token, &who, &account, lp_amount)?;
+            pallet_farming::Pallet::<T>::deposit_farming(account, vault.pool_id, lp_amount, None)?;
+            T::MultiCurrency::deposit(vault.share_token, &who, shares)?;
+            vault.total_lp = vault.total_lp.saturating_add(lp_amount);
+            Vaults::<T>::insert(vault_id, vault);
+
+            Self::deposit_event(Event::Deposited {
+                who,
+                vault_id,
+                lp_amount,
+                shares,
+            });
+            Ok(())
+        }
+
+        /// Burns `shares` of the vault `vault_id` in exchange for their LP tokens.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw())]
+        pub fn withdraw(
+            origin: OriginFor<T>,
+            vault_id: VaultId,
+            shares: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!shares.is_zero(), Error::<T>::ZeroAmount);
+            let mut vault = Self::vaults(vault_id).ok_or(Error::<T>::VaultNotExist)?;
+            ensure!(
+                T::MultiCurrency::free_balance(vault.share_token, &who) >= shares,
+                Error::<T>::InsufficientShares
+            );
+
+            let share_supply = T::MultiCurrency::total_issuance(vault.share_token);
+            let lp_amount = Self::proportion(shares, vault.total_lp, share_supply);
+            T::MultiCurrency::withdraw(vault.share_token, &who, shares)?;
+            if !lp_amount.is_zero() {
+                let account = Self::vault_account(vault_id);
+                pallet_farming::Pallet::<T>::withdraw_and_unstake(
+                    account.clone(),
+                    vault.pool_id,
+                    Some(lp_amount),
+                )?;
+                T::MultiCurrency::transfer(vault.lp_token, &account, &who, lp_amount)?;
+                vault.total_lp = vault.total_lp.saturating_sub(lp_amount);
+                Vaults::<T>::insert(vault_id, vault);
+            }
+
+            Self::deposit_event(Event::Withdrawn {
+                who,
+                vault_id,
+                lp_amount,
+                shares,
+            });
+            Ok(())
+        }
+
+        /// `ControlOrigin` compounds the rewards of the vault `vault_id` immediately.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::compound())]
+        pub fn compound(origin: OriginFor<T>, vault_id: VaultId) -> DispatchResult {
+            <T as Config>::ControlOrigin::ensure_origin(origin)?;
+            Self::compound_inner(vault_id)
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// The account holding the farming shares of the vault `vault_id`.
+    pub fn vault_account(vault_id: VaultId) -> T::AccountId {
+        T::PalletId::get().into_sub_account_truncating(vault_id)
+    }
+
+    /// The LP tokens backing one share of the vault `vault_id`.
+    pub fn share_price(vault_id: VaultId) -> Option<FixedU128> {
+        let vault = Self::vaults(vault_id)?;
+        let share_supply = T::MultiCurrency::total_issuance(vault.share_token);
+        if share_supply.is_zero() {
+            return Some(FixedU128::one());
+        }
+        FixedU128::checked_from_rational(
+            vault.total_lp.saturated_into::<u128>(),
+            share_supply.saturated_into::<u128>(),
+        )
+    }
+
+    /// Starts compounding all vaults every `CompoundInterval` blocks, and compounds at most
+    /// `MaxCompoundsPerBlock` of them per block until all are compounded.
+    fn compound_vaults(n: BlockNumberFor<T>) -> Weight {
+        let interval = T::CompoundInterval::get();
+        let start = match Self::compound_cursor() {
+            Some(cursor) => cursor,
+            None if !interval.is_zero() && (n % interval).is_zero() => Zero::zero(),
+            None => return T::DbWeight::get().reads(1),
+        };
+        let vault_count = Self::next_vault_id();
+        let end = start
+            .saturating_add(T::MaxCompoundsPerBlock::get())
+            .min(vault_count);
+        for vault_id in start..end {
+            // A failed compounding must not fail the block, the rewards are compounded later
+            if let Err(e) = with_storage_layer(|| Self::compound_inner(vault_id)) {
+                log::warn!("Could not compound vault {:?}: {:?}", vault_id, e);
+            }
+        }
+        if end < vault_count {
+            CompoundCursor::<T>::put(end);
+        } else {
+            CompoundCursor::<T>::kill();
+        }
+        <T as Config>::WeightInfo::compound()
+            .saturating_mul(end.saturating_sub(start).into())
+            .saturating_add(T::DbWeight::get().reads_writes(2, 1))
+    }
+
+    /// Samples the prices of the next vault, so that every vault is sampled once every
+    /// `NextVaultId` blocks.
+    fn sample_next_vault() -> Weight {
+        let vault_count = Self::next_vault_id();
+        if vault_count.is_zero() {
+            return T::DbWeight::get().reads(1);
+        }
+        let vault_id = Self::next_sampled_vault() % vault_count;
+        NextSampledVault::<T>::put(vault_id.saturating_add(1) % vault_count);
+        Self::sample_prices(vault_id).saturating_add(T::DbWeight::get().reads_writes(2, 1))
+    }
+
+    /// Moves the reference prices of the swaps of the vault `vault_id` toward the current
+    /// prices, by the part of `CompoundInterval` elapsed since they were last sampled.
+    ///
+    /// A price not sampled for a whole `CompoundInterval` moves all the way to the current
+    /// price, but is not fresh until it is sampled again.
+    pub fn sample_prices(vault_id: VaultId) -> Weight {
+        let vault = match Self::vaults(vault_id) {
+            Some(vault) => vault,
+            None => return T::DbWeight::get().reads(1),
+        };
+        let pool_info = match pallet_farming::Pallet::<T>::pool_infos(vault.pool_id) {
+            Some(pool_info) => pool_info,
+            None => return T::DbWeight::get().reads(2),
+        };
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let window = T::CompoundInterval::get().max(One::one());
+        let paths = Self::swap_paths(&vault, pool_info.rewards.keys().copied());
+        for (asset_in, asset_out) in paths.iter() {
+            let spot = match Self::spot_price(*asset_in, *asset_out) {
+                Some(spot) => spot,
+                None => continue,
+            };
+            let observation = match Self::reference_prices(asset_in, asset_out) {
+                Some(observation) => {
+                    let elapsed = n.saturating_sub(observation.block);
+                    let since = if elapsed < window {
+                        observation.since
+                    } else {
+                        n
+                    };
+                    let elapsed = FixedU128::saturating_from_rational(
+                        elapsed.min(window).saturated_into::<u128>(),
+                        window.saturated_into::<u128>(),
+                    );
+                    let price = if spot >= observation.price {
+                        observation.price.saturating_add(
+                            spot.saturating_sub(observation.price)
+                                .saturating_mul(elapsed),
+                        )
+                    } else {
+                        observation.price.saturating_sub(
+                            observation
+                                .price
+                                .saturating_sub(spot)
+                                .saturating_mul(elapsed),
+                        )
+                    };
+                    PriceObservation {
+                        price,
+                        block: n,
+                        since,
+                    }
+                }
+                None => PriceObservation {
+                    price: spot,
+                    block: n,
+                    since: n,
+                },
+            };
+            ReferencePrices::<T>::insert(asset_in, asset_out, observation);
+        }
+        let paths = paths.len() as u64;
+        T::DbWeight::get().reads_writes(paths.saturating_mul(5).saturating_add(2), paths)
+    }
+
+    /// The reference price of swapping `asset_in` into `asset_out`, `None` unless it was
+    /// sampled twice without a gap of `CompoundInterval` and last sampled less than
+    /// `CompoundInterval` ago.
+    pub fn fresh_price(asset_in: CurrencyIdOf<T>, asset_out: CurrencyIdOf<T>) -> Option<FixedU128> {
+        let observation = Self::reference_prices(asset_in, asset_out)?;
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let window = T::CompoundInterval::get().max(One::one());
+        (observation.since < observation.block && n.saturating_sub(observation.block) < window)
+            .then_some(observation.price)
+    }
+
+    /// The swaps compounding the vault makes: the LP pair itself and every reward currency
+    /// into the assets it is swapped into.
+    fn swap_paths(
+        vault: &VaultInfoOf<T>,
+        reward_currencies: impl Iterator<Item = CurrencyIdOf<T>>,
+    ) -> Vec<(CurrencyIdOf<T>, CurrencyIdOf<T>)> {
+        let (asset_0, asset_1) = vault.assets;
+        let mut paths = Vec::from([(asset_0, asset_1)]);
+        for reward_currency in reward_currencies {
+            if reward_currency == asset_1 {
+                paths.push((asset_1, asset_0));
+            } else if reward_currency != asset_0 && reward_currency != vault.lp_token {
+                paths.push((reward_currency, asset_0));
+                paths.push((reward_currency, asset_1));
+            }
+        }
+        paths
+    }
+
+    /// The current price of swapping `asset_in` into `asset_out`, quoted for a small amount.
+    fn spot_price(asset_in: CurrencyIdOf<T>, asset_out: CurrencyIdOf<T>) -> Option<FixedU128> {
+        let probe = T::MultiCurrency::minimum_balance(asset_in)
+            .max(One::one())
+            .saturating_mul(PRICE_PROBE_UNITS.into());
+        let amount_out = T::LiquidityPool::quote(asset_in, asset_out, probe);
+        FixedU128::checked_from_rational(
+            amount_out.saturated_into::<u128>(),
+            probe.saturated_into::<u128>(),
+        )
+        .filter(|price| !price.is_zero())
+    }
+
+    /// The least of `amount` the compounding accepts.
+    fn amount_min(amount: BalanceOf<T>) -> BalanceOf<T> {
+        Perbill::one().saturating_sub(T::MaxPriceDeviation::get()) * amount
+    }
+
+    /// The least amount of `asset_out` swapping `amount_in` of `asset_in` must receive, `None`
+    /// without a fresh reference price or if the current price deviates more from it.
+    fn amount_out_min(
+        asset_in: CurrencyIdOf<T>,
+        asset_out: CurrencyIdOf<T>,
+        amount_in: BalanceOf<T>,
+    ) -> Option<BalanceOf<T>> {
+        let price = Self::fresh_price(asset_in, asset_out)?;
+        let amount_out_min = Self::amount_min(price.saturating_mul_int(amount_in));
+        (T::LiquidityPool::quote(asset_in, asset_out, amount_in) >= amount_out_min)
+            .then_some(amount_out_min)
+    }
+
+    /// The least amounts adding `amount_0` of `asset_0` and `amount_1` of `asset_1` as
+    /// liquidity must take, from the amounts matching at the reference price.
+    fn liquidity_min(
+        price: FixedU128,
+        amount_0: BalanceOf<T>,
+        amount_1: BalanceOf<T>,
+    ) -> (BalanceOf<T>, BalanceOf<T>) {
+        let amount_1_matching: BalanceOf<T> = price.saturating_mul_int(amount_0);
+        if amount_1_matching <= amount_1 {
+            (
+                Self::amount_min(amount_0),
+                Self::amount_min(amount_1_matching),
+            )
+        } else {
+            let amount_0_matching = price
+                .reciprocal()
+                .map_or(amount_0, |price| price.saturating_mul_int(amount_1))
+                .min(amount_0);
+            (
+                Self::amount_min(amount_0_matching),
+                Self::amount_min(amount_1),
+            )
+        }
+    }
+
+    /// Claims the farming rewards of the vault `vault_id`, swaps them into the assets of the LP
+    /// pair and deposits the liquidity added with them into the farming pool.
+    ///
+    /// Skipped if the prices deviate more than `MaxPriceDeviation` from the reference prices or
+    /// without fresh reference prices, the claimed rewards stay in the vault account until the
+    /// next compounding.
+    pub fn compound_inner(vault_id: VaultId) -> DispatchResult {
+        let mut vault = Self::vaults(vault_id).ok_or(Error::<T>::VaultNotExist)?;
+        let account = Self::vault_account(vault_id);
+        pallet_farming::Pallet::<T>::claim_rewards(&account, vault.pool_id)?;
+
+        let pool_info = pallet_farming::Pallet::<T>::pool_infos(vault.pool_id)
+            .ok_or(Error::<T>::PoolDoesNotExist)?;
+        let (asset_0, asset_1) = vault.assets;
+        // The swaps of each reward currency are made together or not at all
+        let mut swaps = Vec::new();
+        for reward_currency in pool_info.rewards.keys() {
+            let amount = Self::spendable(*reward_currency, &account);
+            if amount.is_zero() || *reward_currency == vault.lp_token {
+                continue;
+            }
+            let half = amount / 2u32.into();
+            if *reward_currency == asset_0 {
+                swaps.push(Vec::from([(asset_0, asset_1, half)]));
+            } else if *reward_currency == asset_1 {
+                swaps.push(Vec::from([(asset_1, asset_0, half)]));
+            } else {
+                swaps.push(Vec::from([
+                    (*reward_currency, asset_0, half),
+                    (*reward_currency, asset_1, amount.saturating_sub(half)),
+                ]));
+            }
+        }
+
+        let pair_price = Self::fresh_price(asset_0, asset_1);
+        let probe = T::MultiCurrency::minimum_balance(asset_0)
+            .max(One::one())
+            .saturating_mul(PRICE_PROBE_UNITS.into());
+        let swaps = swaps
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|(asset_in, asset_out, amount_in)| {
+                        Self::amount_out_min(asset_in, asset_out, amount_in)
+                            .map(|amount_out_min| (asset_in, asset_out, amount_in, amount_out_min))
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+            .collect::<Option<Vec<_>>>();
+        let (swaps, pair_price) = match (
+            swaps,
+            pair_price,
+            Self::amount_out_min(asset_0, asset_1, probe),
+        ) {
+            (Some(swaps), Some(pair_price), Some(_)) => (swaps, pair_price),
+            _ => {
+                Self::deposit_event(Event::CompoundSkipped { vault_id });
+                return Ok(());
+            }
+        };
+
+        for group in swaps {
+            // A reward too small to swap stays in the vault account until the next compounding
+            let _ = with_storage_layer(|| -> DispatchResult {
+                for (asset_in, asset_out, amount_in, amount_out_min) in group {
+                    T::LiquidityPool::swap_exact_in(
+                        &account,
+                        asset_in,
+                        asset_out,
+                        amount_in,
+                        amount_out_min,
+                    )?;
+                }
+                Ok(())
+            });
+        }
+
+        let amount_0 = Self::spendable(asset_0, &account);
+        let amount_1 = Self::spendable(asset_1, &account);
+        let mut lp_amount = Zero::zero();
+        if !amount_0.is_zero() && !amount_1.is_zero() {
+            let (amount_0_min, amount_1_min) = Self::liquidity_min(pair_price, amount_0, amount_1);
+            lp_amount = T::LiquidityPool::add_liquidity(
+                &account,
+                asset_0,
+                asset_1,
+                amount_0,
+                amount_1,
+                amount_0_min,
+                amount_1_min,
+            )?;
+        }
+        if !lp_amount.is_zero() {
+            pallet_farming::Pallet::<T>::deposit_farming(account, vault.pool_id, lp_amount, None)?;
+            vault.total_lp = vault.total_lp.saturating_add(lp_amount);
+        }
+        vault.last_compound_block = frame_system::Pallet::<T>::block_number();
+        Vaults::<T>::insert(vault_id, vault);
+
+        Self::deposit_event(Event::Compounded {
+            vault_id,
+            lp_amount,
+        });
+        Ok(())
+    }
+
+    /// The balance of `currency` of `who` above the existential deposit.
+    fn spendable(currency: CurrencyIdOf<T>, who: &T::AccountId) -> BalanceOf<T> {
+        T::MultiCurrency::free_balance(currency, who)
+            .saturating_sub(T::MultiCurrency::minimum_balance(currency))
+    }
+
+    /// `amount * numerator / denominator` without overflow.
+    fn proportion(
+        amount: BalanceOf<T>,
+        numerator: BalanceOf<T>,
+        denominator: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        U256::from(amount.saturated_into::<u128>())
+            .saturating_mul(U256::from(numerator.saturated_into::<u128>()))
+            .checked_div(U256::from(denominator.saturated_into::<u128>()))
+            .unwrap_or_default()
+            .as_u128()
+            .saturated_into()
+    }
+}
+
+impl<T: Config> pallet_farming::PoolGuard for Pallet<T> {
+    fn is_vault_pool(pool_id: PoolId) -> bool {
+        VaultPools::<T>::contains_key(pool_id)
+    }
+}
@@ -0,0 +1,527 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+// Ensure we're `no_std` when compiling for Wasm.
+
+#![cfg(test)]
+#![allow(non_upper_case_globals)]
+
+use frame_support::{
+    dispatch::DispatchResult,
+    ord_parameter_types,
+    pallet_prelude::DispatchError,
+    parameter_types,
+    traits::{AsEnsureOriginWithArg, EitherOfDiverse, GenesisBuild},
+    PalletId,
+};
+use frame_system::{EnsureNever, EnsureRoot, EnsureSignedBy};
+use manta_primitives::{
+    assets::{
+        AssetConfig, AssetIdType, AssetLocation, AssetRegistry, AssetRegistryMetadata,
+        AssetStorageMetadata, BalanceType, LocationType, NativeAndNonNative,
+    },
+    constants::ASSET_MANAGER_PALLET_ID,
+    currencies::Currencies,
+    types::{Balance, CalamariAssetId},
+};
+use orml_traits::MultiCurrency;
+use sp_core::{ConstU128, ConstU32, ConstU64, H256};
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    AccountId32, FixedU128, Perbill,
+};
+use xcm::{
+    prelude::{Parachain, X1},
+    v3::MultiLocation,
+    VersionedMultiLocation,
+};
+
+use crate::{self as pallet_farming_vault, LiquidityPool, ShareAssetRegistry};
+
+pub type AccountId = AccountId32;
+
+pub const KSM: CalamariAssetId = 8;
+pub const KMA: CalamariAssetId = 1;
+pub const KUSD: CalamariAssetId = 9;
+pub const LP: CalamariAssetId = 10;
+
+pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
+pub const BOB: AccountId = AccountId32::new([1u8; 32]);
+pub const CHARLIE: AccountId = AccountId32::new([3u8; 32]);
+pub const TREASURY_ACCOUNT: AccountId = AccountId32::new([9u8; 32]);
+
+frame_support::construct_runtime!(
+    pub enum Runtime where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Assets: pallet_assets::{Pallet, Storage, Config<T>, Event<T>},
+        AssetManager: pallet_asset_manager::{Pallet, Call, Storage, Event<T>},
+        Farming: pallet_farming::{Pallet, Call, Storage, Event<T>},
+        FarmingVault: pallet_farming_vault::{Pallet, Call, Storage, Event<T>}
+    }
+);
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Runtime {
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type AccountId = AccountId;
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockHashCount = BlockHashCount;
+    type BlockLength = ();
+    type BlockNumber = u64;
+    type BlockWeights = ();
+    type RuntimeCall = RuntimeCall;
+    type DbWeight = ();
+    type RuntimeEvent = RuntimeEvent;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type Header = Header;
+    type Index = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type OnKilledAccount = ();
+    type OnNewAccount = ();
+    type OnSetCode = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type PalletInfo = PalletInfo;
+    type SS58Prefix = ();
+    type SystemWeightInfo = ();
+    type Version = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+}
+impl pallet_balances::Config for Runtime {
+    type AccountStore = frame_system::Pallet<Runtime>;
+    type Balance = Balance;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ExistentialDeposit;
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type WeightInfo = ();
+    type HoldIdentifier = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ConstU32<1>;
+    type MaxHolds = ConstU32<1>;
+}
+
+parameter_types! {
+    // Does not really matter as this will be only called by root
+    pub const AssetDeposit: Balance = 0;
+    pub const AssetAccountDeposit: Balance = 0;
+    pub const ApprovalDeposit: Balance = 0;
+    pub const AssetsStringLimit: u32 = 50;
+    pub const MetadataDepositBase: Balance = 0;
+    pub const MetadataDepositPerByte: Balance = 0;
+}
+
+impl pallet_assets::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type AssetId = CalamariAssetId;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<AccountId32>;
+    type AssetDeposit = AssetDeposit;
+    type AssetAccountDeposit = AssetAccountDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = AssetsStringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+    type RemoveItemsLimit = ConstU32<1000>;
+    type AssetIdParameter = CalamariAssetId;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureNever<AccountId32>>;
+    type CallbackHandle = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
+pub struct MantaAssetRegistry;
+impl BalanceType for MantaAssetRegistry {
+    type Balance = Balance;
+}
+impl AssetIdType for MantaAssetRegistry {
+    type AssetId = CalamariAssetId;
+}
+impl AssetRegistry for MantaAssetRegistry {
+    type Metadata = AssetStorageMetadata;
+    type Error = sp_runtime::DispatchError;
+
+    fn create_asset(
+        asset_id: CalamariAssetId,
+        metadata: AssetStorageMetadata,
+        min_balance: Balance,
+        is_sufficient: bool,
+    ) -> DispatchResult {
+        Assets::force_create(
+            RuntimeOrigin::root(),
+            asset_id,
+            AssetManager::account_id(),
+            is_sufficient,
+            min_balance,
+        )?;
+
+        Assets::force_set_metadata(
+            RuntimeOrigin::root(),
+            asset_id,
+            metadata.name,
+            metadata.symbol,
+            metadata.decimals,
+            metadata.is_frozen,
+        )?;
+
+        Assets::force_asset_status(
+            RuntimeOrigin::root(),
+            asset_id,
+            AssetManager::account_id(),
+            AssetManager::account_id(),
+            AssetManager::account_id(),
+            AssetManager::account_id(),
+            min_balance,
+            is_sufficient,
+            metadata.is_frozen,
+        )
+    }
+
+    fn update_asset_metadata(
+        asset_id: &CalamariAssetId,
+        metadata: AssetStorageMetadata,
+    ) -> DispatchResult {
+        Assets::force_set_metadata(
+            RuntimeOrigin::root(),
+            *asset_id,
+            metadata.name,
+            metadata.symbol,
+            metadata.decimals,
+            metadata.is_frozen,
+        )
+    }
+}
+
+parameter_types! {
+    pub const DummyAssetId: CalamariAssetId = 0;
+    pub const NativeAssetId: CalamariAssetId = 1;
+    pub const StartNonNativeAssetId: CalamariAssetId = 8;
+    pub NativeAssetLocation: AssetLocation = AssetLocation(
+        VersionedMultiLocation::V3(MultiLocation::new(1, X1(Parachain(1024)))));
+    pub NativeAssetMetadata: AssetRegistryMetadata<Balance> = AssetRegistryMetadata {
+        metadata: AssetStorageMetadata {
+            name: b"Calamari".to_vec(),
+            symbol: b"KMA".to_vec(),
+            decimals: 12,
+            is_frozen: false,
+        },
+        min_balance: 1u128,
+        is_sufficient: true,
+    };
+    pub const AssetManagerPalletId: PalletId = ASSET_MANAGER_PALLET_ID;
+}
+
+/// AssetConfig implementations for this runtime
+#[derive(Clone, Eq, PartialEq)]
+pub struct MantaAssetConfig;
+impl LocationType for MantaAssetConfig {
+    type Location = AssetLocation;
+}
+impl AssetIdType for MantaAssetConfig {
+    type AssetId = CalamariAssetId;
+}
+impl BalanceType for MantaAssetConfig {
+    type Balance = Balance;
+}
+impl AssetConfig<Runtime> for MantaAssetConfig {
+    type NativeAssetId = NativeAssetId;
+    type StartNonNativeAssetId = StartNonNativeAssetId;
+    type NativeAssetLocation = NativeAssetLocation;
+    type NativeAssetMetadata = NativeAssetMetadata;
+    type AssetRegistry = MantaAssetRegistry;
+    type FungibleLedger = NativeAndNonNative<Runtime, MantaAssetConfig, Balances, Assets>;
+}
+
+impl pallet_asset_manager::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type AssetId = CalamariAssetId;
+    type Location = AssetLocation;
+    type AssetConfig = MantaAssetConfig;
+    type ModifierOrigin = EnsureRoot<AccountId32>;
+    type SuspenderOrigin = EnsureRoot<AccountId32>;
+    type PalletId = AssetManagerPalletId;
+    type WeightInfo = ();
+    type PermissionlessStartId = ConstU128<100>;
+    type TokenNameMaxLen = ConstU32<100>;
+    type TokenSymbolMaxLen = ConstU32<100>;
+    type PermissionlessAssetRegistryCost = ConstU128<1000>;
+}
+
+parameter_types! {
+    pub const FarmingKeeperPalletId: PalletId = PalletId(*b"bf/fmkpr");
+    pub const FarmingRewardIssuerPalletId: PalletId = PalletId(*b"bf/fmrir");
+    pub FarmingMaxBoost: FixedU128 = FixedU128::from_rational(5, 2);
+    pub const TreasuryAccount: AccountId32 = TREASURY_ACCOUNT;
+}
+
+ord_parameter_types! {
+    pub const One: AccountId = ALICE;
+}
+
+pub type MantaCurrencies = Currencies<Runtime, MantaAssetConfig, Balances, Assets>;
+
+impl pallet_farming::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type CurrencyId = CalamariAssetId;
+    type MultiCurrency = MantaCurrencies;
+    type ControlOrigin = EitherOfDiverse<EnsureRoot<AccountId>, EnsureSignedBy<One, AccountId>>;
+    type TreasuryAccount = TreasuryAccount;
+    type Keeper = FarmingKeeperPalletId;
+    type RewardIssuer = FarmingRewardIssuerPalletId;
+    type MaxIncentives = ConstU32<3>;
+    type VoteEscrowCurrency = Balances;
+    type MaxLockBlocks = ConstU64<100>;
+    type MaxBoost = FarmingMaxBoost;
+    type EpochLength = ConstU64<10>;
+    type MaxPoolVotes = ConstU32<3>;
+    type MaxVotedPools = ConstU32<5>;
+    type MaxAllowlist = ConstU32<5>;
+    type MaxChargeRefunds = ConstU32<5>;
+    type PoolGuard = FarmingVault;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    /// The part of the amount swapped in the swaps lose, to move the price
+    pub static SwapLoss: Perbill = Perbill::zero();
+}
+
+/// Swaps 1:1 less `SwapLoss` and mints one LP token per pair of assets added.
+pub struct MockLiquidityPool;
+impl LiquidityPool<AccountId, CalamariAssetId, Balance> for MockLiquidityPool {
+    fn lp_pair(lp_token: CalamariAssetId) -> Option<(CalamariAssetId, CalamariAssetId)> {
+        (lp_token == LP).then_some((KSM, KUSD))
+    }
+
+    fn quote(
+        _asset_in: CalamariAssetId,
+        _asset_out: CalamariAssetId,
+        amount_in: Balance,
+    ) -> Balance {
+        amount_in - SwapLoss::get() * amount_in
+    }
+
+    fn swap_exact_in(
+        who: &AccountId,
+        asset_in: CalamariAssetId,
+        asset_out: CalamariAssetId,
+        amount_in: Balance,
+        amount_out_min: Balance,
+    ) -> Result<Balance, DispatchError> {
+        let amount_out = Self::quote(asset_in, asset_out, amount_in);
+        if amount_out < amount_out_min {
+            return Err(DispatchError::Other("insufficient target amount"));
+        }
+        MantaCurrencies::withdraw(asset_in, who, amount_in)?;
+        MantaCurrencies::deposit(asset_out, who, amount_out)?;
+        Ok(amount_out)
+    }
+
+    fn add_liquidity(
+        who: &AccountId,
+        asset_0: CalamariAssetId,
+        asset_1: CalamariAssetId,
+        amount_0: Balance,
+        amount_1: Balance,
+        amount_0_min: Balance,
+        amount_1_min: Balance,
+    ) -> Result<Balance, DispatchError> {
+        let lp_amount = amount_0.min(amount_1);
+        if lp_amount < amount_0_min || lp_amount < amount_1_min {
+            return Err(DispatchError::Other("insufficient liquidity amount"));
+        }
+        MantaCurrencies::withdraw(asset_0, who, lp_amount)?;
+        MantaCurrencies::withdraw(asset_1, who, lp_amount)?;
+        MantaCurrencies::deposit(LP, who, lp_amount)?;
+        Ok(lp_amount)
+    }
+}
+
+pub struct MockShareAssetRegistry;
+impl ShareAssetRegistry<CalamariAssetId> for MockShareAssetRegistry {
+    fn register_share_asset(lp_token: CalamariAssetId) -> Result<CalamariAssetId, DispatchError> {
+        let lp_metadata =
+            AssetManager::asset_id_metadata(lp_token).ok_or(DispatchError::CannotLookup)?;
+        let metadata = AssetRegistryMetadata {
+            metadata: AssetStorageMetadata {
+                name: [&b"Vault "[..], &lp_metadata.metadata.name].concat(),
+                symbol: [&b"v"[..], &lp_metadata.metadata.symbol].concat(),
+                decimals: lp_metadata.metadata.decimals,
+                is_frozen: false,
+            },
+            min_balance: lp_metadata.min_balance,
+            is_sufficient: lp_metadata.is_sufficient,
+        };
+        AssetManager::do_register_asset(None, &metadata)
+    }
+}
+
+/// Mints the mock LP token, whose pair always exists.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::BenchmarkHelper<AccountId, CalamariAssetId, Balance> for MockBenchmarkHelper {
+    fn create_lp_pair(who: &AccountId, amount: Balance) -> CalamariAssetId {
+        MantaCurrencies::deposit(LP, who, amount).unwrap();
+        LP
+    }
+}
+
+parameter_types! {
+    pub const FarmingVaultPalletId: PalletId = PalletId(*b"bf/fmvlt");
+    pub const MaxPriceDeviation: Perbill = Perbill::from_percent(5);
+}
+
+impl pallet_farming_vault::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type LiquidityPool = MockLiquidityPool;
+    type ShareAssetRegistry = MockShareAssetRegistry;
+    type ControlOrigin = EitherOfDiverse<EnsureRoot<AccountId>, EnsureSignedBy<One, AccountId>>;
+    type PalletId = FarmingVaultPalletId;
+    type CompoundInterval = ConstU64<10>;
+    type MaxCompoundsPerBlock = ConstU32<1>;
+    type MaxPriceDeviation = MaxPriceDeviation;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = MockBenchmarkHelper;
+    type WeightInfo = ();
+}
+
+fn mock_asset(name: &[u8]) -> AssetRegistryMetadata<Balance> {
+    AssetRegistryMetadata {
+        metadata: AssetStorageMetadata {
+            name: name.to_vec(),
+            symbol: name.to_vec(),
+            decimals: 12,
+            is_frozen: false,
+        },
+        min_balance: 1u128,
+        is_sufficient: true,
+    }
+}
+
+#[derive(Default)]
+pub struct ExtBuilder {
+    endowed_accounts: Vec<(AccountId, CalamariAssetId, Balance)>,
+}
+
+impl ExtBuilder {
+    pub fn balances(
+        mut self,
+        endowed_accounts: Vec<(AccountId, CalamariAssetId, Balance)>,
+    ) -> Self {
+        self.endowed_accounts = endowed_accounts;
+        self
+    }
+
+    pub fn one_hundred_for_alice_n_bob(self) -> Self {
+        self.balances(vec![
+            (ALICE, 1, 3000),
+            (BOB, 1, 400_000),
+            (CHARLIE, 1, 1),
+            (ALICE, KSM, 3000),
+            (BOB, KSM, 10_000_000),
+            (ALICE, LP, 3000),
+            (BOB, LP, 3000),
+        ])
+    }
+
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Runtime>()
+            .unwrap();
+
+        let initial_asset_accounts = self
+            .endowed_accounts
+            .clone()
+            .into_iter()
+            .filter(|(_, asset_id, _)| *asset_id == KSM)
+            .map(|(account_id, asset_id, initial_balance)| (asset_id, account_id, initial_balance))
+            .collect::<Vec<_>>();
+
+        let config: pallet_assets::GenesisConfig<Runtime> = pallet_assets::GenesisConfig {
+            assets: vec![
+                // id, owner, is_sufficient, min_balance
+                (KSM, ALICE, true, 1),
+            ],
+            metadata: vec![
+                // id, name, symbol, decimals
+                (KSM, "KSM".into(), "Kusama".into(), 12),
+            ],
+            accounts: initial_asset_accounts,
+        };
+        config.assimilate_storage(&mut t).unwrap();
+
+        pallet_asset_manager::GenesisConfig::<Runtime> {
+            start_id: <MantaAssetConfig as AssetConfig<Runtime>>::StartNonNativeAssetId::get() + 1,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        pallet_balances::GenesisConfig::<Runtime> {
+            balances: self
+                .endowed_accounts
+                .iter()
+                .cloned()
+                .filter(|(_, asset_id, _)| *asset_id == 1)
+                .map(|(account_id, _, initial_balance)| (account_id, initial_balance))
+                .collect::<Vec<_>>(),
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        let mut ext = sp_io::TestExternalities::new(t);
+        ext.execute_with(|| {
+            // KUSD and LP are registered in asset-manager like the Zenlink LP tokens are
+            assert_eq!(
+                AssetManager::do_register_asset(None, &mock_asset(b"KUSD")),
+                Ok(KUSD)
+            );
+            assert_eq!(
+                AssetManager::do_register_asset(None, &mock_asset(b"KSM-KUSD LP")),
+                Ok(LP)
+            );
+            self.endowed_accounts
+                .into_iter()
+                .filter(|(_, asset_id, _)| *asset_id == KUSD || *asset_id == LP)
+                .for_each(|(account_id, asset_id, initial_balance)| {
+                    MantaCurrencies::deposit(asset_id, &account_id, initial_balance).unwrap();
+                });
+            System::set_block_number(1);
+        });
+        ext
+    }
+}
@@ -0,0 +1,469 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use manta_primitives::types::CalamariAssetId;
+use orml_traits::MultiCurrency;
+use pallet_farming::EmissionCurve;
+
+use crate::{mock::*, *};
+
+fn init_pool(token: CalamariAssetId, withdraw_limit_time: u64) -> PoolId {
+    let pool_id = Farming::pool_next_id();
+    assert_ok!(Farming::create_farming_pool(
+        RuntimeOrigin::signed(ALICE),
+        vec![(token, Perbill::from_percent(100))],
+        vec![(KMA, 100)],
+        None,
+        0,                   // min_deposit_to_start
+        0,                   // after_block_to_start
+        withdraw_limit_time, // withdraw_limit_time
+        0,                   // claim_limit_time
+        1,                   // withdraw_limit_count
+        None,
+        EmissionCurve::Constant
+    ));
+    assert_ok!(Farming::charge(
+        RuntimeOrigin::signed(BOB),
+        pool_id,
+        vec![(KMA, 100_000)]
+    ));
+    pool_id
+}
+
+fn init_vault() -> (PoolId, VaultId, CalamariAssetId) {
+    let pool_id = init_pool(LP, 0);
+    let vault_id = FarmingVault::next_vault_id();
+    assert_ok!(FarmingVault::create_vault(
+        RuntimeOrigin::signed(ALICE),
+        pool_id
+    ));
+    let share_token = FarmingVault::vaults(vault_id).unwrap().share_token;
+    (pool_id, vault_id, share_token)
+}
+
+#[test]
+fn create_vault_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let ksm_pool = init_pool(KSM, 0);
+            let locked_pool = init_pool(LP, 10);
+            let frozen_pool = init_pool(LP, 0);
+            let lp_pool = init_pool(LP, 0);
+            assert_ok!(Farming::edit_pool(
+                RuntimeOrigin::signed(ALICE),
+                frozen_pool,
+                None,
+                None,
+                None,
+                None,
+                Some(0)
+            ));
+
+            assert_noop!(
+                FarmingVault::create_vault(RuntimeOrigin::signed(BOB), lp_pool),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                FarmingVault::create_vault(RuntimeOrigin::signed(ALICE), 100),
+                Error::<Runtime>::PoolDoesNotExist
+            );
+            assert_noop!(
+                FarmingVault::create_vault(RuntimeOrigin::signed(ALICE), locked_pool),
+                Error::<Runtime>::InvalidPool
+            );
+            assert_noop!(
+                FarmingVault::create_vault(RuntimeOrigin::signed(ALICE), frozen_pool),
+                Error::<Runtime>::InvalidPool
+            );
+            assert_noop!(
+                FarmingVault::create_vault(RuntimeOrigin::signed(ALICE), ksm_pool),
+                Error::<Runtime>::NotLiquidityToken
+            );
+
+            assert_ok!(FarmingVault::create_vault(
+                RuntimeOrigin::signed(ALICE),
+                lp_pool
+            ));
+            let vault = FarmingVault::vaults(0).unwrap();
+            assert_eq!(vault.pool_id, lp_pool);
+            assert_eq!(vault.lp_token, LP);
+            assert_eq!(vault.assets, (KSM, KUSD));
+            assert_eq!(vault.total_lp, 0);
+            assert_eq!(FarmingVault::next_vault_id(), 1);
+
+            // The share token is registered in asset-manager after the LP token
+            assert_eq!(vault.share_token, LP + 1);
+            let metadata = AssetManager::asset_id_metadata(vault.share_token).unwrap();
+            assert_eq!(metadata.metadata.symbol, b"vKSM-KUSD LP".to_vec());
+            assert_eq!(FarmingVault::share_price(0), Some(FixedU128::one()));
+            assert_eq!(FarmingVault::vault_pools(lp_pool), Some(0));
+
+            // A pool backs at most one vault
+            assert_noop!(
+                FarmingVault::create_vault(RuntimeOrigin::signed(ALICE), lp_pool),
+                Error::<Runtime>::PoolHasVault
+            );
+        });
+}
+
+#[test]
+fn create_vault_should_keep_fewer_vaults_than_compound_interval() {
+    ExtBuilder::default()
+        .balances(vec![(ALICE, KMA, 3000), (BOB, KMA, 2_000_000)])
+        .build()
+        .execute_with(|| {
+            // Every vault is sampled once every `NextVaultId` blocks, which must stay below
+            // `CompoundInterval` for the reference prices to be fresh
+            for _ in 0..9 {
+                init_vault();
+            }
+            let pool_id = init_pool(LP, 0);
+            assert_noop!(
+                FarmingVault::create_vault(RuntimeOrigin::signed(ALICE), pool_id),
+                Error::<Runtime>::TooManyVaults
+            );
+        });
+}
+
+#[test]
+fn farming_should_not_change_the_pools_of_vaults() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, _, _) = init_vault();
+            assert_noop!(
+                Farming::edit_pool(
+                    RuntimeOrigin::signed(ALICE),
+                    pool_id,
+                    None,
+                    Some(10),
+                    None,
+                    None,
+                    None
+                ),
+                pallet_farming::Error::<Runtime>::PoolBacksVault
+            );
+            assert_noop!(
+                Farming::reset_pool(
+                    RuntimeOrigin::signed(ALICE),
+                    pool_id,
+                    None,
+                    None,
+                    None,
+                    Some(10),
+                    None,
+                    None,
+                    None,
+                    None
+                ),
+                pallet_farming::Error::<Runtime>::PoolBacksVault
+            );
+            assert_noop!(
+                Farming::retire_pool(RuntimeOrigin::signed(ALICE), pool_id),
+                pallet_farming::Error::<Runtime>::PoolBacksVault
+            );
+            assert_noop!(
+                Farming::kill_pool(RuntimeOrigin::signed(ALICE), pool_id),
+                pallet_farming::Error::<Runtime>::PoolBacksVault
+            );
+        });
+}
+
+#[test]
+fn deposit_and_withdraw_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, vault_id, share_token) = init_vault();
+            let account = FarmingVault::vault_account(vault_id);
+
+            assert_noop!(
+                FarmingVault::deposit(RuntimeOrigin::signed(ALICE), vault_id, 0),
+                Error::<Runtime>::ZeroAmount
+            );
+            assert_noop!(
+                FarmingVault::deposit(RuntimeOrigin::signed(ALICE), 1, 100),
+                Error::<Runtime>::VaultNotExist
+            );
+
+            assert_ok!(FarmingVault::deposit(
+                RuntimeOrigin::signed(ALICE),
+                vault_id,
+                1000
+            ));
+            assert_eq!(MantaCurrencies::free_balance(LP, &ALICE), 2000);
+            assert_eq!(MantaCurrencies::free_balance(share_token, &ALICE), 1000);
+            assert_eq!(FarmingVault::vaults(vault_id).unwrap().total_lp, 1000);
+            let share_info = Farming::shares_and_withdrawn_rewards(pool_id, &account).unwrap();
+            assert_eq!(share_info.share, 1000);
+
+            assert_noop!(
+                FarmingVault::withdraw(RuntimeOrigin::signed(ALICE), vault_id, 1001),
+                Error::<Runtime>::InsufficientShares
+            );
+            assert_ok!(FarmingVault::withdraw(
+                RuntimeOrigin::signed(ALICE),
+                vault_id,
+                400
+            ));
+            assert_eq!(MantaCurrencies::free_balance(LP, &ALICE), 2400);
+            assert_eq!(MantaCurrencies::free_balance(share_token, &ALICE), 600);
+            assert_eq!(FarmingVault::vaults(vault_id).unwrap().total_lp, 600);
+            let share_info = Farming::shares_and_withdrawn_rewards(pool_id, &account).unwrap();
+            assert_eq!(share_info.share, 600);
+            assert!(share_info.withdraw_list.is_empty());
+        });
+}
+
+#[test]
+fn compound_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, vault_id, share_token) = init_vault();
+            let account = FarmingVault::vault_account(vault_id);
+            assert_ok!(FarmingVault::deposit(
+                RuntimeOrigin::signed(ALICE),
+                vault_id,
+                1000
+            ));
+
+            // Samples the reference prices twice, which makes them fresh
+            System::set_block_number(System::block_number() + 4);
+            FarmingVault::on_initialize(System::block_number());
+            System::set_block_number(System::block_number() + 1);
+            FarmingVault::on_initialize(System::block_number());
+            let rewards = Farming::get_farming_rewards(&account, pool_id).unwrap();
+            let kma_rewards = rewards[0].1;
+            assert!(kma_rewards > 0);
+
+            assert_noop!(
+                FarmingVault::compound(RuntimeOrigin::signed(BOB), vault_id),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(FarmingVault::compound(
+                RuntimeOrigin::signed(ALICE),
+                vault_id
+            ));
+            // Half of the rewards above the existential deposit is swapped into each asset,
+            // which keep their existential deposit in the vault account as well.
+            let spendable = kma_rewards - 1;
+            let lp_amount = (spendable / 2 - 1).min(spendable - spendable / 2 - 1);
+            let vault = FarmingVault::vaults(vault_id).unwrap();
+            assert_eq!(vault.total_lp, 1000 + lp_amount);
+            assert_eq!(vault.last_compound_block, System::block_number());
+            let share_info = Farming::shares_and_withdrawn_rewards(pool_id, &account).unwrap();
+            assert_eq!(share_info.share, 1000 + lp_amount);
+            assert_eq!(
+                FarmingVault::share_price(vault_id),
+                FixedU128::checked_from_rational(1000 + lp_amount, 1000)
+            );
+
+            // Later depositors get fewer shares for the same LP tokens
+            assert_ok!(FarmingVault::deposit(
+                RuntimeOrigin::signed(BOB),
+                vault_id,
+                1000 + lp_amount
+            ));
+            assert_eq!(MantaCurrencies::free_balance(share_token, &BOB), 1000);
+
+            // ALICE withdraws her LP tokens along with the compounded rewards
+            assert_ok!(FarmingVault::withdraw(
+                RuntimeOrigin::signed(ALICE),
+                vault_id,
+                1000
+            ));
+            assert_eq!(MantaCurrencies::free_balance(LP, &ALICE), 3000 + lp_amount);
+            assert_eq!(
+                FarmingVault::vaults(vault_id).unwrap().total_lp,
+                1000 + lp_amount
+            );
+        });
+}
+
+#[test]
+fn on_initialize_should_compound() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (_, vault_id, _) = init_vault();
+            let (_, next_vault_id, _) = init_vault();
+            assert_ok!(FarmingVault::deposit(
+                RuntimeOrigin::signed(ALICE),
+                vault_id,
+                1000
+            ));
+            assert_ok!(FarmingVault::deposit(
+                RuntimeOrigin::signed(ALICE),
+                next_vault_id,
+                1000
+            ));
+
+            // Vaults are only compounded every `CompoundInterval` blocks, each vault is sampled
+            // every other block meanwhile
+            for n in 6..10 {
+                System::set_block_number(n);
+                FarmingVault::on_initialize(n);
+            }
+            assert_eq!(FarmingVault::vaults(vault_id).unwrap().total_lp, 1000);
+            assert_eq!(FarmingVault::compound_cursor(), None);
+
+            // One vault is compounded per block
+            System::set_block_number(10);
+            FarmingVault::on_initialize(10);
+            let vault = FarmingVault::vaults(vault_id).unwrap();
+            assert!(vault.total_lp > 1000);
+            assert_eq!(vault.last_compound_block, 10);
+            assert_eq!(FarmingVault::vaults(next_vault_id).unwrap().total_lp, 1000);
+            assert_eq!(FarmingVault::compound_cursor(), Some(next_vault_id));
+
+            System::set_block_number(11);
+            FarmingVault::on_initialize(11);
+            let vault = FarmingVault::vaults(next_vault_id).unwrap();
+            assert!(vault.total_lp > 1000);
+            assert_eq!(vault.last_compound_block, 11);
+            assert_eq!(FarmingVault::compound_cursor(), None);
+        });
+}
+
+#[test]
+fn compound_should_skip_deviated_prices() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (_, vault_id, _) = init_vault();
+            let account = FarmingVault::vault_account(vault_id);
+            assert_ok!(FarmingVault::deposit(
+                RuntimeOrigin::signed(ALICE),
+                vault_id,
+                1000
+            ));
+
+            // Nothing is swapped without reference prices
+            System::set_block_number(5);
+            assert_ok!(FarmingVault::compound(
+                RuntimeOrigin::signed(ALICE),
+                vault_id
+            ));
+            System::assert_last_event(RuntimeEvent::FarmingVault(Event::CompoundSkipped {
+                vault_id,
+            }));
+            assert_eq!(FarmingVault::vaults(vault_id).unwrap().total_lp, 1000);
+
+            // Nor with reference prices sampled only once
+            FarmingVault::on_initialize(5);
+            assert_eq!(
+                FarmingVault::reference_prices(KMA, KSM).unwrap().price,
+                FixedU128::one()
+            );
+            assert_eq!(FarmingVault::fresh_price(KMA, KSM), None);
+
+            // A price moved beyond `MaxPriceDeviation` is not compounded at, the claimed
+            // rewards wait in the vault account
+            SwapLoss::set(Perbill::from_percent(10));
+            assert_ok!(FarmingVault::compound(
+                RuntimeOrigin::signed(ALICE),
+                vault_id
+            ));
+            System::assert_last_event(RuntimeEvent::FarmingVault(Event::CompoundSkipped {
+                vault_id,
+            }));
+            assert_eq!(FarmingVault::vaults(vault_id).unwrap().total_lp, 1000);
+            assert!(MantaCurrencies::free_balance(KMA, &account) > 1);
+
+            // The reference price follows the price by the part of `CompoundInterval` elapsed
+            System::set_block_number(6);
+            FarmingVault::on_initialize(6);
+            assert_eq!(
+                FarmingVault::reference_prices(KMA, KSM).unwrap().price,
+                FixedU128::saturating_from_rational(99, 100)
+            );
+
+            SwapLoss::set(Perbill::zero());
+            assert_ok!(FarmingVault::compound(
+                RuntimeOrigin::signed(ALICE),
+                vault_id
+            ));
+            assert!(FarmingVault::vaults(vault_id).unwrap().total_lp > 1000);
+            assert_eq!(MantaCurrencies::free_balance(KMA, &account), 1);
+        });
+}
+
+#[test]
+fn compound_should_skip_stale_prices() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (_, vault_id, _) = init_vault();
+            assert_ok!(FarmingVault::deposit(
+                RuntimeOrigin::signed(ALICE),
+                vault_id,
+                1000
+            ));
+            for n in 2..4 {
+                System::set_block_number(n);
+                FarmingVault::sample_prices(vault_id);
+            }
+            assert_eq!(FarmingVault::fresh_price(KMA, KSM), Some(FixedU128::one()));
+
+            // Not sampled for a whole `CompoundInterval`, the reference prices are stale
+            System::set_block_number(13);
+            assert_eq!(FarmingVault::fresh_price(KMA, KSM), None);
+            assert_ok!(FarmingVault::compound(
+                RuntimeOrigin::signed(ALICE),
+                vault_id
+            ));
+            System::assert_last_event(RuntimeEvent::FarmingVault(Event::CompoundSkipped {
+                vault_id,
+            }));
+            assert_eq!(FarmingVault::vaults(vault_id).unwrap().total_lp, 1000);
+
+            // Sampled again, they move from the previous price and are fresh after the next
+            // sample
+            SwapLoss::set(Perbill::from_percent(2));
+            FarmingVault::sample_prices(vault_id);
+            let observation = FarmingVault::reference_prices(KMA, KSM).unwrap();
+            assert_eq!(
+                observation.price,
+                FixedU128::saturating_from_rational(98, 100)
+            );
+            assert_eq!(observation.since, 13);
+            assert_eq!(FarmingVault::fresh_price(KMA, KSM), None);
+
+            System::set_block_number(14);
+            FarmingVault::sample_prices(vault_id);
+            assert_eq!(
+                FarmingVault::fresh_price(KMA, KSM),
+                Some(FixedU128::saturating_from_rational(98, 100))
+            );
+            assert_ok!(FarmingVault::compound(
+                RuntimeOrigin::signed(ALICE),
+                vault_id
+            ));
+            assert!(FarmingVault::vaults(vault_id).unwrap().total_lp > 1000);
+        });
+}
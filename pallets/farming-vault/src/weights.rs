@@ -0,0 +1,108 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_farming_vault
+//!
+//! Estimated from the weights of the farming and zenlink extrinsics the vault calls into,
+//! until they are generated from `benchmarking.rs` with the command below.
+
+// Command:
+// target/release/manta
+// benchmark
+// pallet
+// --chain=calamari-dev
+// --pallet=pallet-farming-vault
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --steps=50
+// --repeat=20
+// --heap-pages=4096
+// --output=./pallets/farming-vault/src/weights.rs
+// --template=.github/resources/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_farming_vault.
+pub trait WeightInfo {
+	fn create_vault() -> Weight;
+	fn deposit() -> Weight;
+	fn withdraw() -> Weight;
+	fn compound() -> Weight;
+}
+
+/// Weights for pallet_farming_vault using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Farming PoolInfos (r:1 w:0), AssetManager (r:2 w:2), Assets (r:2 w:2), FarmingVault VaultPools (r:1 w:1), FarmingVault NextVaultId (r:1 w:1), FarmingVault Vaults (r:0 w:1)
+	fn create_vault() -> Weight {
+		Weight::from_parts(30_000_000, 6000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: FarmingVault Vaults (r:1 w:1), Farming PoolInfos (r:1 w:1), Farming SharesAndWithdrawnRewards (r:1 w:1), Farming BoostShares (r:1 w:1), Assets (r:5 w:5)
+	fn deposit() -> Weight {
+		Weight::from_parts(70_000_000, 12000)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: FarmingVault Vaults (r:1 w:1), Farming PoolInfos (r:1 w:1), Farming SharesAndWithdrawnRewards (r:1 w:1), Farming BoostShares (r:1 w:1), Assets (r:5 w:5)
+	fn withdraw() -> Weight {
+		Weight::from_parts(80_000_000, 12000)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: FarmingVault Vaults (r:1 w:1), Farming PoolInfos (r:1 w:1), Farming SharesAndWithdrawnRewards (r:1 w:1), ZenlinkProtocol PairStatuses (r:3 w:3), Assets (r:12 w:12)
+	fn compound() -> Weight {
+		Weight::from_parts(250_000_000, 30000)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().writes(18_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Farming PoolInfos (r:1 w:0), AssetManager (r:2 w:2), Assets (r:2 w:2), FarmingVault VaultPools (r:1 w:1), FarmingVault NextVaultId (r:1 w:1), FarmingVault Vaults (r:0 w:1)
+	fn create_vault() -> Weight {
+		Weight::from_parts(30_000_000, 6000)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: FarmingVault Vaults (r:1 w:1), Farming PoolInfos (r:1 w:1), Farming SharesAndWithdrawnRewards (r:1 w:1), Farming BoostShares (r:1 w:1), Assets (r:5 w:5)
+	fn deposit() -> Weight {
+		Weight::from_parts(70_000_000, 12000)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: FarmingVault Vaults (r:1 w:1), Farming PoolInfos (r:1 w:1), Farming SharesAndWithdrawnRewards (r:1 w:1), Farming BoostShares (r:1 w:1), Assets (r:5 w:5)
+	fn withdraw() -> Weight {
+		Weight::from_parts(80_000_000, 12000)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: FarmingVault Vaults (r:1 w:1), Farming PoolInfos (r:1 w:1), Farming SharesAndWithdrawnRewards (r:1 w:1), ZenlinkProtocol PairStatuses (r:3 w:3), Assets (r:12 w:12)
+	fn compound() -> Weight {
+		Weight::from_parts(250_000_000, 30000)
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().writes(18_u64))
+	}
+}
@@ -17,8 +17,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use manta_primitives::types::Balance;
+use manta_primitives::types::{Balance, BlockNumber};
+pub use pallet_farming::{PoolData, PoolState, PositionData};
 use sp_api::decl_runtime_apis;
+use sp_runtime::FixedU128;
 use sp_std::vec::Vec;
 
 decl_runtime_apis! {
@@ -36,5 +38,24 @@ decl_runtime_apis! {
             who: AccountId,
             pid: PoolId,
         ) -> Vec<(CurrencyId, Balance)>;
+
+        #[api_version(2)]
+        fn get_vesting_rewards(
+            who: AccountId,
+        ) -> Vec<(CurrencyId, Balance, Balance)>;
+
+        #[api_version(2)]
+        fn get_pools() -> Vec<PoolData<Balance, CurrencyId, BlockNumber>>;
+
+        #[api_version(2)]
+        fn get_positions(
+            who: AccountId,
+        ) -> Vec<PositionData<Balance, CurrencyId, BlockNumber>>;
+
+        #[api_version(2)]
+        fn get_pool_apr(
+            pid: PoolId,
+            prices: Vec<(CurrencyId, FixedU128)>,
+        ) -> Option<FixedU128>;
     }
 }
@@ -22,12 +22,45 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::error::{CallError, ErrorCode, ErrorObject},
 };
-use manta_primitives::types::Balance;
+use manta_primitives::types::{Balance, BlockNumber, PoolId as PoolIdOf};
 pub use pallet_farming_rpc_runtime_api::{self as runtime_api, FarmingRuntimeApi};
+use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_rpc::number::NumberOrHex;
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::{traits::Block as BlockT, FixedU128, Perbill};
+
+/// A farming pool as returned by `farming_getPools`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPoolData<CurrencyId> {
+    pub pool_id: PoolIdOf,
+    pub state: String,
+    pub tokens_proportion: Vec<(CurrencyId, Perbill)>,
+    pub total_shares: NumberOrHex,
+    pub rewards_per_block: Vec<(CurrencyId, NumberOrHex)>,
+    pub remaining_rewards: Vec<(CurrencyId, NumberOrHex)>,
+    pub end_block: Option<BlockNumber>,
+}
+
+/// A farming position as returned by `farming_getPositions`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPositionData<CurrencyId> {
+    pub pool_id: PoolIdOf,
+    pub share: NumberOrHex,
+    pub pending_rewards: Vec<(CurrencyId, NumberOrHex)>,
+    pub withdraw_list: Vec<(BlockNumber, NumberOrHex)>,
+}
+
+fn to_number_or_hex<CurrencyId>(
+    rewards: Vec<(CurrencyId, Balance)>,
+) -> Vec<(CurrencyId, NumberOrHex)> {
+    rewards
+        .into_iter()
+        .map(|(token, amount)| (token, NumberOrHex::Hex(amount.into())))
+        .collect()
+}
 
 #[rpc(client, server)]
 pub trait FarmingRpcApi<BlockHash, AccountId, CurrencyId, PoolId>
@@ -52,6 +85,36 @@ where
         pid: PoolId,
         at: Option<BlockHash>,
     ) -> RpcResult<Vec<(CurrencyId, NumberOrHex)>>;
+
+    /// rpc method for getting the locked and claimable vesting rewards
+    #[method(name = "farming_getVestingRewards")]
+    async fn get_vesting_rewards(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(CurrencyId, NumberOrHex, NumberOrHex)>>;
+
+    /// rpc method for listing all the farming pools
+    #[method(name = "farming_getPools")]
+    async fn get_pools(&self, at: Option<BlockHash>) -> RpcResult<Vec<RpcPoolData<CurrencyId>>>;
+
+    /// rpc method for listing the farming positions of an account across pools
+    #[method(name = "farming_getPositions")]
+    async fn get_positions(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<RpcPositionData<CurrencyId>>>;
+
+    /// rpc method for getting the APR of a pool, given the prices of the smallest unit
+    /// of each currency in a common quote
+    #[method(name = "farming_getPoolApr")]
+    async fn get_pool_apr(
+        &self,
+        pid: PoolId,
+        prices: Vec<(CurrencyId, FixedU128)>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<FixedU128>>;
 }
 
 #[derive(Clone, Debug)]
@@ -130,4 +193,116 @@ where
         }
         .map_err(jsonrpsee::core::Error::Call)
     }
+
+    async fn get_vesting_rewards(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(CurrencyId, NumberOrHex, NumberOrHex)>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let rs: Result<Vec<(CurrencyId, Balance, Balance)>, _> = api.get_vesting_rewards(at, who);
+
+        match rs {
+            Ok(rewards) => Ok(rewards
+                .into_iter()
+                .map(|(token, locked, claimable)| {
+                    (
+                        token,
+                        NumberOrHex::Hex(locked.into()),
+                        NumberOrHex::Hex(claimable.into()),
+                    )
+                })
+                .collect()),
+            Err(e) => Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Failed to get vesting rewards.",
+                Some(format!("{:?}", e)),
+            ))),
+        }
+        .map_err(jsonrpsee::core::Error::Call)
+    }
+
+    async fn get_pools(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<RpcPoolData<CurrencyId>>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        match api.get_pools(at) {
+            Ok(pools) => Ok(pools
+                .into_iter()
+                .map(|pool| RpcPoolData {
+                    pool_id: pool.pool_id,
+                    state: format!("{:?}", pool.state),
+                    tokens_proportion: pool.tokens_proportion,
+                    total_shares: NumberOrHex::Hex(pool.total_shares.into()),
+                    rewards_per_block: to_number_or_hex(pool.rewards_per_block),
+                    remaining_rewards: to_number_or_hex(pool.remaining_rewards),
+                    end_block: pool.end_block,
+                })
+                .collect()),
+            Err(e) => Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Failed to get farming pools.",
+                Some(format!("{:?}", e)),
+            ))),
+        }
+        .map_err(jsonrpsee::core::Error::Call)
+    }
+
+    async fn get_positions(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<RpcPositionData<CurrencyId>>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        match api.get_positions(at, who) {
+            Ok(positions) => Ok(positions
+                .into_iter()
+                .map(|position| RpcPositionData {
+                    pool_id: position.pool_id,
+                    share: NumberOrHex::Hex(position.share.into()),
+                    pending_rewards: to_number_or_hex(position.pending_rewards),
+                    withdraw_list: position
+                        .withdraw_list
+                        .into_iter()
+                        .map(|(unlock_block, amount)| {
+                            (unlock_block, NumberOrHex::Hex(amount.into()))
+                        })
+                        .collect(),
+                })
+                .collect()),
+            Err(e) => Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Failed to get farming positions.",
+                Some(format!("{:?}", e)),
+            ))),
+        }
+        .map_err(jsonrpsee::core::Error::Call)
+    }
+
+    async fn get_pool_apr(
+        &self,
+        pid: PoolId,
+        prices: Vec<(CurrencyId, FixedU128)>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<FixedU128>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_pool_apr(at, pid, prices)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    ErrorCode::InternalError.code(),
+                    "Failed to get pool APR.",
+                    Some(format!("{:?}", e)),
+                ))
+            })
+            .map_err(jsonrpsee::core::Error::Call)
+    }
 }
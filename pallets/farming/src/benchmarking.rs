@@ -18,8 +18,12 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use crate::{Pallet as Farming, *};
-use frame_benchmarking::{benchmarks, vec, whitelisted_caller};
-use frame_support::{assert_ok, sp_runtime::traits::UniqueSaturatedFrom};
+use frame_benchmarking::{account, benchmarks, vec, whitelisted_caller};
+use frame_support::{
+    assert_ok,
+    sp_runtime::traits::UniqueSaturatedFrom,
+    traits::{Currency, Get},
+};
 use frame_system::{Pallet as System, RawOrigin};
 use manta_primitives::{
     assets::{AssetConfig, AssetRegistryMetadata, FungibleLedger, TestingDefault},
@@ -31,7 +35,6 @@ pub const INITIAL_VALUE: u128 = 1_000_000_000_000_000_000_000u128;
 
 benchmarks! {
     where_clause { where T: pallet_assets::Config + pallet_asset_manager::Config, <T as pallet_asset_manager::Config>::AssetId: From<u32> }
-    on_initialize {}:{Farming::<T>::on_initialize(T::BlockNumber::from(10u32));}
 
     create_farming_pool {
         let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
@@ -47,7 +50,9 @@ benchmarks! {
         BlockNumberFor::<T>::from(0u32),
         BlockNumberFor::<T>::from(7u32),
         BlockNumberFor::<T>::from(6u32),
-        5
+        5,
+        None,
+        EmissionCurve::Constant
     )
 
     charge {
@@ -71,6 +76,8 @@ benchmarks! {
             BlockNumberFor::<T>::from(7u32),
             BlockNumberFor::<T>::from(6u32),
             5,
+            None,
+            EmissionCurve::Constant,
         ));
         let charge_rewards = vec![(ksm_asset_id, BalanceOf::<T>::unique_saturated_from(300000u128))];
 
@@ -82,6 +89,130 @@ benchmarks! {
         // assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
     }: _(RawOrigin::Signed(caller.clone()), 0, charge_rewards)
 
+    refund_charge {
+        let location = T::Location::default();
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        AssetManager::<T>::do_register_asset(Some(&location), &metadata)?;
+
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let caller: T::AccountId = whitelisted_caller();
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            vec![(ksm_asset_id, Perbill::from_percent(100))],
+            vec![(ksm_asset_id, token_amount)],
+            None,
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+            None,
+            EmissionCurve::Constant,
+        ));
+
+        let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+            8.into(),
+            &caller,
+            INITIAL_VALUE,
+        );
+        let charge_rewards = vec![(ksm_asset_id, BalanceOf::<T>::unique_saturated_from(300000u128))];
+        assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
+        System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
+        // The part of the charge never accrued is refunded from the closed pool
+        assert_ok!(Farming::<T>::close_pool(RawOrigin::Root.into(), 0));
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert!(Farming::<T>::charges(0, &caller).is_none());
+    }
+
+    add_incentive {
+        let location = T::Location::default();
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        AssetManager::<T>::do_register_asset(Some(&location), &metadata)?;
+
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let caller: T::AccountId = whitelisted_caller();
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            vec![(ksm_asset_id, Perbill::from_percent(100))],
+            vec![(ksm_asset_id, token_amount)],
+            None,
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+            None,
+            EmissionCurve::Constant,
+        ));
+
+        let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+            8.into(),
+            &caller,
+            INITIAL_VALUE,
+        );
+        let charge_rewards = vec![(ksm_asset_id, BalanceOf::<T>::unique_saturated_from(300000u128))];
+        assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
+        let reward_per_block = BalanceOf::<T>::unique_saturated_from(10u128);
+        let n = System::<T>::block_number();
+        // Every running stream is accrued before the new one is added
+        for _ in 1..T::MaxIncentives::get() {
+            assert_ok!(Farming::<T>::add_incentive(RawOrigin::Signed(caller.clone()).into(), 0, ksm_asset_id, reward_per_block, n, n + BlockNumberFor::<T>::from(100u32)));
+        }
+        System::<T>::set_block_number(n + BlockNumberFor::<T>::from(10u32));
+    }: _(RawOrigin::Signed(caller.clone()), 0, ksm_asset_id, reward_per_block, n + BlockNumberFor::<T>::from(10u32), n + BlockNumberFor::<T>::from(100u32))
+    verify {
+        assert_eq!(Farming::<T>::pool_infos(0).unwrap().incentives.len() as u32, T::MaxIncentives::get());
+    }
+
+    withdraw_incentive {
+        let location = T::Location::default();
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        AssetManager::<T>::do_register_asset(Some(&location), &metadata)?;
+
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let caller: T::AccountId = whitelisted_caller();
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            vec![(ksm_asset_id, Perbill::from_percent(100))],
+            vec![(ksm_asset_id, token_amount)],
+            None,
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+            None,
+            EmissionCurve::Constant,
+        ));
+
+        let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+            8.into(),
+            &caller,
+            INITIAL_VALUE,
+        );
+        let charge_rewards = vec![(ksm_asset_id, BalanceOf::<T>::unique_saturated_from(300000u128))];
+        assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
+        let reward_per_block = BalanceOf::<T>::unique_saturated_from(10u128);
+        let n = System::<T>::block_number();
+        for _ in 0..T::MaxIncentives::get() {
+            assert_ok!(Farming::<T>::add_incentive(RawOrigin::Signed(caller.clone()).into(), 0, ksm_asset_id, reward_per_block, n, n + BlockNumberFor::<T>::from(100u32)));
+        }
+        // The stream is withdrawn early from the closed pool, refunding most of it
+        System::<T>::set_block_number(n + BlockNumberFor::<T>::from(10u32));
+        assert_ok!(Farming::<T>::close_pool(RawOrigin::Root.into(), 0));
+        let incentive_id = Farming::<T>::incentive_next_id() - 1;
+    }: _(RawOrigin::Signed(caller.clone()), 0, incentive_id)
+    verify {
+        assert!(!Farming::<T>::pool_infos(0).unwrap().incentives.contains_key(&incentive_id));
+    }
+
     deposit {
         let location = T::Location::default();
         let metadata = AssetRegistryMetadata::<Balance>::testing_default();
@@ -103,6 +234,8 @@ benchmarks! {
             BlockNumberFor::<T>::from(7u32),
             BlockNumberFor::<T>::from(6u32),
             5,
+            None,
+            EmissionCurve::Constant,
         ));
         let charge_rewards = vec![(ksm_asset_id, BalanceOf::<T>::unique_saturated_from(300000u128))];
 
@@ -136,6 +269,8 @@ benchmarks! {
             BlockNumberFor::<T>::from(7u32),
             BlockNumberFor::<T>::from(6u32),
             5,
+            None,
+            EmissionCurve::Constant,
         ));
 
         let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
@@ -169,6 +304,8 @@ benchmarks! {
             BlockNumberFor::<T>::from(7u32),
             BlockNumberFor::<T>::from(6u32),
             5,
+            None,
+            EmissionCurve::Constant,
         ));
 
         let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
@@ -180,7 +317,6 @@ benchmarks! {
         assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
         assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
         System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
-        Farming::<T>::on_initialize(BlockNumberFor::<T>::from(0u32));
     }: _(RawOrigin::Signed(caller.clone()), 0)
 
     gauge_withdraw {
@@ -204,6 +340,8 @@ benchmarks! {
             BlockNumberFor::<T>::from(7u32),
             BlockNumberFor::<T>::from(6u32),
             5,
+            None,
+            EmissionCurve::Constant,
         ));
 
         let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
@@ -215,4 +353,276 @@ benchmarks! {
         assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
         assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, Some((BalanceOf::<T>::unique_saturated_from(100u128), BlockNumberFor::<T>::from(100u32)))));
     }: _(RawOrigin::Signed(caller.clone()), 0)
+
+    ve_lock {
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = BalanceOf::<T>::unique_saturated_from(1_000_000u128);
+        T::VoteEscrowCurrency::make_free_balance_be(&caller, BalanceOf::<T>::unique_saturated_from(2_000_000u128));
+        let n = System::<T>::block_number();
+        // Extending a lock also moves it out of the unlocks of its old end block
+        assert_ok!(Farming::<T>::ve_lock(RawOrigin::Signed(caller.clone()).into(), amount, n + T::EpochLength::get()));
+    }: _(RawOrigin::Signed(caller.clone()), amount, n + T::MaxLockBlocks::get())
+
+    ve_unlock {
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = BalanceOf::<T>::unique_saturated_from(1_000_000u128);
+        T::VoteEscrowCurrency::make_free_balance_be(&caller, amount);
+        let n = System::<T>::block_number();
+        assert_ok!(Farming::<T>::ve_lock(RawOrigin::Signed(caller.clone()).into(), amount, n + T::EpochLength::get()));
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            vec![(ksm_asset_id, Perbill::from_percent(100))],
+            vec![(ksm_asset_id, BalanceOf::<T>::unique_saturated_from(1000u128))],
+            None,
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+            None,
+            EmissionCurve::Constant,
+        ));
+        let votes: Vec<(PoolId, Perbill)> = vec![(0, Perbill::from_percent(100))];
+        assert_ok!(Farming::<T>::vote(RawOrigin::Signed(caller.clone()).into(), votes));
+        let voted_epoch = n / T::EpochLength::get();
+        System::<T>::set_block_number(n + T::EpochLength::get());
+        // The votes are retracted as if they were cast in the running epoch
+        let epoch = System::<T>::block_number() / T::EpochLength::get();
+        let (_, user_votes) = Farming::<T>::user_votes(&caller).unwrap();
+        UserVotes::<T>::insert(&caller, (epoch, user_votes));
+        PoolVotes::<T>::insert(epoch, Farming::<T>::pool_votes(voted_epoch));
+    }: _(RawOrigin::Signed(caller.clone()))
+
+    update_boost {
+        let location = T::Location::default();
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        AssetManager::<T>::do_register_asset(Some(&location), &metadata)?;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let tokens_proportion = vec![(ksm_asset_id, Perbill::from_percent(100))];
+        let basic_rewards = vec![(ksm_asset_id, token_amount)];
+        let gauge_basic_rewards = vec![(ksm_asset_id, token_amount)];
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            tokens_proportion,
+            basic_rewards,
+            Some((ksm_asset_id, BlockNumberFor::<T>::from(1000u32), gauge_basic_rewards)),
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+            None,
+            EmissionCurve::Constant,
+        ));
+
+        let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+            8.into(),
+            &caller,
+            INITIAL_VALUE,
+        );
+        let charge_rewards = vec![(ksm_asset_id,BalanceOf::<T>::unique_saturated_from(300000u128))];
+        assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
+
+        // The boost is claimed and removed once the lock expired, the heaviest update
+        let amount = BalanceOf::<T>::unique_saturated_from(1_000_000u128);
+        T::VoteEscrowCurrency::make_free_balance_be(&caller, amount);
+        let n = System::<T>::block_number();
+        assert_ok!(Farming::<T>::ve_lock(RawOrigin::Signed(caller.clone()).into(), amount, n + T::EpochLength::get()));
+        assert_ok!(Farming::<T>::update_boost(RawOrigin::Signed(caller.clone()).into(), 0, caller.clone()));
+        System::<T>::set_block_number(n + T::EpochLength::get());
+    }: _(RawOrigin::Signed(caller.clone()), 0, caller.clone())
+
+    vote {
+        let v in 1 .. T::MaxPoolVotes::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = BalanceOf::<T>::unique_saturated_from(1_000_000u128);
+        T::VoteEscrowCurrency::make_free_balance_be(&caller, amount);
+        let n = System::<T>::block_number();
+        assert_ok!(Farming::<T>::ve_lock(RawOrigin::Signed(caller.clone()).into(), amount, n + T::MaxLockBlocks::get()));
+
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        for _ in 0..v {
+            assert_ok!(Farming::<T>::create_farming_pool(
+                RawOrigin::Root.into(),
+                vec![(ksm_asset_id, Perbill::from_percent(100))],
+                vec![(ksm_asset_id, token_amount)],
+                None,
+                BalanceOf::<T>::unique_saturated_from(0u128),
+                BlockNumberFor::<T>::from(0u32),
+                BlockNumberFor::<T>::from(7u32),
+                BlockNumberFor::<T>::from(6u32),
+                5,
+                None,
+                EmissionCurve::Constant,
+            ));
+        }
+        let votes: Vec<(PoolId, Perbill)> = (0..v).map(|pool_id| (pool_id, Perbill::from_rational(1, v))).collect();
+        // Voting again replaces the votes of the epoch
+        assert_ok!(Farming::<T>::vote(RawOrigin::Signed(caller.clone()).into(), votes.clone()));
+    }: _(RawOrigin::Signed(caller.clone()), votes)
+
+    set_vesting {
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            vec![(ksm_asset_id, Perbill::from_percent(100))],
+            vec![(ksm_asset_id, token_amount)],
+            None,
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+            None,
+            EmissionCurve::Constant,
+        ));
+    }: _(RawOrigin::Root, 0, Some(BlockNumberFor::<T>::from(20u32)))
+
+    claim_vested {
+        let location = T::Location::default();
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        AssetManager::<T>::do_register_asset(Some(&location), &metadata)?;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let tokens_proportion = vec![(ksm_asset_id, Perbill::from_percent(100))];
+        let basic_rewards = vec![(ksm_asset_id, token_amount)];
+        let gauge_basic_rewards = vec![(ksm_asset_id, token_amount)];
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            tokens_proportion,
+            basic_rewards,
+            Some((ksm_asset_id, BlockNumberFor::<T>::from(1000u32), gauge_basic_rewards)),
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+            None,
+            EmissionCurve::Constant,
+        ));
+        assert_ok!(Farming::<T>::set_vesting(RawOrigin::Root.into(), 0, Some(BlockNumberFor::<T>::from(20u32))));
+
+        let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+            8.into(),
+            &caller,
+            INITIAL_VALUE,
+        );
+        let charge_rewards = vec![(ksm_asset_id,BalanceOf::<T>::unique_saturated_from(300000u128))];
+        assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
+        System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
+        assert_ok!(Farming::<T>::claim(RawOrigin::Signed(caller.clone()).into(), 0));
+        // Half vested, so the schedule is paid out and kept
+        System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
+    }: _(RawOrigin::Signed(caller.clone()), ksm_asset_id)
+
+    emergency_withdraw {
+        let location = T::Location::default();
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        AssetManager::<T>::do_register_asset(Some(&location), &metadata)?;
+
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let caller: T::AccountId = whitelisted_caller();
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        let tokens_proportion = vec![(ksm_asset_id, Perbill::from_percent(100))];
+        let basic_rewards = vec![(ksm_asset_id, token_amount)];
+        let gauge_basic_rewards = vec![(ksm_asset_id, token_amount)];
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            tokens_proportion,
+            basic_rewards,
+            Some((ksm_asset_id, BlockNumberFor::<T>::from(1000u32), gauge_basic_rewards)),
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+            None,
+            EmissionCurve::Constant,
+        ));
+        assert_ok!(Farming::<T>::set_emergency_withdraw(RawOrigin::Root.into(), 0, true));
+
+        let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+            8.into(),
+            &caller,
+            INITIAL_VALUE,
+        );
+        let charge_rewards = vec![(ksm_asset_id,BalanceOf::<T>::unique_saturated_from(300000u128))];
+        assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
+        // The gauge deposit is returned too
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, Some((BalanceOf::<T>::unique_saturated_from(100u128), BlockNumberFor::<T>::from(100u32)))));
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+
+    kill_pool {
+        let c in 1 .. T::MaxChargeRefunds::get();
+        let location = T::Location::default();
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        AssetManager::<T>::do_register_asset(Some(&location), &metadata)?;
+
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let caller: T::AccountId = whitelisted_caller();
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            vec![(ksm_asset_id, Perbill::from_percent(100))],
+            vec![(ksm_asset_id, token_amount)],
+            None,
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+            None,
+            EmissionCurve::Constant,
+        ));
+        let charge_rewards = vec![(ksm_asset_id, BalanceOf::<T>::unique_saturated_from(300000u128))];
+        for i in 0..c {
+            let charger: T::AccountId = if i == 0 { caller.clone() } else { account("charger", i, 0) };
+            let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+                8.into(),
+                &charger,
+                INITIAL_VALUE,
+            );
+            assert_ok!(Farming::<T>::charge(RawOrigin::Signed(charger).into(), 0, charge_rewards.clone()));
+        }
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
+        System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
+        // Every charge is refunded from a retired pool
+        assert_ok!(Farming::<T>::close_pool(RawOrigin::Root.into(), 0));
+        assert_ok!(Farming::<T>::set_retire_limit(RawOrigin::Root.into(), 10));
+        assert_ok!(Farming::<T>::retire_pool(RawOrigin::Root.into(), 0));
+    }: _(RawOrigin::Root, 0)
+    verify {
+        assert!(Farming::<T>::pool_infos(0).is_none());
+    }
+
+    set_charge_allowlist {
+        let a in 0 .. T::MaxAllowlist::get();
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            vec![(ksm_asset_id, Perbill::from_percent(100))],
+            vec![(ksm_asset_id, token_amount)],
+            None,
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+            None,
+            EmissionCurve::Constant,
+        ));
+        let allowlist: Vec<T::AccountId> = (0..a).map(|i| account("charger", i, 0)).collect();
+    }: _(RawOrigin::Root, 0, Some(allowlist))
 }
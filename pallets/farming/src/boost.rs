@@ -0,0 +1,467 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::{
+    pallet_prelude::*,
+    traits::{Currency, LockIdentifier, LockableCurrency, WithdrawReasons},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{AccountIdConversion, One, Saturating, Zero},
+    FixedPointNumber, FixedU128, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+
+use crate::*;
+
+pub const VOTE_ESCROW_ID: LockIdentifier = *b"farmvote";
+
+/// A vote-escrow lock of the native token, its voting power decays linearly to zero
+/// at `end_block`.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VeLock<BalanceOf, BlockNumberFor> {
+    pub amount: BalanceOf,
+    pub end_block: BlockNumberFor,
+}
+
+/// The sums over all unexpired vote-escrow locks, the total voting power at block `n`
+/// is `(locked_end - locked * n) / MaxLockBlocks`.
+#[derive(Clone, Encode, Decode, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VeSupply<BalanceOf, BlockNumberFor> {
+    /// Sum of the locked amounts
+    pub locked: BalanceOf,
+    /// Sum of the locked amounts weighted by their end block
+    pub locked_end: BalanceOf,
+    /// The block the locks expired up to were removed at
+    pub last_block: BlockNumberFor,
+}
+
+impl<T: Config> Pallet<T> {
+    /// The account holding the emission budget split among pools by the votes.
+    pub fn vote_emission_account() -> T::AccountId {
+        T::RewardIssuer::get().into_account_truncating()
+    }
+
+    /// The first epoch boundary after `n`, where the locks are allowed to end.
+    fn next_epoch_boundary(n: BlockNumberFor<T>) -> BlockNumberFor<T> {
+        let epoch_length = T::EpochLength::get().max(One::one());
+        (n / epoch_length).saturating_add(One::one()) * epoch_length
+    }
+
+    /// `VeSupply` with the locks expired by block `n` removed.
+    pub fn ve_supply_at(n: BlockNumberFor<T>) -> VeSupplyOf<T> {
+        let mut supply = Self::ve_supply();
+        let mut boundary = Self::next_epoch_boundary(supply.last_block);
+        while boundary <= n {
+            let (locked, locked_end) = Self::ve_unlocks(boundary);
+            supply.locked = supply.locked.saturating_sub(locked);
+            supply.locked_end = supply.locked_end.saturating_sub(locked_end);
+            boundary = Self::next_epoch_boundary(boundary);
+        }
+        supply.last_block = n;
+        supply
+    }
+
+    fn checkpoint_ve_supply(n: BlockNumberFor<T>) -> VeSupplyOf<T> {
+        let mut boundary = Self::next_epoch_boundary(Self::ve_supply().last_block);
+        let supply = Self::ve_supply_at(n);
+        while boundary <= n {
+            VeUnlocks::<T>::remove(boundary);
+            boundary = Self::next_epoch_boundary(boundary);
+        }
+        VeSupplyInfo::<T>::put(&supply);
+        supply
+    }
+
+    pub fn voting_power(who: &T::AccountId) -> BalanceOf<T> {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        match Self::ve_locks(who) {
+            Some(lock) if lock.end_block > n => Self::get_reward_inflation(
+                lock.amount,
+                &(lock.end_block - n).saturated_into(),
+                T::MaxLockBlocks::get().saturated_into(),
+            ),
+            _ => Zero::zero(),
+        }
+    }
+
+    pub fn total_voting_power() -> BalanceOf<T> {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let supply = Self::ve_supply_at(n);
+        let max_lock_blocks: BalanceOf<T> = T::MaxLockBlocks::get().saturated_into();
+        if max_lock_blocks.is_zero() {
+            return Zero::zero();
+        }
+        supply
+            .locked_end
+            .saturating_sub(supply.locked.saturating_mul(n.saturated_into()))
+            / max_lock_blocks
+    }
+
+    /// Locks `amount` more of the native token until `unlock_block`, rounded down to an
+    /// epoch boundary and not earlier than the current lock end.
+    pub fn ve_lock_inner(
+        who: &T::AccountId,
+        amount: BalanceOf<T>,
+        unlock_block: BlockNumberFor<T>,
+    ) -> DispatchResult {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let epoch_length = T::EpochLength::get().max(One::one());
+        let end_block = unlock_block / epoch_length * epoch_length;
+        ensure!(
+            end_block > n && end_block <= n.saturating_add(T::MaxLockBlocks::get()),
+            Error::<T>::InvalidVeLock
+        );
+
+        let mut supply = Self::checkpoint_ve_supply(n);
+        let old_lock = Self::ve_locks(who);
+        let old_amount = match &old_lock {
+            Some(lock) => {
+                ensure!(lock.end_block > n, Error::<T>::VeLockExpired);
+                ensure!(end_block >= lock.end_block, Error::<T>::InvalidVeLock);
+                Self::remove_ve_lock_from_supply(&mut supply, lock);
+                lock.amount
+            }
+            None => Zero::zero(),
+        };
+        let lock = VeLock {
+            amount: old_amount.saturating_add(amount),
+            end_block,
+        };
+        ensure!(!lock.amount.is_zero(), Error::<T>::InvalidVeLock);
+        ensure!(
+            T::VoteEscrowCurrency::free_balance(who) >= lock.amount,
+            Error::<T>::InsufficientBalance
+        );
+
+        let locked_end = lock.amount.saturating_mul(end_block.saturated_into());
+        supply.locked = supply.locked.saturating_add(lock.amount);
+        supply.locked_end = supply.locked_end.saturating_add(locked_end);
+        VeUnlocks::<T>::mutate(end_block, |(locked, unlocked_end)| {
+            *locked = locked.saturating_add(lock.amount);
+            *unlocked_end = unlocked_end.saturating_add(locked_end);
+        });
+        VeSupplyInfo::<T>::put(supply);
+        T::VoteEscrowCurrency::set_lock(VOTE_ESCROW_ID, who, lock.amount, WithdrawReasons::all());
+        VeLocks::<T>::insert(who, &lock);
+
+        Self::deposit_event(Event::VeLocked {
+            who: who.clone(),
+            amount: lock.amount,
+            end_block,
+        });
+        Ok(())
+    }
+
+    fn remove_ve_lock_from_supply(supply: &mut VeSupplyOf<T>, lock: &VeLockOf<T>) {
+        let locked_end = lock.amount.saturating_mul(lock.end_block.saturated_into());
+        supply.locked = supply.locked.saturating_sub(lock.amount);
+        supply.locked_end = supply.locked_end.saturating_sub(locked_end);
+        VeUnlocks::<T>::mutate(lock.end_block, |(locked, unlocked_end)| {
+            *locked = locked.saturating_sub(lock.amount);
+            *unlocked_end = unlocked_end.saturating_sub(locked_end);
+        });
+    }
+
+    pub fn ve_unlock_inner(who: &T::AccountId) -> DispatchResult {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let lock = Self::ve_locks(who).ok_or(Error::<T>::VeLockNotExist)?;
+        ensure!(lock.end_block <= n, Error::<T>::VeLockNotExpired);
+
+        // The expired lock left the supply at its end block
+        Self::checkpoint_ve_supply(n);
+        T::VoteEscrowCurrency::remove_lock(VOTE_ESCROW_ID, who);
+        VeLocks::<T>::remove(who);
+        // The votes of the running epoch lose the voting power backing them
+        if let Some((voted_epoch, votes)) = UserVotes::<T>::take(who) {
+            if voted_epoch == Self::epoch_of(n) {
+                PoolVotes::<T>::mutate(voted_epoch, |pool_votes| {
+                    Self::retract_votes(pool_votes, votes)
+                });
+            }
+        }
+
+        Self::deposit_event(Event::VeUnlocked {
+            who: who.clone(),
+            amount: lock.amount,
+        });
+        Ok(())
+    }
+
+    /// The boost shares `who` earns on top of `share` when the pool holds `total_shares`
+    /// without boost: up to `MaxBoost - 1` times the share, limited to the part of the
+    /// pool matching the voting power of `who`.
+    fn boost_target(
+        who: &T::AccountId,
+        share: BalanceOf<T>,
+        total_shares: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let total_voting_power = Self::total_voting_power();
+        if total_voting_power.is_zero() {
+            return Zero::zero();
+        }
+        let voting_shares =
+            Self::get_reward_inflation(Self::voting_power(who), &total_shares, total_voting_power);
+        T::MaxBoost::get()
+            .saturating_sub(FixedU128::one())
+            .saturating_mul_int(share.min(voting_shares).saturated_into::<u128>())
+            .saturated_into()
+    }
+
+    /// Brings the boost shares of `who` in `pool_id` in line with its current voting power.
+    pub fn update_boost_inner(who: &T::AccountId, pool_id: PoolId) -> DispatchResult {
+        let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        Self::accrue_pool_rewards(&mut pool_info);
+        if !PoolState::state_valid(Action::Deposit, pool_info.state) {
+            return Ok(());
+        }
+        let share_info = match Self::shares_and_withdrawn_rewards(pool_id, who) {
+            Some(share_info) => share_info,
+            None => return Ok(()),
+        };
+
+        let boost = Self::boost_shares(pool_id, who);
+        let target = Self::boost_target(
+            who,
+            share_info.share.saturating_sub(boost),
+            pool_info
+                .total_shares
+                .saturating_sub(Self::pool_boosts(pool_id)),
+        );
+        if target > boost {
+            Self::add_share(who, pool_id, &mut pool_info, target - boost);
+        } else if target < boost {
+            Self::claim_rewards(who, pool_id)?;
+            Self::remove_boost_share(who, pool_id, boost - target)?;
+        } else {
+            return Ok(());
+        }
+        if target.is_zero() {
+            BoostShares::<T>::remove(pool_id, who);
+        } else {
+            BoostShares::<T>::insert(pool_id, who, target);
+        }
+        PoolBoosts::<T>::mutate(pool_id, |total_boost| {
+            *total_boost = total_boost.saturating_add(target).saturating_sub(boost);
+        });
+
+        Self::deposit_event(Event::BoostUpdated {
+            who: who.clone(),
+            pid: pool_id,
+            boost: target,
+        });
+        Ok(())
+    }
+
+    fn remove_boost_share(
+        who: &T::AccountId,
+        pool_id: PoolId,
+        remove_amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        SharesAndWithdrawnRewards::<T>::try_mutate(pool_id, who, |maybe_share_info| {
+            let share_info = maybe_share_info
+                .as_mut()
+                .ok_or(Error::<T>::ShareInfoNotExists)?;
+            PoolInfos::<T>::try_mutate(pool_id, |maybe_pool_info| -> DispatchResult {
+                let pool_info = maybe_pool_info
+                    .as_mut()
+                    .ok_or(Error::<T>::PoolDoesNotExist)?;
+                Self::accrue_pool_rewards(pool_info);
+                Self::reduce_share(pool_info, share_info, remove_amount);
+                Ok(())
+            })
+        })
+    }
+
+    pub fn vote_inner(who: &T::AccountId, votes: Vec<(PoolId, Perbill)>) -> DispatchResult {
+        let votes: BoundedVec<(PoolId, Perbill), T::MaxPoolVotes> =
+            votes.try_into().map_err(|_| Error::<T>::TooManyVotes)?;
+        let total_proportion = votes.iter().fold(0u64, |total, (_, proportion)| {
+            total.saturating_add(proportion.deconstruct().into())
+        });
+        ensure!(
+            total_proportion <= Perbill::one().deconstruct().into(),
+            Error::<T>::InvalidVotes
+        );
+        let voting_power = Self::voting_power(who);
+        ensure!(!voting_power.is_zero(), Error::<T>::NoVotingPower);
+
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let epoch = Self::epoch_of(n);
+        let mut pool_votes = Self::pool_votes(epoch);
+        // The votes of a past epoch were already distributed and are just replaced
+        if let Some((voted_epoch, old_votes)) = Self::user_votes(who) {
+            if voted_epoch == epoch {
+                Self::retract_votes(&mut pool_votes, old_votes);
+            }
+        }
+        let votes = votes
+            .into_iter()
+            .map(|(pool_id, proportion)| {
+                ensure!(
+                    PoolInfos::<T>::contains_key(pool_id),
+                    Error::<T>::PoolDoesNotExist
+                );
+                let weight = proportion * voting_power;
+                if !weight.is_zero() {
+                    let total_weight = pool_votes.get(&pool_id).copied().unwrap_or_default();
+                    pool_votes
+                        .try_insert(pool_id, total_weight.saturating_add(weight))
+                        .map_err(|_| Error::<T>::TooManyVotedPools)?;
+                }
+                Ok((pool_id, weight))
+            })
+            .collect::<Result<Vec<_>, DispatchError>>()?;
+        PoolVotes::<T>::insert(epoch, pool_votes);
+        UserVotes::<T>::insert(
+            who,
+            (
+                epoch,
+                BoundedVec::<_, T::MaxPoolVotes>::truncate_from(votes.clone()),
+            ),
+        );
+
+        Self::deposit_event(Event::Voted {
+            who: who.clone(),
+            epoch,
+            votes,
+        });
+        Ok(())
+    }
+
+    /// The epoch block `n` falls into.
+    fn epoch_of(n: BlockNumberFor<T>) -> BlockNumberFor<T> {
+        n / T::EpochLength::get().max(One::one())
+    }
+
+    /// Takes `votes` of an account back out of the pool votes of their epoch.
+    fn retract_votes(
+        pool_votes: &mut BoundedBTreeMap<PoolId, BalanceOf<T>, T::MaxVotedPools>,
+        votes: BoundedVec<(PoolId, BalanceOf<T>), T::MaxPoolVotes>,
+    ) {
+        votes.into_iter().for_each(|(pool_id, weight)| {
+            if let Some(total_weight) = pool_votes.get_mut(&pool_id) {
+                *total_weight = total_weight.saturating_sub(weight);
+                if total_weight.is_zero() {
+                    pool_votes.remove(&pool_id);
+                }
+            }
+        });
+    }
+
+    /// At the start of an epoch, splits `VoteEmission` among the pools by the votes of the
+    /// previous epoch, each share is streamed to its pool over this epoch as an incentive
+    /// sponsored by `vote_emission_account`. The shares of pools already holding
+    /// `MaxIncentives` streams stay in the emission budget.
+    pub fn on_epoch_start(n: BlockNumberFor<T>) -> Weight {
+        let epoch_length = T::EpochLength::get();
+        if epoch_length.is_zero() || n.is_zero() || !(n % epoch_length).is_zero() {
+            return Weight::zero();
+        }
+        Self::checkpoint_ve_supply(n);
+
+        let epoch = n / epoch_length - One::one();
+        let votes = PoolVotes::<T>::take(epoch);
+        let total_weight = votes
+            .iter()
+            .fold(BalanceOf::<T>::zero(), |total, (_, weight)| {
+                total.saturating_add(*weight)
+            });
+        let emission = Self::vote_emission();
+        if total_weight.is_zero() || emission.is_empty() {
+            return T::DbWeight::get().reads_writes(4, 3);
+        }
+
+        let sponsor = Self::vote_emission_account();
+        let mut refunds = 0u64;
+        votes.iter().for_each(|(pool_id, weight)| {
+            let mut pool_info = match Self::pool_infos(pool_id) {
+                Some(pool_info) => pool_info,
+                None => return,
+            };
+            Self::accrue_pool_rewards(&mut pool_info);
+            if !PoolState::state_valid(Action::Deposit, pool_info.state) {
+                return;
+            }
+
+            // Sweep the streams of the past epochs back to the emission budget
+            let ended: Vec<IncentiveId> = pool_info
+                .incentives
+                .iter()
+                .filter(|(_, incentive)| incentive.sponsor == sponsor && incentive.end_block <= n)
+                .map(|(incentive_id, _)| *incentive_id)
+                .collect();
+            ended.into_iter().for_each(|incentive_id| {
+                if let Some(incentive) = pool_info.incentives.get(&incentive_id) {
+                    refunds = refunds.saturating_add(1);
+                    if Self::refund_incentive(*pool_id, &pool_info, incentive_id, incentive).is_ok()
+                    {
+                        pool_info.incentives.remove(&incentive_id);
+                    }
+                }
+            });
+
+            emission.iter().for_each(|(reward_currency, amount)| {
+                if (pool_info.incentives.len() as u32) >= T::MaxIncentives::get() {
+                    return;
+                }
+                let blocks: BalanceOf<T> = epoch_length.saturated_into();
+                let reward_per_block =
+                    Self::get_reward_inflation(*weight, amount, total_weight) / blocks;
+                if reward_per_block.is_zero()
+                    || T::MultiCurrency::transfer(
+                        *reward_currency,
+                        &sponsor,
+                        &pool_info.reward_issuer,
+                        reward_per_block.saturating_mul(blocks),
+                    )
+                    .is_err()
+                {
+                    return;
+                }
+                let incentive_id = Self::incentive_next_id();
+                IncentiveNextId::<T>::put(incentive_id.saturating_add(1));
+                pool_info.incentives.insert(
+                    incentive_id,
+                    IncentiveInfo {
+                        sponsor: sponsor.clone(),
+                        reward_currency: *reward_currency,
+                        reward_per_block,
+                        start_block: n,
+                        end_block: n.saturating_add(epoch_length),
+                        remaining: reward_per_block.saturating_mul(blocks),
+                        last_reward_block: n,
+                    },
+                );
+            });
+            PoolInfos::<T>::insert(pool_id, pool_info);
+        });
+
+        Self::deposit_event(Event::VotesDistributed { epoch });
+        // Every stream paid out or refunded is an asset transfer touching three items
+        let transfers = (votes.len() as u64)
+            .saturating_mul(emission.len() as u64)
+            .saturating_add(refunds);
+        T::DbWeight::get().reads_writes(
+            transfers.saturating_mul(3)
+                + (votes.len() as u64).saturating_mul(emission.len() as u64 + 2)
+                + 4,
+            transfers.saturating_mul(3)
+                + (votes.len() as u64).saturating_mul(emission.len() as u64 + 1)
+                + 4,
+        )
+    }
+}
@@ -72,6 +72,8 @@ pub struct GaugePoolInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf,
     pub total_time_factor: u128,
     pub gauge_state: GaugeState,
     pub gauge_last_block: BlockNumberFor,
+    /// The block up to which `gauge_basic_rewards` have been accrued into `rewards`
+    pub last_reward_block: BlockNumberFor,
 }
 
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
@@ -106,8 +108,9 @@ where
             max_block,
             gauge_amount: Default::default(),
             total_time_factor: Default::default(),
-            gauge_last_block: current_block_number,
+            gauge_last_block: current_block_number.clone(),
             gauge_state: GaugeState::Bonded,
+            last_reward_block: current_block_number,
         }
     }
 }
@@ -117,6 +120,34 @@ where
     BlockNumberFor<T>: AtLeast32BitUnsigned + Copy,
     BalanceOf<T>: AtLeast32BitUnsigned + Copy,
 {
+    /// Accrues `gauge_basic_rewards` of a bonded gauge pool for every block since
+    /// `last_reward_block`.
+    pub fn accrue_gauge_rewards(gauge_pool_info: &mut GaugePoolInfoOf<T>) {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        if gauge_pool_info.gauge_state != GaugeState::Bonded
+            || n <= gauge_pool_info.last_reward_block
+        {
+            return;
+        }
+
+        let blocks: BalanceOf<T> = (n - gauge_pool_info.last_reward_block)
+            .saturated_into::<u128>()
+            .saturated_into();
+        gauge_pool_info.gauge_basic_rewards.clone().iter().for_each(
+            |(reward_currency_id, reward_amount)| {
+                let reward_amount = reward_amount.saturating_mul(blocks);
+                gauge_pool_info
+                    .rewards
+                    .entry(*reward_currency_id)
+                    .and_modify(|(total_reward, _, _)| {
+                        *total_reward = total_reward.saturating_add(reward_amount);
+                    })
+                    .or_insert((reward_amount, Zero::zero(), Zero::zero()));
+            },
+        );
+        gauge_pool_info.last_reward_block = n;
+    }
+
     pub fn create_gauge_pool(
         pool_id: PoolId,
         pool_info: &mut PoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
@@ -235,6 +266,7 @@ where
         let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
         let mut gauge_pool_info =
             GaugePoolInfos::<T>::get(gid).ok_or(Error::<T>::GaugePoolNotExist)?;
+        Self::accrue_gauge_rewards(&mut gauge_pool_info);
         let pool_info =
             PoolInfos::<T>::get(gauge_pool_info.pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
         GaugeInfos::<T>::mutate_exists(gid, who, |maybe_gauge_info| -> DispatchResult {
@@ -272,7 +304,8 @@ where
                             .checked_add(&reward_to_claim)
                             .ok_or(ArithmeticError::Overflow)?;
 
-                        Self::farming_token_transfer(
+                        Self::reward_transfer(
+                            gauge_pool_info.pool_id,
                             reward_currency,
                             reward_to_claim,
                             who,
@@ -318,8 +351,9 @@ where
         match pool_info.gauge {
             None => (),
             Some(gid) => {
-                let gauge_pool_info =
+                let mut gauge_pool_info =
                     GaugePoolInfos::<T>::get(gid).ok_or(Error::<T>::GaugePoolNotExist)?;
+                Self::accrue_gauge_rewards(&mut gauge_pool_info);
                 let gauge_info =
                     GaugeInfos::<T>::get(gid, who).ok_or(Error::<T>::GaugeInfoNotExist)?;
                 let (gauge_rate, _) = Self::get_gauge_rate(&gauge_pool_info, &gauge_info)?;
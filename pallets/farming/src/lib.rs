@@ -16,29 +16,36 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{pallet_prelude::*, PalletId};
+use frame_support::{
+    pallet_prelude::*, storage::with_storage_layer, traits::LockableCurrency, PalletId,
+};
 use frame_system::pallet_prelude::*;
 use manta_primitives::types::PoolId;
 use orml_traits::{arithmetic::CheckedAdd, MultiCurrency};
 use sp_core::U256;
 use sp_runtime::{
-    traits::{AccountIdConversion, AtLeast32BitUnsigned, One, Saturating, Zero},
-    ArithmeticError, Perbill, SaturatedConversion,
+    traits::{AccountIdConversion, AtLeast32BitUnsigned, CheckedMul, One, Saturating, Zero},
+    ArithmeticError, FixedPointNumber, FixedU128, Perbill, SaturatedConversion,
 };
 use sp_std::{borrow::ToOwned, collections::btree_map::BTreeMap, vec::Vec};
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod boost;
 pub mod gauge;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 pub mod rewards;
 #[cfg(test)]
 mod tests;
+pub mod vesting;
 pub mod weights;
+pub use boost::*;
 pub use gauge::*;
 pub use pallet::*;
 pub use rewards::*;
+pub use vesting::*;
 pub use weights::WeightInfo;
 
 #[allow(type_alias_bounds)]
@@ -60,14 +67,30 @@ type GaugeInitType<T: Config> = (
     Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
 );
 
+#[allow(type_alias_bounds)]
+type EmissionInitType<T: Config> = (Option<BlockNumberFor<T>>, EmissionCurve<BlockNumberFor<T>>);
+
 #[allow(type_alias_bounds)]
 type PoolInfoOf<T: Config> =
     PoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>;
 
+#[allow(type_alias_bounds)]
+type IncentiveInfoOf<T> =
+    IncentiveInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>;
+
 #[allow(type_alias_bounds)]
 type GaugePoolInfoOf<T: Config> =
     GaugePoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>;
 
+#[allow(type_alias_bounds)]
+type VeLockOf<T> = VeLock<BalanceOf<T>, BlockNumberFor<T>>;
+
+#[allow(type_alias_bounds)]
+type VeSupplyOf<T> = VeSupply<BalanceOf<T>, BlockNumberFor<T>>;
+
+#[allow(type_alias_bounds)]
+type VestingScheduleOf<T> = VestingSchedule<BalanceOf<T>, BlockNumberFor<T>>;
+
 #[allow(type_alias_bounds)]
 type GaugeInfoOf<T> = GaugeInfo<BalanceOf<T>, BlockNumberFor<T>, AccountIdOf<T>>;
 
@@ -77,12 +100,34 @@ type ShareInfoOf<T> = ShareInfo<BalanceOf<T>, CurrencyIdOf<T>, BlockNumberFor<T>
 #[allow(type_alias_bounds)]
 type RewardOf<T> = Vec<(CurrencyIdOf<T>, BalanceOf<T>)>;
 
+#[allow(type_alias_bounds)]
+pub type PoolDataOf<T> = PoolData<BalanceOf<T>, CurrencyIdOf<T>, BlockNumberFor<T>>;
+
+#[allow(type_alias_bounds)]
+pub type PositionDataOf<T> = PositionData<BalanceOf<T>, CurrencyIdOf<T>, BlockNumberFor<T>>;
+
+/// Tells whether other pallets rely on the parameters of a farming pool.
+pub trait PoolGuard {
+    /// Whether the pool `pool_id` backs a vault, which must not be edited, reset, retired or
+    /// killed.
+    fn is_vault_pool(pool_id: PoolId) -> bool;
+}
+
+impl PoolGuard for () {
+    fn is_vault_pool(_pool_id: PoolId) -> bool {
+        false
+    }
+}
+
 #[frame_support::pallet]
 #[allow(clippy::too_many_arguments)]
 pub mod pallet {
     use super::*;
 
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -119,6 +164,44 @@ pub mod pallet {
 
         #[pallet::constant]
         type RewardIssuer: Get<PalletId>;
+
+        /// The maximum number of incentive streams a pool can hold at once
+        #[pallet::constant]
+        type MaxIncentives: Get<u32>;
+
+        /// The native currency locked for voting power
+        type VoteEscrowCurrency: LockableCurrency<Self::AccountId, Balance = BalanceOf<Self>>;
+
+        /// The longest vote-escrow lock, which gets a voting power equal to the locked amount
+        #[pallet::constant]
+        type MaxLockBlocks: Get<BlockNumberFor<Self>>;
+
+        /// The maximum multiplier of farming shares boosted by voting power
+        #[pallet::constant]
+        type MaxBoost: Get<FixedU128>;
+
+        /// The length of the epochs votes split the emission budget for
+        #[pallet::constant]
+        type EpochLength: Get<BlockNumberFor<Self>>;
+
+        /// The maximum number of pools one vote can split its voting power among
+        #[pallet::constant]
+        type MaxPoolVotes: Get<u32>;
+
+        /// The maximum number of pools the votes of one epoch can go to
+        #[pallet::constant]
+        type MaxVotedPools: Get<u32>;
+
+        /// The maximum number of accounts allowed to charge one pool
+        #[pallet::constant]
+        type MaxAllowlist: Get<u32>;
+
+        /// The maximum number of charges refunded by one `kill_pool`
+        #[pallet::constant]
+        type MaxChargeRefunds: Get<u32>;
+
+        /// The pools other pallets rely on, which can not be edited, reset, retired or killed
+        type PoolGuard: PoolGuard;
     }
 
     #[pallet::event]
@@ -144,6 +227,11 @@ pub mod pallet {
             pid: PoolId,
             rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
         },
+        ChargeRefunded {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+        },
         Deposited {
             who: AccountIdOf<T>,
             pid: PoolId,
@@ -182,6 +270,78 @@ pub mod pallet {
         RetireLimitSet {
             limit: u32,
         },
+        IncentiveAdded {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            incentive_id: IncentiveId,
+            reward_currency: CurrencyIdOf<T>,
+            reward_per_block: BalanceOf<T>,
+            start_block: BlockNumberFor<T>,
+            end_block: BlockNumberFor<T>,
+        },
+        IncentiveWithdrawn {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            incentive_id: IncentiveId,
+            reward_currency: CurrencyIdOf<T>,
+            amount: BalanceOf<T>,
+        },
+        VeLocked {
+            who: AccountIdOf<T>,
+            amount: BalanceOf<T>,
+            end_block: BlockNumberFor<T>,
+        },
+        VeUnlocked {
+            who: AccountIdOf<T>,
+            amount: BalanceOf<T>,
+        },
+        BoostUpdated {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            boost: BalanceOf<T>,
+        },
+        Voted {
+            who: AccountIdOf<T>,
+            epoch: BlockNumberFor<T>,
+            votes: Vec<(PoolId, BalanceOf<T>)>,
+        },
+        VoteEmissionSet {
+            rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+        },
+        VotesDistributed {
+            epoch: BlockNumberFor<T>,
+        },
+        VestingSet {
+            pid: PoolId,
+            vesting_blocks: Option<BlockNumberFor<T>>,
+        },
+        RewardsVested {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            reward_currency: CurrencyIdOf<T>,
+            amount: BalanceOf<T>,
+        },
+        VestedClaimed {
+            who: AccountIdOf<T>,
+            reward_currency: CurrencyIdOf<T>,
+            amount: BalanceOf<T>,
+        },
+        EmergencyWithdrawSet {
+            pid: PoolId,
+            enabled: bool,
+        },
+        EmergencyWithdrawn {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            share: BalanceOf<T>,
+        },
+        ChargeAllowlistSet {
+            pid: PoolId,
+            allowlist: Option<Vec<AccountIdOf<T>>>,
+        },
+        ChargesPartiallyRefunded {
+            pid: PoolId,
+        },
     }
 
     #[pallet::error]
@@ -210,6 +370,46 @@ pub mod pallet {
         CanNotDeposit,
         /// The retire limit number is not set
         RetireLimitNotSet,
+        /// Charge info not exist
+        ChargeNotExist,
+        /// Incentive parameter is invalid
+        InvalidIncentiveParameter,
+        /// Incentive not exist
+        IncentiveNotExist,
+        /// The pool holds the maximum number of incentives
+        TooManyIncentives,
+        /// Only the sponsor can withdraw the incentive
+        NotIncentiveSponsor,
+        /// The incentive is still emitting rewards
+        IncentiveNotEnded,
+        /// Vote-escrow lock amount or unlock block is invalid
+        InvalidVeLock,
+        /// Vote-escrow lock not exist
+        VeLockNotExist,
+        /// Vote-escrow lock is expired and must be unlocked first
+        VeLockExpired,
+        /// Vote-escrow lock is not expired yet
+        VeLockNotExpired,
+        /// Not enough free balance to lock
+        InsufficientBalance,
+        /// The votes split more than the whole voting power
+        InvalidVotes,
+        /// The votes exceed the maximum number of pools
+        TooManyVotes,
+        /// The votes of the epoch already go to the maximum number of pools
+        TooManyVotedPools,
+        /// No voting power to vote with
+        NoVotingPower,
+        /// Vesting schedule not exist
+        VestingNotExist,
+        /// Emergency withdraw is not enabled for the pool
+        EmergencyWithdrawNotEnabled,
+        /// The account is not in the charge allowlist of the pool
+        NotAllowedToCharge,
+        /// The charge allowlist holds more than `MaxAllowlist` accounts
+        TooManyAllowedChargers,
+        /// The pool backs a vault and can not be edited, reset, retired or killed
+        PoolBacksVault,
     }
 
     /// The next farming pool id.
@@ -222,6 +422,11 @@ pub mod pallet {
     #[pallet::getter(fn gauge_pool_next_id)]
     pub type GaugePoolNextId<T: Config> = StorageValue<_, PoolId, ValueQuery>;
 
+    /// The next incentive id.
+    #[pallet::storage]
+    #[pallet::getter(fn incentive_next_id)]
+    pub type IncentiveNextId<T: Config> = StorageValue<_, IncentiveId, ValueQuery>;
+
     /// The retire limit of one operation when retire farming pool.
     #[pallet::storage]
     #[pallet::getter(fn retire_limit)]
@@ -258,54 +463,139 @@ pub mod pallet {
     pub type SharesAndWithdrawnRewards<T: Config> =
         StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, T::AccountId, ShareInfoOf<T>>;
 
+    /// Record the rewards charged by specific `AccountId` to `PoolId`, refundable
+    /// once the pool is dead or killed.
+    ///
+    /// double_map (PoolId, AccountId) => BTreeMap<CurrencyId, Balance>
+    #[pallet::storage]
+    #[pallet::getter(fn charges)]
+    pub type Charges<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        PoolId,
+        Twox64Concat,
+        T::AccountId,
+        BTreeMap<CurrencyIdOf<T>, BalanceOf<T>>,
+    >;
+
+    /// Record the vote-escrow lock of `AccountId`.
+    ///
+    /// map AccountId => VeLock
+    #[pallet::storage]
+    #[pallet::getter(fn ve_locks)]
+    pub type VeLocks<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, VeLockOf<T>>;
+
+    /// The sums over the unexpired vote-escrow locks.
+    #[pallet::storage]
+    #[pallet::getter(fn ve_supply)]
+    pub type VeSupplyInfo<T: Config> = StorageValue<_, VeSupplyOf<T>, ValueQuery>;
+
+    /// The sums over the vote-escrow locks ending at an epoch boundary.
+    ///
+    /// map BlockNumber => (locked, locked_end)
+    #[pallet::storage]
+    #[pallet::getter(fn ve_unlocks)]
+    pub type VeUnlocks<T: Config> =
+        StorageMap<_, Twox64Concat, BlockNumberFor<T>, (BalanceOf<T>, BalanceOf<T>), ValueQuery>;
+
+    /// The shares `AccountId` holds in `PoolId` on top of its deposit thanks to its voting power.
+    ///
+    /// double_map (PoolId, AccountId) => Balance
+    #[pallet::storage]
+    #[pallet::getter(fn boost_shares)]
+    pub type BoostShares<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        PoolId,
+        Twox64Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// The boost shares of all the accounts in `PoolId`.
+    ///
+    /// map PoolId => Balance
+    #[pallet::storage]
+    #[pallet::getter(fn pool_boosts)]
+    pub type PoolBoosts<T: Config> = StorageMap<_, Twox64Concat, PoolId, BalanceOf<T>, ValueQuery>;
+
+    /// The emission budget split among pools by the votes every epoch.
+    #[pallet::storage]
+    #[pallet::getter(fn vote_emission)]
+    pub type VoteEmission<T: Config> =
+        StorageValue<_, BTreeMap<CurrencyIdOf<T>, BalanceOf<T>>, ValueQuery>;
+
+    /// The voting power voted for each pool in an epoch.
+    ///
+    /// map Epoch => BoundedBTreeMap<PoolId, Balance>
+    #[pallet::storage]
+    #[pallet::getter(fn pool_votes)]
+    pub type PoolVotes<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedBTreeMap<PoolId, BalanceOf<T>, T::MaxVotedPools>,
+        ValueQuery,
+    >;
+
+    /// The latest votes of `AccountId` and the epoch they were cast in, the votes of a
+    /// past epoch are stale and replaced by the next vote.
+    ///
+    /// map AccountId => (Epoch, BoundedVec<(PoolId, Balance)>)
+    #[pallet::storage]
+    #[pallet::getter(fn user_votes)]
+    pub type UserVotes<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        (
+            BlockNumberFor<T>,
+            BoundedVec<(PoolId, BalanceOf<T>), T::MaxPoolVotes>,
+        ),
+    >;
+
+    /// The number of blocks the claimed rewards of `PoolId` vest over.
+    ///
+    /// map PoolId => BlockNumber
+    #[pallet::storage]
+    #[pallet::getter(fn pool_vestings)]
+    pub type PoolVestings<T: Config> = StorageMap<_, Twox64Concat, PoolId, BlockNumberFor<T>>;
+
+    /// The vesting schedule of the claimed rewards of `AccountId` in `CurrencyId`.
+    ///
+    /// double_map (AccountId, CurrencyId) => VestingSchedule
+    #[pallet::storage]
+    #[pallet::getter(fn vesting_schedules)]
+    pub type VestingSchedules<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        CurrencyIdOf<T>,
+        VestingScheduleOf<T>,
+    >;
+
+    /// The pools `ControlOrigin` allowed to exit immediately without the rewards.
+    ///
+    /// map PoolId => bool
+    #[pallet::storage]
+    #[pallet::getter(fn emergency_withdraw_enabled)]
+    pub type EmergencyWithdrawEnabled<T: Config> =
+        StorageMap<_, Twox64Concat, PoolId, bool, ValueQuery>;
+
+    /// The accounts allowed to charge the pool, anyone can charge pools without one.
+    ///
+    /// map PoolId => BoundedVec<AccountId, MaxAllowlist>
+    #[pallet::storage]
+    #[pallet::getter(fn charge_allowlists)]
+    pub type ChargeAllowlists<T: Config> =
+        StorageMap<_, Twox64Concat, PoolId, BoundedVec<T::AccountId, T::MaxAllowlist>>;
+
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(n: BlockNumberFor<T>) -> Weight {
-            PoolInfos::<T>::iter().for_each(|(pool_id, mut pool_info)| match pool_info.state {
-                PoolState::Ongoing => {
-                    pool_info.basic_rewards.clone().iter().for_each(
-                        |(reward_currency_id, reward_amount)| {
-                            pool_info
-                                .rewards
-                                .entry(*reward_currency_id)
-                                .and_modify(|(total_reward, _)| {
-                                    *total_reward = total_reward.saturating_add(*reward_amount);
-                                })
-                                .or_insert((*reward_amount, Zero::zero()));
-                        },
-                    );
-                    PoolInfos::<T>::insert(pool_id, &pool_info);
-                }
-                PoolState::Charged => {
-                    if n >= pool_info.after_block_to_start
-                        && pool_info.total_shares >= pool_info.min_deposit_to_start
-                    {
-                        pool_info.block_startup = Some(n);
-                        pool_info.state = PoolState::Ongoing;
-                        PoolInfos::<T>::insert(pool_id, &pool_info);
-                    }
-                }
-                _ => (),
-            });
-
-            GaugePoolInfos::<T>::iter().for_each(|(pool_id, mut gauge_pool_info)| {
-                if gauge_pool_info.gauge_state == GaugeState::Bonded {
-                    gauge_pool_info.gauge_basic_rewards.clone().iter().for_each(
-                        |(reward_currency_id, reward_amount)| {
-                            gauge_pool_info
-                                .rewards
-                                .entry(*reward_currency_id)
-                                .and_modify(|(total_reward, _, _)| {
-                                    *total_reward = total_reward.saturating_add(*reward_amount);
-                                })
-                                .or_insert((*reward_amount, Zero::zero(), Zero::zero()));
-                        },
-                    );
-                    GaugePoolInfos::<T>::insert(pool_id, &gauge_pool_info);
-                }
-            });
-
-            T::WeightInfo::on_initialize()
+            Self::on_epoch_start(n)
         }
     }
 
@@ -316,6 +606,9 @@ pub mod pallet {
         BalanceOf<T>: AtLeast32BitUnsigned + Copy,
     {
         /// `ControlOrigin` create the farming pool.
+        ///
+        /// `basic_rewards` are emitted along `emission_curve` from `after_block_to_start`
+        /// until `end_block` or until the charged rewards are used up.
         #[pallet::call_index(0)]
         #[pallet::weight(T::WeightInfo::create_farming_pool())]
         pub fn create_farming_pool(
@@ -328,8 +621,11 @@ pub mod pallet {
             #[pallet::compact] withdraw_limit_time: BlockNumberFor<T>,
             #[pallet::compact] claim_limit_time: BlockNumberFor<T>,
             #[pallet::compact] withdraw_limit_count: u8,
+            end_block: Option<BlockNumberFor<T>>,
+            emission_curve: EmissionCurve<BlockNumberFor<T>>,
         ) -> DispatchResult {
             T::ControlOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_emission(after_block_to_start, end_block, emission_curve)?;
 
             let pool_id = Self::pool_next_id();
             let keeper = T::Keeper::get().into_sub_account_truncating(pool_id);
@@ -356,6 +652,9 @@ pub mod pallet {
                 withdraw_limit_time,
                 claim_limit_time,
                 withdraw_limit_count,
+                end_block,
+                emission_curve,
+                frame_system::Pallet::<T>::block_number(),
             );
 
             if let Some((gauge_token, max_block, gauge_basic_rewards)) = gauge_init {
@@ -387,6 +686,9 @@ pub mod pallet {
         }
 
         /// After create farming pool, need to deposit reward token into the pool.
+        ///
+        /// Only the accounts in the charge allowlist can charge a pool which has one, and
+        /// retired or dead pools can not be charged.
         #[pallet::call_index(1)]
         #[pallet::weight(T::WeightInfo::charge())]
         pub fn charge(
@@ -397,6 +699,23 @@ pub mod pallet {
             let exchanger = ensure_signed(origin)?;
 
             let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            Self::accrue_pool_rewards(&mut pool_info);
+            let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                PoolState::state_valid(Action::Charge, pool_info.state)
+                    && pool_info
+                        .end_block
+                        .map_or(true, |end_block| current_block_number < end_block),
+                Error::<T>::InvalidPoolState
+            );
+            if let Some(allowlist) = Self::charge_allowlists(pool_id) {
+                ensure!(
+                    allowlist.contains(&exchanger),
+                    Error::<T>::NotAllowedToCharge
+                );
+            }
+
+            let mut charges = Self::charges(pool_id, &exchanger).unwrap_or_default();
             rewards
                 .iter()
                 .try_for_each(|(reward_currency, reward)| -> DispatchResult {
@@ -405,10 +724,29 @@ pub mod pallet {
                         &exchanger,
                         &pool_info.reward_issuer,
                         *reward,
-                    )
+                    )?;
+                    pool_info
+                        .reward_budget
+                        .entry(*reward_currency)
+                        .and_modify(|budget| *budget = budget.saturating_add(*reward))
+                        .or_insert(*reward);
+                    pool_info
+                        .charged_rewards
+                        .entry(*reward_currency)
+                        .and_modify(|charged| *charged = charged.saturating_add(*reward))
+                        .or_insert(*reward);
+                    charges
+                        .entry(*reward_currency)
+                        .and_modify(|charged| *charged = charged.saturating_add(*reward))
+                        .or_insert(*reward);
+                    Ok(())
                 })?;
-            pool_info.state = PoolState::Charged;
+            if pool_info.state == PoolState::UnCharged {
+                pool_info.state = PoolState::Charged;
+            }
+            Self::schedule_pool_startup(&mut pool_info, current_block_number);
             PoolInfos::<T>::insert(pool_id, pool_info);
+            Charges::<T>::insert(pool_id, &exchanger, charges);
 
             Self::deposit_event(Event::Charged {
                 who: exchanger,
@@ -452,7 +790,8 @@ pub mod pallet {
         pub fn claim(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
             let exchanger = ensure_signed(origin)?;
 
-            let pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            Self::accrue_pool_rewards(&mut pool_info);
             ensure!(
                 PoolState::state_valid(Action::Claim, pool_info.state),
                 Error::<T>::InvalidPoolState
@@ -467,6 +806,7 @@ pub mod pallet {
             );
 
             Self::claim_rewards(&exchanger, pool_id)?;
+            Self::update_boost_inner(&exchanger, pool_id)?;
             if let Some(ref gid) = pool_info.gauge {
                 Self::gauge_claim_inner(&exchanger, *gid)?;
             }
@@ -494,6 +834,7 @@ pub mod pallet {
             T::ControlOrigin::ensure_origin(origin)?;
 
             let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            Self::accrue_pool_rewards(&mut pool_info);
             ensure!(
                 PoolState::state_valid(Action::ClosePool, pool_info.state),
                 Error::<T>::InvalidPoolState
@@ -523,6 +864,10 @@ pub mod pallet {
         #[pallet::weight(T::DbWeight::get().write)]
         pub fn retire_pool(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
             T::ControlOrigin::ensure_origin(origin)?;
+            ensure!(
+                !T::PoolGuard::is_vault_pool(pool_id),
+                Error::<T>::PoolBacksVault
+            );
 
             let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
             ensure!(
@@ -553,6 +898,7 @@ pub mod pallet {
                 if let Some(ref gid) = pool_info.gauge {
                     let mut gauge_pool_info =
                         Self::gauge_pool_infos(gid).ok_or(Error::<T>::GaugePoolNotExist)?;
+                    Self::accrue_gauge_rewards(&mut gauge_pool_info);
                     gauge_pool_info.gauge_state = GaugeState::Unbond;
                     GaugePoolInfos::<T>::insert(gid, gauge_pool_info);
                 }
@@ -578,8 +924,13 @@ pub mod pallet {
             claim_limit_time: Option<BlockNumberFor<T>>,
             withdraw_limit_count: Option<u8>,
             gauge_init: Option<GaugeInitType<T>>,
+            emission_init: Option<EmissionInitType<T>>,
         ) -> DispatchResult {
             T::ControlOrigin::ensure_origin(origin)?;
+            ensure!(
+                !T::PoolGuard::is_vault_pool(pool_id),
+                Error::<T>::PoolBacksVault
+            );
 
             let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
             ensure!(
@@ -607,6 +958,15 @@ pub mod pallet {
             if let Some(withdraw_limit_count) = withdraw_limit_count {
                 pool_info.withdraw_limit_count = withdraw_limit_count;
             };
+            if let Some((end_block, emission_curve)) = emission_init {
+                pool_info.end_block = end_block;
+                pool_info.emission_curve = emission_curve;
+            };
+            Self::ensure_valid_emission(
+                pool_info.after_block_to_start,
+                pool_info.end_block,
+                pool_info.emission_curve,
+            )?;
             if let Some((gauge_token, max_block, gauge_basic_rewards)) = gauge_init {
                 let gauge_basic_rewards_map: BTreeMap<CurrencyIdOf<T>, BalanceOf<T>> =
                     gauge_basic_rewards
@@ -632,18 +992,76 @@ pub mod pallet {
             Ok(())
         }
 
+        /// `ControlOrigin` kills a retired or uncharged pool.
+        ///
+        /// The chargers get back the rewards never accrued first, at most `MaxChargeRefunds` of
+        /// them per call. The pool is only removed once all charges are refunded, until then
+        /// `kill_pool` is called again. A refund which fails stays for the next call.
         #[pallet::call_index(10)]
-        #[pallet::weight(T::DbWeight::get().write)]
+        #[pallet::weight(T::WeightInfo::kill_pool(T::MaxChargeRefunds::get()))]
         pub fn kill_pool(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
             T::ControlOrigin::ensure_origin(origin)?;
+            ensure!(
+                !T::PoolGuard::is_vault_pool(pool_id),
+                Error::<T>::PoolBacksVault
+            );
 
-            let pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
             ensure!(
                 PoolState::state_valid(Action::KillPool, pool_info.state),
                 Error::<T>::InvalidPoolState
             );
+            // the chargers get back the rewards never accrued
+            let max_refunds = T::MaxChargeRefunds::get() as usize;
+            let pending = Charges::<T>::iter_prefix(pool_id)
+                .take(max_refunds.saturating_add(1))
+                .collect::<Vec<_>>();
+            let mut all_refunded = pending.len() <= max_refunds;
+            for (exchanger, charges) in pending.into_iter().take(max_refunds) {
+                let mut refunded_pool_info = pool_info.clone();
+                match with_storage_layer(|| {
+                    Self::refund_charges(&mut refunded_pool_info, &exchanger, charges)
+                }) {
+                    Ok(refunds) => {
+                        pool_info = refunded_pool_info;
+                        Charges::<T>::remove(pool_id, &exchanger);
+                        Self::deposit_event(Event::ChargeRefunded {
+                            who: exchanger,
+                            pid: pool_id,
+                            rewards: refunds,
+                        });
+                    }
+                    Err(e) => {
+                        all_refunded = false;
+                        log::warn!(
+                            "Could not refund the charge of {:?} to pool {:?}: {:?}",
+                            exchanger,
+                            pool_id,
+                            e
+                        );
+                    }
+                }
+            }
+            if !all_refunded {
+                PoolInfos::<T>::insert(pool_id, pool_info);
+                Self::deposit_event(Event::ChargesPartiallyRefunded { pid: pool_id });
+                return Ok(());
+            }
+
             #[allow(deprecated)]
             SharesAndWithdrawnRewards::<T>::remove_prefix(pool_id, None);
+            ChargeAllowlists::<T>::remove(pool_id);
+            #[allow(deprecated)]
+            BoostShares::<T>::remove_prefix(pool_id, None);
+            PoolBoosts::<T>::remove(pool_id);
+            PoolVestings::<T>::remove(pool_id);
+            EmergencyWithdrawEnabled::<T>::remove(pool_id);
+            pool_info
+                .incentives
+                .iter()
+                .try_for_each(|(incentive_id, incentive)| {
+                    Self::refund_incentive(pool_id, &pool_info, *incentive_id, incentive)
+                })?;
             PoolInfos::<T>::remove(pool_id);
 
             Self::deposit_event(Event::FarmingPoolKilled { pid: pool_id });
@@ -662,8 +1080,13 @@ pub mod pallet {
             withdraw_limit_count: Option<u8>,
         ) -> DispatchResult {
             T::ControlOrigin::ensure_origin(origin)?;
+            ensure!(
+                !T::PoolGuard::is_vault_pool(pool_id),
+                Error::<T>::PoolBacksVault
+            );
 
             let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            Self::accrue_pool_rewards(&mut pool_info);
             ensure!(
                 PoolState::state_valid(Action::EditPool, pool_info.state),
                 Error::<T>::InvalidPoolState
@@ -693,6 +1116,7 @@ pub mod pallet {
                     pool_info.gauge.ok_or(Error::<T>::GaugePoolNotExist)?,
                     |gauge_pool_info_old| {
                         if let Some(mut gauge_pool_info) = gauge_pool_info_old.take() {
+                            Self::accrue_gauge_rewards(&mut gauge_pool_info);
                             gauge_pool_info.gauge_basic_rewards = gauge_basic_rewards_map;
                             *gauge_pool_info_old = Some(gauge_pool_info);
                         }
@@ -770,10 +1194,422 @@ pub mod pallet {
             }
             Ok(())
         }
+
+        /// Refund the share of the charged rewards that was never accrued to the charger,
+        /// once the pool is dead.
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::refund_charge())]
+        pub fn refund_charge(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            Self::accrue_pool_rewards(&mut pool_info);
+            ensure!(
+                PoolState::state_valid(Action::RefundCharge, pool_info.state),
+                Error::<T>::InvalidPoolState
+            );
+            let charges =
+                Charges::<T>::take(pool_id, &exchanger).ok_or(Error::<T>::ChargeNotExist)?;
+
+            let refunds = Self::refund_charges(&mut pool_info, &exchanger, charges)?;
+            PoolInfos::<T>::insert(pool_id, pool_info);
+
+            Self::deposit_event(Event::ChargeRefunded {
+                who: exchanger,
+                pid: pool_id,
+                rewards: refunds,
+            });
+            Ok(())
+        }
+
+        /// Sponsor an incentive stream on the pool, paying `reward_per_block` of
+        /// `reward_currency` to the shares from `start_block` until `end_block`.
+        ///
+        /// The whole stream is transferred to the pool reward issuer upfront, the rewards
+        /// not accrued can be withdrawn back by the sponsor once the stream is over.
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::add_incentive())]
+        pub fn add_incentive(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            reward_currency: CurrencyIdOf<T>,
+            #[pallet::compact] reward_per_block: BalanceOf<T>,
+            #[pallet::compact] start_block: BlockNumberFor<T>,
+            #[pallet::compact] end_block: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            Self::accrue_pool_rewards(&mut pool_info);
+            ensure!(
+                PoolState::state_valid(Action::AddIncentive, pool_info.state),
+                Error::<T>::InvalidPoolState
+            );
+            let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                !reward_per_block.is_zero()
+                    && start_block >= current_block_number
+                    && end_block > start_block,
+                Error::<T>::InvalidIncentiveParameter
+            );
+            ensure!(
+                (pool_info.incentives.len() as u32) < T::MaxIncentives::get(),
+                Error::<T>::TooManyIncentives
+            );
+
+            let blocks: BalanceOf<T> = (end_block - start_block).saturated_into();
+            let total_reward = reward_per_block
+                .checked_mul(&blocks)
+                .ok_or(ArithmeticError::Overflow)?;
+            T::MultiCurrency::transfer(
+                reward_currency,
+                &exchanger,
+                &pool_info.reward_issuer,
+                total_reward,
+            )?;
+
+            let incentive_id = Self::incentive_next_id();
+            IncentiveNextId::<T>::mutate(|id| -> DispatchResult {
+                *id = id
+                    .checked_add(One::one())
+                    .ok_or(ArithmeticError::Overflow)?;
+                Ok(())
+            })?;
+            pool_info.incentives.insert(
+                incentive_id,
+                IncentiveInfo {
+                    sponsor: exchanger.clone(),
+                    reward_currency,
+                    reward_per_block,
+                    start_block,
+                    end_block,
+                    remaining: total_reward,
+                    last_reward_block: start_block,
+                },
+            );
+            PoolInfos::<T>::insert(pool_id, pool_info);
+
+            Self::deposit_event(Event::IncentiveAdded {
+                who: exchanger,
+                pid: pool_id,
+                incentive_id,
+                reward_currency,
+                reward_per_block,
+                start_block,
+                end_block,
+            });
+            Ok(())
+        }
+
+        /// Withdraw the rewards of an incentive stream which were not accrued to the
+        /// shares back to its sponsor, once it ended or the pool stopped farming.
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::withdraw_incentive())]
+        pub fn withdraw_incentive(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            incentive_id: IncentiveId,
+        ) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            Self::accrue_pool_rewards(&mut pool_info);
+            let incentive = pool_info
+                .incentives
+                .remove(&incentive_id)
+                .ok_or(Error::<T>::IncentiveNotExist)?;
+            ensure!(
+                incentive.sponsor == exchanger,
+                Error::<T>::NotIncentiveSponsor
+            );
+            let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                incentive.end_block <= current_block_number
+                    || !PoolState::state_valid(Action::Deposit, pool_info.state),
+                Error::<T>::IncentiveNotEnded
+            );
+
+            Self::refund_incentive(pool_id, &pool_info, incentive_id, &incentive)?;
+            PoolInfos::<T>::insert(pool_id, pool_info);
+            Ok(())
+        }
+
+        /// Lock `amount` more of the native token for voting power until `unlock_block`,
+        /// rounded down to an epoch boundary.
+        ///
+        /// The voting power decays linearly to zero at the end of the lock, which can only
+        /// be extended.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::ve_lock())]
+        pub fn ve_lock(
+            origin: OriginFor<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+            #[pallet::compact] unlock_block: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            Self::ve_lock_inner(&exchanger, amount, unlock_block)
+        }
+
+        /// Unlock the native token of an expired vote-escrow lock.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::ve_unlock())]
+        pub fn ve_unlock(origin: OriginFor<T>) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            Self::ve_unlock_inner(&exchanger)
+        }
+
+        /// Bring the boosted shares of `who` in the pool in line with its current voting power.
+        ///
+        /// Anyone can update a boost, so that decayed voting power stops boosting.
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::update_boost())]
+        pub fn update_boost(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            who: AccountIdOf<T>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::update_boost_inner(&who, pool_id)
+        }
+
+        /// Split the current voting power among pools for the emission budget of the next epoch.
+        ///
+        /// Voting again in the same epoch replaces the previous votes.
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::vote(votes.len() as u32))]
+        pub fn vote(origin: OriginFor<T>, votes: Vec<(PoolId, Perbill)>) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            Self::vote_inner(&exchanger, votes)
+        }
+
+        /// `ControlOrigin` set the emission budget split among pools by the votes every epoch,
+        /// paid from `vote_emission_account`.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::DbWeight::get().write)]
+        pub fn set_vote_emission(
+            origin: OriginFor<T>,
+            rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+        ) -> DispatchResult {
+            T::ControlOrigin::ensure_origin(origin)?;
+
+            VoteEmission::<T>::put(rewards.iter().copied().collect::<BTreeMap<_, _>>());
+
+            Self::deposit_event(Event::VoteEmissionSet { rewards });
+            Ok(())
+        }
+
+        /// `ControlOrigin` set the claimed rewards of the pool to vest linearly over
+        /// `vesting_blocks`, or to be paid out immediately with `None`.
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::set_vesting())]
+        pub fn set_vesting(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            vesting_blocks: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::ControlOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                PoolInfos::<T>::contains_key(pool_id),
+                Error::<T>::PoolDoesNotExist
+            );
+            match vesting_blocks {
+                Some(vesting_blocks) => {
+                    ensure!(!vesting_blocks.is_zero(), Error::<T>::InvalidPoolParameter);
+                    PoolVestings::<T>::insert(pool_id, vesting_blocks);
+                }
+                None => PoolVestings::<T>::remove(pool_id),
+            }
+
+            Self::deposit_event(Event::VestingSet {
+                pid: pool_id,
+                vesting_blocks,
+            });
+            Ok(())
+        }
+
+        /// Claim the vested part of the rewards in `reward_currency`.
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::claim_vested())]
+        pub fn claim_vested(
+            origin: OriginFor<T>,
+            reward_currency: CurrencyIdOf<T>,
+        ) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            Self::claim_vested_inner(&exchanger, reward_currency)
+        }
+
+        /// `ControlOrigin` allow or forbid the users of the pool to `emergency_withdraw`.
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::DbWeight::get().write)]
+        pub fn set_emergency_withdraw(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            enabled: bool,
+        ) -> DispatchResult {
+            T::ControlOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                PoolInfos::<T>::contains_key(pool_id),
+                Error::<T>::PoolDoesNotExist
+            );
+            if enabled {
+                EmergencyWithdrawEnabled::<T>::insert(pool_id, true);
+            } else {
+                EmergencyWithdrawEnabled::<T>::remove(pool_id);
+            }
+
+            Self::deposit_event(Event::EmergencyWithdrawSet {
+                pid: pool_id,
+                enabled,
+            });
+            Ok(())
+        }
+
+        /// Get back all the tokens deposited to the pool and the gauge immediately, without
+        /// `withdraw_limit_time` and forfeiting the pending rewards.
+        ///
+        /// Only available once `ControlOrigin` enabled it for the pool.
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::emergency_withdraw())]
+        pub fn emergency_withdraw(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            Self::emergency_withdraw_inner(&exchanger, pool_id)
+        }
+
+        /// `ControlOrigin` restrict charging the pool to `allowlist`, or allow anyone to
+        /// charge it with `None`.
+        #[pallet::call_index(26)]
+        #[pallet::weight(T::WeightInfo::set_charge_allowlist(
+            allowlist.as_ref().map_or(0, |allowlist| allowlist.len() as u32)
+        ))]
+        pub fn set_charge_allowlist(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            allowlist: Option<Vec<T::AccountId>>,
+        ) -> DispatchResult {
+            T::ControlOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                PoolInfos::<T>::contains_key(pool_id),
+                Error::<T>::PoolDoesNotExist
+            );
+            match allowlist {
+                Some(ref allowlist) => {
+                    let allowlist: BoundedVec<T::AccountId, T::MaxAllowlist> = allowlist
+                        .clone()
+                        .try_into()
+                        .map_err(|_| Error::<T>::TooManyAllowedChargers)?;
+                    ChargeAllowlists::<T>::insert(pool_id, allowlist)
+                }
+                None => ChargeAllowlists::<T>::remove(pool_id),
+            }
+
+            Self::deposit_event(Event::ChargeAllowlistSet {
+                pid: pool_id,
+                allowlist,
+            });
+            Ok(())
+        }
     }
 }
 
 impl<T: Config> Pallet<T> {
+    /// Refunds `who` its share of the charged rewards of the pool that were never accrued.
+    fn refund_charges(
+        pool_info: &mut PoolInfoOf<T>,
+        who: &T::AccountId,
+        charges: BTreeMap<CurrencyIdOf<T>, BalanceOf<T>>,
+    ) -> Result<RewardOf<T>, DispatchError> {
+        let mut refunds = Vec::new();
+        charges
+            .into_iter()
+            .try_for_each(|(reward_currency, charged)| -> DispatchResult {
+                let total_charged = pool_info
+                    .charged_rewards
+                    .get(&reward_currency)
+                    .copied()
+                    .unwrap_or_default();
+                let budget = pool_info
+                    .reward_budget
+                    .get(&reward_currency)
+                    .copied()
+                    .unwrap_or_default();
+                let refund = if charged >= total_charged {
+                    budget
+                } else {
+                    Self::get_reward_inflation(charged, &budget, total_charged)
+                };
+                pool_info
+                    .charged_rewards
+                    .insert(reward_currency, total_charged.saturating_sub(charged));
+                pool_info
+                    .reward_budget
+                    .insert(reward_currency, budget.saturating_sub(refund));
+                if refund.is_zero() {
+                    return Ok(());
+                }
+                T::MultiCurrency::transfer(reward_currency, &pool_info.reward_issuer, who, refund)?;
+                refunds.push((reward_currency, refund));
+                Ok(())
+            })?;
+        pool_info
+            .charged_rewards
+            .retain(|_, charged| !charged.is_zero());
+        Ok(refunds)
+    }
+
+    fn refund_incentive(
+        pool_id: PoolId,
+        pool_info: &PoolInfoOf<T>,
+        incentive_id: IncentiveId,
+        incentive: &IncentiveInfoOf<T>,
+    ) -> DispatchResult {
+        if !incentive.remaining.is_zero() {
+            T::MultiCurrency::transfer(
+                incentive.reward_currency,
+                &pool_info.reward_issuer,
+                &incentive.sponsor,
+                incentive.remaining,
+            )?;
+        }
+        Self::deposit_event(Event::IncentiveWithdrawn {
+            who: incentive.sponsor.clone(),
+            pid: pool_id,
+            incentive_id,
+            reward_currency: incentive.reward_currency,
+            amount: incentive.remaining,
+        });
+        Ok(())
+    }
+
+    fn ensure_valid_emission(
+        after_block_to_start: BlockNumberFor<T>,
+        end_block: Option<BlockNumberFor<T>>,
+        emission_curve: EmissionCurve<BlockNumberFor<T>>,
+    ) -> DispatchResult {
+        ensure!(
+            end_block.map_or(true, |end_block| end_block > after_block_to_start),
+            Error::<T>::InvalidPoolParameter
+        );
+        match emission_curve {
+            EmissionCurve::Constant => {}
+            EmissionCurve::LinearDecay => {
+                ensure!(end_block.is_some(), Error::<T>::InvalidPoolParameter)
+            }
+            EmissionCurve::StepHalving { period } => {
+                ensure!(!period.is_zero(), Error::<T>::InvalidPoolParameter)
+            }
+        }
+        Ok(())
+    }
+
     fn farming_token_transfer(
         reward_currency: &CurrencyIdOf<T>,
         reward_to_withdraw: BalanceOf<T>,
@@ -803,7 +1639,8 @@ impl<T: Config> Pallet<T> {
     ) -> Result<RewardOf<T>, DispatchError> {
         let share_info = SharesAndWithdrawnRewards::<T>::get(pool_id, who)
             .ok_or(Error::<T>::ShareInfoNotExists)?;
-        let pool_info = PoolInfos::<T>::get(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        let mut pool_info = PoolInfos::<T>::get(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        Self::accrue_pool_rewards(&mut pool_info);
         let total_shares = pool_info.total_shares;
         let mut result_vec = Vec::<(CurrencyIdOf<T>, BalanceOf<T>)>::new();
 
@@ -828,6 +1665,142 @@ impl<T: Config> Pallet<T> {
         Ok(result_vec)
     }
 
+    /// The rewards the pool emits in the current block, basic rewards and incentives.
+    fn rewards_per_block(pool_info: &PoolInfoOf<T>) -> BTreeMap<CurrencyIdOf<T>, BalanceOf<T>> {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let mut rewards = BTreeMap::<CurrencyIdOf<T>, BalanceOf<T>>::new();
+        if pool_info.state != PoolState::Ongoing
+            || pool_info
+                .end_block
+                .map_or(false, |end_block| end_block <= n)
+        {
+            return rewards;
+        }
+
+        pool_info
+            .basic_rewards
+            .iter()
+            .for_each(|(reward_currency, basic_reward)| {
+                let budget = pool_info
+                    .reward_budget
+                    .get(reward_currency)
+                    .copied()
+                    .unwrap_or_default();
+                let reward_amount = Self::emitted_rewards(
+                    pool_info,
+                    *basic_reward,
+                    n,
+                    n.saturating_add(One::one()),
+                )
+                .min(budget);
+                let entry = rewards.entry(*reward_currency).or_default();
+                *entry = entry.saturating_add(reward_amount);
+            });
+        if !pool_info.total_shares.is_zero() {
+            pool_info
+                .incentives
+                .values()
+                .filter(|incentive| incentive.start_block <= n && n < incentive.end_block)
+                .for_each(|incentive| {
+                    let entry = rewards.entry(incentive.reward_currency).or_default();
+                    *entry =
+                        entry.saturating_add(incentive.reward_per_block.min(incentive.remaining));
+                });
+        }
+        rewards.retain(|_, reward_amount| !reward_amount.is_zero());
+        rewards
+    }
+
+    pub fn get_pools() -> Vec<PoolDataOf<T>> {
+        PoolInfos::<T>::iter()
+            .map(|(pool_id, mut pool_info)| {
+                Self::accrue_pool_rewards(&mut pool_info);
+                let mut remaining_rewards = pool_info.reward_budget.clone();
+                pool_info.incentives.values().for_each(|incentive| {
+                    let entry = remaining_rewards
+                        .entry(incentive.reward_currency)
+                        .or_default();
+                    *entry = entry.saturating_add(incentive.remaining);
+                });
+                PoolData {
+                    pool_id,
+                    state: pool_info.state,
+                    tokens_proportion: pool_info.tokens_proportion.clone().into_iter().collect(),
+                    total_shares: pool_info.total_shares,
+                    rewards_per_block: Self::rewards_per_block(&pool_info).into_iter().collect(),
+                    remaining_rewards: remaining_rewards.into_iter().collect(),
+                    end_block: pool_info.end_block,
+                }
+            })
+            .collect()
+    }
+
+    pub fn get_positions(who: &T::AccountId) -> Vec<PositionDataOf<T>> {
+        PoolInfos::<T>::iter_keys()
+            .filter_map(|pool_id| {
+                let share_info = Self::shares_and_withdrawn_rewards(pool_id, who)?;
+                Some(PositionData {
+                    pool_id,
+                    share: share_info.share,
+                    pending_rewards: Self::get_farming_rewards(who, pool_id).unwrap_or_default(),
+                    withdraw_list: share_info.withdraw_list,
+                })
+            })
+            .collect()
+    }
+
+    /// The yearly rewards of the pool at the current emission over the value of its
+    /// deposited tokens, with `prices` of the smallest unit of each currency in a common
+    /// quote. Currencies without a price count as worthless.
+    pub fn get_pool_apr(
+        pool_id: PoolId,
+        prices: Vec<(CurrencyIdOf<T>, FixedU128)>,
+        blocks_per_year: BlockNumberFor<T>,
+    ) -> Option<FixedU128> {
+        let mut pool_info = Self::pool_infos(pool_id)?;
+        Self::accrue_pool_rewards(&mut pool_info);
+        let prices: BTreeMap<CurrencyIdOf<T>, FixedU128> = prices.into_iter().collect();
+        let value_of = |currency_id: &CurrencyIdOf<T>, amount: BalanceOf<T>| -> U256 {
+            prices.get(currency_id).map_or(U256::zero(), |price| {
+                U256::from(amount.saturated_into::<u128>())
+                    .saturating_mul(U256::from(price.into_inner()))
+            })
+        };
+
+        let native_amount = pool_info
+            .basic_token
+            .1
+            .saturating_reciprocal_mul(pool_info.total_shares);
+        let deposited_value =
+            pool_info
+                .tokens_proportion
+                .iter()
+                .fold(U256::zero(), |total, (token, &proportion)| {
+                    total.saturating_add(value_of(token, proportion * native_amount))
+                });
+        if deposited_value.is_zero() {
+            return None;
+        }
+        let blocks: BalanceOf<T> = blocks_per_year.saturated_into::<u128>().saturated_into();
+        let yearly_value = Self::rewards_per_block(&pool_info).iter().fold(
+            U256::zero(),
+            |total, (reward_currency, reward_amount)| {
+                total.saturating_add(value_of(
+                    reward_currency,
+                    reward_amount.saturating_mul(blocks),
+                ))
+            },
+        );
+        Some(FixedU128::from_inner(
+            yearly_value
+                .saturating_mul(U256::from(FixedU128::accuracy()))
+                .checked_div(deposited_value)
+                .unwrap_or_default()
+                .min(U256::from(u128::MAX))
+                .as_u128(),
+        ))
+    }
+
     fn get_reward_amount(
         share_info: &ShareInfoOf<T>,
         total_reward: &BalanceOf<T>,
@@ -873,6 +1846,7 @@ impl<T: Config> Pallet<T> {
         gauge_info: Option<(BalanceOf<T>, BlockNumberFor<T>)>,
     ) -> DispatchResult {
         let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        Self::accrue_pool_rewards(&mut pool_info);
         ensure!(
             PoolState::state_valid(Action::Deposit, pool_info.state),
             Error::<T>::InvalidPoolState
@@ -900,6 +1874,7 @@ impl<T: Config> Pallet<T> {
             },
         )?;
         Self::add_share(&exchanger, pool_id, &mut pool_info, add_value);
+        Self::update_boost_inner(&exchanger, pool_id)?;
 
         if let Some((gauge_value, gauge_block)) = gauge_info {
             Self::gauge_add(
@@ -919,6 +1894,84 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Returns the deposited and pending withdrawal tokens of `who` from the keeper and
+    /// drops its share, the pending rewards are left to the rest of the pool.
+    pub fn emergency_withdraw_inner(who: &T::AccountId, pool_id: PoolId) -> DispatchResult {
+        ensure!(
+            Self::emergency_withdraw_enabled(pool_id),
+            Error::<T>::EmergencyWithdrawNotEnabled
+        );
+        let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        // The remaining shares keep the rewards accrued up to now, including the forfeited ones
+        Self::accrue_pool_rewards(&mut pool_info);
+        ensure!(
+            PoolState::state_valid(Action::Withdraw, pool_info.state),
+            Error::<T>::InvalidPoolState
+        );
+        let mut share_info = SharesAndWithdrawnRewards::<T>::take(pool_id, who)
+            .ok_or(Error::<T>::ShareInfoNotExists)?;
+
+        let boost = BoostShares::<T>::take(pool_id, who);
+        if !boost.is_zero() {
+            PoolBoosts::<T>::mutate(pool_id, |total_boost| {
+                *total_boost = total_boost.saturating_sub(boost);
+            });
+        }
+        let deposited = share_info.share.saturating_sub(boost);
+        let share = share_info.share;
+        Self::reduce_share(&mut pool_info, &mut share_info, share);
+        Self::schedule_pool_startup(&mut pool_info, frame_system::Pallet::<T>::block_number());
+
+        let withdraw_amount = share_info
+            .withdraw_list
+            .iter()
+            .fold(deposited, |total, (_, remove_amount)| {
+                total.saturating_add(*remove_amount)
+            });
+        let native_amount = pool_info
+            .basic_token
+            .1
+            .saturating_reciprocal_mul(withdraw_amount);
+        pool_info.tokens_proportion.iter().try_for_each(
+            |(token, &proportion)| -> DispatchResult {
+                Self::farming_token_transfer(
+                    token,
+                    proportion * native_amount,
+                    who,
+                    &pool_info.keeper,
+                )
+            },
+        )?;
+
+        if let Some(gid) = pool_info.gauge {
+            if let Some(gauge_info) = GaugeInfos::<T>::take(gid, who) {
+                let mut gauge_pool_info =
+                    GaugePoolInfos::<T>::get(gid).ok_or(Error::<T>::GaugePoolNotExist)?;
+                Self::farming_token_transfer(
+                    &gauge_pool_info.token,
+                    gauge_info.gauge_amount,
+                    who,
+                    &gauge_pool_info.keeper,
+                )?;
+                gauge_pool_info.total_time_factor = gauge_pool_info
+                    .total_time_factor
+                    .saturating_sub(gauge_info.total_time_factor);
+                gauge_pool_info.gauge_amount = gauge_pool_info
+                    .gauge_amount
+                    .saturating_sub(gauge_info.gauge_amount);
+                GaugePoolInfos::<T>::insert(gid, gauge_pool_info);
+            }
+        }
+        PoolInfos::<T>::insert(pool_id, pool_info);
+
+        Self::deposit_event(Event::EmergencyWithdrawn {
+            who: who.clone(),
+            pid: pool_id,
+            share: deposited,
+        });
+        Ok(())
+    }
+
     pub fn withdraw_farming(exchanger: T::AccountId, pool_id: PoolId) -> DispatchResult {
         let pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
         Self::process_withdraw_list(&exchanger, pool_id, &pool_info, false)?;
@@ -935,7 +1988,8 @@ impl<T: Config> Pallet<T> {
         pool_id: PoolId,
         remove_value: Option<BalanceOf<T>>,
     ) -> DispatchResult {
-        let pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        Self::accrue_pool_rewards(&mut pool_info);
         ensure!(
             PoolState::state_valid(Action::Withdraw, pool_info.state),
             Error::<T>::InvalidPoolState
@@ -954,6 +2008,7 @@ impl<T: Config> Pallet<T> {
             remove_value,
             pool_info.withdraw_limit_time,
         )?;
+        Self::update_boost_inner(&exchanger, pool_id)?;
 
         Self::deposit_event(Event::Withdrawn {
             who: exchanger,
@@ -0,0 +1,438 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations for the farming pallet.
+
+use super::*;
+use codec::HasCompact;
+use core::marker::PhantomData;
+use frame_support::{
+    pallet_prelude::Weight,
+    storage_alias,
+    traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    Twox64Concat,
+};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+/// `PoolInfo` before rewards were accrued lazily.
+#[derive(Decode)]
+struct OldPoolInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf, BlockNumberFor> {
+    tokens_proportion: BTreeMap<CurrencyIdOf, Perbill>,
+    basic_token: (CurrencyIdOf, Perbill),
+    total_shares: BalanceOf,
+    basic_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+    rewards: BTreeMap<CurrencyIdOf, (BalanceOf, BalanceOf)>,
+    state: PoolState,
+    keeper: AccountIdOf,
+    reward_issuer: AccountIdOf,
+    gauge: Option<PoolId>,
+    block_startup: Option<BlockNumberFor>,
+    min_deposit_to_start: BalanceOf,
+    after_block_to_start: BlockNumberFor,
+    withdraw_limit_time: BlockNumberFor,
+    claim_limit_time: BlockNumberFor,
+    withdraw_limit_count: u8,
+}
+
+/// `GaugePoolInfo` before rewards were accrued lazily.
+#[derive(Decode)]
+struct OldGaugePoolInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf, BlockNumberFor> {
+    pool_id: PoolId,
+    token: CurrencyIdOf,
+    keeper: AccountIdOf,
+    reward_issuer: AccountIdOf,
+    rewards: BTreeMap<CurrencyIdOf, (BalanceOf, BalanceOf, BalanceOf)>,
+    gauge_basic_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+    max_block: BlockNumberFor,
+    gauge_amount: BalanceOf,
+    total_time_factor: u128,
+    gauge_state: GaugeState,
+    gauge_last_block: BlockNumberFor,
+}
+
+/// `PoolInfo` before emission schedules.
+#[derive(Encode, Decode)]
+struct PoolInfoV1<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf, BlockNumberFor> {
+    tokens_proportion: BTreeMap<CurrencyIdOf, Perbill>,
+    basic_token: (CurrencyIdOf, Perbill),
+    total_shares: BalanceOf,
+    basic_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+    rewards: BTreeMap<CurrencyIdOf, (BalanceOf, BalanceOf)>,
+    state: PoolState,
+    keeper: AccountIdOf,
+    reward_issuer: AccountIdOf,
+    gauge: Option<PoolId>,
+    block_startup: Option<BlockNumberFor>,
+    last_reward_block: BlockNumberFor,
+    min_deposit_to_start: BalanceOf,
+    after_block_to_start: BlockNumberFor,
+    withdraw_limit_time: BlockNumberFor,
+    claim_limit_time: BlockNumberFor,
+    withdraw_limit_count: u8,
+}
+
+#[storage_alias]
+type PoolInfosV1<T: Config> = StorageMap<
+    Pallet<T>,
+    Twox64Concat,
+    PoolId,
+    PoolInfoV1<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+>;
+
+/// `PoolInfo` before incentive streams.
+#[derive(Encode, Decode)]
+struct PoolInfoV2<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf, BlockNumberFor> {
+    tokens_proportion: BTreeMap<CurrencyIdOf, Perbill>,
+    basic_token: (CurrencyIdOf, Perbill),
+    total_shares: BalanceOf,
+    basic_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+    rewards: BTreeMap<CurrencyIdOf, (BalanceOf, BalanceOf)>,
+    state: PoolState,
+    keeper: AccountIdOf,
+    reward_issuer: AccountIdOf,
+    gauge: Option<PoolId>,
+    block_startup: Option<BlockNumberFor>,
+    last_reward_block: BlockNumberFor,
+    min_deposit_to_start: BalanceOf,
+    after_block_to_start: BlockNumberFor,
+    withdraw_limit_time: BlockNumberFor,
+    claim_limit_time: BlockNumberFor,
+    withdraw_limit_count: u8,
+    end_block: Option<BlockNumberFor>,
+    emission_curve: EmissionCurve<BlockNumberFor>,
+    reward_budget: BTreeMap<CurrencyIdOf, BalanceOf>,
+    charged_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+}
+
+#[storage_alias]
+type PoolInfosV2<T: Config> = StorageMap<
+    Pallet<T>,
+    Twox64Concat,
+    PoolId,
+    PoolInfoV2<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+>;
+
+/// Storage migration removing the per-block reward hook: every `PoolInfos` and
+/// `GaugePoolInfos` entry records the last block the old hook accrued rewards for, and
+/// charged pools already meeting their start conditions get their startup scheduled.
+///
+/// `SharesAndWithdrawnRewards` entries keep their layout, as each account's
+/// `withdrawn_rewards` still is its debt against the pool's rewards per share.
+pub struct LazyRewardAccrual<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for LazyRewardAccrual<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version < 1 {
+            log::info!(target: "farming", "Start to migrate farming pools to lazy reward accrual.");
+            // The hook last ran for the previous block, this block is accrued on demand
+            let last_reward_block =
+                frame_system::Pallet::<T>::block_number().saturating_sub(One::one());
+            let mut pools: u64 = 0;
+            PoolInfosV1::<T>::translate::<
+                OldPoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+                _,
+            >(|_, old| {
+                pools += 1;
+                // Charged pools already meeting their start conditions start in the next block
+                let block_startup = if old.state == PoolState::Charged {
+                    (old.total_shares >= old.min_deposit_to_start).then(|| {
+                        old.after_block_to_start
+                            .max(last_reward_block.saturating_add(One::one()))
+                    })
+                } else {
+                    old.block_startup
+                };
+                Some(PoolInfoV1 {
+                    tokens_proportion: old.tokens_proportion,
+                    basic_token: old.basic_token,
+                    total_shares: old.total_shares,
+                    basic_rewards: old.basic_rewards,
+                    rewards: old.rewards,
+                    state: old.state,
+                    keeper: old.keeper,
+                    reward_issuer: old.reward_issuer,
+                    gauge: old.gauge,
+                    block_startup,
+                    last_reward_block,
+                    min_deposit_to_start: old.min_deposit_to_start,
+                    after_block_to_start: old.after_block_to_start,
+                    withdraw_limit_time: old.withdraw_limit_time,
+                    claim_limit_time: old.claim_limit_time,
+                    withdraw_limit_count: old.withdraw_limit_count,
+                })
+            });
+            GaugePoolInfos::<T>::translate::<
+                OldGaugePoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+                _,
+            >(|_, old| {
+                pools += 1;
+                Some(GaugePoolInfo {
+                    pool_id: old.pool_id,
+                    token: old.token,
+                    keeper: old.keeper,
+                    reward_issuer: old.reward_issuer,
+                    rewards: old.rewards,
+                    gauge_basic_rewards: old.gauge_basic_rewards,
+                    max_block: old.max_block,
+                    gauge_amount: old.gauge_amount,
+                    total_time_factor: old.total_time_factor,
+                    gauge_state: old.gauge_state,
+                    gauge_last_block: old.gauge_last_block,
+                    last_reward_block,
+                })
+            });
+            StorageVersion::new(1).put::<Pallet<T>>();
+            T::DbWeight::get()
+                .reads(pools + 1)
+                .saturating_add(T::DbWeight::get().writes(pools + 1))
+        } else {
+            log::info!("✅ no lazy reward accrual migration for pallet-farming.");
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version >= 1 {
+            return Err(DispatchError::Other(
+                "Storage version is >= 1, the migration won't be executed.",
+            ));
+        }
+        let pools = (
+            PoolInfos::<T>::iter_keys().count() as u32,
+            GaugePoolInfos::<T>::iter_keys().count() as u32,
+        );
+        Ok(pools.encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version < 1 {
+            return Err(DispatchError::Other(
+                "Storage version is < 1, the migration was not executed.",
+            ));
+        }
+        let pools: (u32, u32) = Decode::decode(&mut &state[..])
+            .map_err(|_| DispatchError::Other("Failed to decode the pool counts."))?;
+        if pools
+            != (
+                PoolInfosV1::<T>::iter().count() as u32,
+                GaugePoolInfos::<T>::iter().count() as u32,
+            )
+        {
+            return Err(DispatchError::Other(
+                "Some farming pools failed to migrate.",
+            ));
+        }
+        log::info!("✅ Storage migration for pallet-farming has been executed successfully.");
+        Ok(())
+    }
+}
+
+/// Storage migration adding emission schedules to `PoolInfos`: existing pools keep a
+/// constant emission without an end block, and their reward budget is what their
+/// reward issuer holds beyond the rewards already accrued to farmers and gaugers.
+///
+/// The budget of existing pools is not recorded against any charger in `Charges`,
+/// so it stays with the reward issuer when those pools die.
+pub struct EmissionSchedules<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for EmissionSchedules<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version == 1 {
+            log::info!(target: "farming", "Start to migrate farming pools to emission schedules.");
+            let mut pools: u64 = 0;
+            PoolInfosV2::<T>::translate::<
+                PoolInfoV1<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+                _,
+            >(|_, old| {
+                pools += 1;
+                let gauge_pool_info = old.gauge.and_then(GaugePoolInfos::<T>::get);
+                let reward_budget: BTreeMap<CurrencyIdOf<T>, BalanceOf<T>> = old
+                    .basic_rewards
+                    .keys()
+                    .map(|reward_currency| {
+                        let accrued = old
+                            .rewards
+                            .get(reward_currency)
+                            .map(|(total_reward, total_withdrawn_reward)| {
+                                total_reward.saturating_sub(*total_withdrawn_reward)
+                            })
+                            .unwrap_or_default();
+                        let gauge_accrued = gauge_pool_info
+                            .as_ref()
+                            .and_then(|gauge_pool_info| {
+                                gauge_pool_info.rewards.get(reward_currency)
+                            })
+                            .map(|(total_reward, _, total_withdrawn_reward)| {
+                                total_reward.saturating_sub(*total_withdrawn_reward)
+                            })
+                            .unwrap_or_default();
+                        let budget =
+                            T::MultiCurrency::free_balance(*reward_currency, &old.reward_issuer)
+                                .saturating_sub(accrued)
+                                .saturating_sub(gauge_accrued);
+                        (*reward_currency, budget)
+                    })
+                    .collect();
+                Some(PoolInfoV2 {
+                    tokens_proportion: old.tokens_proportion,
+                    basic_token: old.basic_token,
+                    total_shares: old.total_shares,
+                    basic_rewards: old.basic_rewards,
+                    rewards: old.rewards,
+                    state: old.state,
+                    keeper: old.keeper,
+                    reward_issuer: old.reward_issuer,
+                    gauge: old.gauge,
+                    block_startup: old.block_startup,
+                    last_reward_block: old.last_reward_block,
+                    min_deposit_to_start: old.min_deposit_to_start,
+                    after_block_to_start: old.after_block_to_start,
+                    withdraw_limit_time: old.withdraw_limit_time,
+                    claim_limit_time: old.claim_limit_time,
+                    withdraw_limit_count: old.withdraw_limit_count,
+                    end_block: None,
+                    emission_curve: EmissionCurve::Constant,
+                    charged_rewards: reward_budget.clone(),
+                    reward_budget,
+                })
+            });
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get()
+                .reads(pools * 3 + 1)
+                .saturating_add(T::DbWeight::get().writes(pools + 1))
+        } else {
+            log::info!("✅ no emission schedule migration for pallet-farming.");
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version != 1 {
+            return Err(DispatchError::Other(
+                "Storage version is not 1, the migration won't be executed.",
+            ));
+        }
+        Ok((PoolInfos::<T>::iter_keys().count() as u32).encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version < 2 {
+            return Err(DispatchError::Other(
+                "Storage version is < 2, the migration was not executed.",
+            ));
+        }
+        let pools: u32 = Decode::decode(&mut &state[..])
+            .map_err(|_| DispatchError::Other("Failed to decode the pool count."))?;
+        if pools != PoolInfosV2::<T>::iter().count() as u32 {
+            return Err(DispatchError::Other(
+                "Some farming pools failed to migrate.",
+            ));
+        }
+        log::info!("✅ Storage migration for pallet-farming has been executed successfully.");
+        Ok(())
+    }
+}
+
+/// Storage migration adding incentive streams to `PoolInfos`, existing pools start
+/// without any incentive.
+pub struct ThirdPartyIncentives<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for ThirdPartyIncentives<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version == 2 {
+            log::info!(target: "farming", "Start to migrate farming pools to incentive streams.");
+            let mut pools: u64 = 0;
+            PoolInfos::<T>::translate::<
+                PoolInfoV2<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+                _,
+            >(|_, old| {
+                pools += 1;
+                Some(PoolInfo {
+                    tokens_proportion: old.tokens_proportion,
+                    basic_token: old.basic_token,
+                    total_shares: old.total_shares,
+                    basic_rewards: old.basic_rewards,
+                    rewards: old.rewards,
+                    state: old.state,
+                    keeper: old.keeper,
+                    reward_issuer: old.reward_issuer,
+                    gauge: old.gauge,
+                    block_startup: old.block_startup,
+                    last_reward_block: old.last_reward_block,
+                    min_deposit_to_start: old.min_deposit_to_start,
+                    after_block_to_start: old.after_block_to_start,
+                    withdraw_limit_time: old.withdraw_limit_time,
+                    claim_limit_time: old.claim_limit_time,
+                    withdraw_limit_count: old.withdraw_limit_count,
+                    end_block: old.end_block,
+                    emission_curve: old.emission_curve,
+                    reward_budget: old.reward_budget,
+                    charged_rewards: old.charged_rewards,
+                    incentives: BTreeMap::new(),
+                })
+            });
+            StorageVersion::new(3).put::<Pallet<T>>();
+            T::DbWeight::get()
+                .reads(pools + 1)
+                .saturating_add(T::DbWeight::get().writes(pools + 1))
+        } else {
+            log::info!("✅ no incentive stream migration for pallet-farming.");
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version != 2 {
+            return Err(DispatchError::Other(
+                "Storage version is not 2, the migration won't be executed.",
+            ));
+        }
+        Ok((PoolInfos::<T>::iter_keys().count() as u32).encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version < 3 {
+            return Err(DispatchError::Other(
+                "Storage version is < 3, the migration was not executed.",
+            ));
+        }
+        let pools: u32 = Decode::decode(&mut &state[..])
+            .map_err(|_| DispatchError::Other("Failed to decode the pool count."))?;
+        if pools != PoolInfos::<T>::iter().count() as u32 {
+            return Err(DispatchError::Other(
+                "Some farming pools failed to migrate.",
+            ));
+        }
+        log::info!("✅ Storage migration for pallet-farming has been executed successfully.");
+        Ok(())
+    }
+}
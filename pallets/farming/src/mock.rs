@@ -35,11 +35,11 @@ use manta_primitives::{
     currencies::Currencies,
     types::CalamariAssetId,
 };
-use sp_core::{ConstU128, ConstU32, H256};
+use sp_core::{ConstU128, ConstU32, ConstU64, H256};
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
-    AccountId32,
+    AccountId32, FixedU128,
 };
 use xcm::{
     prelude::{Parachain, X1},
@@ -279,6 +279,7 @@ impl pallet_asset_manager::Config for Runtime {
 parameter_types! {
     pub const FarmingKeeperPalletId: PalletId = PalletId(*b"bf/fmkpr");
     pub const FarmingRewardIssuerPalletId: PalletId = PalletId(*b"bf/fmrir");
+    pub FarmingMaxBoost: FixedU128 = FixedU128::from_rational(5, 2);
     pub const TreasuryAccount: AccountId32 = TREASURY_ACCOUNT;
 }
 
@@ -296,6 +297,16 @@ impl pallet_farming::Config for Runtime {
     type TreasuryAccount = TreasuryAccount;
     type Keeper = FarmingKeeperPalletId;
     type RewardIssuer = FarmingRewardIssuerPalletId;
+    type MaxIncentives = ConstU32<3>;
+    type VoteEscrowCurrency = Balances;
+    type MaxLockBlocks = ConstU64<100>;
+    type MaxBoost = FarmingMaxBoost;
+    type EpochLength = ConstU64<10>;
+    type MaxPoolVotes = ConstU32<3>;
+    type MaxVotedPools = ConstU32<5>;
+    type MaxAllowlist = ConstU32<2>;
+    type MaxChargeRefunds = ConstU32<1>;
+    type PoolGuard = ();
     type WeightInfo = ();
 }
 
@@ -64,10 +64,13 @@ pub struct PoolInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf, Block
     pub reward_issuer: AccountIdOf,
     /// Gauge pool id
     pub gauge: Option<PoolId>,
+    /// The block farming starts at, scheduled once a charged pool meets its start conditions
     pub block_startup: Option<BlockNumberFor>,
+    /// The block up to which `basic_rewards` have been accrued into `rewards`
+    pub last_reward_block: BlockNumberFor,
     /// The minimum share to starting farming
     pub min_deposit_to_start: BalanceOf,
-    /// The minimum block number to starting farming
+    /// The minimum block number to starting farming, and the block `emission_curve` counts from
     pub after_block_to_start: BlockNumberFor,
     /// The limit block number to withdraw
     pub withdraw_limit_time: BlockNumberFor,
@@ -75,6 +78,17 @@ pub struct PoolInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf, Block
     pub claim_limit_time: BlockNumberFor,
     /// The withdraw limit length
     pub withdraw_limit_count: u8,
+    /// The block farming stops at, the pool turns `Dead` once it is reached
+    pub end_block: Option<BlockNumberFor>,
+    /// How `basic_rewards` are emitted per block
+    pub emission_curve: EmissionCurve<BlockNumberFor>,
+    /// Charged rewards which are not accrued into `rewards` yet <reward_currency, amount>
+    pub reward_budget: BTreeMap<CurrencyIdOf, BalanceOf>,
+    /// Charged rewards which are not refunded yet <reward_currency, amount>
+    pub charged_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+    /// Incentive streams sponsored on top of `basic_rewards`
+    pub incentives:
+        BTreeMap<IncentiveId, IncentiveInfo<BalanceOf, CurrencyIdOf, AccountIdOf, BlockNumberFor>>,
 }
 
 impl<BalanceOf, CurrencyIdOf, AccountIdOf, BlockNumberFor>
@@ -96,6 +110,9 @@ where
         withdraw_limit_time: BlockNumberFor,
         claim_limit_time: BlockNumberFor,
         withdraw_limit_count: u8,
+        end_block: Option<BlockNumberFor>,
+        emission_curve: EmissionCurve<BlockNumberFor>,
+        current_block_number: BlockNumberFor,
     ) -> Self {
         Self {
             tokens_proportion,
@@ -108,15 +125,82 @@ where
             reward_issuer,
             gauge,
             block_startup: None,
+            last_reward_block: current_block_number,
             min_deposit_to_start,
             after_block_to_start,
             withdraw_limit_time,
             claim_limit_time,
             withdraw_limit_count,
+            end_block,
+            emission_curve,
+            reward_budget: BTreeMap::new(),
+            charged_rewards: BTreeMap::new(),
+            incentives: BTreeMap::new(),
         }
     }
 }
 
+pub type IncentiveId = u32;
+
+/// A reward stream any account can sponsor on a pool, distributed pro-rata to
+/// the shares in addition to `basic_rewards`.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct IncentiveInfo<BalanceOf, CurrencyIdOf, AccountIdOf, BlockNumberFor> {
+    pub sponsor: AccountIdOf,
+    pub reward_currency: CurrencyIdOf,
+    pub reward_per_block: BalanceOf,
+    /// The first block emitting `reward_per_block`
+    pub start_block: BlockNumberFor,
+    /// The block the stream stops at
+    pub end_block: BlockNumberFor,
+    /// Sponsored rewards which are not accrued into the pool rewards yet
+    pub remaining: BalanceOf,
+    /// The block up to which the stream has been accrued
+    pub last_reward_block: BlockNumberFor,
+}
+
+/// A farming pool as listed by the runtime API.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PoolData<BalanceOf, CurrencyIdOf, BlockNumberFor> {
+    pub pool_id: PoolId,
+    pub state: PoolState,
+    pub tokens_proportion: Vec<(CurrencyIdOf, Perbill)>,
+    pub total_shares: BalanceOf,
+    /// Rewards emitted in the current block, basic rewards and incentives <reward_currency, amount>
+    pub rewards_per_block: Vec<(CurrencyIdOf, BalanceOf)>,
+    /// Charged rewards and incentives which are not emitted yet <reward_currency, amount>
+    pub remaining_rewards: Vec<(CurrencyIdOf, BalanceOf)>,
+    pub end_block: Option<BlockNumberFor>,
+}
+
+/// The position of an account in a farming pool as listed by the runtime API.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PositionData<BalanceOf, CurrencyIdOf, BlockNumberFor> {
+    pub pool_id: PoolId,
+    pub share: BalanceOf,
+    /// Rewards which can be claimed <reward_currency, amount>
+    pub pending_rewards: Vec<(CurrencyIdOf, BalanceOf)>,
+    /// Withdrawn shares waiting to be unlocked <unlock_block, amount>
+    pub withdraw_list: Vec<(BlockNumberFor, BalanceOf)>,
+}
+
+/// The emission curve of `basic_rewards`, counted from `after_block_to_start`.
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum EmissionCurve<BlockNumberFor> {
+    /// `basic_rewards` every block.
+    Constant,
+    /// `basic_rewards` decaying linearly to zero at `end_block`.
+    LinearDecay,
+    /// `basic_rewards` halved every `period` blocks.
+    StepHalving { period: BlockNumberFor },
+}
+
+impl<BlockNumberFor> Default for EmissionCurve<BlockNumberFor> {
+    fn default() -> Self {
+        EmissionCurve::Constant
+    }
+}
+
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub enum PoolState {
     UnCharged,
@@ -136,6 +220,9 @@ pub enum Action {
     ResetPool,
     KillPool,
     EditPool,
+    RefundCharge,
+    AddIncentive,
+    Charge,
 }
 
 impl PoolState {
@@ -158,11 +245,207 @@ impl PoolState {
                     || state == PoolState::Charged
                     || state == PoolState::UnCharged
             }
+            Action::RefundCharge => state == PoolState::Dead || state == PoolState::Retired,
+            Action::AddIncentive => {
+                state == PoolState::UnCharged
+                    || state == PoolState::Charged
+                    || state == PoolState::Ongoing
+            }
+            Action::Charge => {
+                state == PoolState::UnCharged
+                    || state == PoolState::Charged
+                    || state == PoolState::Ongoing
+            }
         }
     }
 }
 
 impl<T: Config> Pallet<T> {
+    /// Brings `pool_info` up to the current block: a charged pool whose scheduled
+    /// `block_startup` has been reached starts farming, and an ongoing pool accrues
+    /// the rewards emitted since `last_reward_block` out of its `reward_budget`.
+    /// Incentive streams are accrued over the same blocks, while the pool has shares.
+    /// The pool turns `Dead` once its `end_block` is reached or its budget is exhausted.
+    ///
+    /// `total_reward / total_shares` of `rewards` acts as the reward-per-share accumulator, with
+    /// `withdrawn_rewards` of a share as its reward debt. Every share change accrues up to the
+    /// current block first, so each account earns its share of every block's emission exactly
+    /// as when rewards were added in `on_initialize`, without a second accumulator to migrate.
+    pub fn accrue_pool_rewards(pool_info: &mut PoolInfoOf<T>) {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let ended = pool_info
+            .end_block
+            .map_or(false, |end_block| end_block <= n);
+        if pool_info.state == PoolState::Charged {
+            match pool_info.block_startup {
+                Some(block_startup) if block_startup <= n => {
+                    pool_info.state = PoolState::Ongoing;
+                    pool_info.last_reward_block = block_startup;
+                }
+                _ => {
+                    if ended {
+                        pool_info.state = PoolState::Dead;
+                    }
+                    return;
+                }
+            }
+        }
+        if pool_info.state != PoolState::Ongoing {
+            return;
+        }
+
+        let accrue_to = pool_info.end_block.map_or(n, |end_block| end_block.min(n));
+        if accrue_to > pool_info.last_reward_block {
+            let from = pool_info.last_reward_block;
+            pool_info.basic_rewards.clone().iter().for_each(
+                |(reward_currency_id, basic_reward)| {
+                    let budget = pool_info
+                        .reward_budget
+                        .get(reward_currency_id)
+                        .copied()
+                        .unwrap_or_default();
+                    let reward_amount =
+                        Self::emitted_rewards(pool_info, *basic_reward, from, accrue_to)
+                            .min(budget);
+                    if reward_amount.is_zero() {
+                        return;
+                    }
+                    pool_info
+                        .reward_budget
+                        .insert(*reward_currency_id, budget.saturating_sub(reward_amount));
+                    pool_info
+                        .rewards
+                        .entry(*reward_currency_id)
+                        .and_modify(|(total_reward, _)| {
+                            *total_reward = total_reward.saturating_add(reward_amount);
+                        })
+                        .or_insert((reward_amount, Zero::zero()));
+                },
+            );
+            Self::accrue_incentives(pool_info, from, accrue_to);
+            pool_info.last_reward_block = accrue_to;
+        }
+
+        let exhausted = !pool_info.basic_rewards.is_empty()
+            && pool_info.basic_rewards.keys().all(|reward_currency_id| {
+                pool_info
+                    .reward_budget
+                    .get(reward_currency_id)
+                    .map_or(true, |budget| budget.is_zero())
+            });
+        if ended || exhausted {
+            pool_info.state = PoolState::Dead;
+        }
+    }
+
+    fn accrue_incentives(
+        pool_info: &mut PoolInfoOf<T>,
+        from: BlockNumberFor<T>,
+        to: BlockNumberFor<T>,
+    ) {
+        let total_shares = pool_info.total_shares;
+        let rewards = &mut pool_info.rewards;
+        pool_info.incentives.values_mut().for_each(|incentive| {
+            let incentive_from = incentive.last_reward_block.max(from);
+            let incentive_to = incentive.end_block.min(to);
+            if incentive_to <= incentive_from {
+                return;
+            }
+            incentive.last_reward_block = incentive_to;
+            // Blocks without shares emit nothing, their rewards go back to the sponsor
+            if total_shares.is_zero() {
+                return;
+            }
+            let blocks: BalanceOf<T> = (incentive_to - incentive_from).saturated_into();
+            let reward_amount = incentive
+                .reward_per_block
+                .saturating_mul(blocks)
+                .min(incentive.remaining);
+            if reward_amount.is_zero() {
+                return;
+            }
+            incentive.remaining = incentive.remaining.saturating_sub(reward_amount);
+            rewards
+                .entry(incentive.reward_currency)
+                .and_modify(|(total_reward, _)| {
+                    *total_reward = total_reward.saturating_add(reward_amount);
+                })
+                .or_insert((reward_amount, Zero::zero()));
+        });
+    }
+
+    /// The rewards `emission_curve` emits for the blocks `from..to` when paying
+    /// `basic_reward` per block at `after_block_to_start`.
+    pub fn emitted_rewards(
+        pool_info: &PoolInfoOf<T>,
+        basic_reward: BalanceOf<T>,
+        from: BlockNumberFor<T>,
+        to: BlockNumberFor<T>,
+    ) -> BalanceOf<T> {
+        let basic_reward = basic_reward.saturated_into::<u128>();
+        let start = pool_info.after_block_to_start.saturated_into::<u128>();
+        let from = from.saturated_into::<u128>().max(start);
+        let to = to.saturated_into::<u128>();
+        if to <= from {
+            return Zero::zero();
+        }
+
+        let emitted = match pool_info.emission_curve {
+            EmissionCurve::Constant => basic_reward.saturating_mul(to - from),
+            EmissionCurve::LinearDecay => match pool_info.end_block {
+                Some(end) => {
+                    let end = end.saturated_into::<u128>();
+                    let to = to.min(end);
+                    if to <= from {
+                        return Zero::zero();
+                    }
+                    // The block `k` emits `basic_reward * (end - k) / (end - start)`
+                    let triangle = |x: u128| U256::from(x) * U256::from(x + 1) / 2;
+                    U256::from(basic_reward)
+                        .saturating_mul(triangle(end - from) - triangle(end - to))
+                        .checked_div(U256::from(end - start))
+                        .unwrap_or_default()
+                        .min(U256::from(u128::MAX))
+                        .as_u128()
+                }
+                None => 0,
+            },
+            EmissionCurve::StepHalving { period } => {
+                let period = period.saturated_into::<u128>().max(1);
+                let mut emitted: u128 = 0;
+                let mut block = from;
+                while block < to {
+                    let halvings = (block - start) / period;
+                    if halvings >= u128::BITS as u128 {
+                        break;
+                    }
+                    let halving_end = start
+                        .saturating_add(period.saturating_mul(halvings + 1))
+                        .min(to);
+                    emitted = emitted.saturating_add(
+                        (basic_reward >> halvings).saturating_mul(halving_end - block),
+                    );
+                    block = halving_end;
+                }
+                emitted
+            }
+        };
+        emitted.saturated_into()
+    }
+
+    /// Schedules a charged pool to start farming in the block after `n`, but not before
+    /// `after_block_to_start`, as long as it holds at least `min_deposit_to_start` shares.
+    pub fn schedule_pool_startup(pool_info: &mut PoolInfoOf<T>, n: BlockNumberFor<T>) {
+        if pool_info.state == PoolState::Charged {
+            pool_info.block_startup = (pool_info.total_shares >= pool_info.min_deposit_to_start)
+                .then(|| {
+                    pool_info
+                        .after_block_to_start
+                        .max(n.saturating_add(One::one()))
+                });
+        }
+    }
+
     pub fn add_share(
         who: &T::AccountId,
         pool_id: PoolId,
@@ -214,6 +497,7 @@ impl<T: Config> Pallet<T> {
             });
 
         SharesAndWithdrawnRewards::<T>::insert(pool_id, who, share_info);
+        Self::schedule_pool_startup(pool_info, n);
         PoolInfos::<T>::insert(pool_id, pool_info);
     }
 
@@ -235,57 +519,48 @@ impl<T: Config> Pallet<T> {
         SharesAndWithdrawnRewards::<T>::mutate(pool, who, |share_info_old| -> DispatchResult {
             let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
             if let Some(mut share_info) = share_info_old.take() {
+                // boost shares are not backed by any deposit
+                let boost = BoostShares::<T>::get(pool, who);
+                let deposited = share_info.share.saturating_sub(boost);
                 let remove_amount;
                 if let Some(remove_amount_input) = remove_amount_input {
-                    remove_amount = remove_amount_input.min(share_info.share);
+                    remove_amount = remove_amount_input.min(deposited);
                 } else {
-                    remove_amount = share_info.share;
+                    remove_amount = deposited;
                 }
                 if remove_amount.is_zero() {
                     return Ok(());
                 }
+                // the boost goes with the last deposited share
+                let remove_boost = if remove_amount == deposited {
+                    boost
+                } else {
+                    Zero::zero()
+                };
 
                 PoolInfos::<T>::mutate(pool, |maybe_pool_info| -> DispatchResult {
                     let pool_info = maybe_pool_info
                         .as_mut()
                         .ok_or(Error::<T>::PoolDoesNotExist)?;
-                    pool_info.total_shares = pool_info.total_shares.saturating_sub(remove_amount);
-
-                    // update withdrawn rewards for each reward currency
-                    share_info.withdrawn_rewards.iter_mut().try_for_each(
-                        |(reward_currency, withdrawn_reward)| -> DispatchResult {
-                            let withdrawn_amount = Self::get_reward_inflation(
-                                remove_amount,
-                                withdrawn_reward,
-                                share_info.share,
-                            );
-                            if withdrawn_amount.is_zero() {
-                                return Ok(());
-                            }
-
-                            if let Some((total_reward, total_withdrawn_reward)) =
-                                pool_info.rewards.get_mut(reward_currency)
-                            {
-                                *total_reward = total_reward.saturating_sub(withdrawn_amount);
-                                *total_withdrawn_reward =
-                                    total_withdrawn_reward.saturating_sub(withdrawn_amount);
-
-                                // remove if all reward is withdrawn
-                                if total_reward.is_zero() {
-                                    pool_info.rewards.remove(reward_currency);
-                                }
-                            }
-                            *withdrawn_reward = withdrawn_reward.saturating_sub(withdrawn_amount);
-                            Ok(())
-                        },
-                    )?;
+                    Self::accrue_pool_rewards(pool_info);
+                    Self::reduce_share(
+                        pool_info,
+                        &mut share_info,
+                        remove_amount.saturating_add(remove_boost),
+                    );
+                    Self::schedule_pool_startup(pool_info, n);
                     Ok(())
                 })?;
+                if !remove_boost.is_zero() {
+                    BoostShares::<T>::remove(pool, who);
+                    PoolBoosts::<T>::mutate(pool, |total_boost| {
+                        *total_boost = total_boost.saturating_sub(remove_boost);
+                    });
+                }
 
                 share_info
                     .withdraw_list
                     .push((n + withdraw_limit_time, remove_amount));
-                share_info.share = share_info.share.saturating_sub(remove_amount);
                 *share_info_old = Some(share_info);
             }
             Ok(())
@@ -293,6 +568,43 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Removes `remove_amount` of the shares of `share_info` from the pool, along with
+    /// their part of the withdrawn rewards.
+    pub fn reduce_share(
+        pool_info: &mut PoolInfoOf<T>,
+        share_info: &mut ShareInfoOf<T>,
+        remove_amount: BalanceOf<T>,
+    ) {
+        pool_info.total_shares = pool_info.total_shares.saturating_sub(remove_amount);
+
+        // update withdrawn rewards for each reward currency
+        share_info
+            .withdrawn_rewards
+            .iter_mut()
+            .for_each(|(reward_currency, withdrawn_reward)| {
+                let withdrawn_amount =
+                    Self::get_reward_inflation(remove_amount, withdrawn_reward, share_info.share);
+                if withdrawn_amount.is_zero() {
+                    return;
+                }
+
+                if let Some((total_reward, total_withdrawn_reward)) =
+                    pool_info.rewards.get_mut(reward_currency)
+                {
+                    *total_reward = total_reward.saturating_sub(withdrawn_amount);
+                    *total_withdrawn_reward =
+                        total_withdrawn_reward.saturating_sub(withdrawn_amount);
+
+                    // remove if all reward is withdrawn
+                    if total_reward.is_zero() {
+                        pool_info.rewards.remove(reward_currency);
+                    }
+                }
+                *withdrawn_reward = withdrawn_reward.saturating_sub(withdrawn_amount);
+            });
+        share_info.share = share_info.share.saturating_sub(remove_amount);
+    }
+
     pub fn claim_rewards(who: &T::AccountId, pool: PoolId) -> DispatchResult {
         SharesAndWithdrawnRewards::<T>::mutate_exists(
             pool,
@@ -308,6 +620,7 @@ impl<T: Config> Pallet<T> {
                         let pool_info = maybe_pool_info
                             .as_mut()
                             .ok_or(Error::<T>::PoolDoesNotExist)?;
+                        Self::accrue_pool_rewards(pool_info);
 
                         let total_shares = pool_info.total_shares;
                         pool_info.rewards.iter_mut().try_for_each(
@@ -326,7 +639,7 @@ impl<T: Config> Pallet<T> {
 									withdrawn_reward.saturating_add(reward_to_withdraw),
 								);
 
-                                Self::farming_token_transfer(reward_currency, reward_to_withdraw, who, &pool_info.reward_issuer)
+                                Self::reward_transfer(pool, reward_currency, reward_to_withdraw, who, &pool_info.reward_issuer)
 							},
 						)?;
                         Ok(())
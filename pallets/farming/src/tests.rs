@@ -16,7 +16,7 @@
 
 #![cfg(test)]
 
-use frame_support::{assert_err, assert_noop, assert_ok};
+use frame_support::{assert_err, assert_noop, assert_ok, traits::Currency};
 use manta_primitives::types::CalamariAssetId;
 use sp_runtime::traits::AccountIdConversion;
 
@@ -37,7 +37,9 @@ fn init_gauge_900() -> (PoolId, BalanceOf<Runtime>) {
         0, // after_block_to_start
         0, // withdraw_limit_time
         0, // claim_limit_time
-        5  // withdraw_limit_count
+        5, // withdraw_limit_count
+        None,
+        EmissionCurve::Constant
     ));
 
     let pool_id = 0;
@@ -71,7 +73,9 @@ fn init_gauge_1000() -> (PoolId, BalanceOf<Runtime>) {
         0,  // after_block_to_start
         10, // withdraw_limit_time
         0,  // claim_limit_time
-        1   // withdraw_limit_count
+        1,  // withdraw_limit_count
+        None,
+        EmissionCurve::Constant
     ));
 
     let pool_id = 0;
@@ -108,7 +112,9 @@ fn init_no_gauge() -> (PoolId, BalanceOf<Runtime>) {
         0,  // after_block_to_start
         10, // withdraw_limit_time
         0,  // claim_limit_time
-        1   // withdraw_limit_count
+        1,  // withdraw_limit_count
+        None,
+        EmissionCurve::Constant
     ));
 
     let pool_id = 0;
@@ -151,7 +157,9 @@ fn precondition_check_should_work() {
                     0,  // after_block_to_start
                     10, // withdraw_limit_time
                     0,  // claim_limit_time
-                    1   // withdraw_limit_count
+                    1,  // withdraw_limit_count
+                    None,
+                    EmissionCurve::Constant
                 ),
                 Error::<Runtime>::InvalidPoolParameter
             );
@@ -199,6 +207,7 @@ fn precondition_check_should_work() {
                     None,
                     None,
                     None,
+                    None,
                     None
                 ),
                 Error::<Runtime>::PoolDoesNotExist
@@ -259,6 +268,7 @@ fn precondition_check_should_work() {
                     None,
                     None,
                     None,
+                    None
                 ),
                 Error::<Runtime>::InvalidPoolState
             );
@@ -267,8 +277,9 @@ fn precondition_check_should_work() {
                 Error::<Runtime>::InvalidPoolState
             );
 
-            // Pool state is Ongoing
-            Farming::on_initialize(System::block_number() + 10);
+            // Pool state is Ongoing once an action accrues rewards after the startup block
+            System::set_block_number(System::block_number() + 10);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id0));
             let pool1: PoolInfoOf<Runtime> = Farming::pool_infos(pool_id0).unwrap();
             assert_eq!(pool1.state, PoolState::Ongoing);
 
@@ -287,6 +298,7 @@ fn precondition_check_should_work() {
                     None,
                     None,
                     None,
+                    None
                 ),
                 Error::<Runtime>::InvalidPoolState
             );
@@ -332,7 +344,9 @@ fn no_gauge_farming_pool_should_work() {
                 1, // after_block_to_start
                 withdraw_limit_time,
                 6, // claim_limit_time
-                5  // withdraw_limit_count
+                5, // withdraw_limit_count
+                None,
+                EmissionCurve::Constant
             ));
             assert_eq!(PoolNextId::<Runtime>::get(), 1);
             assert_ok!(Farming::create_farming_pool(
@@ -344,7 +358,9 @@ fn no_gauge_farming_pool_should_work() {
                 1, // after_block_to_start
                 withdraw_limit_time,
                 6, // claim_limit_time
-                5  // withdraw_limit_count
+                5, // withdraw_limit_count
+                None,
+                EmissionCurve::Constant
             ));
             assert_eq!(PoolNextId::<Runtime>::get(), 2);
 
@@ -412,10 +428,11 @@ fn no_gauge_farming_pool_should_work() {
             assert!(reward.withdraw_list.is_empty());
             assert_eq!(reward.claim_last_block, 3);
 
-            // The pool state is still on `Charged` until new block produced
+            // The pool state is still on `Charged` until the scheduled startup block
             pool1 = Farming::pool_infos(pool_id).unwrap();
             assert_eq!(pool1.total_shares, deposit_amount);
             assert_eq!(pool1.state, PoolState::Charged);
+            assert_eq!(pool1.block_startup, Some(4));
             assert!(pool1.rewards.is_empty());
 
             // Can't Claim if pool state is `Charged`
@@ -424,16 +441,14 @@ fn no_gauge_farming_pool_should_work() {
                 Error::<Runtime>::InvalidPoolState
             );
 
-            // OnInitialize hook change the pool state and also pool rewards
-            Farming::on_initialize(System::block_number() + 3);
-            Farming::on_initialize(0);
-            pool1 = Farming::pool_infos(pool_id).unwrap();
-            assert_eq!(pool1.total_shares, deposit_amount);
-            assert_eq!(pool1.state, PoolState::Ongoing);
-            assert_eq!(pool1.claim_limit_time, 6);
-            assert_eq!(pool1.rewards.get(&KSM).unwrap(), &(reward_amount, 0));
+            // The pool starts at block 4 and accrues rewards for every block after it
+            System::set_block_number(System::block_number() + 2);
+            assert_eq!(
+                Farming::get_farming_rewards(&ALICE, pool_id),
+                Ok(vec![(KSM, reward_amount)])
+            );
 
-            // Produce new block didn't change the reward info
+            // Accruing rewards didn't change the reward info
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
             assert_eq!(reward.share, deposit_amount);
             assert!(reward.withdrawn_rewards.is_empty());
@@ -452,107 +467,84 @@ fn no_gauge_farming_pool_should_work() {
                 Error::<Runtime>::CanNotClaim
             );
 
-            // Claim success, user get reward.
+            // Claim success, user get the rewards of blocks 5 to 11.
             System::set_block_number(System::block_number() + 6);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
             assert_eq!(
                 Assets::balance(KSM, &ALICE),
-                alice_init_balance - deposit_amount + reward_amount
+                alice_init_balance - deposit_amount + reward_amount * 7
             );
             assert_eq!(
                 Assets::balance(KSM, &pool1.reward_issuer),
-                total_rewards - reward_amount
+                total_rewards - reward_amount * 7
             );
 
             // Claim operation update pool info's rewards and also share info's withdrawn_rewards
             pool1 = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool1.state, PoolState::Ongoing);
+            assert_eq!(pool1.last_reward_block, 11);
             assert_eq!(
                 pool1.rewards.get(&KSM).unwrap(),
-                &(reward_amount, reward_amount)
+                &(reward_amount * 7, reward_amount * 7)
             );
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
-            assert_eq!(reward.withdrawn_rewards.get(&KSM).unwrap(), &reward_amount);
+            assert_eq!(
+                reward.withdrawn_rewards.get(&KSM).unwrap(),
+                &(reward_amount * 7)
+            );
             // The withdraw list of user share info is still empty.
             assert!(reward.withdraw_list.is_empty());
 
-            // Claim without new block.
-            for i in 0..5 {
-                System::set_block_number(System::block_number() + 100);
-                assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-
-                reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
-                assert_eq!(reward.withdrawn_rewards.get(&KSM).unwrap(), &reward_amount);
-                assert_eq!(reward.claim_last_block, 109 + i * 100);
-                assert_eq!(reward.share, deposit_amount);
-                assert!(reward.withdraw_list.is_empty());
-
-                pool1 = Farming::pool_infos(pool_id).unwrap();
-                assert_eq!(
-                    pool1.rewards.get(&KSM).unwrap(),
-                    &(reward_amount, reward_amount)
-                );
-                assert_eq!(pool1.total_shares, deposit_amount);
-
-                assert_eq!(
-                    Assets::balance(KSM, &ALICE),
-                    alice_init_balance - deposit_amount + reward_amount
-                );
-                assert_eq!(
-                    Assets::balance(KSM, &pool1.reward_issuer),
-                    total_rewards - reward_amount
-                );
-                assert_eq!(Assets::balance(KSM, &pool1.keeper), deposit_amount);
-            }
-            assert_eq!(
-                Assets::balance(KSM, &pool1.reward_issuer),
-                total_rewards - reward_amount
+            // Claim again within the claim limit time.
+            assert_noop!(
+                Farming::claim(RuntimeOrigin::signed(ALICE), pool_id),
+                Error::<Runtime>::CanNotClaim
             );
-            assert_eq!(Assets::balance(KSM, &pool1.keeper), deposit_amount);
-            // Claim with new block
+
+            // Claim with new blocks
             for i in 1..5 {
                 System::set_block_number(System::block_number() + 6);
-                Farming::on_initialize(System::block_number() + 3);
                 assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
 
                 reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
                 assert_eq!(
                     reward.withdrawn_rewards.get(&KSM).unwrap(),
-                    &(reward_amount * (i + 1))
+                    &(reward_amount * (7 + i * 6))
                 );
-                assert_eq!(reward.claim_last_block as u128, 509 + i * 6);
+                assert_eq!(reward.claim_last_block as u128, 11 + i * 6);
                 assert_eq!(reward.share, deposit_amount);
                 assert!(reward.withdraw_list.is_empty());
 
                 pool1 = Farming::pool_infos(pool_id).unwrap();
                 assert_eq!(
                     pool1.rewards.get(&KSM).unwrap(),
-                    &(reward_amount * (i + 1), reward_amount * (i + 1))
+                    &(reward_amount * (7 + i * 6), reward_amount * (7 + i * 6))
                 );
                 assert_eq!(pool1.total_shares, deposit_amount);
 
                 assert_eq!(
                     Assets::balance(KSM, &ALICE),
-                    alice_init_balance - deposit_amount + reward_amount * (i + 1)
+                    alice_init_balance - deposit_amount + reward_amount * (7 + i * 6)
                 );
                 assert_eq!(
                     Assets::balance(KSM, &pool1.reward_issuer),
-                    total_rewards - reward_amount * (i + 1)
+                    total_rewards - reward_amount * (7 + i * 6)
                 );
                 // Because withdraw_list of user share is empty, keeper not return token to user.
                 assert_eq!(Assets::balance(KSM, &pool1.keeper), deposit_amount);
             }
             assert_eq!(
                 Assets::balance(KSM, &ALICE),
-                alice_init_balance - deposit_amount + reward_amount * 5
+                alice_init_balance - deposit_amount + reward_amount * 31
             );
             assert_eq!(
                 Assets::balance(KSM, &pool1.reward_issuer),
-                total_rewards - reward_amount * 5
+                total_rewards - reward_amount * 31
             );
             assert_eq!(Assets::balance(KSM, &pool1.keeper), deposit_amount);
             assert_eq!(
                 pool1.rewards.get(&KSM).unwrap(),
-                &(reward_amount * 5, reward_amount * 5)
+                &(reward_amount * 31, reward_amount * 31)
             );
 
             // Withdraw failed because of share info not exist.
@@ -561,17 +553,17 @@ fn no_gauge_farming_pool_should_work() {
                 Error::<Runtime>::ShareInfoNotExists
             );
 
-            // Claim again without new blocks, no new rewards
+            // No new rewards within the block of the last claim
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
             pool1 = Farming::pool_infos(pool_id).unwrap();
             assert!(reward.withdraw_list.is_empty());
 
             let share_reward = reward.withdrawn_rewards.get(&KSM).unwrap();
-            assert_eq!(share_reward, &(reward_amount * 5));
+            assert_eq!(share_reward, &(reward_amount * 31));
             let (total_reward, total_withdrawn_reward) = pool1.rewards.get(&KSM).unwrap();
             assert_eq!(
                 pool1.rewards.get(&KSM).unwrap(),
-                &(reward_amount * 5, reward_amount * 5)
+                &(reward_amount * 31, reward_amount * 31)
             );
 
             let reward_amount1 = Farming::get_reward_amount(
@@ -582,10 +574,10 @@ fn no_gauge_farming_pool_should_work() {
                 &KSM,
             )
             .unwrap();
-            assert_eq!(reward_amount1, (reward_amount * 5, 0));
+            assert_eq!(reward_amount1, (reward_amount * 31, 0));
             let reward_inflation =
                 Farming::get_reward_inflation(reward.share, total_reward, pool1.total_shares);
-            assert_eq!(reward_inflation, reward_amount * 5);
+            assert_eq!(reward_inflation, reward_amount * 31);
 
             let reward_inflation = Farming::get_reward_inflation(800, share_reward, reward.share);
             assert_eq!(reward_inflation, share_reward * 8 / 10);
@@ -595,10 +587,10 @@ fn no_gauge_farming_pool_should_work() {
             assert_eq!(reward_inflation, share_reward / 10);
 
             // Withdraw partial tokens
-            assert_eq!(System::block_number(), 533);
+            assert_eq!(System::block_number(), 35);
             assert_eq!(
                 Assets::balance(KSM, &ALICE),
-                alice_init_balance - deposit_amount + reward_amount * 5
+                alice_init_balance - deposit_amount + reward_amount * 31
             );
             assert_ok!(Farming::withdraw(
                 RuntimeOrigin::signed(ALICE),
@@ -606,48 +598,54 @@ fn no_gauge_farming_pool_should_work() {
                 Some(800)
             ));
 
-            // Although withdraw also has claim, but no new rewards due to no new block
+            // Although withdraw also has claim, but no new rewards within the same block
             // So both user and reward issuer account balance not change.
             assert_eq!(
                 Assets::balance(KSM, &ALICE),
-                alice_init_balance - deposit_amount + reward_amount * 5
+                alice_init_balance - deposit_amount + reward_amount * 31
             );
             assert_eq!(
                 Assets::balance(KSM, &pool1.reward_issuer),
-                total_rewards - reward_amount * 5
+                total_rewards - reward_amount * 31
             );
             assert_eq!(Assets::balance(KSM, &pool1.keeper), deposit_amount);
 
             // Withdraw operation has only one operation `remove_share`.
             // And `remove_share` will claim rewards and also update user share info.
-            // We already know that due to no new block, claim rewards actually has no reward.
+            // We already know that within the same block, claim rewards actually has no reward.
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
             pool1 = Farming::pool_infos(pool_id).unwrap();
 
             assert_eq!(pool1.total_shares, 200);
-            assert_eq!(reward.withdraw_list, vec![(533 + withdraw_limit_time, 800)]);
+            assert_eq!(reward.withdraw_list, vec![(35 + withdraw_limit_time, 800)]);
             assert_eq!(reward.share, 200);
-            assert_eq!(reward.withdrawn_rewards.get(&KSM).unwrap(), &reward_amount);
+            assert_eq!(
+                reward.withdrawn_rewards.get(&KSM).unwrap(),
+                &(reward_amount * 31 / 5)
+            );
             assert_eq!(
                 pool1.rewards.get(&KSM).unwrap(),
-                &(reward_amount, reward_amount)
+                &(reward_amount * 31 / 5, reward_amount * 31 / 5)
             );
 
             System::set_block_number(System::block_number() + 6);
-            Farming::on_initialize(System::block_number() + 3);
 
-            // Withdraw rest all of share
+            // Withdraw rest all of share, claiming the rewards of the last 6 blocks
             assert_ok!(Farming::withdraw(
                 RuntimeOrigin::signed(ALICE),
                 pool_id,
                 Some(300)
             ));
+            assert_eq!(
+                Assets::balance(KSM, &ALICE),
+                alice_init_balance - deposit_amount + reward_amount * 37
+            );
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
             pool1 = Farming::pool_infos(pool_id).unwrap();
             assert_eq!(pool1.total_shares, 0);
             assert!(pool1.rewards.is_empty());
             assert_eq!(reward.share, 0);
-            assert_eq!(reward.withdraw_list, vec![(540, 800), (546, 200)]);
+            assert_eq!(reward.withdraw_list, vec![(42, 800), (48, 200)]);
             assert_eq!(reward.withdrawn_rewards.get(&KSM).unwrap(), &0);
         })
 }
@@ -681,7 +679,9 @@ fn staking_minimum_kma(stake_token: CalamariAssetId, reward_token: CalamariAsset
                 1, // after_block_to_start
                 7, // withdraw_limit_time,
                 6, // claim_limit_time
-                5  // withdraw_limit_count
+                5, // withdraw_limit_count
+                None,
+                EmissionCurve::Constant
             ));
 
             // Charge to the pool reward issuer
@@ -706,7 +706,6 @@ fn staking_minimum_kma(stake_token: CalamariAssetId, reward_token: CalamariAsset
             assert_eq!(Balances::free_balance(&CHARLIE), 0);
             assert_eq!(Balances::free_balance(&pool1.keeper), charlie_init_kma);
 
-            Farming::on_initialize(System::block_number() + 10);
             System::set_block_number(System::block_number() + 10);
             assert_eq!(Balances::free_balance(&CHARLIE), 0);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(CHARLIE), pool_id));
@@ -760,7 +759,9 @@ fn staking_all_kma(stake_token: CalamariAssetId, reward_token: CalamariAssetId)
                 1, // after_block_to_start
                 7, // withdraw_limit_time,
                 6, // claim_limit_time
-                5  // withdraw_limit_count
+                5, // withdraw_limit_count
+                None,
+                EmissionCurve::Constant
             ));
 
             // Charge to the pool reward issuer
@@ -785,7 +786,6 @@ fn staking_all_kma(stake_token: CalamariAssetId, reward_token: CalamariAssetId)
             assert_eq!(Balances::free_balance(&ALICE), 0);
             assert_eq!(Balances::free_balance(&pool1.keeper), alice_init_kma);
 
-            Farming::on_initialize(System::block_number() + 10);
             System::set_block_number(System::block_number() + 10);
             assert_eq!(Balances::free_balance(&ALICE), 0);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
@@ -843,7 +843,9 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
                 1, // after_block_to_start
                 withdraw_limit_time,
                 6, // claim_limit_time
-                5  // withdraw_limit_count
+                5, // withdraw_limit_count
+                None,
+                EmissionCurve::Constant
             ));
 
             // Charge to the pool reward issuer
@@ -905,22 +907,19 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             assert!(reward.withdraw_list.is_empty());
             assert_eq!(reward.claim_last_block, 3);
 
-            // The pool state is still on `Charged` until new block produced
+            // The pool state is still on `Charged` until the scheduled startup block
             pool1 = Farming::pool_infos(pool_id).unwrap();
             assert_eq!(pool1.total_shares, deposit_amount);
             assert_eq!(pool1.state, PoolState::Charged);
+            assert_eq!(pool1.claim_limit_time, 6);
+            assert_eq!(pool1.block_startup, Some(4));
             assert!(pool1.rewards.is_empty());
 
-            // OnInitialize hook change the pool state and also pool rewards
-            Farming::on_initialize(System::block_number() + 3);
-            Farming::on_initialize(0);
-            pool1 = Farming::pool_infos(pool_id).unwrap();
-            assert_eq!(pool1.total_shares, deposit_amount);
-            assert_eq!(pool1.state, PoolState::Ongoing);
-            assert_eq!(pool1.claim_limit_time, 6);
+            // Rewards accrue for every block after the startup block
+            System::set_block_number(System::block_number() + 2);
             assert_eq!(
-                pool1.rewards.get(&reward_token).unwrap(),
-                &(reward_amount, 0)
+                Farming::get_farming_rewards(&ALICE, pool_id),
+                Ok(vec![(reward_token, reward_amount)])
             );
 
             // Produce new block didn't change the reward info
@@ -930,126 +929,86 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             assert!(reward.withdraw_list.is_empty());
             assert_eq!(reward.claim_last_block, 3);
 
-            // Claim success, user get reward.
-            System::set_block_number(System::block_number() + 6);
+            // Claim success, user get the rewards of blocks 5 to 9.
+            System::set_block_number(System::block_number() + 4);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
             if reward_token == KSM {
                 // Stake KMA, reward KSM
                 assert_eq!(
                     Assets::balance(reward_token, &ALICE),
-                    alice_init_ksm + reward_amount
+                    alice_init_ksm + reward_amount * 5
                 );
                 assert_eq!(
                     Assets::balance(reward_token, &pool1.reward_issuer),
-                    total_rewards - reward_amount
+                    total_rewards - reward_amount * 5
                 );
             } else {
                 // Stake KSM, reward KMA
                 assert_eq!(
                     Balances::free_balance(&ALICE),
-                    alice_init_kma + reward_amount
+                    alice_init_kma + reward_amount * 5
                 );
                 assert_eq!(
                     Balances::free_balance(&pool1.reward_issuer),
-                    total_rewards - reward_amount
+                    total_rewards - reward_amount * 5
                 );
             }
 
             // Claim operation update pool info's rewards and also share info's withdrawn_rewards
             pool1 = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool1.state, PoolState::Ongoing);
             assert_eq!(
                 pool1.rewards.get(&reward_token).unwrap(),
-                &(reward_amount, reward_amount)
+                &(reward_amount * 5, reward_amount * 5)
             );
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
             assert_eq!(
                 reward.withdrawn_rewards.get(&reward_token).unwrap(),
-                &reward_amount
+                &(reward_amount * 5)
             );
             // The withdraw list of user share info is still empty.
             assert!(reward.withdraw_list.is_empty());
 
-            // Claim without new block.
-            for i in 0..5 {
-                System::set_block_number(System::block_number() + 100);
-                assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-
-                reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
-                assert_eq!(
-                    reward.withdrawn_rewards.get(&reward_token).unwrap(),
-                    &reward_amount
-                );
-                assert_eq!(reward.claim_last_block, 109 + i * 100);
-                assert_eq!(reward.share, deposit_amount);
-                assert!(reward.withdraw_list.is_empty());
-
-                pool1 = Farming::pool_infos(pool_id).unwrap();
-                assert_eq!(
-                    pool1.rewards.get(&reward_token).unwrap(),
-                    &(reward_amount, reward_amount)
-                );
-                assert_eq!(pool1.total_shares, deposit_amount);
-            }
-            if reward_token == KSM {
-                // Stake KMA, reward KSM
-                assert_eq!(
-                    Assets::balance(reward_token, &pool1.reward_issuer),
-                    total_rewards - reward_amount
-                );
-            } else {
-                // Stake KSM, reward KMA
-                assert_eq!(
-                    Balances::free_balance(&pool1.reward_issuer),
-                    total_rewards - reward_amount
-                );
-            }
-            if stake_token == KSM {
-                assert_eq!(Assets::balance(stake_token, &pool1.keeper), deposit_amount);
-            } else {
-                assert_eq!(Balances::free_balance(&pool1.keeper), deposit_amount);
-            }
-
-            // Claim with new block
+            // Claim with new blocks
             for i in 1..5 {
                 System::set_block_number(System::block_number() + 6);
-                Farming::on_initialize(System::block_number() + 3);
                 assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
 
                 reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
                 assert_eq!(
                     reward.withdrawn_rewards.get(&reward_token).unwrap(),
-                    &(reward_amount * (i + 1))
+                    &(reward_amount * (5 + i * 6))
                 );
-                assert_eq!(reward.claim_last_block as u128, 509 + i * 6);
+                assert_eq!(reward.claim_last_block as u128, 9 + i * 6);
                 assert_eq!(reward.share, deposit_amount);
                 assert!(reward.withdraw_list.is_empty());
 
                 pool1 = Farming::pool_infos(pool_id).unwrap();
                 assert_eq!(
                     pool1.rewards.get(&reward_token).unwrap(),
-                    &(reward_amount * (i + 1), reward_amount * (i + 1))
+                    &(reward_amount * (5 + i * 6), reward_amount * (5 + i * 6))
                 );
                 assert_eq!(pool1.total_shares, deposit_amount);
             }
             if reward_token == KSM {
-                // Stake KMA, reward another 4 times KSM
+                // Stake KMA, reward KSM
                 assert_eq!(
                     Assets::balance(reward_token, &ALICE),
-                    alice_init_ksm + reward_amount * 5
+                    alice_init_ksm + reward_amount * 29
                 );
                 assert_eq!(
                     Assets::balance(reward_token, &pool1.reward_issuer),
-                    total_rewards - reward_amount * 5
+                    total_rewards - reward_amount * 29
                 );
             } else {
-                // Stake KSM, reward another 4 times KMA
+                // Stake KSM, reward KMA
                 assert_eq!(
                     Balances::free_balance(&ALICE),
-                    alice_init_kma + reward_amount * 5
+                    alice_init_kma + reward_amount * 29
                 );
                 assert_eq!(
                     Balances::free_balance(&pool1.reward_issuer),
-                    total_rewards - reward_amount * 5
+                    total_rewards - reward_amount * 29
                 );
             }
             if stake_token == KSM {
@@ -1059,7 +1018,7 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             }
             assert_eq!(
                 pool1.rewards.get(&reward_token).unwrap(),
-                &(reward_amount * 5, reward_amount * 5)
+                &(reward_amount * 29, reward_amount * 29)
             );
 
             // Withdraw failed because of share info not exist.
@@ -1068,17 +1027,17 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
                 Error::<Runtime>::ShareInfoNotExists
             );
 
-            // Claim again without new blocks, no new rewards
+            // No new rewards within the block of the last claim
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
             pool1 = Farming::pool_infos(pool_id).unwrap();
             assert!(reward.withdraw_list.is_empty());
 
             let share_reward = reward.withdrawn_rewards.get(&reward_token).unwrap();
-            assert_eq!(share_reward, &(reward_amount * 5));
+            assert_eq!(share_reward, &(reward_amount * 29));
             let (total_reward, total_withdrawn_reward) = pool1.rewards.get(&reward_token).unwrap();
             assert_eq!(
                 pool1.rewards.get(&reward_token).unwrap(),
-                &(reward_amount * 5, reward_amount * 5)
+                &(reward_amount * 29, reward_amount * 29)
             );
 
             let reward_amount1 = Farming::get_reward_amount(
@@ -1089,10 +1048,10 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
                 &reward_token,
             )
             .unwrap();
-            assert_eq!(reward_amount1, (reward_amount * 5, 0));
+            assert_eq!(reward_amount1, (reward_amount * 29, 0));
             let reward_inflation =
                 Farming::get_reward_inflation(reward.share, total_reward, pool1.total_shares);
-            assert_eq!(reward_inflation, reward_amount * 5);
+            assert_eq!(reward_inflation, reward_amount * 29);
 
             let reward_inflation = Farming::get_reward_inflation(800, share_reward, reward.share);
             assert_eq!(reward_inflation, share_reward * 8 / 10);
@@ -1102,33 +1061,33 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             assert_eq!(reward_inflation, share_reward / 10);
 
             // Withdraw partial tokens
-            assert_eq!(System::block_number(), 533);
+            assert_eq!(System::block_number(), 33);
             assert_ok!(Farming::withdraw(
                 RuntimeOrigin::signed(ALICE),
                 pool_id,
                 Some(800)
             ));
-            // Although withdraw also has claim, but no new rewards due to no new block
+            // Although withdraw also has claim, but no new rewards within the same block
             // So both user and reward issuer account balance not change.
             if reward_token == KSM {
                 // Stake KMA, reward KSM
                 assert_eq!(
                     Assets::balance(reward_token, &ALICE),
-                    alice_init_ksm + reward_amount * 5
+                    alice_init_ksm + reward_amount * 29
                 );
                 assert_eq!(
                     Assets::balance(reward_token, &pool1.reward_issuer),
-                    total_rewards - reward_amount * 5
+                    total_rewards - reward_amount * 29
                 );
             } else {
                 // Stake KSM, reward KMA
                 assert_eq!(
                     Balances::free_balance(&ALICE),
-                    alice_init_kma + reward_amount * 5
+                    alice_init_kma + reward_amount * 29
                 );
                 assert_eq!(
                     Balances::free_balance(&pool1.reward_issuer),
-                    total_rewards - reward_amount * 5
+                    total_rewards - reward_amount * 29
                 );
             }
             if stake_token == KSM {
@@ -1139,26 +1098,25 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
 
             // Withdraw operation has only one operation `remove_share`.
             // And `remove_share` will claim rewards and also update user share info.
-            // We already know that due to no new block, claim rewards actually has no reward.
+            // We already know that within the same block, claim rewards actually has no reward.
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
             pool1 = Farming::pool_infos(pool_id).unwrap();
 
             assert_eq!(pool1.total_shares, 200);
-            assert_eq!(reward.withdraw_list, vec![(533 + withdraw_limit_time, 800)]);
+            assert_eq!(reward.withdraw_list, vec![(33 + withdraw_limit_time, 800)]);
             assert_eq!(reward.share, 200);
             assert_eq!(
                 reward.withdrawn_rewards.get(&reward_token).unwrap(),
-                &reward_amount
+                &(reward_amount * 29 / 5)
             );
             assert_eq!(
                 pool1.rewards.get(&reward_token).unwrap(),
-                &(reward_amount, reward_amount)
+                &(reward_amount * 29 / 5, reward_amount * 29 / 5)
             );
 
             System::set_block_number(System::block_number() + 6);
-            Farming::on_initialize(System::block_number() + 3);
 
-            // Withdraw rest all of share
+            // Withdraw rest all of share, claiming the rewards of the last 6 blocks
             assert_ok!(Farming::withdraw(
                 RuntimeOrigin::signed(ALICE),
                 pool_id,
@@ -1169,27 +1127,27 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             assert_eq!(pool1.total_shares, 0);
             assert!(pool1.rewards.is_empty());
             assert_eq!(reward.share, 0);
-            assert_eq!(reward.withdraw_list, vec![(540, 800), (546, 200)]);
+            assert_eq!(reward.withdraw_list, vec![(40, 800), (46, 200)]);
             assert_eq!(reward.withdrawn_rewards.get(&reward_token).unwrap(), &0);
             if reward_token == KSM {
                 // Stake KMA, reward KSM
                 assert_eq!(
                     Assets::balance(reward_token, &ALICE),
-                    alice_init_ksm + reward_amount * 6
+                    alice_init_ksm + reward_amount * 35
                 );
                 assert_eq!(
                     Assets::balance(reward_token, &pool1.reward_issuer),
-                    total_rewards - reward_amount * 6
+                    total_rewards - reward_amount * 35
                 );
             } else {
                 // Stake KSM, reward KMA
                 assert_eq!(
                     Balances::free_balance(&ALICE),
-                    alice_init_kma + reward_amount * 6
+                    alice_init_kma + reward_amount * 35
                 );
                 assert_eq!(
                     Balances::free_balance(&pool1.reward_issuer),
-                    total_rewards - reward_amount * 6
+                    total_rewards - reward_amount * 35
                 );
             }
             if stake_token == KSM {
@@ -1228,6 +1186,57 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
         })
 }
 
+#[test]
+fn lazy_accrual_matches_per_block_rewards() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            // 1000 KSM are emitted per block, blocks are produced one after another as on chain
+            let (pool_id, tokens) = init_no_gauge();
+            let startup = System::block_number() + 1;
+            assert_eq!(
+                Farming::pool_infos(pool_id).unwrap().block_startup,
+                Some(startup)
+            );
+
+            // The pool starts in `startup`, the two blocks after it pay ALICE alone
+            System::set_block_number(startup + 2);
+            assert_eq!(
+                Farming::get_farming_rewards(&ALICE, pool_id),
+                Ok(vec![(KSM, 2000)])
+            );
+
+            // BOB joins after the emission of this block was accrued for ALICE
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                tokens,
+                None
+            ));
+            assert_eq!(Farming::get_farming_rewards(&BOB, pool_id), Ok(vec![]));
+
+            // The next four blocks are split evenly
+            System::set_block_number(startup + 6);
+            assert_eq!(
+                Farming::get_farming_rewards(&ALICE, pool_id),
+                Ok(vec![(KSM, 4000)])
+            );
+            assert_eq!(
+                Farming::get_farming_rewards(&BOB, pool_id),
+                Ok(vec![(KSM, 2000)])
+            );
+
+            // Querying doesn't accrue anything, claiming pays out the same amount
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 3000 - tokens + 4000);
+            assert_eq!(
+                Farming::get_farming_rewards(&BOB, pool_id),
+                Ok(vec![(KSM, 2000)])
+            );
+        });
+}
+
 #[test]
 fn gauge_farming_pool_should_work() {
     ExtBuilder::default()
@@ -1249,7 +1258,9 @@ fn gauge_farming_pool_should_work() {
                 1,
                 7,
                 6,
-                5
+                5,
+                None,
+                EmissionCurve::Constant
             ));
             assert_eq!(PoolNextId::<Runtime>::get(), 1);
             assert_ok!(Farming::create_farming_pool(
@@ -1261,7 +1272,9 @@ fn gauge_farming_pool_should_work() {
                 1,
                 7,
                 6,
-                5
+                5,
+                None,
+                EmissionCurve::Constant
             ));
             assert_eq!(PoolNextId::<Runtime>::get(), 2);
 
@@ -1307,26 +1320,24 @@ fn gauge_farming_pool_should_work() {
             assert_eq!(pool1.min_deposit_to_start, 2);
             assert_eq!(pool1.state, PoolState::Charged);
 
-            // OnInitialize hook change the pool state
-            Farming::on_initialize(System::block_number() + 3);
-            Farming::on_initialize(0);
-            pool1 = Farming::pool_infos(pool_id).unwrap();
-            assert_eq!(pool1.total_shares, 1000);
-            assert_eq!(pool1.min_deposit_to_start, 2);
-            assert_eq!(pool1.state, PoolState::Ongoing);
-
-            // Claim to get rewards
+            // The pool starts at the next block, rewards accrue lazily from then on
+            assert_eq!(pool1.block_startup, Some(4));
             assert_err!(
                 Farming::claim(RuntimeOrigin::signed(ALICE), pool_id),
-                Error::<Runtime>::CanNotClaim
+                Error::<Runtime>::InvalidPoolState
             );
+
+            // Claim to get rewards
             System::set_block_number(System::block_number() + 6);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 3008);
+            assert_eq!(Assets::balance(KSM, &ALICE), 7386);
+            pool1 = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool1.total_shares, 1000);
+            assert_eq!(pool1.state, PoolState::Ongoing);
 
             System::set_block_number(System::block_number() + 100);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 4698);
+            assert_eq!(Assets::balance(KSM, &ALICE), 199243);
 
             // Withdraw part tokens
             assert_ok!(Farming::withdraw(
@@ -1334,7 +1345,7 @@ fn gauge_farming_pool_should_work() {
                 pool_id,
                 Some(800)
             ));
-            assert_eq!(Assets::balance(KSM, &ALICE), 4698);
+            assert_eq!(Assets::balance(KSM, &ALICE), 199243);
 
             // Claim again
             System::set_block_number(System::block_number() + 6);
@@ -1343,10 +1354,10 @@ fn gauge_farming_pool_should_work() {
                 Farming::claim(RuntimeOrigin::signed(ALICE), pool_id),
                 Error::<Runtime>::CanNotClaim
             );
-            assert_eq!(Assets::balance(KSM, &ALICE), 4698);
+            assert_eq!(Assets::balance(KSM, &ALICE), 205243);
             System::set_block_number(System::block_number() + 6);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 5498);
+            assert_eq!(Assets::balance(KSM, &ALICE), 212043);
         })
 }
 
@@ -1443,46 +1454,58 @@ fn deposit_should_work(use_gauge: bool) {
             total_time_factor: 39900,
             gauge_last_block: System::block_number(),
             gauge_state: GaugeState::Bonded,
+            last_reward_block: 0,
         };
         assert_eq!(Farming::gauge_pool_infos(0), Some(gauge_pool_info));
     } else {
         assert_eq!(Farming::gauge_pool_infos(0), None);
     }
 
-    Farming::on_initialize(0);
-    assert_eq!(Farming::pool_infos(0).unwrap().state, PoolState::Ongoing);
-    assert!(Farming::pool_infos(0).unwrap().rewards.is_empty());
+    // Deposits accrue the pool rewards up to the current block
+    let mut pool_info = Farming::pool_infos(0).unwrap();
+    assert_eq!(pool_info.state, PoolState::Ongoing);
+    assert_eq!(pool_info.block_startup, Some(1));
+    assert_eq!(pool_info.last_reward_block, 2);
+    let (total_reward, _) = *pool_info.rewards.get(&KSM).unwrap();
     if use_gauge {
-        assert_eq!(
-            Farming::gauge_pool_infos(0).unwrap().rewards.get(&KSM),
-            Some(&(1000, 0, 0))
-        );
+        assert_eq!(total_reward, 1000);
+    } else {
+        assert_eq!(total_reward, 1050);
     }
 
-    Farming::on_initialize(0);
-    assert_eq!(
-        Farming::pool_infos(0).unwrap().rewards.get(&KSM),
-        Some(&(1000, 0))
-    );
+    System::set_block_number(System::block_number() + 1);
+    Farming::accrue_pool_rewards(&mut pool_info);
+    assert_eq!(pool_info.rewards.get(&KSM).unwrap().0, total_reward + 1000);
+    assert_eq!(pool_info.last_reward_block, 3);
     if use_gauge {
-        assert_eq!(
-            Farming::gauge_pool_infos(0).unwrap().rewards.get(&KSM),
-            Some(&(2000, 0, 0))
-        );
+        let mut gauge_pool_info = Farming::gauge_pool_infos(0).unwrap();
+        assert!(gauge_pool_info.rewards.is_empty());
+        Farming::accrue_gauge_rewards(&mut gauge_pool_info);
+        assert_eq!(gauge_pool_info.rewards.get(&KSM), Some(&(3000, 0, 0)));
+        assert_eq!(gauge_pool_info.last_reward_block, 3);
     }
 
-    System::set_block_number(System::block_number() + 1000);
-    Farming::on_initialize(0);
+    // Accruing twice within the same block adds nothing
+    Farming::accrue_pool_rewards(&mut pool_info);
+    assert_eq!(pool_info.rewards.get(&KSM).unwrap().0, total_reward + 1000);
+
+    System::set_block_number(System::block_number() + 10);
+    Farming::accrue_pool_rewards(&mut pool_info);
     assert_eq!(
-        Farming::pool_infos(0).unwrap().rewards.get(&KSM),
-        Some(&(2000, 0))
+        pool_info.rewards.get(&KSM).unwrap().0,
+        total_reward + 11 * 1000
     );
-    if use_gauge {
-        assert_eq!(
-            Farming::gauge_pool_infos(0).unwrap().rewards.get(&KSM),
-            Some(&(3000, 0, 0))
-        );
-    }
+    assert_eq!(
+        pool_info.reward_budget.get(&KSM),
+        Some(&(100000 - total_reward - 11 * 1000))
+    );
+
+    // Accrual stops at the charged budget and the pool dies on its own
+    System::set_block_number(System::block_number() + 1000);
+    Farming::accrue_pool_rewards(&mut pool_info);
+    assert_eq!(pool_info.rewards.get(&KSM).unwrap().0, 100000);
+    assert_eq!(pool_info.reward_budget.get(&KSM), Some(&0));
+    assert_eq!(pool_info.state, PoolState::Dead);
 }
 
 #[test]
@@ -1517,9 +1540,7 @@ fn withdraw_should_work(use_gauge: bool) {
     assert_eq!(Assets::balance(KSM, &reward_issuer), 100_000);
     assert_eq!(Assets::balance(KSM, &token_keeper), 1_000);
 
-    Farming::on_initialize(0);
-    Farming::on_initialize(0);
-    System::set_block_number(System::block_number() + 1);
+    System::set_block_number(System::block_number() + 2);
 
     let reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
     assert!(reward.withdraw_list.is_empty());
@@ -1552,7 +1573,6 @@ fn withdraw_should_work(use_gauge: bool) {
     assert_eq!(Assets::balance(KSM, &token_keeper), 1_000);
 
     // Bob deposit
-    System::set_block_number(System::block_number() + 100);
     if use_gauge {
         assert_ok!(Farming::deposit(
             RuntimeOrigin::signed(BOB),
@@ -1576,11 +1596,12 @@ fn withdraw_should_work(use_gauge: bool) {
         assert_eq!(Assets::balance(KSM, &token_keeper), 2_000);
     }
 
-    // Alice claim again, because new block produces, so has reward now
-    Farming::on_initialize(0);
+    // Alice claim again, because new blocks produced, so has reward now
+    // and the first withdrawal is released as well
+    System::set_block_number(System::block_number() + 10);
     assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-    assert_eq!(Assets::balance(KSM, &ALICE), 3_966);
-    assert_eq!(Assets::balance(KSM, &reward_issuer), 98_834);
+    assert_eq!(Assets::balance(KSM, &ALICE), 5_466);
+    assert_eq!(Assets::balance(KSM, &reward_issuer), 97_334);
 
     // withdraw
     assert_ok!(Farming::withdraw(
@@ -1588,8 +1609,8 @@ fn withdraw_should_work(use_gauge: bool) {
         pool_id,
         Some(200)
     ));
-    assert_eq!(Assets::balance(KSM, &ALICE), 3_966);
-    assert_eq!(Assets::balance(KSM, &reward_issuer), 98_834);
+    assert_eq!(Assets::balance(KSM, &ALICE), 5_466);
+    assert_eq!(Assets::balance(KSM, &reward_issuer), 97_334);
     if use_gauge {
         assert_eq!(Assets::balance(KSM, &token_keeper), 1_300);
     } else {
@@ -1598,13 +1619,13 @@ fn withdraw_should_work(use_gauge: bool) {
 
     // `withdraw_claim` operation will transfer back user stake token
     // User unStake 200 KSM, so keeper transfer back 200 KSM to user.
-    System::set_block_number(System::block_number() + 100);
+    System::set_block_number(System::block_number() + 10);
     assert_ok!(Farming::withdraw_claim(
         RuntimeOrigin::signed(ALICE),
         pool_id
     ));
-    assert_eq!(Assets::balance(KSM, &ALICE), 4_166);
-    assert_eq!(Assets::balance(KSM, &reward_issuer), 98_834);
+    assert_eq!(Assets::balance(KSM, &ALICE), 5_666);
+    assert_eq!(Assets::balance(KSM, &reward_issuer), 97_334);
     if use_gauge {
         assert_eq!(Assets::balance(KSM, &token_keeper), 1_100);
     } else {
@@ -1615,8 +1636,8 @@ fn withdraw_should_work(use_gauge: bool) {
     let reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
     assert!(reward.withdraw_list.is_empty());
     assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-    assert_eq!(Assets::balance(KSM, &ALICE), 4_166);
-    assert_eq!(Assets::balance(KSM, &reward_issuer), 98_834);
+    assert_eq!(Assets::balance(KSM, &ALICE), 5_666);
+    assert_eq!(Assets::balance(KSM, &reward_issuer), 97_334);
     if use_gauge {
         assert_eq!(Assets::balance(KSM, &token_keeper), 1_100);
     } else {
@@ -1647,14 +1668,13 @@ fn claim() {
                 Error::<Runtime>::InvalidPoolState
             );
 
-            System::set_block_number(System::block_number() + 100);
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 1);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
             assert_eq!(Assets::balance(KSM, &ALICE), 2000);
             assert_eq!(Assets::balance(KSM, &keeper), 1000);
             assert_eq!(Assets::balance(KSM, &reward_issuer), 100_000);
 
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 1);
             assert_ok!(Farming::withdraw_claim(
                 RuntimeOrigin::signed(ALICE),
                 pool_id
@@ -1666,7 +1686,7 @@ fn claim() {
             assert_eq!(Assets::balance(KSM, &keeper), 1000);
             assert_eq!(Assets::balance(KSM, &reward_issuer), 99_000);
 
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 1);
             assert_ok!(Farming::close_pool(RuntimeOrigin::signed(ALICE), pool_id));
 
             assert_ok!(Farming::retire_pool(RuntimeOrigin::signed(ALICE), pool_id));
@@ -1684,16 +1704,19 @@ fn gauge() {
         .execute_with(|| {
             let (pool_id, tokens) = init_gauge_900();
             assert_eq!(Assets::balance(KSM, &ALICE), 1900);
+            // Top up the reward issuer to cover the rewards accrued over the whole test
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 1_000_000)]
+            ));
             if let Some(gauge_pool_infos) = Farming::gauge_pool_infos(0) {
                 assert!(gauge_pool_infos.rewards.is_empty());
             };
-            Farming::on_initialize(0);
-            System::set_block_number(System::block_number() + 1);
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 2);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 2918);
+            assert_eq!(Assets::balance(KSM, &ALICE), 2936);
 
-            Farming::on_initialize(0);
             System::set_block_number(System::block_number() + 10);
             assert_noop!(
                 Farming::deposit(
@@ -1710,11 +1733,11 @@ fn gauge() {
                 tokens,
                 Some((100, 100))
             ));
-            assert_eq!(Assets::balance(KSM, &ALICE), 1818);
+            assert_eq!(Assets::balance(KSM, &ALICE), 1836);
 
             System::set_block_number(System::block_number() + 20);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 3163);
+            assert_eq!(Assets::balance(KSM, &ALICE), 35543);
 
             assert_ok!(Farming::deposit(
                 RuntimeOrigin::signed(BOB),
@@ -1722,12 +1745,12 @@ fn gauge() {
                 10,
                 None
             ));
-            assert_eq!(Assets::balance(KSM, &BOB), 9699990);
+            assert_eq!(Assets::balance(KSM, &BOB), 8699990);
 
             System::set_block_number(System::block_number() + 200);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 5383);
-            assert_eq!(Assets::balance(KSM, &BOB), 9699990);
+            assert_eq!(Assets::balance(KSM, &ALICE), 411438);
+            assert_eq!(Assets::balance(KSM, &BOB), 8699990);
             assert_ok!(Farming::deposit(
                 RuntimeOrigin::signed(BOB),
                 pool_id,
@@ -1745,7 +1768,7 @@ fn gauge() {
                 RuntimeOrigin::signed(ALICE),
                 pool_id
             ));
-            assert_eq!(Assets::balance(KSM, &BOB), 9699991);
+            assert_eq!(Assets::balance(KSM, &BOB), 8701022);
 
             assert_ok!(Farming::deposit(
                 RuntimeOrigin::signed(ALICE),
@@ -1777,20 +1800,18 @@ fn gauge_withdraw() {
             if let Some(gauge_pool_infos) = Farming::gauge_pool_infos(0) {
                 assert_eq!(gauge_pool_infos.gauge_amount, 100)
             };
-            Farming::on_initialize(0);
-            System::set_block_number(System::block_number() + 1);
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 2);
             assert_ok!(Farming::gauge_withdraw(
                 RuntimeOrigin::signed(ALICE),
                 pool_id
             ));
-            assert_eq!(Assets::balance(KSM, &ALICE), 1918);
-            System::set_block_number(System::block_number() + 1000);
+            assert_eq!(Assets::balance(KSM, &ALICE), 1936);
+            System::set_block_number(System::block_number() + 100);
             assert_ok!(Farming::gauge_withdraw(
                 RuntimeOrigin::signed(ALICE),
                 pool_id
             ));
-            assert_eq!(Assets::balance(KSM, &ALICE), 3782);
+            assert_eq!(Assets::balance(KSM, &ALICE), 91965);
             if let Some(gauge_pool_infos) = Farming::gauge_pool_infos(0) {
                 assert_eq!(gauge_pool_infos.gauge_amount, 0)
             };
@@ -1804,7 +1825,6 @@ fn pool_admin_operation_should_work() {
         .build()
         .execute_with(|| {
             let (pool_id, tokens) = init_gauge_1000();
-            Farming::on_initialize(0);
             System::set_block_number(System::block_number() + 1);
             assert_ok!(Farming::deposit(
                 RuntimeOrigin::signed(ALICE),
@@ -1840,6 +1860,7 @@ fn pool_admin_operation_should_work() {
                     None,
                     None,
                     None,
+                    None,
                     None
                 ),
                 Error::<Runtime>::InvalidPoolState
@@ -1871,6 +1892,7 @@ fn pool_admin_operation_should_work() {
                     None,
                     None,
                     None,
+                    None,
                     None
                 ),
                 Error::<Runtime>::InvalidPoolState
@@ -1904,8 +1926,8 @@ fn pool_admin_operation_should_work() {
             let pool: PoolInfoOf<Runtime> = Farming::pool_infos(pool_id).unwrap();
             assert_eq!(pool.state, PoolState::Retired);
 
-            // claim all rewards automatically to user
-            assert_eq!(Assets::balance(KSM, &ALICE), 3000);
+            // claim all rewards automatically to user, including the block before closing
+            assert_eq!(Assets::balance(KSM, &ALICE), 4000);
             assert_eq!(Farming::shares_and_withdrawn_rewards(pool_id, &ALICE), None);
 
             // Pool is retired, not allow to retire again, deposit, withdraw, claim, close
@@ -1944,11 +1966,9 @@ fn reset() {
         .execute_with(|| {
             let (pool_id, _tokens) = init_gauge_900();
             assert_eq!(Assets::balance(KSM, &ALICE), 1900);
-            Farming::on_initialize(0);
-            System::set_block_number(System::block_number() + 1);
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 2);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 2918);
+            assert_eq!(Assets::balance(KSM, &ALICE), 2936);
             assert_ok!(Farming::close_pool(RuntimeOrigin::signed(ALICE), pool_id));
             assert_ok!(Farming::set_retire_limit(RuntimeOrigin::signed(ALICE), 10));
             assert_ok!(Farming::retire_pool(RuntimeOrigin::signed(ALICE), pool_id));
@@ -1963,6 +1983,7 @@ fn reset() {
                 None,
                 None,
                 Some((KSM, 1000, basic_rewards)),
+                None
             ));
             let keeper: AccountId =
                 <Runtime as Config>::Keeper::get().into_sub_account_truncating(pool_id);
@@ -1986,11 +2007,17 @@ fn reset() {
                 reward_issuer: reward_issuer.clone(),
                 gauge: Some(1),
                 block_startup: None,
+                last_reward_block: 2,
                 min_deposit_to_start: Default::default(),
                 after_block_to_start: Default::default(),
                 withdraw_limit_time: Default::default(),
                 claim_limit_time: Default::default(),
                 withdraw_limit_count: 5,
+                end_block: None,
+                emission_curve: EmissionCurve::Constant,
+                reward_budget: BTreeMap::from([(KSM, 299000)]),
+                charged_rewards: BTreeMap::from([(KSM, 300000)]),
+                incentives: BTreeMap::new(),
             };
             assert_eq!(Farming::pool_infos(0), Some(pool_infos));
             let gauge_pool_info = GaugePoolInfo {
@@ -2008,9 +2035,10 @@ fn reset() {
                 total_time_factor: 0,
                 gauge_last_block: System::block_number(),
                 gauge_state: GaugeState::Bonded,
+                last_reward_block: System::block_number(),
             };
             assert_eq!(Farming::gauge_pool_infos(1), Some(gauge_pool_info));
-            assert_eq!(Assets::balance(KSM, &ALICE), 3918);
+            assert_eq!(Assets::balance(KSM, &ALICE), 3936);
             let charge_rewards = vec![(KSM, 300000)];
             assert_ok!(Farming::charge(
                 RuntimeOrigin::signed(BOB),
@@ -2023,10 +2051,1059 @@ fn reset() {
                 1,
                 Some((100, 100))
             ));
-            assert_eq!(Assets::balance(KSM, &ALICE), 3817);
-            Farming::on_initialize(0);
+            assert_eq!(Assets::balance(KSM, &ALICE), 3835);
             System::set_block_number(System::block_number() + 20);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 4017);
+            assert_eq!(Assets::balance(KSM, &ALICE), 26835);
+        })
+}
+
+#[test]
+fn emission_curve_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                None,
+                0,  // min_deposit_to_start
+                10, // after_block_to_start
+                0,  // withdraw_limit_time
+                0,  // claim_limit_time
+                5,  // withdraw_limit_count
+                Some(20),
+                EmissionCurve::LinearDecay
+            ));
+            let mut pool_info = Farming::pool_infos(0).unwrap();
+
+            // Nothing is emitted before `after_block_to_start` or after `end_block`
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 0, 10), 0);
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 20, 30), 0);
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 10, 11), 1000);
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 15, 20), 1500);
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 0, 30), 5500);
+
+            pool_info.emission_curve = EmissionCurve::StepHalving { period: 5 };
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 10, 15), 5000);
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 10, 20), 7500);
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 12, 17), 4000);
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 0, 10_000), 10000);
+
+            pool_info.emission_curve = EmissionCurve::Constant;
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 0, 12), 2000);
+            assert_eq!(Farming::emitted_rewards(&pool_info, 1000, 10, 20), 10000);
+        })
+}
+
+#[test]
+fn invalid_emission_should_not_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let create_pool = |end_block, emission_curve| {
+                Farming::create_farming_pool(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![(KSM, Perbill::from_percent(100))],
+                    vec![(KSM, 1000)],
+                    None,
+                    0,  // min_deposit_to_start
+                    10, // after_block_to_start
+                    0,  // withdraw_limit_time
+                    0,  // claim_limit_time
+                    5,  // withdraw_limit_count
+                    end_block,
+                    emission_curve,
+                )
+            };
+            assert_noop!(
+                create_pool(Some(10), EmissionCurve::Constant),
+                Error::<Runtime>::InvalidPoolParameter
+            );
+            assert_noop!(
+                create_pool(None, EmissionCurve::LinearDecay),
+                Error::<Runtime>::InvalidPoolParameter
+            );
+            assert_noop!(
+                create_pool(None, EmissionCurve::StepHalving { period: 0 }),
+                Error::<Runtime>::InvalidPoolParameter
+            );
+            assert_ok!(create_pool(Some(11), EmissionCurve::LinearDecay));
+        })
+}
+
+#[test]
+fn end_block_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5, // withdraw_limit_count
+                Some(10),
+                EmissionCurve::Constant
+            ));
+            let pool_id = 0;
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 100000)]
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+            assert_eq!(Assets::balance(KSM, &ALICE), 2000);
+
+            // Not allow to refund while the pool is still farming
+            System::set_block_number(System::block_number() + 5);
+            assert_noop!(
+                Farming::refund_charge(RuntimeOrigin::signed(BOB), pool_id),
+                Error::<Runtime>::InvalidPoolState
+            );
+
+            // Rewards stop at `end_block` and the pool dies on its own
+            System::set_block_number(20);
+            assert_noop!(
+                Farming::charge(RuntimeOrigin::signed(BOB), pool_id, vec![(KSM, 1000)]),
+                Error::<Runtime>::InvalidPoolState
+            );
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 11000);
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_info.state, PoolState::Dead);
+            assert_eq!(pool_info.last_reward_block, 10);
+            assert_eq!(pool_info.reward_budget.get(&KSM), Some(&91000));
+
+            // The rewards never accrued go back to the charger
+            assert_ok!(Farming::refund_charge(RuntimeOrigin::signed(BOB), pool_id));
+            assert_eq!(Assets::balance(KSM, &BOB), 10_000_000 - 9000);
+            assert_noop!(
+                Farming::refund_charge(RuntimeOrigin::signed(BOB), pool_id),
+                Error::<Runtime>::ChargeNotExist
+            );
+        })
+}
+
+#[test]
+fn refund_charge_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 100)],
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5, // withdraw_limit_count
+                None,
+                EmissionCurve::Constant
+            ));
+            let pool_id = 0;
+            let reward_issuer: AccountId =
+                <Runtime as Config>::RewardIssuer::get().into_sub_account_truncating(pool_id);
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                vec![(KSM, 1000)]
+            ));
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 3000)]
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+            assert_eq!(Assets::balance(KSM, &ALICE), 1000);
+            assert_eq!(
+                Farming::charges(pool_id, &ALICE),
+                Some(BTreeMap::from([(KSM, 1000)]))
+            );
+            assert_eq!(
+                Farming::charges(pool_id, &BOB),
+                Some(BTreeMap::from([(KSM, 3000)]))
+            );
+
+            System::set_block_number(System::block_number() + 11);
+            assert_ok!(Farming::close_pool(RuntimeOrigin::signed(ALICE), pool_id));
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_info.reward_budget.get(&KSM), Some(&3000));
+            assert_eq!(pool_info.charged_rewards.get(&KSM), Some(&4000));
+
+            // Each charger gets back its share of the remaining budget
+            assert_noop!(
+                Farming::refund_charge(RuntimeOrigin::signed(CHARLIE), pool_id),
+                Error::<Runtime>::ChargeNotExist
+            );
+            assert_ok!(Farming::refund_charge(
+                RuntimeOrigin::signed(ALICE),
+                pool_id
+            ));
+            assert_eq!(Assets::balance(KSM, &ALICE), 1750);
+            assert_ok!(Farming::refund_charge(RuntimeOrigin::signed(BOB), pool_id));
+            assert_eq!(Assets::balance(KSM, &BOB), 10_000_000 - 750);
+            assert_eq!(Farming::charges(pool_id, &BOB), None);
+
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_info.reward_budget.get(&KSM), Some(&0));
+            assert!(pool_info.charged_rewards.is_empty());
+            // Only the accrued rewards owed to the shareholders are left
+            assert_eq!(Assets::balance(KSM, &reward_issuer), 1000);
+        })
+}
+
+#[test]
+fn incentive_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, _tokens) = init_no_gauge();
+            let reward_issuer: AccountId =
+                <Runtime as Config>::RewardIssuer::get().into_sub_account_truncating(pool_id);
+            assert_noop!(
+                Farming::add_incentive(RuntimeOrigin::signed(BOB), pool_id, KMA, 0, 5, 15),
+                Error::<Runtime>::InvalidIncentiveParameter
+            );
+            assert_noop!(
+                Farming::add_incentive(RuntimeOrigin::signed(BOB), pool_id, KMA, 10, 5, 5),
+                Error::<Runtime>::InvalidIncentiveParameter
+            );
+            assert_ok!(Farming::add_incentive(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                KMA,
+                10,
+                5,
+                15
+            ));
+            assert_eq!(Balances::free_balance(&BOB), 400_000 - 100);
+            assert_eq!(Balances::free_balance(&reward_issuer), 100);
+
+            // The incentive is paid on top of the basic rewards from its start block
+            System::set_block_number(System::block_number() + 10);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 11000);
+            assert_eq!(Balances::free_balance(&ALICE), 3050);
+
+            assert_noop!(
+                Farming::withdraw_incentive(RuntimeOrigin::signed(BOB), pool_id, 1),
+                Error::<Runtime>::IncentiveNotExist
+            );
+            assert_noop!(
+                Farming::withdraw_incentive(RuntimeOrigin::signed(ALICE), pool_id, 0),
+                Error::<Runtime>::NotIncentiveSponsor
+            );
+            assert_noop!(
+                Farming::withdraw_incentive(RuntimeOrigin::signed(BOB), pool_id, 0),
+                Error::<Runtime>::IncentiveNotEnded
+            );
+
+            // Nothing is emitted while the pool has no shares
+            System::set_block_number(System::block_number() + 2);
+            assert_ok!(Farming::withdraw(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                None
+            ));
+            assert_eq!(Assets::balance(KSM, &ALICE), 13000);
+            assert_eq!(Balances::free_balance(&ALICE), 3070);
+
+            System::set_block_number(System::block_number() + 8);
+            assert_ok!(Farming::withdraw_incentive(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                0
+            ));
+            assert_eq!(Balances::free_balance(&BOB), 400_000 - 70);
+            assert_eq!(Balances::free_balance(&reward_issuer), 0);
+            assert!(Farming::pool_infos(pool_id).unwrap().incentives.is_empty());
+        })
+}
+
+#[test]
+fn incentive_should_be_refunded_with_pool() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, _tokens) = init_no_gauge();
+            for _ in 0..3 {
+                assert_ok!(Farming::add_incentive(
+                    RuntimeOrigin::signed(BOB),
+                    pool_id,
+                    KMA,
+                    1,
+                    10,
+                    20
+                ));
+            }
+            assert_eq!(Farming::incentive_next_id(), 3);
+            assert_noop!(
+                Farming::add_incentive(RuntimeOrigin::signed(BOB), pool_id, KMA, 1, 10, 20),
+                Error::<Runtime>::TooManyIncentives
+            );
+            assert_eq!(Balances::free_balance(&BOB), 400_000 - 30);
+
+            // The sponsor can leave a pool which stopped farming
+            System::set_block_number(System::block_number() + 5);
+            assert_ok!(Farming::close_pool(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_ok!(Farming::withdraw_incentive(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                0
+            ));
+            assert_eq!(Balances::free_balance(&BOB), 400_000 - 20);
+
+            // Killing the pool refunds the other incentives
+            assert_ok!(Farming::set_retire_limit(RuntimeOrigin::signed(ALICE), 10));
+            assert_ok!(Farming::retire_pool(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_ok!(Farming::kill_pool(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Balances::free_balance(&BOB), 400_000);
+        })
+}
+
+#[test]
+fn ve_lock_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Farming::ve_lock(RuntimeOrigin::signed(ALICE), 1000, 5),
+                Error::<Runtime>::InvalidVeLock
+            );
+            assert_noop!(
+                Farming::ve_lock(RuntimeOrigin::signed(ALICE), 1000, 110),
+                Error::<Runtime>::InvalidVeLock
+            );
+            assert_noop!(
+                Farming::ve_lock(RuntimeOrigin::signed(ALICE), 5000, 100),
+                Error::<Runtime>::InsufficientBalance
+            );
+
+            // The unlock block is rounded down to an epoch boundary
+            assert_ok!(Farming::ve_lock(RuntimeOrigin::signed(ALICE), 1000, 105));
+            assert_eq!(
+                Farming::ve_locks(&ALICE),
+                Some(VeLock {
+                    amount: 1000,
+                    end_block: 100
+                })
+            );
+            assert_eq!(Balances::locks(&ALICE)[0].amount, 1000);
+            assert_eq!(Farming::voting_power(&ALICE), 1000);
+            assert_eq!(Farming::total_voting_power(), 1000);
+
+            // The voting power decays linearly
+            System::set_block_number(System::block_number() + 50);
+            assert_eq!(Farming::voting_power(&ALICE), 500);
+            assert_eq!(Farming::total_voting_power(), 500);
+            assert_ok!(Farming::ve_lock(RuntimeOrigin::signed(BOB), 2000, 80));
+            assert_eq!(Farming::voting_power(&BOB), 600);
+            assert_eq!(Farming::total_voting_power(), 1100);
+
+            // A lock can only be extended
+            assert_noop!(
+                Farming::ve_unlock(RuntimeOrigin::signed(ALICE)),
+                Error::<Runtime>::VeLockNotExpired
+            );
+            assert_noop!(
+                Farming::ve_lock(RuntimeOrigin::signed(ALICE), 0, 90),
+                Error::<Runtime>::InvalidVeLock
+            );
+            assert_ok!(Farming::ve_lock(RuntimeOrigin::signed(ALICE), 0, 120));
+            assert_eq!(Farming::voting_power(&ALICE), 700);
+            assert_eq!(Farming::total_voting_power(), 1300);
+
+            // The expired lock leaves the supply at its end block
+            System::set_block_number(System::block_number() + 30);
+            assert_eq!(Farming::voting_power(&BOB), 0);
+            assert_eq!(Farming::total_voting_power(), 400);
+            assert_noop!(
+                Farming::ve_lock(RuntimeOrigin::signed(BOB), 100, 150),
+                Error::<Runtime>::VeLockExpired
+            );
+            assert_ok!(Farming::ve_unlock(RuntimeOrigin::signed(BOB)));
+            assert!(Balances::locks(&BOB).is_empty());
+            assert_eq!(Farming::ve_locks(&BOB), None);
+            assert_noop!(
+                Farming::ve_unlock(RuntimeOrigin::signed(BOB)),
+                Error::<Runtime>::VeLockNotExist
+            );
+            assert_eq!(Farming::total_voting_power(), 400);
+        })
+}
+
+#[test]
+fn ve_unlock_should_retract_votes_of_the_running_epoch() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, _tokens) = init_no_gauge();
+            assert_ok!(Farming::ve_lock(RuntimeOrigin::signed(ALICE), 1000, 10));
+            assert_ok!(Farming::ve_lock(RuntimeOrigin::signed(BOB), 1000, 100));
+            assert_ok!(Farming::vote(
+                RuntimeOrigin::signed(ALICE),
+                vec![(pool_id, Perbill::from_percent(100))]
+            ));
+            assert_eq!(Farming::pool_votes(0).get(&pool_id), Some(&100));
+
+            // The votes of a past epoch are left to their distribution
+            System::set_block_number(10);
+            assert_ok!(Farming::vote(
+                RuntimeOrigin::signed(BOB),
+                vec![(pool_id, Perbill::from_percent(100))]
+            ));
+            assert_ok!(Farming::ve_unlock(RuntimeOrigin::signed(ALICE)));
+            assert_eq!(Farming::user_votes(&ALICE), None);
+            assert_eq!(Farming::pool_votes(0).get(&pool_id), Some(&100));
+            assert_eq!(Farming::pool_votes(1).get(&pool_id), Some(&900));
+
+            assert_ok!(Farming::ve_lock(RuntimeOrigin::signed(ALICE), 1000, 20));
+            assert_ok!(Farming::vote(
+                RuntimeOrigin::signed(ALICE),
+                vec![(pool_id, Perbill::from_percent(100))]
+            ));
+            assert_eq!(Farming::pool_votes(1).get(&pool_id), Some(&1000));
+
+            // A lock expiring in the epoch it voted in, as after a change of the epoch length
+            System::set_block_number(20);
+            let (_, votes) = Farming::user_votes(&ALICE).unwrap();
+            UserVotes::<Runtime>::insert(ALICE, (2, votes));
+            PoolVotes::<Runtime>::insert(2, Farming::pool_votes(1));
+            assert_ok!(Farming::ve_unlock(RuntimeOrigin::signed(ALICE)));
+            assert_eq!(Farming::pool_votes(2).get(&pool_id), Some(&900));
+        })
+}
+
+#[test]
+fn boost_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, tokens) = init_no_gauge();
+            assert_ok!(Farming::ve_lock(RuntimeOrigin::signed(ALICE), 1000, 100));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                500,
+                None
+            ));
+
+            // The share of ALICE is boosted up to MaxBoost
+            assert_ok!(Farming::update_boost(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                ALICE
+            ));
+            assert_eq!(Farming::boost_shares(pool_id, &ALICE), 1500);
+            assert_eq!(
+                Farming::shares_and_withdrawn_rewards(pool_id, &ALICE)
+                    .unwrap()
+                    .share,
+                tokens + 1500
+            );
+            assert_eq!(Farming::boost_shares(pool_id, &BOB), 0);
+            assert_eq!(Farming::pool_infos(pool_id).unwrap().total_shares, 3000);
+
+            System::set_block_number(System::block_number() + 7);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(BOB), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 7000);
+            assert_eq!(Assets::balance(KSM, &BOB), 10_000_000 - 100000 - 500 + 1000);
+
+            // The boost of ALICE shrinks as BOB takes part of the voting power
+            assert_ok!(Farming::ve_lock(RuntimeOrigin::signed(BOB), 1000, 100));
+            assert_ok!(Farming::update_boost(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                ALICE
+            ));
+            assert_ok!(Farming::update_boost(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                BOB
+            ));
+            assert_eq!(Farming::boost_shares(pool_id, &ALICE), 1125);
+            assert_eq!(Farming::boost_shares(pool_id, &BOB), 750);
+            assert_eq!(Farming::pool_boosts(pool_id), 1875);
+            assert_eq!(Farming::pool_infos(pool_id).unwrap().total_shares, 3375);
+
+            // The boost goes with the withdrawal of the deposited share
+            assert_ok!(Farming::withdraw(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                None
+            ));
+            assert_eq!(Farming::boost_shares(pool_id, &ALICE), 0);
+            assert_eq!(Farming::pool_boosts(pool_id), 750);
+            assert_eq!(
+                Farming::shares_and_withdrawn_rewards(pool_id, &ALICE)
+                    .unwrap()
+                    .share,
+                0
+            );
+            assert_eq!(Farming::pool_infos(pool_id).unwrap().total_shares, 1250);
+        })
+}
+
+#[test]
+fn vote_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, _tokens) = init_no_gauge();
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                1, // withdraw_limit_count
+                None,
+                EmissionCurve::Constant
+            ));
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                1,
+                vec![(KSM, 100000)]
+            ));
+
+            assert_noop!(
+                Farming::vote(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![(pool_id, Perbill::from_percent(100))]
+                ),
+                Error::<Runtime>::NoVotingPower
+            );
+            assert_ok!(Farming::ve_lock(RuntimeOrigin::signed(ALICE), 1000, 100));
+            assert_noop!(
+                Farming::vote(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![(0, Perbill::from_percent(10)); 4]
+                ),
+                Error::<Runtime>::TooManyVotes
+            );
+            assert_noop!(
+                Farming::vote(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![
+                        (0, Perbill::from_percent(60)),
+                        (1, Perbill::from_percent(50))
+                    ]
+                ),
+                Error::<Runtime>::InvalidVotes
+            );
+            assert_noop!(
+                Farming::vote(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![
+                        (0, Perbill::from_percent(75)),
+                        (2, Perbill::from_percent(25))
+                    ]
+                ),
+                Error::<Runtime>::PoolDoesNotExist
+            );
+
+            // A new vote replaces the previous one of the epoch
+            assert_ok!(Farming::vote(
+                RuntimeOrigin::signed(ALICE),
+                vec![
+                    (0, Perbill::from_percent(50)),
+                    (1, Perbill::from_percent(50))
+                ]
+            ));
+            assert_ok!(Farming::vote(
+                RuntimeOrigin::signed(ALICE),
+                vec![
+                    (0, Perbill::from_percent(75)),
+                    (1, Perbill::from_percent(25))
+                ]
+            ));
+            assert_eq!(Farming::pool_votes(0).get(&0), Some(&750));
+            assert_eq!(Farming::pool_votes(0).get(&1), Some(&250));
+
+            assert_noop!(
+                Farming::set_vote_emission(RuntimeOrigin::signed(BOB), vec![(KMA, 400)]),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Farming::set_vote_emission(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KMA, 400)]
+            ));
+            let vote_account = Farming::vote_emission_account();
+            Balances::make_free_balance_be(&vote_account, 1000);
+
+            // The emission is split by the votes of the previous epoch
+            System::set_block_number(10);
+            Farming::on_initialize(10);
+            assert_eq!(Balances::free_balance(&vote_account), 600);
+            assert!(Farming::pool_votes(0).is_empty());
+            let incentives = Farming::pool_infos(0).unwrap().incentives;
+            let incentive = incentives.values().next().unwrap();
+            assert_eq!(incentive.sponsor, vote_account);
+            assert_eq!(incentive.reward_per_block, 30);
+            assert_eq!(incentive.end_block, 20);
+            let incentives = Farming::pool_infos(1).unwrap().incentives;
+            assert_eq!(incentives.values().next().unwrap().reward_per_block, 10);
+
+            System::set_block_number(20);
+            Farming::on_initialize(20);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Balances::free_balance(&ALICE), 3300);
+
+            // The ended streams are swept on the next distribution to the pool, the stale
+            // votes of a past epoch are replaced
+            assert_eq!(Farming::user_votes(ALICE).map(|(epoch, _)| epoch), Some(0));
+            assert_ok!(Farming::vote(
+                RuntimeOrigin::signed(ALICE),
+                vec![(pool_id, Perbill::from_percent(100))]
+            ));
+            assert_eq!(Farming::user_votes(ALICE).map(|(epoch, _)| epoch), Some(2));
+            assert_eq!(Farming::pool_votes(2).get(&pool_id), Some(&800));
+            System::set_block_number(30);
+            Farming::on_initialize(30);
+            assert_eq!(Balances::free_balance(&vote_account), 200);
+            let incentives = Farming::pool_infos(pool_id).unwrap().incentives;
+            assert_eq!(incentives.len(), 1);
+            assert_eq!(incentives[&2].reward_per_block, 40);
+        })
+}
+
+#[test]
+fn vesting_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, _tokens) = init_no_gauge();
+            assert_noop!(
+                Farming::set_vesting(RuntimeOrigin::signed(BOB), pool_id, Some(20)),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Farming::set_vesting(RuntimeOrigin::signed(ALICE), pool_id, Some(0)),
+                Error::<Runtime>::InvalidPoolParameter
+            );
+            assert_noop!(
+                Farming::set_vesting(RuntimeOrigin::signed(ALICE), 5, Some(20)),
+                Error::<Runtime>::PoolDoesNotExist
+            );
+            assert_ok!(Farming::set_vesting(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(20)
+            ));
+
+            // The claimed rewards go to the vesting schedule
+            System::set_block_number(System::block_number() + 10);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 2000);
+            assert_eq!(Assets::balance(KSM, &Farming::vesting_account()), 9000);
+            assert_eq!(
+                Farming::vesting_schedules(&ALICE, KSM),
+                Some(VestingSchedule {
+                    locked: 9000,
+                    claimable: 0,
+                    start_block: 10,
+                    end_block: 30
+                })
+            );
+            assert_noop!(
+                Farming::claim_vested(RuntimeOrigin::signed(BOB), KSM),
+                Error::<Runtime>::VestingNotExist
+            );
+
+            System::set_block_number(System::block_number() + 10);
+            assert_eq!(
+                Farming::get_vesting_rewards(&ALICE),
+                vec![(KSM, 4500, 4500)]
+            );
+            assert_ok!(Farming::claim_vested(RuntimeOrigin::signed(ALICE), KSM));
+            assert_eq!(Assets::balance(KSM, &ALICE), 6500);
+
+            // The rewards still locked vest along with the new ones
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(
+                Farming::vesting_schedules(&ALICE, KSM),
+                Some(VestingSchedule {
+                    locked: 14500,
+                    claimable: 0,
+                    start_block: 20,
+                    end_block: 40
+                })
+            );
+
+            // The rewards are paid out immediately once the vesting is unset
+            assert_ok!(Farming::set_vesting(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                None
+            ));
+            System::set_block_number(System::block_number() + 10);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 16500);
+            assert_eq!(
+                Farming::get_vesting_rewards(&ALICE),
+                vec![(KSM, 7250, 7250)]
+            );
+
+            System::set_block_number(System::block_number() + 10);
+            assert_ok!(Farming::claim_vested(RuntimeOrigin::signed(ALICE), KSM));
+            assert_eq!(Assets::balance(KSM, &ALICE), 31000);
+            assert_eq!(Assets::balance(KSM, &Farming::vesting_account()), 0);
+            assert_eq!(Farming::vesting_schedules(&ALICE, KSM), None);
+        })
+}
+
+#[test]
+fn emergency_withdraw_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, tokens) = init_gauge_900();
+            assert_eq!(Assets::balance(KSM, &ALICE), 3000 - tokens - 100);
+            assert_noop!(
+                Farming::emergency_withdraw(RuntimeOrigin::signed(ALICE), pool_id),
+                Error::<Runtime>::EmergencyWithdrawNotEnabled
+            );
+            assert_noop!(
+                Farming::set_emergency_withdraw(RuntimeOrigin::signed(BOB), pool_id, true),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Farming::set_emergency_withdraw(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                true
+            ));
+
+            // The deposit and the gauge come back without the rewards
+            System::set_block_number(System::block_number() + 10);
+            assert_ok!(Farming::emergency_withdraw(
+                RuntimeOrigin::signed(ALICE),
+                pool_id
+            ));
+            assert_eq!(Assets::balance(KSM, &ALICE), 3000);
+            assert_eq!(Farming::shares_and_withdrawn_rewards(pool_id, &ALICE), None);
+            assert_eq!(Farming::pool_infos(pool_id).unwrap().total_shares, 0);
+            assert_eq!(Farming::gauge_infos(0, &ALICE), None);
+            assert_eq!(Farming::gauge_pool_infos(0).unwrap().gauge_amount, 0);
+            assert_noop!(
+                Farming::emergency_withdraw(RuntimeOrigin::signed(ALICE), pool_id),
+                Error::<Runtime>::ShareInfoNotExists
+            );
+
+            assert_ok!(Farming::set_emergency_withdraw(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                false
+            ));
+            assert!(!Farming::emergency_withdraw_enabled(pool_id));
+        })
+}
+
+/// Rewards claimed by ALICE and BOB sharing a pool for 20 blocks, with BOB exiting halfway if `exit`
+fn rewards_with_emergency_exit(exit: bool) -> (BalanceOf<Runtime>, BalanceOf<Runtime>) {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                1, // withdraw_limit_count
+                None,
+                EmissionCurve::Constant
+            ));
+            let pool_id = 0;
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 100000)]
+            ));
+            assert_ok!(Farming::set_emergency_withdraw(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                true
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                1000,
+                None
+            ));
+
+            // The pool started farming without being touched since
+            System::set_block_number(System::block_number() + 10);
+            if exit {
+                assert_ok!(Farming::emergency_withdraw(
+                    RuntimeOrigin::signed(BOB),
+                    pool_id
+                ));
+            }
+            System::set_block_number(System::block_number() + 10);
+
+            let claimed = |who: AccountId| {
+                let before = Assets::balance(KSM, &who);
+                if Farming::shares_and_withdrawn_rewards(pool_id, &who).is_some() {
+                    assert_ok!(Farming::claim(RuntimeOrigin::signed(who), pool_id));
+                }
+                Assets::balance(KSM, &who) - before
+            };
+            (claimed(ALICE), claimed(BOB))
+        })
+}
+
+#[test]
+fn emergency_withdraw_should_leave_the_forfeited_rewards_to_the_pool() {
+    let (alice_rewards, bob_rewards) = rewards_with_emergency_exit(false);
+    assert_eq!((alice_rewards, bob_rewards), (9500, 9500));
+
+    // No block of rewards is lost, BOB's pending rewards go to ALICE
+    let (alice_rewards, bob_rewards) = rewards_with_emergency_exit(true);
+    assert_eq!(bob_rewards, 0);
+    assert_eq!(alice_rewards, 19000);
+}
+
+#[test]
+fn runtime_api_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, _tokens) = init_no_gauge();
+            assert_ok!(Farming::add_incentive(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                KMA,
+                10,
+                5,
+                15
+            ));
+
+            System::set_block_number(System::block_number() + 10);
+            assert_eq!(
+                Farming::get_pools(),
+                vec![PoolData {
+                    pool_id,
+                    state: PoolState::Ongoing,
+                    tokens_proportion: vec![(KSM, Perbill::from_percent(100))],
+                    total_shares: 1000,
+                    rewards_per_block: vec![(KMA, 10), (KSM, 1000)],
+                    remaining_rewards: vec![(KMA, 50), (KSM, 91000)],
+                    end_block: None,
+                }]
+            );
+
+            assert_ok!(Farming::withdraw(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(200)
+            ));
+            assert_eq!(
+                Farming::get_positions(&ALICE),
+                vec![PositionData {
+                    pool_id,
+                    share: 800,
+                    pending_rewards: vec![],
+                    withdraw_list: vec![(20, 200)],
+                }]
+            );
+            assert_eq!(Farming::get_positions(&BOB), vec![]);
+
+            // Rewards in currencies without a price are worthless
+            assert_eq!(
+                Farming::get_pool_apr(pool_id, vec![(KSM, FixedU128::one())], 1000),
+                Some(FixedU128::from_u32(1250))
+            );
+            assert_eq!(
+                Farming::get_pool_apr(
+                    pool_id,
+                    vec![(KSM, FixedU128::one()), (KMA, FixedU128::from_u32(2))],
+                    1000
+                ),
+                Some(FixedU128::from_u32(1275))
+            );
+            assert_eq!(
+                Farming::get_pool_apr(1, vec![(KSM, FixedU128::one())], 1000),
+                None
+            );
+        })
+}
+
+#[test]
+fn charge_restrictions_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 100)],
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5, // withdraw_limit_count
+                None,
+                EmissionCurve::Constant
+            ));
+            let pool_id = 0;
+
+            // Only `ControlOrigin` restricts charging to an allowlist
+            assert_noop!(
+                Farming::set_charge_allowlist(RuntimeOrigin::signed(BOB), pool_id, Some(vec![BOB])),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Farming::set_charge_allowlist(RuntimeOrigin::signed(ALICE), 1, Some(vec![BOB])),
+                Error::<Runtime>::PoolDoesNotExist
+            );
+            assert_noop!(
+                Farming::set_charge_allowlist(
+                    RuntimeOrigin::signed(ALICE),
+                    pool_id,
+                    Some(vec![ALICE, BOB, CHARLIE])
+                ),
+                Error::<Runtime>::TooManyAllowedChargers
+            );
+            assert_ok!(Farming::set_charge_allowlist(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(vec![BOB])
+            ));
+            assert_noop!(
+                Farming::charge(RuntimeOrigin::signed(ALICE), pool_id, vec![(KSM, 1000)]),
+                Error::<Runtime>::NotAllowedToCharge
+            );
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 1000)]
+            ));
+            assert_eq!(
+                Farming::pool_infos(pool_id).unwrap().state,
+                PoolState::Charged
+            );
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+
+            // Charging an ongoing pool keeps it farming
+            System::set_block_number(System::block_number() + 5);
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 1000)]
+            ));
+            assert_eq!(
+                Farming::pool_infos(pool_id).unwrap().state,
+                PoolState::Ongoing
+            );
+            assert_eq!(
+                Farming::charges(pool_id, &BOB),
+                Some(BTreeMap::from([(KSM, 2000)]))
+            );
+
+            // Anyone can charge once the allowlist is removed
+            assert_ok!(Farming::set_charge_allowlist(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                None
+            ));
+            assert_eq!(Farming::charge_allowlists(pool_id), None);
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                vec![(KSM, 1000)]
+            ));
+
+            // Dead and retired pools can not be charged
+            assert_ok!(Farming::close_pool(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_noop!(
+                Farming::charge(RuntimeOrigin::signed(BOB), pool_id, vec![(KSM, 1000)]),
+                Error::<Runtime>::InvalidPoolState
+            );
+            assert_ok!(Farming::set_retire_limit(RuntimeOrigin::signed(ALICE), 10));
+            assert_ok!(Farming::retire_pool(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_noop!(
+                Farming::charge(RuntimeOrigin::signed(BOB), pool_id, vec![(KSM, 1000)]),
+                Error::<Runtime>::InvalidPoolState
+            );
+
+            // Killing the pool refunds the remaining budget to the chargers, one per call
+            assert_ok!(Farming::set_charge_allowlist(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(vec![BOB])
+            ));
+            let budget = *Farming::pool_infos(pool_id)
+                .unwrap()
+                .reward_budget
+                .get(&KSM)
+                .unwrap();
+            assert!(budget > 0 && budget < 3000);
+            let alice_balance = Assets::balance(KSM, &ALICE);
+            let bob_balance = Assets::balance(KSM, &BOB);
+            assert_ok!(Farming::kill_pool(RuntimeOrigin::signed(ALICE), pool_id));
+            assert!(Farming::pool_infos(pool_id).is_some());
+            assert!(Farming::charge_allowlists(pool_id).is_some());
+            assert_eq!(Charges::<Runtime>::iter_prefix(pool_id).count(), 1);
+            assert_ok!(Farming::kill_pool(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Farming::pool_infos(pool_id), None);
+            let alice_refund = Assets::balance(KSM, &ALICE) - alice_balance;
+            let bob_refund = Assets::balance(KSM, &BOB) - bob_balance;
+            assert_eq!(alice_refund + bob_refund, budget);
+            assert!(bob_refund > alice_refund);
+            assert_eq!(Farming::charges(pool_id, &ALICE), None);
+            assert_eq!(Farming::charges(pool_id, &BOB), None);
+            assert_eq!(Farming::charge_allowlists(pool_id), None);
         })
 }
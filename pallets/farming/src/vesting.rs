@@ -0,0 +1,155 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{AccountIdConversion, Saturating, Zero},
+    RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+
+use crate::*;
+
+/// The claimed rewards of an account in one currency, released linearly from
+/// `start_block` to `end_block`.
+#[derive(Clone, Encode, Decode, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VestingSchedule<BalanceOf, BlockNumberFor> {
+    /// Rewards not vested yet
+    pub locked: BalanceOf,
+    /// Rewards vested but not claimed yet
+    pub claimable: BalanceOf,
+    /// The block `locked` vests from
+    pub start_block: BlockNumberFor,
+    /// The block `locked` is fully vested at
+    pub end_block: BlockNumberFor,
+}
+
+impl<T: Config> Pallet<T> {
+    /// The account holding the claimed rewards until they are vested.
+    pub fn vesting_account() -> T::AccountId {
+        T::RewardIssuer::get().into_sub_account_truncating(b"vesting")
+    }
+
+    /// Moves the part of `schedule` vested by block `n` from `locked` to `claimable`.
+    fn vest(schedule: &mut VestingScheduleOf<T>, n: BlockNumberFor<T>) {
+        if n <= schedule.start_block {
+            return;
+        }
+        let vested = if n >= schedule.end_block {
+            schedule.locked
+        } else {
+            Self::get_reward_inflation(
+                schedule.locked,
+                &(n - schedule.start_block).saturated_into(),
+                (schedule.end_block - schedule.start_block).saturated_into(),
+            )
+        };
+        schedule.locked = schedule.locked.saturating_sub(vested);
+        schedule.claimable = schedule.claimable.saturating_add(vested);
+        schedule.start_block = n;
+    }
+
+    /// Pays `reward_to_withdraw` of the rewards of `pool_id` to `who`, into its vesting
+    /// schedule if the pool has vesting set.
+    pub fn reward_transfer(
+        pool_id: PoolId,
+        reward_currency: &CurrencyIdOf<T>,
+        reward_to_withdraw: BalanceOf<T>,
+        who: &T::AccountId,
+        from: &T::AccountId,
+    ) -> DispatchResult {
+        let vesting_blocks = match Self::pool_vestings(pool_id) {
+            Some(vesting_blocks)
+                if reward_to_withdraw >= T::MultiCurrency::minimum_balance(*reward_currency) =>
+            {
+                vesting_blocks
+            }
+            // dust below the existential deposit is paid out directly
+            _ => {
+                return Self::farming_token_transfer(reward_currency, reward_to_withdraw, who, from)
+            }
+        };
+
+        T::MultiCurrency::transfer(
+            *reward_currency,
+            from,
+            &Self::vesting_account(),
+            reward_to_withdraw,
+        )?;
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        VestingSchedules::<T>::mutate(who, reward_currency, |maybe_schedule| {
+            let schedule = maybe_schedule.get_or_insert_with(|| VestingSchedule {
+                locked: Zero::zero(),
+                claimable: Zero::zero(),
+                start_block: n,
+                end_block: n,
+            });
+            Self::vest(schedule, n);
+            schedule.locked = schedule.locked.saturating_add(reward_to_withdraw);
+            schedule.start_block = n;
+            schedule.end_block = schedule.end_block.max(n.saturating_add(vesting_blocks));
+        });
+
+        Self::deposit_event(Event::RewardsVested {
+            who: who.clone(),
+            pid: pool_id,
+            reward_currency: *reward_currency,
+            amount: reward_to_withdraw,
+        });
+        Ok(())
+    }
+
+    pub fn claim_vested_inner(
+        who: &T::AccountId,
+        reward_currency: CurrencyIdOf<T>,
+    ) -> DispatchResult {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let mut schedule =
+            Self::vesting_schedules(who, reward_currency).ok_or(Error::<T>::VestingNotExist)?;
+        Self::vest(&mut schedule, n);
+        let amount = schedule.claimable;
+        if !amount.is_zero() {
+            Self::farming_token_transfer(&reward_currency, amount, who, &Self::vesting_account())?;
+            schedule.claimable = Zero::zero();
+        }
+        if schedule.locked.is_zero() {
+            VestingSchedules::<T>::remove(who, reward_currency);
+        } else {
+            VestingSchedules::<T>::insert(who, reward_currency, schedule);
+        }
+
+        Self::deposit_event(Event::VestedClaimed {
+            who: who.clone(),
+            reward_currency,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// The locked and claimable vesting rewards of `who` in each currency.
+    pub fn get_vesting_rewards(
+        who: &T::AccountId,
+    ) -> Vec<(CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>)> {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        VestingSchedules::<T>::iter_prefix(who)
+            .map(|(reward_currency, mut schedule)| {
+                Self::vest(&mut schedule, n);
+                (reward_currency, schedule.locked, schedule.claimable)
+            })
+            .collect()
+    }
+}
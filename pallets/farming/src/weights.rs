@@ -45,30 +45,29 @@ use core::marker::PhantomData;
 
 /// Weight functions needed for pallet_farming.
 pub trait WeightInfo {
-	fn on_initialize() -> Weight;
 	fn create_farming_pool() -> Weight;
 	fn charge() -> Weight;
+	fn refund_charge() -> Weight;
+	fn add_incentive() -> Weight;
+	fn withdraw_incentive() -> Weight;
 	fn deposit() -> Weight;
 	fn withdraw() -> Weight;
 	fn claim() -> Weight;
 	fn gauge_withdraw() -> Weight;
+	fn ve_lock() -> Weight;
+	fn ve_unlock() -> Weight;
+	fn update_boost() -> Weight;
+	fn vote(v: u32, ) -> Weight;
+	fn set_vesting() -> Weight;
+	fn claim_vested() -> Weight;
+	fn emergency_withdraw() -> Weight;
+	fn kill_pool(c: u32, ) -> Weight;
+	fn set_charge_allowlist(a: u32, ) -> Weight;
 }
 
 /// Weights for pallet_farming using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: Farming PoolInfos (r:1 w:0)
-	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Farming GaugePoolInfos (r:1 w:0)
-	/// Proof Skipped: Farming GaugePoolInfos (max_values: None, max_size: None, mode: Measured)
-	fn on_initialize() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `10`
-		//  Estimated: `3475`
-		// Minimum execution time: 4_298_000 picoseconds.
-		Weight::from_parts(4_509_000, 3475)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-	}
 	/// Storage: Farming PoolNextId (r:1 w:1)
 	/// Proof Skipped: Farming PoolNextId (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Farming GaugePoolNextId (r:1 w:1)
@@ -105,6 +104,61 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	}
 	/// Storage: Farming PoolInfos (r:1 w:1)
 	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming Charges (r:1 w:1)
+	/// Proof Skipped: Farming Charges (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn refund_charge() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1024`
+		//  Estimated: `6232`
+		// Minimum execution time: 29_846_000 picoseconds.
+		Weight::from_parts(30_512_000, 6232)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming IncentiveNextId (r:1 w:1)
+	/// Proof Skipped: Farming IncentiveNextId (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn add_incentive() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1187`
+		//  Estimated: `6232`
+		// Minimum execution time: 33_418_000 picoseconds.
+		Weight::from_parts(34_205_000, 6232)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn withdraw_incentive() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1187`
+		//  Estimated: `6232`
+		// Minimum execution time: 31_907_000 picoseconds.
+		Weight::from_parts(32_644_000, 6232)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Assets Asset (r:1 w:1)
 	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
 	/// Storage: Assets Account (r:2 w:2)
@@ -167,22 +221,209 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
-}
-
-// For backwards compatibility and tests
-impl WeightInfo for () {
+	/// Storage: Farming VeSupplyInfo (r:1 w:1)
+	/// Proof Skipped: Farming VeSupplyInfo (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Farming VeLocks (r:1 w:1)
+	/// Proof Skipped: Farming VeLocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming VeUnlocks (r:2 w:2)
+	/// Proof Skipped: Farming VeUnlocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn ve_lock() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `6552`
+		// Minimum execution time: 31_806_000 picoseconds.
+		Weight::from_parts(32_473_000, 6552)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Farming VeLocks (r:1 w:1)
+	/// Proof Skipped: Farming VeLocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming VeSupplyInfo (r:1 w:1)
+	/// Proof Skipped: Farming VeSupplyInfo (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Farming UserVotes (r:1 w:1)
+	/// Proof Skipped: Farming UserVotes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolVotes (r:1 w:1)
+	/// Proof Skipped: Farming PoolVotes (max_values: None, max_size: None, mode: Measured)
+	fn ve_unlock() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `681`
+		//  Estimated: `6146`
+		// Minimum execution time: 24_117_000 picoseconds.
+		Weight::from_parts(24_690_000, 6146)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming SharesAndWithdrawnRewards (r:1 w:1)
+	/// Proof Skipped: Farming SharesAndWithdrawnRewards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming BoostShares (r:1 w:1)
+	/// Proof Skipped: Farming BoostShares (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolBoosts (r:1 w:1)
+	/// Proof Skipped: Farming PoolBoosts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming VeSupplyInfo (r:1 w:0)
+	/// Proof Skipped: Farming VeSupplyInfo (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Farming VeLocks (r:1 w:0)
+	/// Proof Skipped: Farming VeLocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugeInfos (r:1 w:0)
+	/// Proof Skipped: Farming GaugeInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	fn update_boost() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1482`
+		//  Estimated: `7422`
+		// Minimum execution time: 58_964_000 picoseconds.
+		Weight::from_parts(60_218_000, 7422)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Farming VeLocks (r:1 w:0)
+	/// Proof Skipped: Farming VeLocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolVotes (r:1 w:1)
+	/// Proof Skipped: Farming PoolVotes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming UserVotes (r:1 w:1)
+	/// Proof Skipped: Farming UserVotes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolInfos (r:10 w:0)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `v` is `[1, 10]`.
+	fn vote(v: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `698 + v * (102 ±0)`
+		//  Estimated: `4163 + v * (2577 ±0)`
+		// Minimum execution time: 19_842_000 picoseconds.
+		Weight::from_parts(18_457_314, 4163)
+			// Standard Error: 6_120
+			.saturating_add(Weight::from_parts(2_904_526, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(Weight::from_parts(0, 2577).saturating_mul(v.into()))
+	}
 	/// Storage: Farming PoolInfos (r:1 w:0)
 	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Farming GaugePoolInfos (r:1 w:0)
+	/// Storage: Farming PoolVestings (r:0 w:1)
+	/// Proof Skipped: Farming PoolVestings (max_values: None, max_size: None, mode: Measured)
+	fn set_vesting() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `382`
+		//  Estimated: `3847`
+		// Minimum execution time: 10_214_000 picoseconds.
+		Weight::from_parts(10_562_000, 3847)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Farming VestingSchedules (r:1 w:1)
+	/// Proof Skipped: Farming VestingSchedules (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn claim_vested() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `874`
+		//  Estimated: `6232`
+		// Minimum execution time: 30_845_000 picoseconds.
+		Weight::from_parts(31_427_000, 6232)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Farming EmergencyWithdrawEnabled (r:1 w:0)
+	/// Proof Skipped: Farming EmergencyWithdrawEnabled (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming SharesAndWithdrawnRewards (r:1 w:1)
+	/// Proof Skipped: Farming SharesAndWithdrawnRewards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming BoostShares (r:1 w:1)
+	/// Proof Skipped: Farming BoostShares (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:3 w:3)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Farming GaugeInfos (r:1 w:1)
+	/// Proof Skipped: Farming GaugeInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugePoolInfos (r:1 w:1)
 	/// Proof Skipped: Farming GaugePoolInfos (max_values: None, max_size: None, mode: Measured)
-	fn on_initialize() -> Weight {
+	fn emergency_withdraw() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `10`
-		//  Estimated: `3475`
-		// Minimum execution time: 4_298_000 picoseconds.
-		Weight::from_parts(4_509_000, 3475)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
+		//  Measured:  `1536`
+		//  Estimated: `8853`
+		// Minimum execution time: 51_372_000 picoseconds.
+		Weight::from_parts(52_641_000, 8853)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming Charges (r:11 w:10)
+	/// Proof Skipped: Farming Charges (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:10 w:10)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:20 w:20)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:10 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Farming SharesAndWithdrawnRewards (r:0 w:1)
+	/// Proof Skipped: Farming SharesAndWithdrawnRewards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming ChargeAllowlists (r:0 w:1)
+	/// Proof Skipped: Farming ChargeAllowlists (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming BoostShares (r:0 w:1)
+	/// Proof Skipped: Farming BoostShares (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolBoosts (r:0 w:1)
+	/// Proof Skipped: Farming PoolBoosts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolVestings (r:0 w:1)
+	/// Proof Skipped: Farming PoolVestings (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming EmergencyWithdrawEnabled (r:0 w:1)
+	/// Proof Skipped: Farming EmergencyWithdrawEnabled (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `c` is `[1, 10]`.
+	fn kill_pool(c: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512 + c * (189 ±0)`
+		//  Estimated: `3977 + c * (5242 ±0)`
+		// Minimum execution time: 21_408_000 picoseconds.
+		Weight::from_parts(22_036_517, 3977)
+			// Standard Error: 14_302
+			.saturating_add(Weight::from_parts(24_718_260, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 5242).saturating_mul(c.into()))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:0)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming ChargeAllowlists (r:0 w:1)
+	/// Proof Skipped: Farming ChargeAllowlists (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `a` is `[0, 20]`.
+	fn set_charge_allowlist(a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `382`
+		//  Estimated: `3847`
+		// Minimum execution time: 10_937_000 picoseconds.
+		Weight::from_parts(11_472_908, 3847)
+			// Standard Error: 1_204
+			.saturating_add(Weight::from_parts(38_716, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
 	/// Storage: Farming PoolNextId (r:1 w:1)
 	/// Proof Skipped: Farming PoolNextId (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Farming GaugePoolNextId (r:1 w:1)
@@ -219,6 +460,61 @@ impl WeightInfo for () {
 	}
 	/// Storage: Farming PoolInfos (r:1 w:1)
 	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming Charges (r:1 w:1)
+	/// Proof Skipped: Farming Charges (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn refund_charge() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1024`
+		//  Estimated: `6232`
+		// Minimum execution time: 29_846_000 picoseconds.
+		Weight::from_parts(30_512_000, 6232)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming IncentiveNextId (r:1 w:1)
+	/// Proof Skipped: Farming IncentiveNextId (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn add_incentive() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1187`
+		//  Estimated: `6232`
+		// Minimum execution time: 33_418_000 picoseconds.
+		Weight::from_parts(34_205_000, 6232)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn withdraw_incentive() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1187`
+		//  Estimated: `6232`
+		// Minimum execution time: 31_907_000 picoseconds.
+		Weight::from_parts(32_644_000, 6232)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Assets Asset (r:1 w:1)
 	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
 	/// Storage: Assets Account (r:2 w:2)
@@ -281,4 +577,203 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: Farming VeSupplyInfo (r:1 w:1)
+	/// Proof Skipped: Farming VeSupplyInfo (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Farming VeLocks (r:1 w:1)
+	/// Proof Skipped: Farming VeLocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming VeUnlocks (r:2 w:2)
+	/// Proof Skipped: Farming VeUnlocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn ve_lock() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `6552`
+		// Minimum execution time: 31_806_000 picoseconds.
+		Weight::from_parts(32_473_000, 6552)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Farming VeLocks (r:1 w:1)
+	/// Proof Skipped: Farming VeLocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming VeSupplyInfo (r:1 w:1)
+	/// Proof Skipped: Farming VeSupplyInfo (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Farming UserVotes (r:1 w:1)
+	/// Proof Skipped: Farming UserVotes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolVotes (r:1 w:1)
+	/// Proof Skipped: Farming PoolVotes (max_values: None, max_size: None, mode: Measured)
+	fn ve_unlock() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `681`
+		//  Estimated: `6146`
+		// Minimum execution time: 24_117_000 picoseconds.
+		Weight::from_parts(24_690_000, 6146)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming SharesAndWithdrawnRewards (r:1 w:1)
+	/// Proof Skipped: Farming SharesAndWithdrawnRewards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming BoostShares (r:1 w:1)
+	/// Proof Skipped: Farming BoostShares (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolBoosts (r:1 w:1)
+	/// Proof Skipped: Farming PoolBoosts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming VeSupplyInfo (r:1 w:0)
+	/// Proof Skipped: Farming VeSupplyInfo (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Farming VeLocks (r:1 w:0)
+	/// Proof Skipped: Farming VeLocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugeInfos (r:1 w:0)
+	/// Proof Skipped: Farming GaugeInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	fn update_boost() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1482`
+		//  Estimated: `7422`
+		// Minimum execution time: 58_964_000 picoseconds.
+		Weight::from_parts(60_218_000, 7422)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Farming VeLocks (r:1 w:0)
+	/// Proof Skipped: Farming VeLocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolVotes (r:1 w:1)
+	/// Proof Skipped: Farming PoolVotes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming UserVotes (r:1 w:1)
+	/// Proof Skipped: Farming UserVotes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolInfos (r:10 w:0)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `v` is `[1, 10]`.
+	fn vote(v: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `698 + v * (102 ±0)`
+		//  Estimated: `4163 + v * (2577 ±0)`
+		// Minimum execution time: 19_842_000 picoseconds.
+		Weight::from_parts(18_457_314, 4163)
+			// Standard Error: 6_120
+			.saturating_add(Weight::from_parts(2_904_526, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(Weight::from_parts(0, 2577).saturating_mul(v.into()))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:0)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolVestings (r:0 w:1)
+	/// Proof Skipped: Farming PoolVestings (max_values: None, max_size: None, mode: Measured)
+	fn set_vesting() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `382`
+		//  Estimated: `3847`
+		// Minimum execution time: 10_214_000 picoseconds.
+		Weight::from_parts(10_562_000, 3847)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Farming VestingSchedules (r:1 w:1)
+	/// Proof Skipped: Farming VestingSchedules (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn claim_vested() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `874`
+		//  Estimated: `6232`
+		// Minimum execution time: 30_845_000 picoseconds.
+		Weight::from_parts(31_427_000, 6232)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Farming EmergencyWithdrawEnabled (r:1 w:0)
+	/// Proof Skipped: Farming EmergencyWithdrawEnabled (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming SharesAndWithdrawnRewards (r:1 w:1)
+	/// Proof Skipped: Farming SharesAndWithdrawnRewards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming BoostShares (r:1 w:1)
+	/// Proof Skipped: Farming BoostShares (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:3 w:3)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Farming GaugeInfos (r:1 w:1)
+	/// Proof Skipped: Farming GaugeInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugePoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming GaugePoolInfos (max_values: None, max_size: None, mode: Measured)
+	fn emergency_withdraw() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1536`
+		//  Estimated: `8853`
+		// Minimum execution time: 51_372_000 picoseconds.
+		Weight::from_parts(52_641_000, 8853)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming Charges (r:11 w:10)
+	/// Proof Skipped: Farming Charges (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:10 w:10)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:20 w:20)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:10 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Farming SharesAndWithdrawnRewards (r:0 w:1)
+	/// Proof Skipped: Farming SharesAndWithdrawnRewards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming ChargeAllowlists (r:0 w:1)
+	/// Proof Skipped: Farming ChargeAllowlists (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming BoostShares (r:0 w:1)
+	/// Proof Skipped: Farming BoostShares (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolBoosts (r:0 w:1)
+	/// Proof Skipped: Farming PoolBoosts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolVestings (r:0 w:1)
+	/// Proof Skipped: Farming PoolVestings (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming EmergencyWithdrawEnabled (r:0 w:1)
+	/// Proof Skipped: Farming EmergencyWithdrawEnabled (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `c` is `[1, 10]`.
+	fn kill_pool(c: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512 + c * (189 ±0)`
+		//  Estimated: `3977 + c * (5242 ±0)`
+		// Minimum execution time: 21_408_000 picoseconds.
+		Weight::from_parts(22_036_517, 3977)
+			// Standard Error: 14_302
+			.saturating_add(Weight::from_parts(24_718_260, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 5242).saturating_mul(c.into()))
+	}
+	/// Storage: Farming PoolInfos (r:1 w:0)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming ChargeAllowlists (r:0 w:1)
+	/// Proof Skipped: Farming ChargeAllowlists (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `a` is `[0, 20]`.
+	fn set_charge_allowlist(a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `382`
+		//  Estimated: `3847`
+		// Minimum execution time: 10_937_000 picoseconds.
+		Weight::from_parts(11_472_908, 3847)
+			// Standard Error: 1_204
+			.saturating_add(Weight::from_parts(38_716, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
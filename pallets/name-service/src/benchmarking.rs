@@ -18,12 +18,15 @@
 
 #![cfg(feature = "runtime-benchmarks")]
 
-use crate::{Call, Config, Event, Pallet};
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_support::traits::{Currency, Get};
+use crate::{Call, Config, Event, Pallet, Records, Salt};
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+    traits::{Currency, Get},
+    BoundedVec,
+};
 use frame_system::RawOrigin;
 use manta_support::manta_pay::AccountId;
-use sp_runtime::traits::Hash;
 use sp_std::prelude::*;
 
 #[inline]
@@ -36,6 +39,52 @@ where
     assert_eq!(events[events.len() - 1].event, event.into().into());
 }
 
+const SALT: Salt = [7u8; 32];
+
+/// Registers `username` for the caller, funding it for the register fees.
+fn register_name<T>(caller: &T::AccountId, username: &[u8]) -> Result<(), &'static str>
+where
+    T: Config,
+    T::AccountId: From<AccountId> + Into<AccountId>,
+{
+    let factor = 1_000u32;
+    <T as crate::Config>::Currency::make_free_balance_be(
+        caller,
+        T::RegisterPrice::get() * factor.into(),
+    );
+    let origin = RawOrigin::Signed(caller.clone());
+    let username = username.to_vec();
+    let commitment = Pallet::<T>::commitment_of(&username, &caller.clone().into(), &caller, &SALT);
+    Pallet::<T>::register(origin.clone().into(), commitment).map_err(|e| e.into())?;
+    // move blocknumber forward so pending register is available to move to records
+    let new_block: T::BlockNumber = 10u32.into();
+    frame_system::Pallet::<T>::set_block_number(new_block);
+    Pallet::<T>::accept_register(origin.into(), username, caller.clone().into(), SALT)
+        .map_err(|e| e.into())
+}
+
+/// Resolver records filling every bound, funding the caller for their deposit.
+fn max_records<T: Config>(caller: &T::AccountId) -> Records<T::AccountId> {
+    let length = T::MaxRecordLength::get() as usize;
+    let count = T::MaxRecords::get();
+    let records = Records {
+        account: Some(caller.clone()),
+        evm_address: Some([1u8; 20]),
+        chain_addresses: (0..count).map(|chain| (chain, vec![1u8; length])).collect(),
+        texts: (0..count)
+            .map(|key| {
+                let mut key = key.to_le_bytes().to_vec();
+                key.resize(length, 0);
+                (key, vec![1u8; length])
+            })
+            .collect(),
+    };
+    let deposit = T::RecordDepositBase::get()
+        + T::RecordDepositPerByte::get() * (records.encoded_size() as u32).into();
+    <T as crate::Config>::Currency::deposit_creating(caller, deposit);
+    records
+}
+
 benchmarks! {
     where_clause {  where T: Config,
         T::AccountId: From<AccountId> + Into<AccountId>,
@@ -47,14 +96,14 @@ benchmarks! {
         <T as crate::Config>::Currency::make_free_balance_be(&caller, T::RegisterPrice::get() * factor.into());
         let origin = RawOrigin::Signed(caller.clone());
         let username = "test".as_bytes().to_vec();
+        let commitment = Pallet::<T>::commitment_of(&username, &caller.clone().into(), &caller, &SALT);
     }: register(
         origin,
-        username.clone(),
-        caller.clone().into()
+        commitment
     ) verify {
         assert_last_event::<T, _>(Event::NameQueuedForRegister {
-            hash_username: T::Hashing::hash_of(&username),
-            hash_owner: T::Hashing::hash_of(&caller.into()),
+            commitment,
+            controller: caller,
         });
     }
 
@@ -66,7 +115,8 @@ benchmarks! {
         let factor = 1_000u32;
         <T as crate::Config>::Currency::make_free_balance_be(&caller, T::RegisterPrice::get() * factor.into());
 
-        Pallet::<T>::register(origin.clone().into(), username.clone(), caller.clone().into())?;
+        let commitment = Pallet::<T>::commitment_of(&username, &caller.clone().into(), &caller, &SALT);
+        Pallet::<T>::register(origin.clone().into(), commitment)?;
         // move blocknumber forward so pending register is available to move to records
         let new_block: T::BlockNumber = 10u32.into();
         frame_system::Pallet::<T>::set_block_number(new_block);
@@ -74,7 +124,8 @@ benchmarks! {
     }: accept_register(
         origin,
         username.clone(),
-        caller.clone().into()
+        caller.clone().into(),
+        SALT
     ) verify {
         assert_last_event::<T, _>(Event::NameRegistered {
             username,
@@ -90,11 +141,12 @@ benchmarks! {
         let factor = 1_000u32;
         <T as crate::Config>::Currency::make_free_balance_be(&caller, T::RegisterPrice::get() * factor.into());
 
-        Pallet::<T>::register(origin.clone().into(), username.clone(), caller.clone().into())?;
+        let commitment = Pallet::<T>::commitment_of(&username, &caller.clone().into(), &caller, &SALT);
+        Pallet::<T>::register(origin.clone().into(), commitment)?;
         // move blocknumber forward so pending register is available to move to records
         let new_block: T::BlockNumber = 10u32.into();
         frame_system::Pallet::<T>::set_block_number(new_block);
-        Pallet::<T>::accept_register(origin.clone().into(), username.clone(), caller.clone().into())?;
+        Pallet::<T>::accept_register(origin.clone().into(), username.clone(), caller.clone().into(), SALT)?;
 
     }: set_primary_name(
         origin,
@@ -115,23 +167,24 @@ benchmarks! {
         let factor = 1_000u32;
         <T as crate::Config>::Currency::make_free_balance_be(&caller, T::RegisterPrice::get() * factor.into());
 
-        Pallet::<T>::register(origin.clone().into(), username.clone(), caller.clone().into())?;
+        let commitment = Pallet::<T>::commitment_of(&username, &caller.clone().into(), &caller, &SALT);
+        Pallet::<T>::register(origin.clone().into(), commitment)?;
         // move blocknumber forward so pending register is available to move to records
         let new_block: T::BlockNumber = 10u32.into();
         frame_system::Pallet::<T>::set_block_number(new_block);
 
     }: cancel_pending_register(
         origin,
-        username.clone(),
-        caller.clone().into()
+        commitment
     ) verify {
         assert_last_event::<T, _>(Event::RegisterCanceled{
-            hash_username: T::Hashing::hash_of(&username),
-            hash_owner: T::Hashing::hash_of(&caller.into()),
+            commitment,
+            refund: T::RegisterPrice::get(),
         });
     }
 
     remove_register {
+        let s in 0 .. T::MaxSubnames::get();
         let caller: T::AccountId = whitelisted_caller();
         let origin = RawOrigin::Signed(caller.clone());
         let username = "test".as_bytes().to_vec();
@@ -139,12 +192,16 @@ benchmarks! {
         let factor = 1_000u32;
         <T as crate::Config>::Currency::make_free_balance_be(&caller, T::RegisterPrice::get() * factor.into());
 
-        Pallet::<T>::register(origin.clone().into(), username.clone(), caller.clone().into())?;
+        let commitment = Pallet::<T>::commitment_of(&username, &caller.clone().into(), &caller, &SALT);
+        Pallet::<T>::register(origin.clone().into(), commitment)?;
         // move blocknumber forward so pending register is available to move to records
         let new_block: T::BlockNumber = 10u32.into();
         frame_system::Pallet::<T>::set_block_number(new_block);
-        Pallet::<T>::accept_register(origin.clone().into(), username.clone(), caller.clone().into())?;
-
+        Pallet::<T>::accept_register(origin.clone().into(), username.clone(), caller.clone().into(), SALT)?;
+        for i in 0..s {
+            let label = vec![b'a' + (i / 26) as u8, b'a' + (i % 26) as u8];
+            Pallet::<T>::create_subname(origin.clone().into(), username.clone(), label, caller.clone().into(), caller.clone())?;
+        }
     }: remove_register(
         origin,
         username.clone(),
@@ -155,6 +212,271 @@ benchmarks! {
             owner: caller.into(),
         });
     }
+
+    renew {
+        let caller: T::AccountId = whitelisted_caller();
+        let origin = RawOrigin::Signed(caller.clone());
+        let username = "test".as_bytes().to_vec();
+
+        let factor = 1_000u32;
+        <T as crate::Config>::Currency::make_free_balance_be(&caller, T::RegisterPrice::get() * factor.into());
+
+        let commitment = Pallet::<T>::commitment_of(&username, &caller.clone().into(), &caller, &SALT);
+        Pallet::<T>::register(origin.clone().into(), commitment)?;
+        // move blocknumber forward so pending register is available to move to records
+        let new_block: T::BlockNumber = 10u32.into();
+        frame_system::Pallet::<T>::set_block_number(new_block);
+        Pallet::<T>::accept_register(origin.clone().into(), username.clone(), caller.clone().into(), SALT)?;
+        let expiry = Pallet::<T>::expiries(&username).unwrap();
+
+    }: renew(
+        origin,
+        username.clone(),
+        1
+    ) verify {
+        assert_last_event::<T, _>(Event::NameRenewed {
+            username,
+            expiry: expiry + T::RegistrationPeriod::get(),
+        });
+    }
+
+    transfer_name {
+        let caller: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        Pallet::<T>::set_primary_name(RawOrigin::Signed(caller.clone()).into(), username.clone(), caller.clone().into())?;
+    }: transfer_name(
+        RawOrigin::Signed(caller.clone()),
+        username.clone(),
+        receiver.clone().into(),
+        caller.clone()
+    ) verify {
+        assert_last_event::<T, _>(Event::NameTransferred {
+            username,
+            from: caller.into(),
+            to: receiver.into(),
+        });
+    }
+
+    accept_transfer {
+        let caller: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        Pallet::<T>::set_primary_name(RawOrigin::Signed(caller.clone()).into(), username.clone(), caller.clone().into())?;
+        Pallet::<T>::transfer_name(RawOrigin::Signed(caller.clone()).into(), username.clone(), receiver.clone().into(), receiver.clone())?;
+    }: accept_transfer(
+        RawOrigin::Signed(receiver.clone()),
+        username.clone()
+    ) verify {
+        assert_last_event::<T, _>(Event::NameTransferred {
+            username,
+            from: caller.into(),
+            to: receiver.into(),
+        });
+    }
+
+    list_name {
+        let caller: T::AccountId = whitelisted_caller();
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        let price = T::RegisterPrice::get();
+    }: list_name(
+        RawOrigin::Signed(caller),
+        username.clone(),
+        price
+    ) verify {
+        assert_last_event::<T, _>(Event::NameListed {
+            username,
+            price,
+        });
+    }
+
+    unlist_name {
+        let caller: T::AccountId = whitelisted_caller();
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        Pallet::<T>::list_name(RawOrigin::Signed(caller.clone()).into(), username.clone(), T::RegisterPrice::get())?;
+    }: unlist_name(
+        RawOrigin::Signed(caller),
+        username.clone()
+    ) verify {
+        assert_last_event::<T, _>(Event::NameUnlisted {
+            username,
+        });
+    }
+
+    buy_name {
+        let caller: T::AccountId = whitelisted_caller();
+        let buyer: T::AccountId = account("buyer", 0, 0);
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        Pallet::<T>::set_primary_name(RawOrigin::Signed(caller.clone()).into(), username.clone(), caller.clone().into())?;
+        let price = T::RegisterPrice::get();
+        Pallet::<T>::list_name(RawOrigin::Signed(caller.clone()).into(), username.clone(), price)?;
+        let factor = 1_000u32;
+        <T as crate::Config>::Currency::make_free_balance_be(&buyer, price * factor.into());
+    }: buy_name(
+        RawOrigin::Signed(buyer.clone()),
+        username.clone(),
+        buyer.clone().into(),
+        price
+    ) verify {
+        assert_last_event::<T, _>(Event::NameSold {
+            username,
+            buyer,
+            price,
+        });
+    }
+
+    set_records {
+        let caller: T::AccountId = whitelisted_caller();
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        let records = max_records::<T>(&caller);
+        let deposit = T::RecordDepositBase::get()
+            + T::RecordDepositPerByte::get() * (records.encoded_size() as u32).into();
+    }: set_records(
+        RawOrigin::Signed(caller.clone()),
+        username.clone(),
+        records
+    ) verify {
+        assert_last_event::<T, _>(Event::RecordsSet {
+            username,
+            deposit,
+        });
+    }
+
+    clear_records {
+        let caller: T::AccountId = whitelisted_caller();
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        let records = max_records::<T>(&caller);
+        Pallet::<T>::set_records(RawOrigin::Signed(caller.clone()).into(), username.clone(), records)?;
+    }: clear_records(
+        RawOrigin::Signed(caller.clone()),
+        username.clone()
+    ) verify {
+        assert_last_event::<T, _>(Event::RecordsCleared {
+            username,
+        });
+    }
+
+    create_subname {
+        let caller: T::AccountId = whitelisted_caller();
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        let owner: T::AccountId = account("owner", 0, 0);
+    }: create_subname(
+        RawOrigin::Signed(caller.clone()),
+        username,
+        "alice".as_bytes().to_vec(),
+        owner.clone().into(),
+        owner.clone()
+    ) verify {
+        assert_last_event::<T, _>(Event::SubnameCreated {
+            username: "alice.test".as_bytes().to_vec(),
+            owner: owner.clone().into(),
+            controller: owner,
+        });
+    }
+
+    revoke_subname {
+        let caller: T::AccountId = whitelisted_caller();
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        Pallet::<T>::create_subname(RawOrigin::Signed(caller.clone()).into(), username, "alice".as_bytes().to_vec(), caller.clone().into(), caller.clone())?;
+        Pallet::<T>::set_primary_name(RawOrigin::Signed(caller.clone()).into(), "alice.test".as_bytes().to_vec(), caller.clone().into())?;
+        let records = max_records::<T>(&caller);
+        Pallet::<T>::set_records(RawOrigin::Signed(caller.clone()).into(), "alice.test".as_bytes().to_vec(), records)?;
+    }: revoke_subname(
+        RawOrigin::Signed(caller.clone()),
+        "alice.test".as_bytes().to_vec()
+    ) verify {
+        assert_last_event::<T, _>(Event::SubnameRevoked {
+            username: "alice.test".as_bytes().to_vec(),
+            owner: caller.into(),
+        });
+    }
+
+    set_subname_price {
+        let caller: T::AccountId = whitelisted_caller();
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        let price = Some(T::RegisterPrice::get());
+    }: set_subname_price(
+        RawOrigin::Signed(caller.clone()),
+        username.clone(),
+        price
+    ) verify {
+        assert_last_event::<T, _>(Event::SubnamePriceSet {
+            parent: username,
+            price,
+        });
+    }
+
+    buy_subname {
+        let caller: T::AccountId = whitelisted_caller();
+        let buyer: T::AccountId = account("buyer", 0, 0);
+        let username = "test".as_bytes().to_vec();
+        register_name::<T>(&caller, &username)?;
+        let price = T::RegisterPrice::get();
+        Pallet::<T>::set_subname_price(RawOrigin::Signed(caller.clone()).into(), username.clone(), Some(price))?;
+        let factor = 1_000u32;
+        <T as crate::Config>::Currency::make_free_balance_be(&buyer, price * factor.into());
+    }: buy_subname(
+        RawOrigin::Signed(buyer.clone()),
+        username,
+        "alice".as_bytes().to_vec(),
+        buyer.clone().into(),
+        price
+    ) verify {
+        assert_last_event::<T, _>(Event::SubnameSold {
+            username: "alice.test".as_bytes().to_vec(),
+            buyer,
+            price,
+        });
+    }
+
+    sweep_fees {
+        let fees = T::RegisterPrice::get();
+        <T as crate::Config>::Currency::make_free_balance_be(
+            &Pallet::<T>::account_id(),
+            fees + <T as crate::Config>::Currency::minimum_balance(),
+        );
+    }: sweep_fees(RawOrigin::Root) verify {
+        assert_last_event::<T, _>(Event::FeesSwept {
+            amount: fees,
+        });
+    }
+
+    assign_legacy_controller {
+        let n in 1 .. T::MaxLegacyControllers::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let owner: AccountId = caller.clone().into();
+        let factor = 10u32;
+        let price = T::RegisterPrice::get();
+        <T as crate::Config>::Currency::make_free_balance_be(&caller, price * factor.into());
+        // Every name hands its legacy deposit to the controller
+        <T as crate::Config>::Currency::make_free_balance_be(&Pallet::<T>::account_id(), price * (n + factor).into());
+        crate::LegacyDepositTotal::<T>::put(price * n.into());
+        let mut controllers = Vec::new();
+        for i in 0..n {
+            let username = vec![b'a' + (i / 26) as u8, b'a' + (i % 26) as u8, b'a'];
+            crate::UsernameRecords::<T>::insert(&username, owner);
+            crate::LegacyDeposits::<T>::insert(&username, price);
+            controllers.push((username, caller.clone()));
+        }
+        let username = controllers[controllers.len() - 1].0.clone();
+        let controllers: BoundedVec<_, T::MaxLegacyControllers> = controllers.try_into().unwrap();
+    }: assign_legacy_controller(RawOrigin::Root, controllers) verify {
+        assert!(!crate::LegacyDeposits::<T>::contains_key(&username));
+        assert_last_event::<T, _>(Event::LegacyControllerAssigned {
+            username,
+            controller: caller,
+        });
+    }
 }
 
 impl_benchmark_test_suite!(
@@ -23,18 +23,24 @@ mod benchmarking;
 
 use frame_support::{
     pallet_prelude::*,
-    traits::{Currency, ExistenceRequirement, ReservableCurrency},
+    traits::{BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency},
     transactional,
 };
 use frame_system::pallet_prelude::*;
 use safe_regex::{regex, Matcher0};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
-    traits::{AccountIdConversion, Hash, Saturating},
-    DispatchResult,
+    traits::{AccountIdConversion, Convert, Hash, Saturating, Zero},
+    DispatchResult, Percent,
 };
 use sp_std::vec::Vec;
 
+pub mod migrations;
 mod mock;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+pub mod runtime;
 mod tests;
 pub mod weights;
 
@@ -45,9 +51,46 @@ pub type ZkAddressType = [u8; 32];
 
 pub type UserName = Vec<u8>;
 
+/// Secret mixed into a registration commitment so the username can't be guessed from it
+pub type Salt = [u8; 32];
+
+/// Ethereum style address a name can resolve to
+pub type EvmAddress = [u8; 20];
+
+/// Identifier of the chain an address record belongs to, e.g. a SLIP-44 coin type
+pub type ChainId = u32;
+
+/// Resolver records the controller of a name sets for it
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+pub struct Records<AccountId> {
+    /// Substrate account the name resolves to
+    pub account: Option<AccountId>,
+    /// EVM address the name resolves to
+    pub evm_address: Option<EvmAddress>,
+    /// Addresses on other chains, sorted by chain
+    pub chain_addresses: Vec<(ChainId, Vec<u8>)>,
+    /// Text records such as `avatar`, `url` or `email`, sorted by key
+    pub texts: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl<AccountId> Default for Records<AccountId> {
+    fn default() -> Self {
+        Self {
+            account: None,
+            evm_address: None,
+            chain_addresses: Vec::new(),
+            texts: Vec::new(),
+        }
+    }
+}
+
 pub const NAME_MAX_LEN: usize = 64;
 pub const NAME_MIN_LEN: usize = 3;
 
+/// Separates the label of a subname from its parent name, as in `alice.project`
+pub const SUBNAME_SEPARATOR: u8 = b'.';
+
 /// Type alias for currency balance.
 pub type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -57,7 +100,7 @@ pub mod pallet {
     use super::*;
     use frame_support::{traits::StorageVersion, PalletId};
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -68,12 +111,65 @@ pub mod pallet {
 
         type Currency: ReservableCurrency<Self::AccountId>;
 
+        /// Origin allowed to sweep collected fees to the treasury
+        type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Account receiving the swept fees
+        #[pallet::constant]
+        type TreasuryAccount: Get<Self::AccountId>;
+
         #[pallet::constant]
         type RegisterWaitingPeriod: Get<Self::BlockNumber>;
 
+        /// Deposit reserved from the controller for each commitment and registered name,
+        /// released when it is canceled or removed
         #[pallet::constant]
         type RegisterPrice: Get<BalanceOf<Self>>;
 
+        /// Blocks after which a commitment can no longer be revealed
+        #[pallet::constant]
+        type CommitmentLifetime: Get<Self::BlockNumber>;
+
+        /// Share of the register deposit released when a commitment expires unrevealed,
+        /// the rest is kept as a fee
+        #[pallet::constant]
+        type CommitmentRefund: Get<Percent>;
+
+        /// Blocks a name is registered for with each paid period
+        #[pallet::constant]
+        type RegistrationPeriod: Get<Self::BlockNumber>;
+
+        /// Blocks after expiry during which only the controller may renew a name
+        #[pallet::constant]
+        type GracePeriod: Get<Self::BlockNumber>;
+
+        /// Price of one registration period for a name of the given length
+        type NamePrice: Convert<u32, BalanceOf<Self>>;
+
+        /// Deposit reserved from the controller for storing resolver records
+        #[pallet::constant]
+        type RecordDepositBase: Get<BalanceOf<Self>>;
+
+        /// Additional deposit reserved per byte of resolver records
+        #[pallet::constant]
+        type RecordDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Maximum number of chain addresses, and of text records, a name may have
+        #[pallet::constant]
+        type MaxRecords: Get<u32>;
+
+        /// Maximum length of a chain address, text record key or text record value
+        #[pallet::constant]
+        type MaxRecordLength: Get<u32>;
+
+        /// Maximum number of subnames under a single name
+        #[pallet::constant]
+        type MaxSubnames: Get<u32>;
+
+        /// Maximum number of legacy names `assign_legacy_controller` assigns at once
+        #[pallet::constant]
+        type MaxLegacyControllers: Get<u32>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -91,12 +187,44 @@ pub mod pallet {
         InvalidUsernameFormat,
         /// Already pending Register
         AlreadyPendingRegister,
-        /// Not Found (used in cases of canceling)
+        /// Not Found (used in cases of revealing or canceling)
         UsernameNotFound,
         /// Username registered but is not primary (transfers)
         UsernameNotPrimary,
         /// Not enough balance for Register payment
         InsufficientBalance,
+        /// Caller is not the controller of the username
+        NotController,
+        /// The commitment can no longer be revealed
+        CommitmentExpired,
+        /// Username registration expired
+        NameExpired,
+        /// Renewal needs at least one period
+        ZeroRenewalPeriods,
+        /// No transfer of the username is pending
+        NoPendingTransfer,
+        /// Username is not listed for sale
+        NotListed,
+        /// Listing price is above what the buyer is willing to pay
+        PriceTooHigh,
+        /// More chain addresses or text records than `MaxRecords`
+        TooManyRecords,
+        /// Record longer than `MaxRecordLength`
+        RecordTooLong,
+        /// Same chain or text key set twice
+        DuplicateRecord,
+        /// Username has no resolver records
+        NoRecords,
+        /// Parent already has `MaxSubnames` subnames
+        SubnameLimitReached,
+        /// Username is not a subname of a registered name
+        NotSubname,
+        /// Subnames can't have subnames of their own
+        NestedSubname,
+        /// Parent does not sell subnames
+        SubnamesNotForSale,
+        /// Username already has a controller
+        ControllerAlreadyAssigned,
     }
 
     #[pallet::event]
@@ -107,21 +235,87 @@ pub mod pallet {
             owner: ZkAddressType,
         },
         NameQueuedForRegister {
-            hash_username: T::Hash,
-            hash_owner: T::Hash,
+            commitment: T::Hash,
+            controller: T::AccountId,
         },
         NameSetAsPrimary {
             owner: ZkAddressType,
             username: UserName,
         },
         RegisterCanceled {
-            hash_username: T::Hash,
-            hash_owner: T::Hash,
+            commitment: T::Hash,
+            refund: BalanceOf<T>,
         },
         RegisterRemoved {
             username: UserName,
             owner: ZkAddressType,
         },
+        CommitmentExpired {
+            commitment: T::Hash,
+            refund: BalanceOf<T>,
+        },
+        NameRenewed {
+            username: UserName,
+            expiry: T::BlockNumber,
+        },
+        NameReleased {
+            username: UserName,
+            owner: ZkAddressType,
+        },
+        TransferRequested {
+            username: UserName,
+            new_owner: ZkAddressType,
+            new_controller: T::AccountId,
+        },
+        NameTransferred {
+            username: UserName,
+            from: ZkAddressType,
+            to: ZkAddressType,
+        },
+        NameListed {
+            username: UserName,
+            price: BalanceOf<T>,
+        },
+        NameUnlisted {
+            username: UserName,
+        },
+        NameSold {
+            username: UserName,
+            buyer: T::AccountId,
+            price: BalanceOf<T>,
+        },
+        RecordsSet {
+            username: UserName,
+            deposit: BalanceOf<T>,
+        },
+        RecordsCleared {
+            username: UserName,
+        },
+        SubnameCreated {
+            username: UserName,
+            owner: ZkAddressType,
+            controller: T::AccountId,
+        },
+        SubnameRevoked {
+            username: UserName,
+            owner: ZkAddressType,
+        },
+        SubnamePriceSet {
+            parent: UserName,
+            price: Option<BalanceOf<T>>,
+        },
+        SubnameSold {
+            username: UserName,
+            buyer: T::AccountId,
+            price: BalanceOf<T>,
+        },
+        FeesSwept {
+            amount: BalanceOf<T>,
+        },
+        LegacyControllerAssigned {
+            username: UserName,
+            controller: T::AccountId,
+        },
     }
 
     /// All registered Names
@@ -130,11 +324,90 @@ pub mod pallet {
     pub type UsernameRecords<T: Config> =
         StorageMap<_, Twox64Concat, UserName, ZkAddressType, OptionQuery>;
 
-    /// Names pending to be registered with the given blocknumber(wait time) [username,(registrant,blocknumber)]
+    /// Commitments to names pending to be revealed [controller,hash(username,registrant,controller,salt),(blocknumber,deposit)]
     #[pallet::storage]
     #[pallet::getter(fn pending_register)]
-    pub type PendingRegister<T: Config> =
-        StorageMap<_, Twox64Concat, T::Hash, (T::Hash, T::BlockNumber), OptionQuery>;
+    pub type PendingRegister<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::Hash,
+        (T::BlockNumber, BalanceOf<T>),
+        OptionQuery,
+    >;
+
+    /// Raw key of the last commitment checked for expiry, sweeping resumes after it
+    #[pallet::storage]
+    pub type CommitmentSweepCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+    /// Block each registered name expires at, it is released once its grace period passed as well
+    #[pallet::storage]
+    #[pallet::getter(fn expiries)]
+    pub type Expiries<T: Config> =
+        StorageMap<_, Twox64Concat, UserName, T::BlockNumber, OptionQuery>;
+
+    /// Raw key of the last name checked for release, sweeping resumes after it
+    #[pallet::storage]
+    pub type NameSweepCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+    /// Account controlling each registered name, only it may set the name as primary or remove it.
+    /// Names registered before controllers were recorded have none until `ControlOrigin` assigns one.
+    #[pallet::storage]
+    #[pallet::getter(fn controllers)]
+    pub type Controllers<T: Config> =
+        StorageMap<_, Twox64Concat, UserName, T::AccountId, OptionQuery>;
+
+    /// Transfers waiting for the new controller to accept them [username,(new owner,new controller)]
+    #[pallet::storage]
+    #[pallet::getter(fn pending_transfers)]
+    pub type PendingTransfers<T: Config> =
+        StorageMap<_, Twox64Concat, UserName, (ZkAddressType, T::AccountId), OptionQuery>;
+
+    /// Names listed for sale by their controller at a fixed price in the native token
+    #[pallet::storage]
+    #[pallet::getter(fn listings)]
+    pub type Listings<T: Config> = StorageMap<_, Twox64Concat, UserName, BalanceOf<T>, OptionQuery>;
+
+    /// Resolver records of names [username,(records,depositor,deposit)]
+    #[pallet::storage]
+    #[pallet::getter(fn resolvers)]
+    pub type Resolvers<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        UserName,
+        (Records<T::AccountId>, T::AccountId, BalanceOf<T>),
+        OptionQuery,
+    >;
+
+    /// Subnames created under each top level name, they expire and are released with it
+    #[pallet::storage]
+    #[pallet::getter(fn subnames)]
+    pub type Subnames<T: Config> = StorageMap<_, Twox64Concat, UserName, Vec<UserName>, ValueQuery>;
+
+    /// Price at which anyone may claim a subname of a top level name, paid to its controller
+    #[pallet::storage]
+    #[pallet::getter(fn subname_prices)]
+    pub type SubnamePrices<T: Config> =
+        StorageMap<_, Twox64Concat, UserName, BalanceOf<T>, OptionQuery>;
+
+    /// Register deposits reserved for registered names [username,(depositor,deposit)]
+    #[pallet::storage]
+    #[pallet::getter(fn deposits)]
+    pub type Deposits<T: Config> =
+        StorageMap<_, Twox64Concat, UserName, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+    /// Register deposits of legacy names without a controller, held by the pallet account
+    /// until `assign_legacy_controller` reserves them for the assigned controller [username,deposit]
+    #[pallet::storage]
+    #[pallet::getter(fn legacy_deposits)]
+    pub type LegacyDeposits<T: Config> =
+        StorageMap<_, Twox64Concat, UserName, BalanceOf<T>, OptionQuery>;
+
+    /// Sum of `LegacyDeposits`, which `sweep_fees` keeps in the pallet account
+    #[pallet::storage]
+    #[pallet::getter(fn legacy_deposit_total)]
+    pub type LegacyDepositTotal<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     /// Primary Records, 1 AccountID may have only one primary name
     #[pallet::storage]
@@ -148,43 +421,44 @@ pub mod pallet {
     pub struct Pallet<T>(_);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+        fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            let used = Self::sweep_expired_commitments(remaining_weight);
+            used.saturating_add(Self::sweep_expired_names(
+                remaining_weight.saturating_sub(used),
+            ))
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Queue Username for Register if it has not been registered or queued yet
+        /// Queue a commitment to `hash(username, registrant, controller, salt)` for Register, reserving the register deposit
         #[pallet::call_index(0)]
         #[pallet::weight(T::WeightInfo::register())]
         #[transactional]
-        pub fn register(
-            origin: OriginFor<T>,
-            username: UserName,
-            registrant: ZkAddressType,
-        ) -> DispatchResult {
+        pub fn register(origin: OriginFor<T>, commitment: T::Hash) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
-            <T as pallet::Config>::Currency::transfer(
-                &who,
-                &Self::account_id(),
-                T::RegisterPrice::get(),
-                ExistenceRequirement::KeepAlive,
-            )?;
-
-            Self::do_register(&username, registrant)
+            Self::do_register(commitment, who)
         }
 
-        /// After Pending Register has passed its block wait time, finish regiser
+        /// After the commitment has passed its block wait time, reveal it to finish regiser
         #[pallet::call_index(1)]
-        #[pallet::weight(T::WeightInfo::accept_register())]
+        // Revealing may release an expired name along with its subnames
+        #[pallet::weight(
+            T::WeightInfo::accept_register()
+                .saturating_add(T::WeightInfo::remove_register(T::MaxSubnames::get()))
+        )]
         #[transactional]
         pub fn accept_register(
             origin: OriginFor<T>,
             username: UserName,
             registrant: ZkAddressType,
+            salt: Salt,
         ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
 
-            Self::do_accept_register(username, registrant)?;
+            Self::do_accept_register(username, registrant, salt, &who)?;
 
             Ok(())
         }
@@ -198,42 +472,274 @@ pub mod pallet {
             username: UserName,
             registrant: ZkAddressType,
         ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
 
-            Self::try_set_primary_name(username, registrant)?;
+            Self::try_set_primary_name(username, registrant, &who)?;
 
             Ok(())
         }
 
-        /// Cancel pending name for register
+        /// Cancel pending commitment for register, releasing its deposit
         #[pallet::call_index(3)]
         #[pallet::weight(T::WeightInfo::cancel_pending_register())]
         #[transactional]
         pub fn cancel_pending_register(
             origin: OriginFor<T>,
-            username: UserName,
-            registrant: ZkAddressType,
+            commitment: T::Hash,
         ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
 
-            Self::try_cancel_pending_register(username, registrant)?;
+            Self::try_cancel_pending_register(commitment, &who)?;
 
             Ok(())
         }
 
-        /// Remove Already Registered Name
+        /// Remove Already Registered Name, along with its subnames, releasing its deposit
         #[pallet::call_index(4)]
-        #[pallet::weight(T::WeightInfo::remove_register())]
+        #[pallet::weight(T::WeightInfo::remove_register(T::MaxSubnames::get()))]
         #[transactional]
         pub fn remove_register(
             origin: OriginFor<T>,
             username: UserName,
             registrant: ZkAddressType,
         ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
+
+            Self::try_remove_register(username, registrant, &who)?;
+
+            Ok(())
+        }
+
+        /// Extend a registered name by the given number of periods, paying for each of them.
+        /// Expired names can only be renewed by their controller during the grace period
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::renew())]
+        #[transactional]
+        pub fn renew(origin: OriginFor<T>, username: UserName, periods: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::try_renew(username, periods, &who)?;
+
+            Ok(())
+        }
+
+        /// Transfer an owned name to a new zk address. Handing the name to another controller
+        /// only takes effect once the new controller accepts it
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::transfer_name())]
+        #[transactional]
+        pub fn transfer_name(
+            origin: OriginFor<T>,
+            username: UserName,
+            new_owner: ZkAddressType,
+            new_controller: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::try_transfer_name(username, new_owner, new_controller, &who)?;
+
+            Ok(())
+        }
+
+        /// Accept a pending transfer as its new controller
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::accept_transfer())]
+        #[transactional]
+        pub fn accept_transfer(origin: OriginFor<T>, username: UserName) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::try_accept_transfer(username, &who)?;
+
+            Ok(())
+        }
+
+        /// List an owned name for sale at a fixed price, replacing any previous listing
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::list_name())]
+        #[transactional]
+        pub fn list_name(
+            origin: OriginFor<T>,
+            username: UserName,
+            price: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::try_list_name(username, price, &who)?;
+
+            Ok(())
+        }
+
+        /// Withdraw a name from sale
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::unlist_name())]
+        #[transactional]
+        pub fn unlist_name(origin: OriginFor<T>, username: UserName) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_controller(&username, &who)?;
+            ensure!(
+                Listings::<T>::take(&username).is_some(),
+                Error::<T>::NotListed
+            );
+
+            Self::deposit_event(Event::NameUnlisted { username });
+            Ok(())
+        }
+
+        /// Buy a listed name for the given zk address, paying its controller the listing price
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::buy_name())]
+        #[transactional]
+        pub fn buy_name(
+            origin: OriginFor<T>,
+            username: UserName,
+            new_owner: ZkAddressType,
+            max_price: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::try_buy_name(username, new_owner, max_price, who)?;
+
+            Ok(())
+        }
+
+        /// Replace the resolver records of an owned name, adjusting the reserved deposit
+        /// to their size
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::set_records())]
+        #[transactional]
+        pub fn set_records(
+            origin: OriginFor<T>,
+            username: UserName,
+            records: Records<T::AccountId>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::try_set_records(username, records, who)?;
+
+            Ok(())
+        }
+
+        /// Remove the resolver records of an owned name, releasing their deposit
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::clear_records())]
+        #[transactional]
+        pub fn clear_records(origin: OriginFor<T>, username: UserName) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_controller(&username, &who)?;
+            ensure!(Self::remove_records(&username), Error::<T>::NoRecords);
+
+            Self::deposit_event(Event::RecordsCleared { username });
+            Ok(())
+        }
+
+        /// Create `label.parent` for the given zk address, delegating it to the given
+        /// controller. The parent's controller can revoke it at any time
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::create_subname())]
+        #[transactional]
+        pub fn create_subname(
+            origin: OriginFor<T>,
+            parent: UserName,
+            label: UserName,
+            owner: ZkAddressType,
+            controller: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_controller(&parent, &who)?;
+            Self::do_create_subname(parent, label, owner, controller)?;
+
+            Ok(())
+        }
+
+        /// Remove a subname as the controller of its parent
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::revoke_subname())]
+        #[transactional]
+        pub fn revoke_subname(origin: OriginFor<T>, username: UserName) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::try_revoke_subname(username, &who)?;
+
+            Ok(())
+        }
+
+        /// Let anyone claim subnames of an owned name at the given price, or stop selling
+        /// them with `None`
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::set_subname_price())]
+        #[transactional]
+        pub fn set_subname_price(
+            origin: OriginFor<T>,
+            parent: UserName,
+            price: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::try_set_subname_price(parent, price, &who)?;
+
+            Ok(())
+        }
+
+        /// Claim `label.parent` for the given zk address, paying the parent's controller its
+        /// subname price
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::buy_subname())]
+        #[transactional]
+        pub fn buy_subname(
+            origin: OriginFor<T>,
+            parent: UserName,
+            label: UserName,
+            owner: ZkAddressType,
+            max_price: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::try_buy_subname(parent, label, owner, max_price, who)?;
+
+            Ok(())
+        }
+
+        /// Send the fees collected by the pallet account to the treasury, keeping back the
+        /// register deposits of legacy names
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::sweep_fees())]
+        #[transactional]
+        pub fn sweep_fees(origin: OriginFor<T>) -> DispatchResult {
+            T::ControlOrigin::ensure_origin(origin)?;
+
+            let account = Self::account_id();
+            let amount = <T as pallet::Config>::Currency::free_balance(&account)
+                .saturating_sub(<T as pallet::Config>::Currency::minimum_balance())
+                .saturating_sub(LegacyDepositTotal::<T>::get());
+            <T as pallet::Config>::Currency::transfer(
+                &account,
+                &T::TreasuryAccount::get(),
+                amount,
+                ExistenceRequirement::KeepAlive,
+            )?;
+
+            Self::deposit_event(Event::FeesSwept { amount });
+            Ok(())
+        }
 
-            Self::try_remove_register(username, registrant)?;
+        /// Set the controllers of names registered before controllers were recorded, the
+        /// accounts which paid for their registration as found in the chain history. The
+        /// register deposit the pallet account holds for each name is reserved for its controller
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::assign_legacy_controller(controllers.len() as u32))]
+        #[transactional]
+        pub fn assign_legacy_controller(
+            origin: OriginFor<T>,
+            controllers: BoundedVec<(UserName, T::AccountId), T::MaxLegacyControllers>,
+        ) -> DispatchResult {
+            T::ControlOrigin::ensure_origin(origin)?;
 
+            for (username, controller) in controllers {
+                Self::do_assign_legacy_controller(username, controller)?;
+            }
             Ok(())
         }
     }
@@ -246,70 +752,126 @@ impl<T: Config> Pallet<T> {
         T::PalletId::get().into_account_truncating()
     }
 
-    /// Queue username for regiser
-    fn do_register(username: &UserName, registrant: ZkAddressType) -> DispatchResult {
-        // Username checks
-        username_validation(username).ok_or(Error::<T>::InvalidUsernameFormat)?;
+    /// Commitment of `controller` to register `username` for `registrant`, as submitted to
+    /// `register`. Binding the controller keeps others from revealing a copied commitment.
+    pub fn commitment_of(
+        username: &UserName,
+        registrant: &ZkAddressType,
+        controller: &T::AccountId,
+        salt: &Salt,
+    ) -> T::Hash {
+        T::Hashing::hash_of(&(username, registrant, controller, salt))
+    }
 
-        let (hash_user, hash_address) = (
-            T::Hashing::hash_of(username),
-            T::Hashing::hash_of(&registrant),
+    /// Set the controller of a legacy name which has none yet, handing it the register deposit
+    /// held for the name
+    pub(crate) fn do_assign_legacy_controller(
+        username: UserName,
+        controller: T::AccountId,
+    ) -> DispatchResult {
+        ensure!(
+            UsernameRecords::<T>::contains_key(&username),
+            Error::<T>::NotRegistered
         );
-
-        // Check if already Pending Register
         ensure!(
-            !PendingRegister::<T>::contains_key(hash_user),
-            Error::<T>::AlreadyPendingRegister
+            !Controllers::<T>::contains_key(&username),
+            Error::<T>::ControllerAlreadyAssigned
         );
+        if let Some(deposit) = LegacyDeposits::<T>::take(&username) {
+            <T as pallet::Config>::Currency::transfer(
+                &Self::account_id(),
+                &controller,
+                deposit,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            <T as pallet::Config>::Currency::reserve(&controller, deposit)?;
+            LegacyDepositTotal::<T>::mutate(|total| *total = total.saturating_sub(deposit));
+            Deposits::<T>::insert(&username, (controller.clone(), deposit));
+        }
+        Controllers::<T>::insert(&username, &controller);
+
+        Self::deposit_event(Event::LegacyControllerAssigned {
+            username,
+            controller,
+        });
+        Ok(())
+    }
 
-        // Check if already registered
+    /// Queue commitment for regiser
+    fn do_register(commitment: T::Hash, controller: T::AccountId) -> DispatchResult {
+        // Check if already Pending Register, commitments of other accounts don't collide
         ensure!(
-            !UsernameRecords::<T>::contains_key(username),
-            Error::<T>::NameAlreadyRegistered
+            !PendingRegister::<T>::contains_key(&controller, commitment),
+            Error::<T>::AlreadyPendingRegister
         );
 
+        let deposit = T::RegisterPrice::get();
+        <T as pallet::Config>::Currency::reserve(&controller, deposit)?;
+
         PendingRegister::<T>::insert(
-            hash_user,
-            (
-                hash_address,
-                frame_system::Pallet::<T>::block_number()
-                    .saturating_add(T::RegisterWaitingPeriod::get()),
-            ),
+            &controller,
+            commitment,
+            (frame_system::Pallet::<T>::block_number(), deposit),
         );
 
         Self::deposit_event(Event::NameQueuedForRegister {
-            hash_username: hash_user,
-            hash_owner: hash_address,
+            commitment,
+            controller,
         });
         Ok(())
     }
 
-    /// Finish Register after block time has passed
-    fn do_accept_register(username: UserName, registrant: ZkAddressType) -> DispatchResult {
-        // Username checks
+    /// Finish Register by revealing the commitment after block time has passed
+    fn do_accept_register(
+        username: UserName,
+        registrant: ZkAddressType,
+        salt: Salt,
+        who: &T::AccountId,
+    ) -> DispatchResult {
+        // Username checks, subnames are only created through their parent
         username_validation(&username).ok_or(Error::<T>::InvalidUsernameFormat)?;
-
-        let (hash_user, hash_address) = (
-            T::Hashing::hash_of(&username),
-            T::Hashing::hash_of(&registrant),
+        ensure!(
+            Self::parent_of(&username).is_none(),
+            Error::<T>::InvalidUsernameFormat
         );
 
-        // check if block number has been passed
-        let pending_register_data =
-            PendingRegister::<T>::get(hash_user).ok_or(Error::<T>::UsernameNotFound)?;
+        let commitment = Self::commitment_of(&username, &registrant, who, &salt);
+        let (committed_at, deposit) =
+            PendingRegister::<T>::get(who, commitment).ok_or(Error::<T>::UsernameNotFound)?;
+        let controller = who.clone();
+
+        // check if block number has been passed, but the commitment is still fresh
+        let now = frame_system::Pallet::<T>::block_number();
         ensure!(
-            frame_system::Pallet::<T>::block_number() > pending_register_data.1,
+            now > committed_at.saturating_add(T::RegisterWaitingPeriod::get()),
             Error::<T>::RegisterTimeNotReached
         );
+        ensure!(
+            now <= committed_at.saturating_add(T::CommitmentLifetime::get()),
+            Error::<T>::CommitmentExpired
+        );
 
+        // Check if already registered by an earlier reveal, names past their grace period are free again
+        Self::release_if_expired(&username);
         ensure!(
-            pending_register_data.0 == hash_address,
-            Error::<T>::NotOwned
+            !UsernameRecords::<T>::contains_key(&username),
+            Error::<T>::NameAlreadyRegistered
         );
 
+        // Pay for the first period, the commitment's deposit stays reserved for the name
+        <T as pallet::Config>::Currency::transfer(
+            &controller,
+            &Self::account_id(),
+            Self::name_price(&username),
+            ExistenceRequirement::KeepAlive,
+        )?;
+
         // Move from pending into records
-        PendingRegister::<T>::remove(hash_user);
+        PendingRegister::<T>::remove(&controller, commitment);
         UsernameRecords::<T>::insert(&username, registrant);
+        Deposits::<T>::insert(&username, (controller.clone(), deposit));
+        Controllers::<T>::insert(&username, controller);
+        Expiries::<T>::insert(&username, now.saturating_add(T::RegistrationPeriod::get()));
 
         Self::deposit_event(Event::NameRegistered {
             username,
@@ -319,7 +881,11 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Set primary name if register and owned
-    fn try_set_primary_name(username: UserName, registrant: ZkAddressType) -> DispatchResult {
+    fn try_set_primary_name(
+        username: UserName,
+        registrant: ZkAddressType,
+        who: &T::AccountId,
+    ) -> DispatchResult {
         // Username checks
         username_validation(&username).ok_or(Error::<T>::InvalidUsernameFormat)?;
 
@@ -333,6 +899,8 @@ impl<T: Config> Pallet<T> {
             UsernameRecords::<T>::get(&username).unwrap() == registrant,
             Error::<T>::NotOwned
         );
+        Self::ensure_controller(&username, who)?;
+        ensure!(!Self::is_expired(&username), Error::<T>::NameExpired);
 
         // check if we already have a primary
         if PrimaryRecords::<T>::contains_key(registrant) {
@@ -350,74 +918,632 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    fn try_cancel_pending_register(
+    fn try_cancel_pending_register(commitment: T::Hash, who: &T::AccountId) -> DispatchResult {
+        let (_, deposit) =
+            PendingRegister::<T>::get(who, commitment).ok_or(Error::<T>::UsernameNotFound)?;
+
+        PendingRegister::<T>::remove(who, commitment);
+        <T as pallet::Config>::Currency::unreserve(who, deposit);
+
+        Self::deposit_event(Event::RegisterCanceled {
+            commitment,
+            refund: deposit,
+        });
+        Ok(())
+    }
+
+    fn try_remove_register(
         username: UserName,
         registrant: ZkAddressType,
+        who: &T::AccountId,
     ) -> DispatchResult {
         // Username checks
         username_validation(&username).ok_or(Error::<T>::InvalidUsernameFormat)?;
 
-        let (hash_user, hash_address) = (
-            T::Hashing::hash_of(&username),
-            T::Hashing::hash_of(&registrant),
+        ensure!(
+            UsernameRecords::<T>::contains_key(&username),
+            Error::<T>::NotRegistered
         );
 
-        let pending_register_data =
-            PendingRegister::<T>::get(hash_user).ok_or(Error::<T>::UsernameNotFound)?;
-
         ensure!(
-            pending_register_data.0 == hash_address,
+            UsernameRecords::<T>::get(&username).unwrap() == registrant,
             Error::<T>::NotOwned
         );
+        Self::ensure_controller(&username, who)?;
 
-        PendingRegister::<T>::remove(hash_user);
+        Self::remove_name(&username, registrant);
 
-        Self::deposit_event(Event::RegisterCanceled {
-            hash_username: hash_user,
-            hash_owner: hash_address,
+        Self::deposit_event(Event::RegisterRemoved {
+            username,
+            owner: registrant,
         });
         Ok(())
     }
 
-    fn try_remove_register(username: UserName, registrant: ZkAddressType) -> DispatchResult {
-        // Username checks
-        username_validation(&username).ok_or(Error::<T>::InvalidUsernameFormat)?;
+    fn try_renew(username: UserName, periods: u32, who: &T::AccountId) -> DispatchResult {
+        ensure!(periods > 0, Error::<T>::ZeroRenewalPeriods);
+        let expiry = Expiries::<T>::get(&username).ok_or(Error::<T>::NotRegistered)?;
+
+        // Expired names are reserved for their controller until the grace period passed
+        let now = frame_system::Pallet::<T>::block_number();
+        if now > expiry {
+            ensure!(
+                now <= expiry.saturating_add(T::GracePeriod::get()),
+                Error::<T>::NameExpired
+            );
+            Self::ensure_controller(&username, who)?;
+        }
+
+        <T as pallet::Config>::Currency::transfer(
+            who,
+            &Self::account_id(),
+            Self::name_price(&username).saturating_mul(periods.into()),
+            ExistenceRequirement::KeepAlive,
+        )?;
+
+        let expiry =
+            expiry.saturating_add(T::RegistrationPeriod::get().saturating_mul(periods.into()));
+        Expiries::<T>::insert(&username, expiry);
+
+        Self::deposit_event(Event::NameRenewed { username, expiry });
+        Ok(())
+    }
+
+    fn try_transfer_name(
+        username: UserName,
+        new_owner: ZkAddressType,
+        new_controller: T::AccountId,
+        who: &T::AccountId,
+    ) -> DispatchResult {
+        let owner = UsernameRecords::<T>::get(&username).ok_or(Error::<T>::NotRegistered)?;
+        Self::ensure_controller(&username, who)?;
+        ensure!(!Self::is_expired(&username), Error::<T>::NameExpired);
+
+        if new_controller == *who {
+            Self::do_transfer(&username, owner, new_owner, new_controller)?;
+        } else {
+            PendingTransfers::<T>::insert(&username, (new_owner, new_controller.clone()));
+            Self::deposit_event(Event::TransferRequested {
+                username,
+                new_owner,
+                new_controller,
+            });
+        }
+        Ok(())
+    }
+
+    fn try_accept_transfer(username: UserName, who: &T::AccountId) -> DispatchResult {
+        let (new_owner, new_controller) =
+            PendingTransfers::<T>::get(&username).ok_or(Error::<T>::NoPendingTransfer)?;
+        ensure!(*who == new_controller, Error::<T>::NotController);
+        ensure!(!Self::is_expired(&username), Error::<T>::NameExpired);
+        let owner = UsernameRecords::<T>::get(&username).ok_or(Error::<T>::NotRegistered)?;
+
+        Self::do_transfer(&username, owner, new_owner, new_controller)
+    }
+
+    fn try_list_name(
+        username: UserName,
+        price: BalanceOf<T>,
+        who: &T::AccountId,
+    ) -> DispatchResult {
+        ensure!(
+            UsernameRecords::<T>::contains_key(&username),
+            Error::<T>::NotRegistered
+        );
+        Self::ensure_controller(&username, who)?;
+        ensure!(!Self::is_expired(&username), Error::<T>::NameExpired);
+
+        Listings::<T>::insert(&username, price);
+
+        Self::deposit_event(Event::NameListed { username, price });
+        Ok(())
+    }
+
+    fn try_buy_name(
+        username: UserName,
+        new_owner: ZkAddressType,
+        max_price: BalanceOf<T>,
+        buyer: T::AccountId,
+    ) -> DispatchResult {
+        let price = Listings::<T>::get(&username).ok_or(Error::<T>::NotListed)?;
+        ensure!(price <= max_price, Error::<T>::PriceTooHigh);
+        ensure!(!Self::is_expired(&username), Error::<T>::NameExpired);
+        let owner = UsernameRecords::<T>::get(&username).ok_or(Error::<T>::NotRegistered)?;
+        let seller = Controllers::<T>::get(&username).ok_or(Error::<T>::NotRegistered)?;
+
+        <T as pallet::Config>::Currency::transfer(
+            &buyer,
+            &seller,
+            price,
+            ExistenceRequirement::KeepAlive,
+        )?;
+        Self::do_transfer(&username, owner, new_owner, buyer.clone())?;
+
+        Self::deposit_event(Event::NameSold {
+            username,
+            buyer,
+            price,
+        });
+        Ok(())
+    }
 
+    fn try_set_records(
+        username: UserName,
+        mut records: Records<T::AccountId>,
+        who: T::AccountId,
+    ) -> DispatchResult {
         ensure!(
             UsernameRecords::<T>::contains_key(&username),
             Error::<T>::NotRegistered
         );
+        Self::ensure_controller(&username, &who)?;
+        ensure!(!Self::is_expired(&username), Error::<T>::NameExpired);
 
+        // Bound the records before sorting them
+        let max_records = T::MaxRecords::get() as usize;
         ensure!(
-            UsernameRecords::<T>::get(&username).unwrap() == registrant,
-            Error::<T>::NotOwned
+            records.chain_addresses.len() <= max_records && records.texts.len() <= max_records,
+            Error::<T>::TooManyRecords
+        );
+        let max_length = T::MaxRecordLength::get() as usize;
+        ensure!(
+            records
+                .chain_addresses
+                .iter()
+                .all(|(_, address)| address.len() <= max_length)
+                && records
+                    .texts
+                    .iter()
+                    .all(|(key, value)| key.len() <= max_length && value.len() <= max_length),
+            Error::<T>::RecordTooLong
+        );
+        // Keep records canonical so each chain and text key resolves to one value
+        records.chain_addresses.sort_by_key(|(chain, _)| *chain);
+        records.texts.sort_by(|(a, _), (b, _)| a.cmp(b));
+        ensure!(
+            records.chain_addresses.windows(2).all(|w| w[0].0 != w[1].0)
+                && records.texts.windows(2).all(|w| w[0].0 != w[1].0),
+            Error::<T>::DuplicateRecord
         );
 
-        UsernameRecords::<T>::remove(&username);
+        let deposit = T::RecordDepositBase::get().saturating_add(
+            T::RecordDepositPerByte::get().saturating_mul((records.encoded_size() as u32).into()),
+        );
+        Self::remove_records(&username);
+        <T as pallet::Config>::Currency::reserve(&who, deposit)?;
+        Resolvers::<T>::insert(&username, (records, who, deposit));
 
-        // check if the name we are removing is a primary name to keep storage synced
-        if let Ok(primary_username) = PrimaryRecords::<T>::try_get(registrant) {
-            if primary_username == username {
-                PrimaryRecords::<T>::remove(registrant);
+        Self::deposit_event(Event::RecordsSet { username, deposit });
+        Ok(())
+    }
+
+    fn try_revoke_subname(username: UserName, who: &T::AccountId) -> DispatchResult {
+        let parent = Self::parent_of(&username).ok_or(Error::<T>::NotSubname)?;
+        Self::ensure_controller(&parent, who)?;
+        ensure!(
+            Subnames::<T>::get(&parent).contains(&username),
+            Error::<T>::NotSubname
+        );
+        let owner = UsernameRecords::<T>::get(&username).ok_or(Error::<T>::NotRegistered)?;
+
+        Self::remove_name(&username, owner);
+
+        Self::deposit_event(Event::SubnameRevoked { username, owner });
+        Ok(())
+    }
+
+    fn try_set_subname_price(
+        parent: UserName,
+        price: Option<BalanceOf<T>>,
+        who: &T::AccountId,
+    ) -> DispatchResult {
+        Self::ensure_controller(&parent, who)?;
+        ensure!(
+            Self::parent_of(&parent).is_none(),
+            Error::<T>::NestedSubname
+        );
+        ensure!(!Self::is_expired(&parent), Error::<T>::NameExpired);
+
+        SubnamePrices::<T>::set(&parent, price);
+
+        Self::deposit_event(Event::SubnamePriceSet { parent, price });
+        Ok(())
+    }
+
+    fn try_buy_subname(
+        parent: UserName,
+        label: UserName,
+        owner: ZkAddressType,
+        max_price: BalanceOf<T>,
+        buyer: T::AccountId,
+    ) -> DispatchResult {
+        let price = SubnamePrices::<T>::get(&parent).ok_or(Error::<T>::SubnamesNotForSale)?;
+        ensure!(price <= max_price, Error::<T>::PriceTooHigh);
+        let seller = Controllers::<T>::get(&parent).ok_or(Error::<T>::NotRegistered)?;
+
+        <T as pallet::Config>::Currency::transfer(
+            &buyer,
+            &seller,
+            price,
+            ExistenceRequirement::KeepAlive,
+        )?;
+        let username = Self::do_create_subname(parent, label, owner, buyer.clone())?;
+
+        Self::deposit_event(Event::SubnameSold {
+            username,
+            buyer,
+            price,
+        });
+        Ok(())
+    }
+
+    /// Register `label.parent` under an unexpired top level name, returning the subname
+    fn do_create_subname(
+        parent: UserName,
+        label: UserName,
+        owner: ZkAddressType,
+        controller: T::AccountId,
+    ) -> Result<UserName, DispatchError> {
+        ensure!(
+            Self::parent_of(&parent).is_none(),
+            Error::<T>::NestedSubname
+        );
+        ensure!(
+            UsernameRecords::<T>::contains_key(&parent),
+            Error::<T>::NotRegistered
+        );
+        ensure!(!Self::is_expired(&parent), Error::<T>::NameExpired);
+
+        let mut username = label;
+        username.push(SUBNAME_SEPARATOR);
+        username.extend_from_slice(&parent);
+        username_validation(&username).ok_or(Error::<T>::InvalidUsernameFormat)?;
+        ensure!(
+            !UsernameRecords::<T>::contains_key(&username),
+            Error::<T>::NameAlreadyRegistered
+        );
+
+        Subnames::<T>::try_mutate(&parent, |subnames| -> DispatchResult {
+            ensure!(
+                (subnames.len() as u32) < T::MaxSubnames::get(),
+                Error::<T>::SubnameLimitReached
+            );
+            subnames.push(username.clone());
+            Ok(())
+        })?;
+        UsernameRecords::<T>::insert(&username, owner);
+        Controllers::<T>::insert(&username, controller.clone());
+
+        Self::deposit_event(Event::SubnameCreated {
+            username: username.clone(),
+            owner,
+            controller,
+        });
+        Ok(username)
+    }
+
+    /// Move a registered name to a new owner and controller, dropping any pending transfer,
+    /// listing, subname price or resolver records. The new controller takes over the
+    /// register deposit. The name stops being the previous owner's primary and becomes
+    /// the new owner's primary if they have none yet
+    fn do_transfer(
+        username: &UserName,
+        old_owner: ZkAddressType,
+        new_owner: ZkAddressType,
+        new_controller: T::AccountId,
+    ) -> DispatchResult {
+        if let Some((depositor, deposit)) = Deposits::<T>::get(username) {
+            if depositor != new_controller {
+                <T as pallet::Config>::Currency::unreserve(&depositor, deposit);
+                <T as pallet::Config>::Currency::reserve(&new_controller, deposit)?;
+                Deposits::<T>::insert(username, (new_controller.clone(), deposit));
             }
         }
+        UsernameRecords::<T>::insert(username, new_owner);
+        Controllers::<T>::insert(username, new_controller);
+        PendingTransfers::<T>::remove(username);
+        Listings::<T>::remove(username);
+        SubnamePrices::<T>::remove(username);
+        Self::remove_records(username);
+
+        if PrimaryRecords::<T>::get(old_owner).as_ref() == Some(username) {
+            PrimaryRecords::<T>::remove(old_owner);
+        }
+        if !PrimaryRecords::<T>::contains_key(new_owner) {
+            PrimaryRecords::<T>::insert(new_owner, username);
+        }
 
-        Self::deposit_event(Event::RegisterRemoved {
-            username,
-            owner: registrant,
+        Self::deposit_event(Event::NameTransferred {
+            username: username.clone(),
+            from: old_owner,
+            to: new_owner,
         });
         Ok(())
     }
+
+    /// Price of one registration period for the username
+    pub fn name_price(username: &UserName) -> BalanceOf<T> {
+        T::NamePrice::convert(username.len() as u32)
+    }
+
+    /// Owner of the username, unless its registration expired
+    pub fn owner_of(username: &UserName) -> Option<ZkAddressType> {
+        if Self::is_expired(username) {
+            return None;
+        }
+        UsernameRecords::<T>::get(username)
+    }
+
+    /// Resolver records of a registered name, empty if none were set. `None` once its
+    /// registration expired
+    pub fn resolve(username: &UserName) -> Option<Records<T::AccountId>> {
+        Self::owner_of(username)?;
+        Some(
+            Resolvers::<T>::get(username)
+                .map(|(records, _, _)| records)
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Primary name of the zk address, unless its registration expired
+    pub fn reverse(owner: &ZkAddressType) -> Option<UserName> {
+        PrimaryRecords::<T>::get(owner).filter(|username| Self::owner_of(username) == Some(*owner))
+    }
+
+    /// Parent of a subname, `None` for top level names
+    pub fn parent_of(username: &UserName) -> Option<UserName> {
+        username
+            .iter()
+            .position(|c| *c == SUBNAME_SEPARATOR)
+            .map(|i| username[i + 1..].to_vec())
+    }
+
+    /// Block a registered username expires at, subnames expire with their parent
+    fn expiry_of(username: &UserName) -> Option<T::BlockNumber> {
+        Expiries::<T>::get(username)
+            .or_else(|| Self::parent_of(username).and_then(Expiries::<T>::get))
+    }
+
+    /// Whether a registered username is past its expiry
+    fn is_expired(username: &UserName) -> bool {
+        Self::expiry_of(username).map_or(false, |expiry| {
+            frame_system::Pallet::<T>::block_number() > expiry
+        })
+    }
+
+    /// Whether a name expiring at the given block is past its grace period
+    fn is_releasable(expiry: T::BlockNumber) -> bool {
+        frame_system::Pallet::<T>::block_number() > expiry.saturating_add(T::GracePeriod::get())
+    }
+
+    /// Release the username if its grace period passed, returning whether it was released
+    fn release_if_expired(username: &UserName) -> bool {
+        match (
+            Expiries::<T>::get(username),
+            UsernameRecords::<T>::get(username),
+        ) {
+            (Some(expiry), Some(owner)) if Self::is_releasable(expiry) => {
+                Self::remove_name(username, owner);
+                Self::deposit_event(Event::NameReleased {
+                    username: username.clone(),
+                    owner,
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove a registered name along with its subnames. Removed subnames leave their
+    /// parent's list
+    fn remove_name(username: &UserName, owner: ZkAddressType) {
+        Self::clear_name(username, owner);
+
+        match Self::parent_of(username) {
+            Some(parent) => Subnames::<T>::mutate_exists(&parent, |subnames| {
+                if let Some(names) = subnames {
+                    names.retain(|name| name != username);
+                    if names.is_empty() {
+                        *subnames = None;
+                    }
+                }
+            }),
+            None => {
+                SubnamePrices::<T>::remove(username);
+                for subname in Subnames::<T>::take(username) {
+                    if let Some(owner) = UsernameRecords::<T>::get(&subname) {
+                        Self::clear_name(&subname, owner);
+                        Self::deposit_event(Event::SubnameRevoked {
+                            username: subname,
+                            owner,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Remove a registered name's controller, expiry, pending transfer, listing, resolver
+    /// records and primary record, releasing its deposits. The deposit of a legacy name
+    /// without a controller is kept as a fee.
+    fn clear_name(username: &UserName, owner: ZkAddressType) {
+        if let Some((depositor, deposit)) = Deposits::<T>::take(username) {
+            <T as pallet::Config>::Currency::unreserve(&depositor, deposit);
+        } else if Self::parent_of(username).is_none() {
+            if let Some(deposit) = LegacyDeposits::<T>::take(username) {
+                LegacyDepositTotal::<T>::mutate(|total| *total = total.saturating_sub(deposit));
+            }
+        }
+        UsernameRecords::<T>::remove(username);
+        Controllers::<T>::remove(username);
+        Expiries::<T>::remove(username);
+        PendingTransfers::<T>::remove(username);
+        Listings::<T>::remove(username);
+        Self::remove_records(username);
+
+        // check if the name we are removing is a primary name to keep storage synced
+        if let Ok(primary_username) = PrimaryRecords::<T>::try_get(owner) {
+            if primary_username == *username {
+                PrimaryRecords::<T>::remove(owner);
+            }
+        }
+    }
+
+    /// Remove the resolver records of a name and release their deposit, returning whether
+    /// there were any
+    fn remove_records(username: &UserName) -> bool {
+        match Resolvers::<T>::take(username) {
+            Some((_, depositor, deposit)) => {
+                <T as pallet::Config>::Currency::unreserve(&depositor, deposit);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Release the `CommitmentRefund` share of an expired commitment's deposit to its
+    /// controller, moving the rest to the pallet account as a fee
+    fn forfeit_commitment(
+        controller: &T::AccountId,
+        deposit: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let refund = T::CommitmentRefund::get() * deposit;
+        let fee = deposit.saturating_sub(refund);
+        if !fee.is_zero() {
+            <T as pallet::Config>::Currency::repatriate_reserved(
+                controller,
+                &Self::account_id(),
+                fee,
+                BalanceStatus::Free,
+            )?;
+        }
+        <T as pallet::Config>::Currency::unreserve(controller, refund);
+        Ok(refund)
+    }
+
+    /// Remove commitments past their `CommitmentLifetime` within the given weight,
+    /// resuming from where the previous sweep stopped
+    pub(crate) fn sweep_expired_commitments(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        // Each commitment is read, expired ones are removed and their deposit released
+        let (read, remove) = (db_weight.reads(1), db_weight.reads_writes(2, 3));
+        let mut used = db_weight.reads_writes(2, 1);
+        if remaining_weight.any_lt(used.saturating_add(read).saturating_add(remove)) {
+            return Weight::zero();
+        }
+
+        let now = frame_system::Pallet::<T>::block_number();
+        let lifetime = T::CommitmentLifetime::get();
+        let mut iter = match CommitmentSweepCursor::<T>::take() {
+            Some(cursor) => PendingRegister::<T>::iter_from(cursor),
+            None => PendingRegister::<T>::iter(),
+        };
+        let mut expired = Vec::new();
+        let mut finished = false;
+        while !remaining_weight.any_lt(used.saturating_add(read).saturating_add(remove)) {
+            used = used.saturating_add(read);
+            match iter.next() {
+                Some((controller, commitment, (committed_at, deposit))) => {
+                    if now > committed_at.saturating_add(lifetime) {
+                        used = used.saturating_add(remove);
+                        expired.push((commitment, controller, deposit));
+                    }
+                }
+                None => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+        if !finished {
+            CommitmentSweepCursor::<T>::put(iter.last_raw_key().to_vec());
+        }
+
+        for (commitment, controller, deposit) in expired {
+            match Self::forfeit_commitment(&controller, deposit) {
+                Ok(refund) => {
+                    PendingRegister::<T>::remove(&controller, commitment);
+                    Self::deposit_event(Event::CommitmentExpired { commitment, refund });
+                }
+                Err(e) => log::warn!(
+                    target: "name-service",
+                    "Failed to refund expired commitment {:?}: {:?}",
+                    commitment,
+                    e
+                ),
+            }
+        }
+        used
+    }
+
+    /// Release names past their grace period within the given weight, resuming from
+    /// where the previous sweep stopped
+    pub(crate) fn sweep_expired_names(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        // Each expiry is read, released names are removed along with their primary record,
+        // resolver records and subnames, releasing their deposits
+        let read = db_weight.reads(1);
+        let release = db_weight.reads_writes(9, 14).saturating_add(
+            db_weight
+                .reads_writes(3, 7)
+                .saturating_mul(T::MaxSubnames::get().into()),
+        );
+        let mut used = db_weight.reads_writes(2, 1);
+        if remaining_weight.any_lt(used.saturating_add(read).saturating_add(release)) {
+            return Weight::zero();
+        }
+
+        let mut iter = match NameSweepCursor::<T>::take() {
+            Some(cursor) => Expiries::<T>::iter_from(cursor),
+            None => Expiries::<T>::iter(),
+        };
+        let mut releasable = Vec::new();
+        let mut finished = false;
+        while !remaining_weight.any_lt(used.saturating_add(read).saturating_add(release)) {
+            used = used.saturating_add(read);
+            match iter.next() {
+                Some((username, expiry)) => {
+                    if Self::is_releasable(expiry) {
+                        used = used.saturating_add(release);
+                        releasable.push(username);
+                    }
+                }
+                None => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+        if !finished {
+            NameSweepCursor::<T>::put(iter.last_raw_key().to_vec());
+        }
+
+        for username in releasable {
+            Self::release_if_expired(&username);
+        }
+        used
+    }
+
+    /// Check the account controls a registered name
+    fn ensure_controller(username: &UserName, who: &T::AccountId) -> DispatchResult {
+        ensure!(
+            Controllers::<T>::get(username).as_ref() == Some(who),
+            Error::<T>::NotController
+        );
+        Ok(())
+    }
 }
 
-/// username validation
+/// username validation, a name is a top level label optionally preceded by a subname label
 fn username_validation(username: &Vec<u8>) -> Option<()> {
     if !(NAME_MIN_LEN..=NAME_MAX_LEN).contains(&username.len()) {
         return None;
     }
 
-    let username_format: Matcher0<_> = regex!(br"[a-zA-Z][-a-zA-Z0-9\._]*[a-zA-Z0-9]");
-    if username_format.is_match(username.as_slice()) {
+    let label_format: Matcher0<_> = regex!(br"[a-zA-Z][-a-zA-Z0-9_]*[a-zA-Z0-9]");
+    let labels: Vec<&[u8]> = username.split(|c| *c == SUBNAME_SEPARATOR).collect();
+    if labels.len() <= 2 && labels.iter().all(|label| label_format.is_match(label)) {
         return Some(());
     }
     None
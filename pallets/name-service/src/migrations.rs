@@ -0,0 +1,412 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations for the name service pallet.
+
+use super::*;
+use core::marker::PhantomData;
+use frame_support::{
+    storage_alias,
+    traits::{
+        Currency, ExistenceRequirement, Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion,
+    },
+    Twox64Concat,
+};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+/// `PendingRegister` before commit-reveal registration [hash(username),(hash(registrant),blocknumber)]
+#[storage_alias]
+type PendingRegisterV2<T: Config> = StorageMap<
+    Pallet<T>,
+    Twox64Concat,
+    <T as frame_system::Config>::Hash,
+    (
+        <T as frame_system::Config>::Hash,
+        <T as frame_system::Config>::BlockNumber,
+    ),
+>;
+
+/// Account which queued each pending name before commit-reveal registration
+#[storage_alias]
+type PendingControllers<T: Config> = StorageMap<
+    Pallet<T>,
+    Twox64Concat,
+    <T as frame_system::Config>::Hash,
+    <T as frame_system::Config>::AccountId,
+>;
+
+/// Storage migration introducing controllers: names registered before controllers were
+/// recorded have no known registering account, their zk address isn't one. Each name in `L`
+/// gets the account listed for it, as found to have paid for its registration in the chain
+/// history. Names missing from `L` are left without a controller until `ControlOrigin`
+/// assigns one with `assign_legacy_controller`.
+pub struct AssignControllers<T, L>(PhantomData<(T, L)>);
+
+impl<T, L> OnRuntimeUpgrade for AssignControllers<T, L>
+where
+    T: Config,
+    L: Get<Vec<(UserName, T::AccountId)>>,
+{
+    fn on_runtime_upgrade() -> Weight {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version < 2 {
+            let controllers = L::get();
+            let listed = controllers.len() as u64;
+            let mut assigned: u64 = 0;
+            for (username, controller) in controllers {
+                match Pallet::<T>::do_assign_legacy_controller(username.clone(), controller) {
+                    Ok(()) => assigned += 1,
+                    Err(e) => log::warn!(
+                        target: "name-service",
+                        "Failed to assign the controller of {:?}: {:?}",
+                        username,
+                        e
+                    ),
+                }
+            }
+            let names = UsernameRecords::<T>::iter_keys().count() as u64;
+            log::info!(
+                target: "name-service",
+                "Assigned {} controllers, {} registered names are left for `ControlOrigin` to assign a controller.",
+                assigned,
+                names.saturating_sub(assigned)
+            );
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get()
+                .reads(listed * 2 + names + 1)
+                .saturating_add(T::DbWeight::get().writes(assigned * 2 + 1))
+        } else {
+            log::info!("✅ no controller migration for pallet-name-service.");
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version >= 2 {
+            return Err(DispatchError::Other(
+                "Storage version is >= 2, the migration won't be executed.",
+            ));
+        }
+        Ok(Vec::new())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version < 2 {
+            return Err(DispatchError::Other(
+                "Storage version is < 2, the migration was not executed.",
+            ));
+        }
+        let unassigned = L::get().into_iter().any(|(username, controller)| {
+            UsernameRecords::<T>::contains_key(&username)
+                && Controllers::<T>::get(&username) != Some(controller)
+        });
+        if unassigned {
+            return Err(DispatchError::Other(
+                "Some listed legacy names were not assigned their controller.",
+            ));
+        }
+        log::info!("✅ Storage migration for pallet-name-service has been executed successfully.");
+        Ok(())
+    }
+}
+
+/// Storage migration to commit-reveal registration: pending registrations keyed by the
+/// plain username hash can't be revealed anymore, so they are dropped and their
+/// `RegisterPrice` is refunded to the account which queued them.
+///
+/// Registrations queued before `PendingControllers` recorded the queuing account are
+/// forfeited: they are dropped without a refund and their `RegisterPrice` stays in the
+/// pallet account as a fee.
+pub struct CommitReveal<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for CommitReveal<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version == 2 {
+            log::info!(target: "name-service", "Start to drop pending registrations for commit-reveal.");
+            let price = T::RegisterPrice::get();
+            let mut pending: u64 = 0;
+            let mut refunded: u64 = 0;
+            for (hash_user, _) in PendingRegisterV2::<T>::drain() {
+                pending += 1;
+                if let Some(controller) = PendingControllers::<T>::take(hash_user) {
+                    refunded += 1;
+                    if let Err(e) = <T as Config>::Currency::transfer(
+                        &Pallet::<T>::account_id(),
+                        &controller,
+                        price,
+                        ExistenceRequirement::KeepAlive,
+                    ) {
+                        log::warn!(
+                            target: "name-service",
+                            "Failed to refund pending registration of {:?}: {:?}",
+                            controller,
+                            e
+                        );
+                    }
+                }
+            }
+            StorageVersion::new(3).put::<Pallet<T>>();
+            T::DbWeight::get()
+                .reads(pending * 2 + refunded * 2 + 1)
+                .saturating_add(T::DbWeight::get().writes(pending * 2 + refunded * 2 + 1))
+        } else {
+            log::info!("✅ no commit-reveal migration for pallet-name-service.");
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version != 2 {
+            return Err(DispatchError::Other(
+                "Storage version is not 2, the migration won't be executed.",
+            ));
+        }
+        Ok(Vec::new())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version < 3 {
+            return Err(DispatchError::Other(
+                "Storage version is < 3, the migration was not executed.",
+            ));
+        }
+        if PendingRegister::<T>::iter_keys().next().is_some() {
+            return Err(DispatchError::Other(
+                "Some pending registrations were not dropped.",
+            ));
+        }
+        log::info!("✅ Storage migration for pallet-name-service has been executed successfully.");
+        Ok(())
+    }
+}
+
+/// Storage migration adding expiries to registered names: existing names are registered
+/// for one `RegistrationPeriod` from the upgrade, after which they need to be renewed.
+pub struct NameExpiries<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for NameExpiries<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version == 3 {
+            log::info!(target: "name-service", "Start to add expiries to registered names.");
+            let expiry = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::RegistrationPeriod::get());
+            let mut names: u64 = 0;
+            for username in UsernameRecords::<T>::iter_keys() {
+                names += 1;
+                Expiries::<T>::insert(username, expiry);
+            }
+            StorageVersion::new(4).put::<Pallet<T>>();
+            T::DbWeight::get()
+                .reads(names + 1)
+                .saturating_add(T::DbWeight::get().writes(names + 1))
+        } else {
+            log::info!("✅ no name expiry migration for pallet-name-service.");
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version != 3 {
+            return Err(DispatchError::Other(
+                "Storage version is not 3, the migration won't be executed.",
+            ));
+        }
+        Ok((UsernameRecords::<T>::iter_keys().count() as u32).encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version < 4 {
+            return Err(DispatchError::Other(
+                "Storage version is < 4, the migration was not executed.",
+            ));
+        }
+        let names: u32 = Decode::decode(&mut &state[..])
+            .map_err(|_| DispatchError::Other("Failed to decode the name count."))?;
+        if names != Expiries::<T>::iter_keys().count() as u32 {
+            return Err(DispatchError::Other(
+                "Some registered names failed to migrate.",
+            ));
+        }
+        log::info!("✅ Storage migration for pallet-name-service has been executed successfully.");
+        Ok(())
+    }
+}
+
+/// Storage migration to reserved register deposits: the `RegisterPrice` collected by the
+/// pallet account for each registered name and pending commitment is handed back to its
+/// controller and reserved as the deposit, which is released again when the name is removed
+/// or the commitment canceled.
+///
+/// Legacy names without a controller keep their fee in the pallet account as a `LegacyDeposits`
+/// entry, which `assign_legacy_controller` reserves for the controller it assigns and
+/// `sweep_fees` keeps back until then.
+///
+/// Deposits the pallet account can't pay back, legacy ones included, are skipped, leaving the
+/// name without one, and so are names controlled by the pallet account itself, whose fee would
+/// never leave it and stay reserved where no one can release it.
+pub struct ReservedDeposits<T>(PhantomData<T>);
+
+impl<T: Config> ReservedDeposits<T> {
+    /// Whether the pallet account can pay out `deposit` on top of the legacy deposits it holds
+    fn can_pay(deposit: BalanceOf<T>, held: BalanceOf<T>) -> bool {
+        <T as Config>::Currency::free_balance(&Pallet::<T>::account_id())
+            .saturating_sub(<T as Config>::Currency::minimum_balance())
+            .saturating_sub(held)
+            >= deposit
+    }
+
+    /// Move `deposit` from the pallet account to the controller's reserved balance, leaving
+    /// the `held` legacy deposits in place
+    fn reserve_from_pallet(
+        controller: &T::AccountId,
+        deposit: BalanceOf<T>,
+        held: BalanceOf<T>,
+    ) -> bool {
+        if *controller == Pallet::<T>::account_id() {
+            log::warn!(
+                target: "name-service",
+                "Skipped the register deposit controlled by the pallet account"
+            );
+            return false;
+        }
+        if !Self::can_pay(deposit, held) {
+            log::warn!(
+                target: "name-service",
+                "Skipped the register deposit of {:?} the pallet account can't pay back",
+                controller
+            );
+            return false;
+        }
+        match <T as Config>::Currency::transfer(
+            &Pallet::<T>::account_id(),
+            controller,
+            deposit,
+            ExistenceRequirement::KeepAlive,
+        )
+        .and_then(|_| <T as Config>::Currency::reserve(controller, deposit))
+        {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!(
+                    target: "name-service",
+                    "Failed to reserve the register deposit of {:?}: {:?}",
+                    controller,
+                    e
+                );
+                false
+            }
+        }
+    }
+}
+
+impl<T: Config> OnRuntimeUpgrade for ReservedDeposits<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version == 4 {
+            log::info!(target: "name-service", "Start to reserve register deposits.");
+            let price = T::RegisterPrice::get();
+            let mut reads: u64 = 1;
+            let mut writes: u64 = 1;
+            let mut legacy_total = BalanceOf::<T>::zero();
+            for (controller, _, (_, deposit)) in PendingRegister::<T>::iter() {
+                reads += 1;
+                if Self::reserve_from_pallet(&controller, deposit, legacy_total) {
+                    reads += 2;
+                    writes += 2;
+                }
+            }
+            // Subnames were never charged, only names with their own expiry are registrations
+            for username in Expiries::<T>::iter_keys() {
+                reads += 2;
+                match Controllers::<T>::get(&username) {
+                    Some(controller) => {
+                        if Self::reserve_from_pallet(&controller, price, legacy_total) {
+                            reads += 2;
+                            writes += 3;
+                            Deposits::<T>::insert(username, (controller, price));
+                        }
+                    }
+                    None => {
+                        reads += 1;
+                        if Self::can_pay(price, legacy_total) {
+                            writes += 1;
+                            legacy_total = legacy_total.saturating_add(price);
+                            LegacyDeposits::<T>::insert(username, price);
+                        }
+                    }
+                }
+            }
+            LegacyDepositTotal::<T>::put(legacy_total);
+            writes += 1;
+            StorageVersion::new(5).put::<Pallet<T>>();
+            T::DbWeight::get()
+                .reads(reads)
+                .saturating_add(T::DbWeight::get().writes(writes))
+        } else {
+            log::info!("✅ no register deposit migration for pallet-name-service.");
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version != 4 {
+            return Err(DispatchError::Other(
+                "Storage version is not 4, the migration won't be executed.",
+            ));
+        }
+        Ok(Vec::new())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+        let storage_version = Pallet::<T>::on_chain_storage_version();
+        if storage_version < 5 {
+            return Err(DispatchError::Other(
+                "Storage version is < 5, the migration was not executed.",
+            ));
+        }
+        let unreserved = Expiries::<T>::iter_keys()
+            .count()
+            .saturating_sub(Deposits::<T>::iter_keys().count())
+            .saturating_sub(LegacyDeposits::<T>::iter_keys().count());
+        if unreserved != 0 {
+            log::warn!(
+                target: "name-service",
+                "{} registered names are left without a register deposit.",
+                unreserved
+            );
+        }
+        log::info!("✅ Storage migration for pallet-name-service has been executed successfully.");
+        Ok(())
+    }
+}
@@ -22,6 +22,7 @@ use super::*;
 use frame_support::{
     construct_runtime, parameter_types,
     traits::{ConstU128, ConstU32, Everything},
+    weights::constants::RocksDbWeight,
     PalletId,
 };
 use manta_primitives::{
@@ -58,7 +59,7 @@ impl frame_system::Config for Runtime {
     type AccountData = pallet_balances::AccountData<Balance>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type BaseCallFilter = Everything;
     type SystemWeightInfo = ();
     type SS58Prefix = ();
@@ -90,13 +91,40 @@ impl pallet_balances::Config for Runtime {
 
 parameter_types! {
     pub const NameServicePalletId: PalletId = NAME_SERVICE_PALLET_ID;
+    pub const CommitmentRefund: Percent = Percent::from_percent(50);
+    pub const TreasuryAccount: AccountId32 = AccountId32::new([9u8; 32]);
+}
+
+/// Three letter names cost three times as much per period, four letter names twice as much.
+pub struct NamePrice;
+impl Convert<u32, Balance> for NamePrice {
+    fn convert(len: u32) -> Balance {
+        match len {
+            0..=3 => 300,
+            4 => 200,
+            _ => 100,
+        }
+    }
 }
 
 impl Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type Currency = Balances;
+    type ControlOrigin = frame_system::EnsureRoot<AccountId32>;
+    type TreasuryAccount = TreasuryAccount;
     type RegisterWaitingPeriod = ConstU32<2>;
-    type RegisterPrice = ConstU128<0>;
+    type RegisterPrice = ConstU128<100>;
+    type CommitmentLifetime = ConstU32<10>;
+    type CommitmentRefund = CommitmentRefund;
+    type RegistrationPeriod = ConstU32<100>;
+    type GracePeriod = ConstU32<20>;
+    type NamePrice = NamePrice;
+    type RecordDepositBase = ConstU128<10>;
+    type RecordDepositPerByte = ConstU128<1>;
+    type MaxRecords = ConstU32<2>;
+    type MaxRecordLength = ConstU32<16>;
+    type MaxSubnames = ConstU32<2>;
+    type MaxLegacyControllers = ConstU32<2>;
     type PalletId = NameServicePalletId;
     type WeightInfo = ();
 }
@@ -0,0 +1,103 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Name Service RPC Interfaces
+
+use crate::{runtime::NameServiceApi, Records, ZkAddressType};
+use codec::Codec;
+use core::marker::PhantomData;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block;
+use sp_std::sync::Arc;
+
+pub const NAME_SERVICE_ERROR: i32 = 778;
+
+#[rpc(server)]
+pub trait NameServiceRpc<AccountId>
+where
+    AccountId: Send + Sync + 'static,
+{
+    #[method(name = "nameService_resolve", blocking)]
+    fn resolve(&self, username: String) -> RpcResult<Option<Records<AccountId>>>;
+
+    #[method(name = "nameService_reverse", blocking)]
+    fn reverse(&self, owner: ZkAddressType) -> RpcResult<Option<String>>;
+}
+
+/// Name Service RPC API Implementation
+pub struct NameService<B, C> {
+    /// Client
+    client: Arc<C>,
+
+    /// Type Parameter Marker
+    __: PhantomData<B>,
+}
+
+impl<B, C> NameService<B, C> {
+    /// Builds a new [`NameService`] RPC API implementation.
+    #[inline]
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            __: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<B, C, AccountId> NameServiceRpcServer<AccountId> for NameService<B, C>
+where
+    B: Block,
+    C: 'static + ProvideRuntimeApi<B> + HeaderBackend<B>,
+    C::Api: NameServiceApi<B, AccountId>,
+    AccountId: Codec + Send + Sync + 'static,
+{
+    #[inline]
+    fn resolve(&self, username: String) -> RpcResult<Option<Records<AccountId>>> {
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+        api.resolve(at, username.into_bytes()).map_err(|err| {
+            CallError::Custom(ErrorObject::owned(
+                NAME_SERVICE_ERROR,
+                "Unable to resolve name",
+                Some(format!("{err:?}")),
+            ))
+            .into()
+        })
+    }
+
+    #[inline]
+    fn reverse(&self, owner: ZkAddressType) -> RpcResult<Option<String>> {
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+        api.reverse(at, owner)
+            .map(|username| username.map(|name| String::from_utf8_lossy(&name).into_owned()))
+            .map_err(|err| {
+                CallError::Custom(ErrorObject::owned(
+                    NAME_SERVICE_ERROR,
+                    "Unable to look up primary name",
+                    Some(format!("{err:?}")),
+                ))
+                .into()
+            })
+    }
+}
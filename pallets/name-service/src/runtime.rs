@@ -0,0 +1,31 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Name Service Runtime APIs
+
+use crate::{Records, UserName, ZkAddressType};
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// Resolution of registered names, both return `None` for unregistered or expired names
+    pub trait NameServiceApi<AccountId> where
+        AccountId: Codec,
+    {
+        fn resolve(username: UserName) -> Option<Records<AccountId>>;
+        fn reverse(owner: ZkAddressType) -> Option<UserName>;
+    }
+}
@@ -20,10 +20,25 @@
 
 use super::*;
 use crate::mock::{NameService, Runtime, RuntimeOrigin as MockOrigin, *};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+};
 
 pub const ALICE: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([0u8; 32]);
 pub const BOB: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([1u8; 32]);
+pub const CHARLIE: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([2u8; 32]);
+pub const SALT: Salt = [7u8; 32];
+
+/// Commitment of `registrant` to register `username` for itself with the test salt.
+fn commitment(username: &str, registrant: sp_runtime::AccountId32) -> sp_core::H256 {
+    NameService::commitment_of(
+        &username.as_bytes().to_vec(),
+        &registrant.clone().into(),
+        &registrant,
+        &SALT,
+    )
+}
 
 /// Initializes a test by funding accounts.
 #[inline]
@@ -46,14 +61,14 @@ fn register_should_work() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into()
+            commitment("test", ALICE)
         ));
         System::set_block_number(5);
         assert_ok!(NameService::accept_register(
             MockOrigin::signed(ALICE),
             "test".as_bytes().to_vec(),
-            ALICE.into()
+            ALICE.into(),
+            SALT
         ));
     });
 }
@@ -64,17 +79,18 @@ fn re_register_should_work() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into(),
+            commitment("test", ALICE)
         ));
         assert!(crate::PendingRegister::<Runtime>::contains_key(
-            <Runtime as frame_system::Config>::Hashing::hash_of(&"test".as_bytes().to_vec())
+            ALICE,
+            commitment("test", ALICE)
         ));
         System::set_block_number(5);
         assert_ok!(NameService::accept_register(
             MockOrigin::signed(ALICE),
             "test".as_bytes().to_vec(),
             ALICE.into(),
+            SALT
         ));
         assert!(crate::UsernameRecords::<Runtime>::contains_key(
             "test".as_bytes().to_vec()
@@ -88,23 +104,25 @@ fn re_register_should_work() {
             "test".as_bytes().to_vec()
         ));
         assert!(!crate::PendingRegister::<Runtime>::contains_key(
-            <Runtime as frame_system::Config>::Hashing::hash_of(&"test".as_bytes().to_vec())
+            ALICE,
+            commitment("test", ALICE)
         ));
 
         // test registering again
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into(),
+            commitment("test", ALICE)
         ));
         assert!(crate::PendingRegister::<Runtime>::contains_key(
-            <Runtime as frame_system::Config>::Hashing::hash_of(&"test".as_bytes().to_vec())
+            ALICE,
+            commitment("test", ALICE)
         ));
         System::set_block_number(10);
         assert_ok!(NameService::accept_register(
             MockOrigin::signed(ALICE),
             "test".as_bytes().to_vec(),
             ALICE.into(),
+            SALT
         ));
         assert!(crate::UsernameRecords::<Runtime>::contains_key(
             "test".as_bytes().to_vec()
@@ -118,14 +136,14 @@ fn set_primary_should_work() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into()
+            commitment("test", ALICE)
         ));
         System::set_block_number(5);
         assert_ok!(NameService::accept_register(
             MockOrigin::signed(ALICE),
             "test".as_bytes().to_vec(),
-            ALICE.into()
+            ALICE.into(),
+            SALT
         ));
         assert_ok!(NameService::set_primary_name(
             MockOrigin::signed(ALICE),
@@ -141,13 +159,11 @@ fn cancel_register_should_work() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into()
+            commitment("test", ALICE)
         ));
         assert_ok!(NameService::cancel_pending_register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into()
+            commitment("test", ALICE)
         ));
     });
 }
@@ -158,20 +174,23 @@ fn remove_register_should_work() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into()
+            commitment("test", ALICE)
         ));
         System::set_block_number(5);
         assert_ok!(NameService::accept_register(
             MockOrigin::signed(ALICE),
             "test".as_bytes().to_vec(),
-            ALICE.into()
+            ALICE.into(),
+            SALT
         ));
+        assert_eq!(Balances::reserved_balance(ALICE), 100);
         assert_ok!(NameService::remove_register(
             MockOrigin::signed(ALICE),
             "test".as_bytes().to_vec(),
             ALICE.into()
         ));
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(NameService::deposits("test".as_bytes().to_vec()), None);
     });
 }
 
@@ -181,30 +200,21 @@ fn register_should_fail() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test.test_123".as_bytes().to_vec(),
-            ALICE.into(),
+            commitment("test.test_123", ALICE)
         ));
         assert_noop!(
             NameService::register(
                 MockOrigin::signed(ALICE),
-                "test.test_123".as_bytes().to_vec(),
-                ALICE.into()
+                commitment("test.test_123", ALICE)
             ),
             Error::<Runtime>::AlreadyPendingRegister
         );
-        assert_noop!(
-            NameService::register(
-                MockOrigin::signed(ALICE),
-                ".test.test_123".as_bytes().to_vec(),
-                ALICE.into()
-            ),
-            Error::<Runtime>::InvalidUsernameFormat
-        );
         assert_noop!(
             NameService::accept_register(
                 MockOrigin::signed(ALICE),
                 "test.test_123".as_bytes().to_vec(),
-                ALICE.into()
+                ALICE.into(),
+                SALT
             ),
             Error::<Runtime>::RegisterTimeNotReached
         );
@@ -217,14 +227,14 @@ fn register_time_should_fail() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into(),
+            commitment("test", ALICE)
         ));
         assert_noop!(
             NameService::accept_register(
                 MockOrigin::signed(ALICE),
                 "test".as_bytes().to_vec(),
-                ALICE.into()
+                ALICE.into(),
+                SALT
             ),
             Error::<Runtime>::RegisterTimeNotReached
         );
@@ -237,17 +247,27 @@ fn register_accept_should_fail() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into(),
+            commitment("test", ALICE)
         ));
         System::set_block_number(5);
+        // Revealing another registrant doesn't match the commitment
         assert_noop!(
             NameService::accept_register(
-                MockOrigin::signed(BOB),
+                MockOrigin::signed(ALICE),
                 "test".as_bytes().to_vec(),
-                BOB.into()
+                BOB.into(),
+                SALT
             ),
-            Error::<Runtime>::NotOwned
+            Error::<Runtime>::UsernameNotFound
+        );
+        assert_noop!(
+            NameService::accept_register(
+                MockOrigin::signed(ALICE),
+                "test".as_bytes().to_vec(),
+                ALICE.into(),
+                [0u8; 32]
+            ),
+            Error::<Runtime>::UsernameNotFound
         );
     });
 }
@@ -258,14 +278,14 @@ fn set_primary_should_fail() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into(),
+            commitment("test", ALICE)
         ));
         System::set_block_number(5);
         assert_ok!(NameService::accept_register(
             MockOrigin::signed(ALICE),
             "test".as_bytes().to_vec(),
             ALICE.into(),
+            SALT
         ));
         assert_noop!(
             NameService::set_primary_name(
@@ -292,22 +312,19 @@ fn cancel_register_should_fail() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into(),
+            commitment("test", ALICE)
         ));
         assert_noop!(
             NameService::cancel_pending_register(
                 MockOrigin::signed(BOB),
-                "test".as_bytes().to_vec(),
-                BOB.into()
+                commitment("test", ALICE)
             ),
-            Error::<Runtime>::NotOwned
+            Error::<Runtime>::UsernameNotFound
         );
         assert_noop!(
             NameService::cancel_pending_register(
                 MockOrigin::signed(ALICE),
-                "testtest".as_bytes().to_vec(),
-                ALICE.into()
+                commitment("testtest", ALICE)
             ),
             Error::<Runtime>::UsernameNotFound
         );
@@ -320,14 +337,14 @@ fn remove_register_should_fail() {
         initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test".as_bytes().to_vec(),
-            ALICE.into(),
+            commitment("test", ALICE)
         ));
         System::set_block_number(5);
         assert_ok!(NameService::accept_register(
             MockOrigin::signed(ALICE),
             "test".as_bytes().to_vec(),
             ALICE.into(),
+            SALT
         ));
         assert_noop!(
             NameService::remove_register(
@@ -349,97 +366,1419 @@ fn remove_register_should_fail() {
 }
 
 #[test]
-fn username_format_test() {
+fn only_controller_should_manage_names() {
     ExtBuilder.build().execute_with(|| {
         initialize_test();
-        // 3 char test
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "two".as_bytes().to_vec(),
-            ALICE.into()
+            commitment("test", ALICE)
         ));
-        // 64 char test
-        assert_ok!(NameService::register(
+        System::set_block_number(5);
+        // BOB knows ALICE's zk address but does not control her names
+        assert_noop!(
+            NameService::accept_register(
+                MockOrigin::signed(BOB),
+                "test".as_bytes().to_vec(),
+                ALICE.into(),
+                SALT
+            ),
+            Error::<Runtime>::UsernameNotFound
+        );
+        assert_noop!(
+            NameService::cancel_pending_register(
+                MockOrigin::signed(BOB),
+                commitment("test", ALICE)
+            ),
+            Error::<Runtime>::UsernameNotFound
+        );
+        assert_ok!(NameService::accept_register(
             MockOrigin::signed(ALICE),
-            "bLuuXKxxOpqCsTvSglVeQvEbYoNVeswqbLuuXKxxOpqCsTvSglVeQvEbYoNVeswq"
-                .as_bytes()
-                .to_vec(),
-            ALICE.into()
+            "test".as_bytes().to_vec(),
+            ALICE.into(),
+            SALT
         ));
-        // allowed special and numerics
-        assert_ok!(NameService::register(
+        assert_eq!(
+            NameService::controllers("test".as_bytes().to_vec()),
+            Some(ALICE)
+        );
+
+        assert_noop!(
+            NameService::set_primary_name(
+                MockOrigin::signed(BOB),
+                "test".as_bytes().to_vec(),
+                ALICE.into()
+            ),
+            Error::<Runtime>::NotController
+        );
+        assert_noop!(
+            NameService::remove_register(
+                MockOrigin::signed(BOB),
+                "test".as_bytes().to_vec(),
+                ALICE.into()
+            ),
+            Error::<Runtime>::NotController
+        );
+        assert_ok!(NameService::remove_register(
             MockOrigin::signed(ALICE),
-            "test_test.123".as_bytes().to_vec(),
+            "test".as_bytes().to_vec(),
             ALICE.into()
         ));
+        assert_eq!(NameService::controllers("test".as_bytes().to_vec()), None);
+    });
+}
+
+#[test]
+fn commitment_reveal_window_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
         assert_ok!(NameService::register(
             MockOrigin::signed(ALICE),
-            "test123".as_bytes().to_vec(),
-            ALICE.into()
+            commitment("test", ALICE)
+        ));
+        assert_ok!(NameService::register(
+            MockOrigin::signed(BOB),
+            commitment("test", BOB)
         ));
-        // test invalid formats
-        // starting special signs
+        assert_eq!(
+            NameService::pending_register(ALICE, commitment("test", ALICE)),
+            Some((0, 100))
+        );
+
+        // The commitment can't be revealed once its lifetime has passed
+        System::set_block_number(11);
         assert_noop!(
-            NameService::register(
+            NameService::accept_register(
                 MockOrigin::signed(ALICE),
-                ".test".as_bytes().to_vec(),
-                ALICE.into()
+                "test".as_bytes().to_vec(),
+                ALICE.into(),
+                SALT
             ),
-            Error::<Runtime>::InvalidUsernameFormat
+            Error::<Runtime>::CommitmentExpired
         );
+        System::set_block_number(10);
+        assert_ok!(NameService::accept_register(
+            MockOrigin::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            ALICE.into(),
+            SALT
+        ));
+        // The first reveal wins the name
         assert_noop!(
-            NameService::register(
-                MockOrigin::signed(ALICE),
-                "_test".as_bytes().to_vec(),
-                ALICE.into()
+            NameService::accept_register(
+                MockOrigin::signed(BOB),
+                "test".as_bytes().to_vec(),
+                BOB.into(),
+                SALT
             ),
-            Error::<Runtime>::InvalidUsernameFormat
+            Error::<Runtime>::NameAlreadyRegistered
+        );
+
+        // Canceling releases the whole deposit
+        let balance = Balances::free_balance(BOB);
+        assert_ok!(NameService::cancel_pending_register(
+            MockOrigin::signed(BOB),
+            commitment("test", BOB)
+        ));
+        assert_eq!(Balances::free_balance(BOB), balance + 100);
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(
+            NameService::pending_register(BOB, commitment("test", BOB)),
+            None
         );
-        // starting numbers
+    });
+}
+
+#[test]
+fn copied_commitment_should_neither_block_nor_reveal() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        // BOB copies ALICE's commitment before hers is included
+        assert_ok!(NameService::register(
+            MockOrigin::signed(BOB),
+            commitment("test", ALICE)
+        ));
+        assert_ok!(NameService::register(
+            MockOrigin::signed(ALICE),
+            commitment("test", ALICE)
+        ));
+
+        // Revealed by BOB, the commitment binds another controller
+        System::set_block_number(5);
         assert_noop!(
-            NameService::register(
-                MockOrigin::signed(ALICE),
-                "1test".as_bytes().to_vec(),
-                ALICE.into()
+            NameService::accept_register(
+                MockOrigin::signed(BOB),
+                "test".as_bytes().to_vec(),
+                ALICE.into(),
+                SALT
             ),
-            Error::<Runtime>::InvalidUsernameFormat
+            Error::<Runtime>::UsernameNotFound
+        );
+        assert_ok!(NameService::accept_register(
+            MockOrigin::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            ALICE.into(),
+            SALT
+        ));
+        assert_eq!(
+            NameService::controllers("test".as_bytes().to_vec()),
+            Some(ALICE)
+        );
+    });
+}
+
+#[test]
+fn expired_commitments_should_be_swept() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        assert_ok!(NameService::register(
+            MockOrigin::signed(ALICE),
+            commitment("test", ALICE)
+        ));
+        System::set_block_number(5);
+        assert_ok!(NameService::register(
+            MockOrigin::signed(BOB),
+            commitment("test", BOB)
+        ));
+
+        let balance = Balances::free_balance(ALICE);
+        System::set_block_number(11);
+        NameService::on_idle(11, Weight::MAX);
+        // Only ALICE's commitment expired
+        assert_eq!(
+            NameService::pending_register(ALICE, commitment("test", ALICE)),
+            None
+        );
+        assert!(NameService::pending_register(BOB, commitment("test", BOB)).is_some());
+        // The rest of the deposit is kept as a fee
+        assert_eq!(Balances::free_balance(ALICE), balance + 50);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(NameService::account_id()), 50);
+        System::assert_last_event(RuntimeEvent::NameService(Event::CommitmentExpired {
+            commitment: commitment("test", ALICE),
+            refund: 50,
+        }));
+
+        // Sweeping stops when running out of weight
+        System::set_block_number(16);
+        assert_eq!(NameService::on_idle(16, Weight::zero()), Weight::zero());
+        assert!(NameService::pending_register(BOB, commitment("test", BOB)).is_some());
+        NameService::on_idle(16, Weight::MAX);
+        assert_eq!(
+            NameService::pending_register(BOB, commitment("test", BOB)),
+            None
+        );
+    });
+}
+
+#[test]
+fn commit_reveal_migration_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        assert_ok!(Balances::force_set_balance(
+            MockOrigin::root(),
+            NameService::account_id(),
+            1_000,
+        ));
+        let prefix = |storage: &[u8]| {
+            [
+                sp_io::hashing::twox_128(b"NameService"),
+                sp_io::hashing::twox_128(storage),
+            ]
+            .concat()
+        };
+        let hash_user =
+            <Runtime as frame_system::Config>::Hashing::hash_of(&"test".as_bytes().to_vec());
+        let legacy_hash_user =
+            <Runtime as frame_system::Config>::Hashing::hash_of(&"legacy".as_bytes().to_vec());
+        let hash_owner =
+            <Runtime as frame_system::Config>::Hashing::hash_of(&ZkAddressType::from(BOB));
+        for hash in [hash_user, legacy_hash_user] {
+            let key = [
+                prefix(b"PendingRegister"),
+                hash.using_encoded(sp_io::hashing::twox_64).to_vec(),
+                hash.encode(),
+            ]
+            .concat();
+            frame_support::storage::unhashed::put(&key, &(hash_owner, 2u32));
+        }
+        let key = [
+            prefix(b"PendingControllers"),
+            hash_user.using_encoded(sp_io::hashing::twox_64).to_vec(),
+            hash_user.encode(),
+        ]
+        .concat();
+        frame_support::storage::unhashed::put(&key, &BOB);
+        StorageVersion::new(2).put::<NameService>();
+
+        let balance = Balances::free_balance(BOB);
+        crate::migrations::CommitReveal::<Runtime>::on_runtime_upgrade();
+
+        assert_eq!(NameService::on_chain_storage_version(), 3);
+        assert_eq!(crate::PendingRegister::<Runtime>::iter_keys().count(), 0);
+        assert!(!frame_support::storage::unhashed::exists(&key));
+        // Only the registration with a known controller is refunded
+        assert_eq!(Balances::free_balance(BOB), balance + 100);
+        assert_eq!(Balances::free_balance(NameService::account_id()), 900);
+    });
+}
+
+/// Registers `username` for `who` with the commitment revealed 5 blocks later.
+fn register_name(username: &str, who: sp_runtime::AccountId32) {
+    assert_ok!(NameService::register(
+        MockOrigin::signed(who.clone()),
+        commitment(username, who.clone())
+    ));
+    System::set_block_number(System::block_number() + 5);
+    assert_ok!(NameService::accept_register(
+        MockOrigin::signed(who.clone()),
+        username.as_bytes().to_vec(),
+        who.into(),
+        SALT
+    ));
+}
+
+#[test]
+fn renew_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        // The first period is paid on top of the reserved register deposit
+        let balance = Balances::free_balance(ALICE);
+        register_name("test", ALICE);
+        assert_eq!(Balances::free_balance(ALICE), balance - 300);
+        assert_eq!(Balances::reserved_balance(ALICE), 100);
+        assert_eq!(
+            NameService::deposits("test".as_bytes().to_vec()),
+            Some((ALICE, 100))
         );
-        // ending with special character
+        assert_eq!(NameService::expiries("test".as_bytes().to_vec()), Some(105));
+
+        // Short names cost more per period
+        assert_eq!(NameService::name_price(&"two".as_bytes().to_vec()), 300);
+        assert_eq!(NameService::name_price(&"test".as_bytes().to_vec()), 200);
+        assert_eq!(NameService::name_price(&"test1".as_bytes().to_vec()), 100);
+
         assert_noop!(
-            NameService::register(
-                MockOrigin::signed(ALICE),
-                "test.".as_bytes().to_vec(),
-                ALICE.into()
-            ),
-            Error::<Runtime>::InvalidUsernameFormat
+            NameService::renew(MockOrigin::signed(ALICE), "test".as_bytes().to_vec(), 0),
+            Error::<Runtime>::ZeroRenewalPeriods
         );
         assert_noop!(
-            NameService::register(
-                MockOrigin::signed(ALICE),
-                "test_".as_bytes().to_vec(),
-                ALICE.into()
-            ),
-            Error::<Runtime>::InvalidUsernameFormat
+            NameService::renew(MockOrigin::signed(ALICE), "testtest".as_bytes().to_vec(), 1),
+            Error::<Runtime>::NotRegistered
+        );
+
+        // Anyone may renew a name before it expires
+        let balance = Balances::free_balance(BOB);
+        assert_ok!(NameService::renew(
+            MockOrigin::signed(BOB),
+            "test".as_bytes().to_vec(),
+            2
+        ));
+        assert_eq!(Balances::free_balance(BOB), balance - 400);
+        assert_eq!(NameService::expiries("test".as_bytes().to_vec()), Some(305));
+        System::assert_last_event(RuntimeEvent::NameService(Event::NameRenewed {
+            username: "test".as_bytes().to_vec(),
+            expiry: 305,
+        }));
+    });
+}
+
+#[test]
+fn expired_names_should_be_released() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        register_name("test", ALICE);
+        assert_eq!(
+            NameService::owner_of(&"test".as_bytes().to_vec()),
+            Some(ALICE.into())
         );
-        // too short
+
+        // During the grace period only the controller may renew
+        System::set_block_number(106);
+        assert_eq!(NameService::owner_of(&"test".as_bytes().to_vec()), None);
         assert_noop!(
-            NameService::register(
+            NameService::set_primary_name(
                 MockOrigin::signed(ALICE),
-                "ab".as_bytes().to_vec(),
+                "test".as_bytes().to_vec(),
                 ALICE.into()
             ),
-            Error::<Runtime>::InvalidUsernameFormat
+            Error::<Runtime>::NameExpired
         );
-        // too long 65+
         assert_noop!(
-            NameService::register(
-                MockOrigin::signed(ALICE),
-                "bLuuXKxxOpqCsTvSglVeQvEbYoNVeswqbLuuXKxxOpqCsTvSglVeQvEbYoNVeswqa"
-                    .as_bytes()
-                    .to_vec(),
-                ALICE.into()
+            NameService::renew(MockOrigin::signed(BOB), "test".as_bytes().to_vec(), 1),
+            Error::<Runtime>::NotController
+        );
+        assert_ok!(NameService::renew(
+            MockOrigin::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            1
+        ));
+        assert_eq!(NameService::expiries("test".as_bytes().to_vec()), Some(205));
+
+        // The name can't be renewed nor revealed by others until the grace period passed
+        System::set_block_number(220);
+        assert_ok!(NameService::register(
+            MockOrigin::signed(BOB),
+            commitment("test", BOB)
+        ));
+        System::set_block_number(225);
+        assert_noop!(
+            NameService::accept_register(
+                MockOrigin::signed(BOB),
+                "test".as_bytes().to_vec(),
+                BOB.into(),
+                SALT
             ),
-            Error::<Runtime>::InvalidUsernameFormat
+            Error::<Runtime>::NameAlreadyRegistered
+        );
+        System::set_block_number(226);
+        assert_noop!(
+            NameService::renew(MockOrigin::signed(ALICE), "test".as_bytes().to_vec(), 1),
+            Error::<Runtime>::NameExpired
+        );
+
+        // Revealing a released name registers it for the new owner
+        assert_ok!(NameService::accept_register(
+            MockOrigin::signed(BOB),
+            "test".as_bytes().to_vec(),
+            BOB.into(),
+            SALT
+        ));
+        assert!(System::events().iter().any(|record| record.event
+            == RuntimeEvent::NameService(Event::NameReleased {
+                username: "test".as_bytes().to_vec(),
+                owner: ALICE.into(),
+            })));
+        assert_eq!(
+            NameService::username_records("test".as_bytes().to_vec()),
+            Some(BOB.into())
         );
+        assert_eq!(
+            NameService::controllers("test".as_bytes().to_vec()),
+            Some(BOB)
+        );
+        assert_eq!(NameService::expiries("test".as_bytes().to_vec()), Some(326));
+    });
+}
+
+#[test]
+fn on_idle_should_release_names() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        register_name("test", ALICE);
+        assert_ok!(NameService::set_primary_name(
+            MockOrigin::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            ALICE.into()
+        ));
+
+        System::set_block_number(125);
+        NameService::on_idle(125, Weight::MAX);
+        assert!(crate::UsernameRecords::<Runtime>::contains_key(
+            "test".as_bytes().to_vec()
+        ));
+
+        System::set_block_number(126);
+        assert_eq!(NameService::on_idle(126, Weight::zero()), Weight::zero());
+        assert!(crate::UsernameRecords::<Runtime>::contains_key(
+            "test".as_bytes().to_vec()
+        ));
+        NameService::on_idle(126, Weight::MAX);
+        assert!(!crate::UsernameRecords::<Runtime>::contains_key(
+            "test".as_bytes().to_vec()
+        ));
+        assert_eq!(NameService::controllers("test".as_bytes().to_vec()), None);
+        assert_eq!(NameService::expiries("test".as_bytes().to_vec()), None);
+        assert_eq!(
+            NameService::primary_records(ZkAddressType::from(ALICE)),
+            None
+        );
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        System::assert_last_event(RuntimeEvent::NameService(Event::NameReleased {
+            username: "test".as_bytes().to_vec(),
+            owner: ALICE.into(),
+        }));
+    });
+}
+
+#[test]
+fn name_expiries_migration_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        System::set_block_number(10);
+        crate::UsernameRecords::<Runtime>::insert(
+            "test".as_bytes().to_vec(),
+            ZkAddressType::from(ALICE),
+        );
+        StorageVersion::new(3).put::<NameService>();
+
+        crate::migrations::NameExpiries::<Runtime>::on_runtime_upgrade();
+
+        assert_eq!(NameService::on_chain_storage_version(), 4);
+        assert_eq!(NameService::expiries("test".as_bytes().to_vec()), Some(110));
+    });
+}
+
+#[test]
+fn reserved_deposits_migration_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        assert_ok!(Balances::force_set_balance(
+            MockOrigin::root(),
+            NameService::account_id(),
+            1_000,
+        ));
+        crate::UsernameRecords::<Runtime>::insert(
+            "test".as_bytes().to_vec(),
+            ZkAddressType::from(ALICE),
+        );
+        crate::Controllers::<Runtime>::insert("test".as_bytes().to_vec(), ALICE);
+        crate::Expiries::<Runtime>::insert("test".as_bytes().to_vec(), 105);
+        crate::PendingRegister::<Runtime>::insert(BOB, commitment("test1", BOB), (0, 100));
+        // Legacy names without a controller keep their deposit in the pallet account, names
+        // controlled by the pallet account keep none
+        crate::UsernameRecords::<Runtime>::insert(
+            "legacy".as_bytes().to_vec(),
+            ZkAddressType::from(CHARLIE),
+        );
+        crate::Expiries::<Runtime>::insert("legacy".as_bytes().to_vec(), 105);
+        crate::UsernameRecords::<Runtime>::insert(
+            "pallet".as_bytes().to_vec(),
+            ZkAddressType::from(CHARLIE),
+        );
+        crate::Controllers::<Runtime>::insert(
+            "pallet".as_bytes().to_vec(),
+            NameService::account_id(),
+        );
+        crate::Expiries::<Runtime>::insert("pallet".as_bytes().to_vec(), 105);
+        StorageVersion::new(4).put::<NameService>();
+
+        let (alice_balance, bob_balance) =
+            (Balances::free_balance(ALICE), Balances::free_balance(BOB));
+        crate::migrations::ReservedDeposits::<Runtime>::on_runtime_upgrade();
+
+        assert_eq!(NameService::on_chain_storage_version(), 5);
+        assert_eq!(
+            NameService::deposits("test".as_bytes().to_vec()),
+            Some((ALICE, 100))
+        );
+        assert_eq!(Balances::reserved_balance(ALICE), 100);
+        assert_eq!(Balances::reserved_balance(BOB), 100);
+        assert_eq!(Balances::free_balance(ALICE), alice_balance);
+        assert_eq!(Balances::free_balance(BOB), bob_balance);
+        assert_eq!(Balances::free_balance(NameService::account_id()), 800);
+        assert_eq!(Balances::reserved_balance(NameService::account_id()), 0);
+        assert_eq!(NameService::deposits("legacy".as_bytes().to_vec()), None);
+        assert_eq!(
+            NameService::legacy_deposits("legacy".as_bytes().to_vec()),
+            Some(100)
+        );
+        assert_eq!(NameService::legacy_deposit_total(), 100);
+        assert_eq!(NameService::deposits("pallet".as_bytes().to_vec()), None);
+        assert_eq!(
+            NameService::legacy_deposits("pallet".as_bytes().to_vec()),
+            None
+        );
+
+        // Migrated deposits are released like new ones
+        assert_ok!(NameService::cancel_pending_register(
+            MockOrigin::signed(BOB),
+            commitment("test1", BOB)
+        ));
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+    });
+}
+
+#[test]
+fn transfer_name_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        register_name("test", ALICE);
+        assert_ok!(NameService::set_primary_name(
+            MockOrigin::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            ALICE.into()
+        ));
+
+        assert_noop!(
+            NameService::transfer_name(
+                MockOrigin::signed(BOB),
+                "test".as_bytes().to_vec(),
+                BOB.into(),
+                BOB
+            ),
+            Error::<Runtime>::NotController
+        );
+
+        // Moving the name to another zk address under the same controller is immediate
+        assert_ok!(NameService::transfer_name(
+            MockOrigin::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            CHARLIE.into(),
+            ALICE
+        ));
+        assert_eq!(
+            NameService::username_records("test".as_bytes().to_vec()),
+            Some(CHARLIE.into())
+        );
+        assert_eq!(
+            NameService::primary_records(ZkAddressType::from(ALICE)),
+            None
+        );
+        assert_eq!(
+            NameService::primary_records(ZkAddressType::from(CHARLIE)),
+            Some("test".as_bytes().to_vec())
+        );
+
+        // Handing the name to another controller waits for it to accept
+        assert_ok!(NameService::transfer_name(
+            MockOrigin::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            BOB.into(),
+            BOB
+        ));
+        assert_eq!(
+            NameService::username_records("test".as_bytes().to_vec()),
+            Some(CHARLIE.into())
+        );
+        assert_noop!(
+            NameService::accept_transfer(MockOrigin::signed(ALICE), "test".as_bytes().to_vec()),
+            Error::<Runtime>::NotController
+        );
+        assert_ok!(NameService::accept_transfer(
+            MockOrigin::signed(BOB),
+            "test".as_bytes().to_vec()
+        ));
+        assert_eq!(
+            NameService::username_records("test".as_bytes().to_vec()),
+            Some(BOB.into())
+        );
+        // The new controller takes over the register deposit
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::reserved_balance(BOB), 100);
+        assert_eq!(
+            NameService::deposits("test".as_bytes().to_vec()),
+            Some((BOB, 100))
+        );
+        assert_eq!(
+            NameService::controllers("test".as_bytes().to_vec()),
+            Some(BOB)
+        );
+        assert_eq!(
+            NameService::pending_transfers("test".as_bytes().to_vec()),
+            None
+        );
+        assert_eq!(
+            NameService::primary_records(ZkAddressType::from(CHARLIE)),
+            None
+        );
+        assert_noop!(
+            NameService::accept_transfer(MockOrigin::signed(BOB), "test".as_bytes().to_vec()),
+            Error::<Runtime>::NoPendingTransfer
+        );
+
+        // Names can't be transferred once expired
+        System::set_block_number(106);
+        assert_noop!(
+            NameService::transfer_name(
+                MockOrigin::signed(BOB),
+                "test".as_bytes().to_vec(),
+                ALICE.into(),
+                ALICE
+            ),
+            Error::<Runtime>::NameExpired
+        );
+    });
+}
+
+#[test]
+fn name_market_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        register_name("test", ALICE);
+        // BOB already has a primary name, which the bought name doesn't replace
+        register_name("test1", BOB);
+        assert_ok!(NameService::set_primary_name(
+            MockOrigin::signed(BOB),
+            "test1".as_bytes().to_vec(),
+            BOB.into()
+        ));
+
+        assert_noop!(
+            NameService::buy_name(
+                MockOrigin::signed(BOB),
+                "test".as_bytes().to_vec(),
+                BOB.into(),
+                1_000
+            ),
+            Error::<Runtime>::NotListed
+        );
+        assert_noop!(
+            NameService::list_name(MockOrigin::signed(BOB), "test".as_bytes().to_vec(), 1_000),
+            Error::<Runtime>::NotController
+        );
+        assert_ok!(NameService::list_name(
+            MockOrigin::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            1_000
+        ));
+        assert_eq!(
+            NameService::listings("test".as_bytes().to_vec()),
+            Some(1_000)
+        );
+
+        assert_noop!(
+            NameService::unlist_name(MockOrigin::signed(BOB), "test".as_bytes().to_vec()),
+            Error::<Runtime>::NotController
+        );
+        assert_ok!(NameService::unlist_name(
+            MockOrigin::signed(ALICE),
+            "test".as_bytes().to_vec()
+        ));
+        assert_noop!(
+            NameService::unlist_name(MockOrigin::signed(ALICE), "test".as_bytes().to_vec()),
+            Error::<Runtime>::NotListed
+        );
+
+        assert_ok!(NameService::list_name(
+            MockOrigin::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            2_000
+        ));
+        // The buyer is protected against the price being raised
+        assert_noop!(
+            NameService::buy_name(
+                MockOrigin::signed(BOB),
+                "test".as_bytes().to_vec(),
+                BOB.into(),
+                1_000
+            ),
+            Error::<Runtime>::PriceTooHigh
+        );
+        let (alice_balance, bob_balance) =
+            (Balances::free_balance(ALICE), Balances::free_balance(BOB));
+        assert_ok!(NameService::buy_name(
+            MockOrigin::signed(BOB),
+            "test".as_bytes().to_vec(),
+            BOB.into(),
+            2_000
+        ));
+        // The register deposit moves to the buyer along with the name
+        assert_eq!(Balances::free_balance(ALICE), alice_balance + 2_100);
+        assert_eq!(Balances::free_balance(BOB), bob_balance - 2_100);
+        assert_eq!(
+            NameService::username_records("test".as_bytes().to_vec()),
+            Some(BOB.into())
+        );
+        assert_eq!(
+            NameService::controllers("test".as_bytes().to_vec()),
+            Some(BOB)
+        );
+        assert_eq!(NameService::listings("test".as_bytes().to_vec()), None);
+        assert_eq!(
+            NameService::primary_records(ZkAddressType::from(BOB)),
+            Some("test1".as_bytes().to_vec())
+        );
+        System::assert_last_event(RuntimeEvent::NameService(Event::NameSold {
+            username: "test".as_bytes().to_vec(),
+            buyer: BOB,
+            price: 2_000,
+        }));
+    });
+}
+
+/// Resolver records setting every kind of record for the account.
+fn records(who: sp_runtime::AccountId32) -> Records<sp_runtime::AccountId32> {
+    Records {
+        account: Some(who),
+        evm_address: Some([1u8; 20]),
+        chain_addresses: vec![(60, vec![2u8; 20])],
+        texts: vec![
+            (b"url".to_vec(), b"https://manta.network".to_vec()),
+            (b"avatar".to_vec(), b"ipfs://avatar".to_vec()),
+        ],
+    }
+}
+
+/// Deposit reserved for the resolver records.
+fn deposit_of(records: &Records<sp_runtime::AccountId32>) -> u128 {
+    10 + records.encoded_size() as u128
+}
+
+#[test]
+fn set_records_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        register_name("test", ALICE);
+        let name = "test".as_bytes().to_vec();
+
+        assert_noop!(
+            NameService::set_records(MockOrigin::signed(BOB), name.clone(), records(ALICE)),
+            Error::<Runtime>::NotController
+        );
+        assert_noop!(
+            NameService::set_records(
+                MockOrigin::signed(ALICE),
+                "test1".as_bytes().to_vec(),
+                records(ALICE)
+            ),
+            Error::<Runtime>::NotRegistered
+        );
+        let mut too_many = records(ALICE);
+        too_many.chain_addresses = vec![(0, vec![]), (1, vec![]), (2, vec![])];
+        assert_noop!(
+            NameService::set_records(MockOrigin::signed(ALICE), name.clone(), too_many),
+            Error::<Runtime>::TooManyRecords
+        );
+        let mut too_long = records(ALICE);
+        too_long.texts[0].1 = vec![0u8; 17];
+        assert_noop!(
+            NameService::set_records(MockOrigin::signed(ALICE), name.clone(), too_long),
+            Error::<Runtime>::RecordTooLong
+        );
+        let mut duplicate = records(ALICE);
+        duplicate.texts[1].0 = b"url".to_vec();
+        assert_noop!(
+            NameService::set_records(MockOrigin::signed(ALICE), name.clone(), duplicate),
+            Error::<Runtime>::DuplicateRecord
+        );
+
+        // Records are stored sorted, reserving a deposit for their size
+        let balance = Balances::free_balance(ALICE);
+        assert_ok!(NameService::set_records(
+            MockOrigin::signed(ALICE),
+            name.clone(),
+            records(ALICE)
+        ));
+        let mut expected = records(ALICE);
+        expected.texts.reverse();
+        let deposit = deposit_of(&expected);
+        assert_eq!(NameService::resolve(&name), Some(expected));
+        assert_eq!(Balances::reserved_balance(ALICE), deposit + 100);
+        assert_eq!(Balances::free_balance(ALICE), balance - deposit);
+        System::assert_last_event(RuntimeEvent::NameService(Event::RecordsSet {
+            username: name.clone(),
+            deposit,
+        }));
+
+        // Replacing the records adjusts the deposit
+        let smaller = Records {
+            account: Some(BOB),
+            ..Default::default()
+        };
+        assert_ok!(NameService::set_records(
+            MockOrigin::signed(ALICE),
+            name.clone(),
+            smaller.clone()
+        ));
+        assert_eq!(NameService::resolve(&name), Some(smaller.clone()));
+        assert_eq!(
+            Balances::reserved_balance(ALICE),
+            deposit_of(&smaller) + 100
+        );
+
+        assert_ok!(NameService::clear_records(
+            MockOrigin::signed(ALICE),
+            name.clone()
+        ));
+        assert_eq!(NameService::resolve(&name), Some(Records::default()));
+        assert_eq!(Balances::reserved_balance(ALICE), 100);
+        assert_eq!(Balances::free_balance(ALICE), balance);
+        assert_noop!(
+            NameService::clear_records(MockOrigin::signed(ALICE), name),
+            Error::<Runtime>::NoRecords
+        );
+    });
+}
+
+#[test]
+fn resolution_should_follow_ownership() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        register_name("test", ALICE);
+        let name = "test".as_bytes().to_vec();
+        assert_eq!(NameService::reverse(&ALICE.into()), None);
+        assert_ok!(NameService::set_primary_name(
+            MockOrigin::signed(ALICE),
+            name.clone(),
+            ALICE.into()
+        ));
+        assert_eq!(NameService::reverse(&ALICE.into()), Some(name.clone()));
+        assert_ok!(NameService::set_records(
+            MockOrigin::signed(ALICE),
+            name.clone(),
+            records(ALICE)
+        ));
+
+        // Records of the previous controller are dropped with their deposit on transfer
+        assert_ok!(NameService::transfer_name(
+            MockOrigin::signed(ALICE),
+            name.clone(),
+            BOB.into(),
+            ALICE
+        ));
+        assert_eq!(NameService::resolve(&name), Some(Records::default()));
+        assert_eq!(Balances::reserved_balance(ALICE), 100);
+        assert_eq!(NameService::reverse(&ALICE.into()), None);
+        assert_eq!(NameService::reverse(&BOB.into()), Some(name.clone()));
+
+        // Expired names resolve to nothing, and are released along with their records
+        assert_ok!(NameService::set_records(
+            MockOrigin::signed(ALICE),
+            name.clone(),
+            records(BOB)
+        ));
+        System::set_block_number(NameService::expiries(&name).unwrap() + 1);
+        assert_eq!(NameService::resolve(&name), None);
+        assert_eq!(NameService::reverse(&BOB.into()), None);
+        System::set_block_number(System::block_number() + 20);
+        NameService::on_idle(System::block_number(), Weight::MAX);
+        assert_eq!(NameService::resolvers(&name), None);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(NameService::resolve(&name), None);
+    });
+}
+
+#[test]
+fn subnames_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        register_name("project", ALICE);
+        let parent = "project".as_bytes().to_vec();
+        let subname = "alice.project".as_bytes().to_vec();
+
+        assert_noop!(
+            NameService::create_subname(
+                MockOrigin::signed(BOB),
+                parent.clone(),
+                "alice".as_bytes().to_vec(),
+                BOB.into(),
+                BOB
+            ),
+            Error::<Runtime>::NotController
+        );
+        assert_noop!(
+            NameService::create_subname(
+                MockOrigin::signed(ALICE),
+                parent.clone(),
+                "1alice".as_bytes().to_vec(),
+                BOB.into(),
+                BOB
+            ),
+            Error::<Runtime>::InvalidUsernameFormat
+        );
+        // The subname is delegated to its own controller
+        assert_ok!(NameService::create_subname(
+            MockOrigin::signed(ALICE),
+            parent.clone(),
+            "alice".as_bytes().to_vec(),
+            BOB.into(),
+            BOB
+        ));
+        System::assert_last_event(RuntimeEvent::NameService(Event::SubnameCreated {
+            username: subname.clone(),
+            owner: BOB.into(),
+            controller: BOB,
+        }));
+        assert_eq!(NameService::owner_of(&subname), Some(BOB.into()));
+        assert_eq!(NameService::controllers(&subname), Some(BOB));
+        assert_eq!(NameService::subnames(&parent), vec![subname.clone()]);
+        assert_ok!(NameService::set_primary_name(
+            MockOrigin::signed(BOB),
+            subname.clone(),
+            BOB.into()
+        ));
+        assert_eq!(NameService::reverse(&BOB.into()), Some(subname.clone()));
+
+        assert_noop!(
+            NameService::create_subname(
+                MockOrigin::signed(ALICE),
+                parent.clone(),
+                "alice".as_bytes().to_vec(),
+                ALICE.into(),
+                ALICE
+            ),
+            Error::<Runtime>::NameAlreadyRegistered
+        );
+        assert_noop!(
+            NameService::create_subname(
+                MockOrigin::signed(BOB),
+                subname.clone(),
+                "nested".as_bytes().to_vec(),
+                BOB.into(),
+                BOB
+            ),
+            Error::<Runtime>::NestedSubname
+        );
+        assert_ok!(NameService::create_subname(
+            MockOrigin::signed(ALICE),
+            parent.clone(),
+            "bob".as_bytes().to_vec(),
+            ALICE.into(),
+            ALICE
+        ));
+        assert_noop!(
+            NameService::create_subname(
+                MockOrigin::signed(ALICE),
+                parent.clone(),
+                "charlie".as_bytes().to_vec(),
+                CHARLIE.into(),
+                CHARLIE
+            ),
+            Error::<Runtime>::SubnameLimitReached
+        );
+
+        // Only the parent's controller revokes subnames, their own controller can still remove them
+        assert_noop!(
+            NameService::revoke_subname(MockOrigin::signed(BOB), subname.clone()),
+            Error::<Runtime>::NotController
+        );
+        assert_noop!(
+            NameService::revoke_subname(MockOrigin::signed(ALICE), parent.clone()),
+            Error::<Runtime>::NotSubname
+        );
+        assert_ok!(NameService::revoke_subname(
+            MockOrigin::signed(ALICE),
+            subname.clone()
+        ));
+        System::assert_last_event(RuntimeEvent::NameService(Event::SubnameRevoked {
+            username: subname.clone(),
+            owner: BOB.into(),
+        }));
+        assert_eq!(NameService::username_records(&subname), None);
+        assert_eq!(NameService::reverse(&BOB.into()), None);
+        assert_ok!(NameService::remove_register(
+            MockOrigin::signed(ALICE),
+            "bob.project".as_bytes().to_vec(),
+            ALICE.into()
+        ));
+        assert!(!Subnames::<Runtime>::contains_key(&parent));
+    });
+}
+
+#[test]
+fn subname_sales_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        register_name("project", ALICE);
+        let parent = "project".as_bytes().to_vec();
+
+        assert_noop!(
+            NameService::buy_subname(
+                MockOrigin::signed(BOB),
+                parent.clone(),
+                "bob".as_bytes().to_vec(),
+                BOB.into(),
+                1_000
+            ),
+            Error::<Runtime>::SubnamesNotForSale
+        );
+        assert_noop!(
+            NameService::set_subname_price(MockOrigin::signed(BOB), parent.clone(), Some(500)),
+            Error::<Runtime>::NotController
+        );
+        assert_ok!(NameService::set_subname_price(
+            MockOrigin::signed(ALICE),
+            parent.clone(),
+            Some(500)
+        ));
+        assert_noop!(
+            NameService::buy_subname(
+                MockOrigin::signed(BOB),
+                parent.clone(),
+                "bob".as_bytes().to_vec(),
+                BOB.into(),
+                499
+            ),
+            Error::<Runtime>::PriceTooHigh
+        );
+
+        let (alice_balance, bob_balance) =
+            (Balances::free_balance(ALICE), Balances::free_balance(BOB));
+        assert_ok!(NameService::buy_subname(
+            MockOrigin::signed(BOB),
+            parent.clone(),
+            "bob".as_bytes().to_vec(),
+            BOB.into(),
+            500
+        ));
+        System::assert_last_event(RuntimeEvent::NameService(Event::SubnameSold {
+            username: "bob.project".as_bytes().to_vec(),
+            buyer: BOB,
+            price: 500,
+        }));
+        assert_eq!(Balances::free_balance(ALICE), alice_balance + 500);
+        assert_eq!(Balances::free_balance(BOB), bob_balance - 500);
+        assert_eq!(
+            NameService::controllers("bob.project".as_bytes().to_vec()),
+            Some(BOB)
+        );
+
+        assert_ok!(NameService::set_subname_price(
+            MockOrigin::signed(ALICE),
+            parent.clone(),
+            None
+        ));
+        assert_noop!(
+            NameService::buy_subname(
+                MockOrigin::signed(BOB),
+                parent,
+                "bob2".as_bytes().to_vec(),
+                BOB.into(),
+                500
+            ),
+            Error::<Runtime>::SubnamesNotForSale
+        );
+    });
+}
+
+#[test]
+fn subnames_should_follow_parent() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        register_name("project", ALICE);
+        register_name("other", ALICE);
+        for parent in ["project", "other"] {
+            assert_ok!(NameService::create_subname(
+                MockOrigin::signed(ALICE),
+                parent.as_bytes().to_vec(),
+                "alice".as_bytes().to_vec(),
+                BOB.into(),
+                BOB
+            ));
+        }
+
+        // Removing the parent removes its subnames
+        assert_ok!(NameService::remove_register(
+            MockOrigin::signed(ALICE),
+            "other".as_bytes().to_vec(),
+            ALICE.into()
+        ));
+        assert_eq!(
+            NameService::username_records("alice.other".as_bytes().to_vec()),
+            None
+        );
+        assert!(!Subnames::<Runtime>::contains_key(
+            "other".as_bytes().to_vec()
+        ));
+
+        // Subnames expire with their parent and are released along with it
+        let subname = "alice.project".as_bytes().to_vec();
+        let expiry = NameService::expiries("project".as_bytes().to_vec()).unwrap();
+        System::set_block_number(expiry + 1);
+        assert_eq!(NameService::owner_of(&subname), None);
+        assert_eq!(NameService::resolve(&subname), None);
+        assert_noop!(
+            NameService::transfer_name(MockOrigin::signed(BOB), subname.clone(), ALICE.into(), BOB),
+            Error::<Runtime>::NameExpired
+        );
+        System::set_block_number(expiry + 21);
+        NameService::on_idle(expiry + 21, Weight::MAX);
+        assert_eq!(NameService::username_records(&subname), None);
+        assert_eq!(NameService::controllers(&subname), None);
+        assert!(!Subnames::<Runtime>::contains_key(
+            "project".as_bytes().to_vec()
+        ));
+    });
+}
+
+#[test]
+fn sweep_fees_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        register_name("test", ALICE);
+        // Only the rent is collected, the register deposit stays with ALICE
+        assert_eq!(Balances::free_balance(NameService::account_id()), 200);
+
+        assert_noop!(
+            NameService::sweep_fees(MockOrigin::signed(ALICE)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(NameService::sweep_fees(MockOrigin::root()));
+        let minimum_balance = Balances::minimum_balance();
+        assert_eq!(
+            Balances::free_balance(NameService::account_id()),
+            minimum_balance
+        );
+        assert_eq!(
+            Balances::free_balance(TreasuryAccount::get()),
+            200 - minimum_balance
+        );
+        System::assert_last_event(RuntimeEvent::NameService(Event::FeesSwept {
+            amount: 200 - minimum_balance,
+        }));
+    });
+}
+
+frame_support::parameter_types! {
+    /// Registrants of legacy names as found in the chain history
+    pub LegacyControllers: Vec<(UserName, sp_runtime::AccountId32)> = vec![
+        ("test".as_bytes().to_vec(), BOB),
+        ("unregistered".as_bytes().to_vec(), ALICE),
+    ];
+}
+
+/// Controllers to assign with `assign_legacy_controller`.
+fn legacy_controllers(
+    controllers: Vec<(&str, sp_runtime::AccountId32)>,
+) -> BoundedVec<(UserName, sp_runtime::AccountId32), ConstU32<2>> {
+    controllers
+        .into_iter()
+        .map(|(username, controller)| (username.as_bytes().to_vec(), controller))
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+#[test]
+fn assign_controllers_migration_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        crate::UsernameRecords::<Runtime>::insert(
+            "test".as_bytes().to_vec(),
+            ZkAddressType::from(BOB),
+        );
+        crate::UsernameRecords::<Runtime>::insert(
+            "legacy".as_bytes().to_vec(),
+            ZkAddressType::from(CHARLIE),
+        );
+        StorageVersion::new(1).put::<NameService>();
+
+        crate::migrations::AssignControllers::<Runtime, LegacyControllers>::on_runtime_upgrade();
+
+        assert_eq!(NameService::on_chain_storage_version(), 2);
+        // Listed names get their registrant, names missing from the list stay unassigned
+        assert_eq!(
+            NameService::controllers("test".as_bytes().to_vec()),
+            Some(BOB)
+        );
+        assert_eq!(
+            NameService::controllers("unregistered".as_bytes().to_vec()),
+            None
+        );
+        assert_eq!(NameService::controllers("legacy".as_bytes().to_vec()), None);
+        assert_noop!(
+            NameService::remove_register(
+                MockOrigin::signed(CHARLIE),
+                "legacy".as_bytes().to_vec(),
+                CHARLIE.into()
+            ),
+            Error::<Runtime>::NotController
+        );
+        assert_ok!(NameService::remove_register(
+            MockOrigin::signed(BOB),
+            "test".as_bytes().to_vec(),
+            BOB.into()
+        ));
+
+        // Running again does nothing
+        crate::UsernameRecords::<Runtime>::insert(
+            "test".as_bytes().to_vec(),
+            ZkAddressType::from(BOB),
+        );
+        crate::migrations::AssignControllers::<Runtime, LegacyControllers>::on_runtime_upgrade();
+        assert_eq!(NameService::controllers("test".as_bytes().to_vec()), None);
+    });
+}
+
+#[test]
+fn assign_legacy_controller_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        crate::UsernameRecords::<Runtime>::insert(
+            "test".as_bytes().to_vec(),
+            ZkAddressType::from(BOB),
+        );
+        crate::UsernameRecords::<Runtime>::insert(
+            "legacy".as_bytes().to_vec(),
+            ZkAddressType::from(CHARLIE),
+        );
+
+        assert_noop!(
+            NameService::assign_legacy_controller(
+                MockOrigin::signed(BOB),
+                legacy_controllers(vec![("test", BOB)])
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        // A batch is assigned entirely or not at all
+        assert_noop!(
+            NameService::assign_legacy_controller(
+                MockOrigin::root(),
+                legacy_controllers(vec![("test", BOB), ("other", BOB)])
+            ),
+            Error::<Runtime>::NotRegistered
+        );
+        assert_ok!(NameService::assign_legacy_controller(
+            MockOrigin::root(),
+            legacy_controllers(vec![("test", BOB), ("legacy", CHARLIE)])
+        ));
+        System::assert_has_event(RuntimeEvent::NameService(Event::LegacyControllerAssigned {
+            username: "test".as_bytes().to_vec(),
+            controller: BOB,
+        }));
+        System::assert_last_event(RuntimeEvent::NameService(Event::LegacyControllerAssigned {
+            username: "legacy".as_bytes().to_vec(),
+            controller: CHARLIE,
+        }));
+        assert_noop!(
+            NameService::assign_legacy_controller(
+                MockOrigin::root(),
+                legacy_controllers(vec![("test", ALICE)])
+            ),
+            Error::<Runtime>::ControllerAlreadyAssigned
+        );
+        assert_ok!(NameService::remove_register(
+            MockOrigin::signed(BOB),
+            "test".as_bytes().to_vec(),
+            BOB.into()
+        ));
+        assert_ok!(NameService::remove_register(
+            MockOrigin::signed(CHARLIE),
+            "legacy".as_bytes().to_vec(),
+            CHARLIE.into()
+        ));
+    });
+}
+
+#[test]
+fn legacy_deposits_should_work() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        assert_ok!(Balances::force_set_balance(
+            MockOrigin::root(),
+            NameService::account_id(),
+            1_000,
+        ));
+        for (username, owner) in [("test", BOB), ("legacy", CHARLIE)] {
+            crate::UsernameRecords::<Runtime>::insert(
+                username.as_bytes().to_vec(),
+                ZkAddressType::from(owner),
+            );
+            crate::Expiries::<Runtime>::insert(username.as_bytes().to_vec(), 105);
+            crate::LegacyDeposits::<Runtime>::insert(username.as_bytes().to_vec(), 100);
+        }
+        crate::LegacyDepositTotal::<Runtime>::put(200);
+
+        // Legacy deposits stay in the pallet account
+        assert_ok!(NameService::sweep_fees(MockOrigin::root()));
+        let minimum_balance = Balances::minimum_balance();
+        assert_eq!(
+            Balances::free_balance(NameService::account_id()),
+            200 + minimum_balance
+        );
+        assert_eq!(
+            Balances::free_balance(TreasuryAccount::get()),
+            800 - minimum_balance
+        );
+
+        // and are reserved for the controller once assigned
+        let bob_balance = Balances::free_balance(BOB);
+        assert_ok!(NameService::assign_legacy_controller(
+            MockOrigin::root(),
+            legacy_controllers(vec![("test", BOB)])
+        ));
+        assert_eq!(
+            NameService::legacy_deposits("test".as_bytes().to_vec()),
+            None
+        );
+        assert_eq!(NameService::legacy_deposit_total(), 100);
+        assert_eq!(
+            NameService::deposits("test".as_bytes().to_vec()),
+            Some((BOB, 100))
+        );
+        assert_eq!(Balances::free_balance(BOB), bob_balance);
+        assert_eq!(Balances::reserved_balance(BOB), 100);
+        assert_eq!(
+            Balances::free_balance(NameService::account_id()),
+            100 + minimum_balance
+        );
+
+        // A released legacy name without a controller leaves its deposit as a fee
+        System::set_block_number(126);
+        NameService::on_idle(126, Weight::MAX);
+        assert!(!crate::UsernameRecords::<Runtime>::contains_key(
+            "test".as_bytes().to_vec()
+        ));
+        assert!(!crate::UsernameRecords::<Runtime>::contains_key(
+            "legacy".as_bytes().to_vec()
+        ));
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(
+            NameService::legacy_deposits("legacy".as_bytes().to_vec()),
+            None
+        );
+        assert_eq!(NameService::legacy_deposit_total(), 0);
+        assert_ok!(NameService::sweep_fees(MockOrigin::root()));
+        assert_eq!(
+            Balances::free_balance(NameService::account_id()),
+            minimum_balance
+        );
+    });
+}
+
+#[test]
+fn username_format_test() {
+    ExtBuilder.build().execute_with(|| {
+        initialize_test();
+        let valid = [
+            // 3 char test
+            "two",
+            // 64 char test
+            "bLuuXKxxOpqCsTvSglVeQvEbYoNVeswqbLuuXKxxOpqCsTvSglVeQvEbYoNVeswq",
+            // allowed special and numerics
+            "test_test-123",
+            "test123",
+        ];
+        for username in valid {
+            assert_ok!(NameService::register(
+                MockOrigin::signed(ALICE),
+                commitment(username, ALICE)
+            ));
+        }
+        System::set_block_number(5);
+        for username in valid {
+            assert_ok!(NameService::accept_register(
+                MockOrigin::signed(ALICE),
+                username.as_bytes().to_vec(),
+                ALICE.into(),
+                SALT
+            ));
+        }
+
+        // test invalid formats, rejected when revealed
+        let invalid = [
+            // starting special signs
+            ".test",
+            "_test",
+            // starting numbers
+            "1test",
+            // ending with special character
+            "test.",
+            "test_",
+            // too short
+            "ab",
+            // subnames are only created through their parent
+            "sub.test",
+            // too long 65+
+            "bLuuXKxxOpqCsTvSglVeQvEbYoNVeswqbLuuXKxxOpqCsTvSglVeQvEbYoNVeswqa",
+        ];
+        for username in invalid {
+            assert_ok!(NameService::register(
+                MockOrigin::signed(ALICE),
+                commitment(username, ALICE)
+            ));
+        }
+        System::set_block_number(10);
+        for username in invalid {
+            assert_noop!(
+                NameService::accept_register(
+                    MockOrigin::signed(ALICE),
+                    username.as_bytes().to_vec(),
+                    ALICE.into(),
+                    SALT
+                ),
+                Error::<Runtime>::InvalidUsernameFormat
+            );
+        }
     });
 }
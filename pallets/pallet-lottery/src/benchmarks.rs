@@ -17,8 +17,12 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 //! Benchmarking
-use crate::{Call, Config, Pallet, Request};
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, Zero};
+use crate::{
+    AutoRedeposit, Call, Config, DepositStrategyKind, DuplicateWinnerPolicy, LiquidityBuffer,
+    Pallet, PendingRedeposits, PrizeTier, Request, TicketCount, TotalUnclaimedWinnings,
+    UnclaimedWinningsByAccount, WithdrawStrategyKind, MAX_WINNERS_PER_DRAWING,
+};
+use frame_benchmarking::{benchmarks_instance_pallet, impl_benchmark_test_suite, Zero};
 use frame_support::{
     assert_ok,
     traits::{Currency, EstimateCallFee, Get, OnFinalize, OnInitialize},
@@ -28,13 +32,15 @@ use pallet_parachain_staking::{
     benchmarks::{create_funded_collator, create_funded_user, parachain_staking_on_finalize},
     BalanceOf, Pallet as Staking,
 };
-use sp_runtime::Saturating;
+use sp_runtime::{Percent, Saturating};
+use sp_std::vec;
 
 const MAX_COLLATOR_COUNT: u32 = 63;
+const MAX_TICKET_TREE_DEPTH: u32 = 20;
 const USER_SEED: u32 = 696969;
 
 /// Run to end block and author
-fn roll_rounds_and_author<T: Config>(rounds: u32) {
+fn roll_rounds_and_author<T: Config<I>, I: 'static>(rounds: u32) {
     let total_rounds = rounds + 1u32;
     let round_length: T::BlockNumber = Staking::<T>::round().length.into();
     let mut now = <frame_system::Pallet<T>>::block_number() + 1u32.into();
@@ -53,14 +59,14 @@ fn roll_rounds_and_author<T: Config>(rounds: u32) {
     }
 }
 
-fn fund_lottery_account<T: Config>(bal: BalanceOf<T>) {
+fn fund_lottery_account<T: Config<I>, I: 'static>(bal: BalanceOf<T>) {
     <T as pallet_parachain_staking::Config>::Currency::deposit_creating(
-        &Pallet::<T>::account_id(),
+        &Pallet::<T, I>::account_id(),
         bal,
     );
 }
 
-fn register_collators<T: Config>(number: u32) {
+fn register_collators<T: Config<I>, I: 'static>(number: u32) {
     let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
     let mut collator_seed: u32 = 444;
     for _ in 0..number {
@@ -75,150 +81,219 @@ fn register_collators<T: Config>(number: u32) {
     }
 }
 
-fn deposit_prior_users<T: Config>(number: u32, amount: BalanceOf<T>) {
+/// Allocates ticket positions so the next depositor is placed at `depth` of the ticket trees
+fn fill_ticket_positions<T: Config<I>, I: 'static>(depth: u32) {
+    TicketCount::<T, I>::put((1u32 << (depth - 1)) - 1);
+}
+
+/// Allocates ticket positions so updates of the first position walk `depth` levels of the ticket trees
+fn grow_ticket_trees<T: Config<I>, I: 'static>(depth: u32) {
+    TicketCount::<T, I>::put(1u32 << (depth - 1));
+}
+
+/// Credits the minimum deposit as unclaimed winnings to `number` accounts opted into auto redeposit
+fn pending_redeposits<T: Config<I>, I: 'static>(number: u32) {
+    let winnings = Pallet::<T, I>::min_deposit();
+    fund_lottery_account::<T, I>(winnings.saturating_mul(number.into()));
     for user in 0..number {
-        <frame_system::Pallet<T>>::set_block_number(user.into());
-        let (depositor, _) = create_funded_user::<T>("depositor", USER_SEED - 1 - user, amount);
-        assert_ok!(Pallet::<T>::deposit(
-            RawOrigin::Signed(depositor).into(),
-            amount
-        ));
+        let (winner, _) = create_funded_user::<T>("winner", USER_SEED + 1 + user, Zero::zero());
+        UnclaimedWinningsByAccount::<T, I>::insert(&winner, winnings);
+        AutoRedeposit::<T, I>::insert(&winner, ());
+        PendingRedeposits::<T, I>::insert(&winner, ());
     }
+    TotalUnclaimedWinnings::<T, I>::mutate(|total| {
+        *total = total.saturating_add(winnings.saturating_mul(number.into()))
+    });
 }
 
-benchmarks! {
+benchmarks_instance_pallet! {
     // USER DISPATCHABLES
 
     deposit {
-        let x in 0u32..1_000u32; // other users that have already deposited to the lottery previously
+        let d in 1..MAX_TICKET_TREE_DEPTH; // depth of the ticket trees updated by the deposit
         let y in 0..MAX_COLLATOR_COUNT; // registered collators
 
-        fund_lottery_account::<T>(Pallet::<T>::gas_reserve());
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
         let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
         let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
         let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
         // fill collators
-        register_collators::<T>(y);
+        register_collators::<T, I>(y);
         assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
-        assert_eq!(Pallet::<T>::total_pot(), Zero::zero());
+        assert_eq!(Pallet::<T, I>::total_pot(), Zero::zero());
 
         let original_staked_amount = Staking::<T>::total();
-        deposit_prior_users::<T>(x,deposit_amount);
-
-        let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
+        let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount.saturating_mul(2u32.into()));
+        // the first position has a node on every level of the ticket trees
+        assert_ok!(Pallet::<T, I>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        grow_ticket_trees::<T, I>(d);
+        // deposits made into the drawing period are penalized as well
+        <frame_system::Pallet<T>>::set_block_number(<frame_system::Pallet<T>>::block_number() + 1u32.into());
     }: _(RawOrigin::Signed(caller.clone()), deposit_amount)
     verify {
-        assert_eq!(Pallet::<T>::active_balance_per_user(caller), deposit_amount);
-        assert_eq!(Pallet::<T>::total_pot(), deposit_amount.saturating_mul((x+1).into()));
-        assert_eq!(Staking::<T>::total(), original_staked_amount + deposit_amount.saturating_mul((x+1).into()));
+        assert_eq!(Pallet::<T, I>::active_balance_per_user(caller), deposit_amount.saturating_mul(2u32.into()));
+        assert_eq!(Pallet::<T, I>::total_pot(), deposit_amount.saturating_mul(2u32.into()));
+        assert_eq!(Staking::<T>::total(), original_staked_amount + deposit_amount.saturating_mul(2u32.into()));
     }
 
     request_withdraw{
-        let x in 0..1_000; // other users that have already deposited to the lottery previously
+        let d in 1..MAX_TICKET_TREE_DEPTH; // depth of the ticket trees updated by the withdrawal
         let y in 0..MAX_COLLATOR_COUNT; // registered collators
 
-        fund_lottery_account::<T>(Pallet::<T>::gas_reserve());
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
         let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
         let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
         let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
         // fill collators
-        register_collators::<T>(y);
+        register_collators::<T, I>(y);
         assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
 
-        let original_staked_amount = Staking::<T>::total();
-        deposit_prior_users::<T>(x,deposit_amount);
-
         let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
-        assert_ok!(Pallet::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
-        assert_eq!(Pallet::<T>::active_balance_per_user(caller.clone()), deposit_amount);
+        // the first position has a node on every level of the ticket trees
+        assert_ok!(Pallet::<T, I>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        assert_eq!(Pallet::<T, I>::active_balance_per_user(caller.clone()), deposit_amount);
+        grow_ticket_trees::<T, I>(d);
+        // withdrawals made into the drawing period are credited as well
+        <frame_system::Pallet<T>>::set_block_number(<frame_system::Pallet<T>>::block_number() + 1u32.into());
     }: _(RawOrigin::Signed(caller.clone()), deposit_amount)
     verify {
-        assert!(Pallet::<T>::active_balance_per_user(caller.clone()).is_zero());
+        assert!(Pallet::<T, I>::active_balance_per_user(caller.clone()).is_zero());
         let now = <frame_system::Pallet<T>>::block_number();
         let should_be_request = Request {
             user: caller.clone(),
             block: now,
             balance: deposit_amount,
         };
-        let mut request_queue = Pallet::<T>::withdrawal_request_queue();
+        let mut request_queue = Pallet::<T, I>::withdrawal_request_queue();
         assert_eq!(request_queue.len(),1usize);
         assert_eq!(request_queue.pop().unwrap(), should_be_request);
     }
 
+    sponsor {
+        let y in 0..MAX_COLLATOR_COUNT; // registered collators
+
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
+        let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+        let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
+        let sponsor_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
+        // fill collators
+        register_collators::<T, I>(y);
+        assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
+
+        let original_staked_amount = Staking::<T>::total();
+        let (caller, _) = create_funded_user::<T>("caller", USER_SEED, sponsor_amount);
+    }: _(RawOrigin::Signed(caller.clone()), sponsor_amount)
+    verify {
+        assert_eq!(Pallet::<T, I>::sponsor_balance(caller), sponsor_amount);
+        assert_eq!(Pallet::<T, I>::total_sponsored(), sponsor_amount);
+        assert_eq!(Staking::<T>::total(), original_staked_amount + sponsor_amount);
+    }
+
+    request_sponsor_withdraw {
+        let y in 0..MAX_COLLATOR_COUNT; // registered collators
+
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
+        let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+        let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
+        let sponsor_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
+        // fill collators
+        register_collators::<T, I>(y);
+        assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
+
+        let (caller, _) = create_funded_user::<T>("caller", USER_SEED, sponsor_amount);
+        assert_ok!(Pallet::<T, I>::sponsor(RawOrigin::Signed(caller.clone()).into(), sponsor_amount));
+    }: _(RawOrigin::Signed(caller.clone()), sponsor_amount)
+    verify {
+        assert!(Pallet::<T, I>::sponsor_balance(caller).is_zero());
+        assert!(Pallet::<T, I>::total_sponsored().is_zero());
+        assert_eq!(Pallet::<T, I>::withdrawal_request_queue().len(), 1usize);
+    }
+
     claim_my_winnings {
         let y in 0..MAX_COLLATOR_COUNT; // registered collators
 
         // NOTE: We fund 2x gas reserve to have 1x gas reserve to pay out as winnings
-        fund_lottery_account::<T>(Pallet::<T>::gas_reserve().saturating_add(Pallet::<T>::gas_reserve()));
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve().saturating_add(Pallet::<T, I>::gas_reserve()));
 
         let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
         let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
         let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
         // fill collators
-        register_collators::<T>(y);
+        register_collators::<T, I>(y);
         assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
 
         let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
-        assert_ok!(Pallet::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
-        assert_eq!(Pallet::<T>::active_balance_per_user(caller.clone()), deposit_amount);
-        roll_rounds_and_author::<T>(2);
-        assert_ok!(Pallet::<T>::draw_lottery(RawOrigin::Root.into()));
+        assert_ok!(Pallet::<T, I>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        assert_eq!(Pallet::<T, I>::active_balance_per_user(caller.clone()), deposit_amount);
+        roll_rounds_and_author::<T, I>(2);
+        assert_ok!(Pallet::<T, I>::draw_lottery(RawOrigin::Root.into()));
         // should have won now
-        let unclaimed_winnings = Pallet::<T>::total_unclaimed_winnings();
+        let unclaimed_winnings = Pallet::<T, I>::total_unclaimed_winnings();
         let account_balance_before = <T as pallet_parachain_staking::Config>::Currency::free_balance(&caller);
-        let fee_estimate  = T::EstimateCallFee::estimate_call_fee(&Call::<T>::claim_my_winnings {  }, None.into());
+        let fee_estimate  = T::EstimateCallFee::estimate_call_fee(&Call::<T, I>::claim_my_winnings {  }, None.into());
         assert!(!unclaimed_winnings.is_zero());
-        assert_eq!(unclaimed_winnings,Pallet::<T>::unclaimed_winnings_by_account(caller.clone()).unwrap());
+        assert_eq!(unclaimed_winnings,Pallet::<T, I>::unclaimed_winnings_by_account(caller.clone()).unwrap());
     }: _(RawOrigin::Signed(caller.clone()))
     verify {
-        assert!(Pallet::<T>::total_unclaimed_winnings().is_zero());
+        assert!(Pallet::<T, I>::total_unclaimed_winnings().is_zero());
         let account_balance_after = <T as pallet_parachain_staking::Config>::Currency::free_balance(&caller);
-        assert!(Pallet::<T>::unclaimed_winnings_by_account(caller.clone()).is_none());
+        assert!(Pallet::<T, I>::unclaimed_winnings_by_account(caller.clone()).is_none());
         assert!(account_balance_after >= account_balance_before + unclaimed_winnings - fee_estimate);
         assert!(account_balance_after <= account_balance_before + unclaimed_winnings);
     }
 
     // ROOT DISPATCHABLES
     start_lottery {
-                fund_lottery_account::<T>(Pallet::<T>::gas_reserve());
+                fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
     }: _(RawOrigin::Root)
     verify {
-        assert!(Pallet::<T>::next_drawing_at().is_some());
+        assert!(Pallet::<T, I>::next_drawing_at().is_some());
     }
 
     stop_lottery {
-                fund_lottery_account::<T>(Pallet::<T>::gas_reserve());
-        assert_ok!(Pallet::<T>::start_lottery(RawOrigin::Root.into()));
+                fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
+        assert_ok!(Pallet::<T, I>::start_lottery(RawOrigin::Root.into()));
     }: _(RawOrigin::Root)
     verify {
-        assert!(Pallet::<T>::next_drawing_at().is_none());
+        assert!(Pallet::<T, I>::next_drawing_at().is_none());
     }
 
     draw_lottery {
-        let x in 0..1_000; // other users that have already deposited to the lottery previously
+        let d in 1..MAX_TICKET_TREE_DEPTH; // depth of the ticket trees every winner is looked up in
         let y in 0..MAX_COLLATOR_COUNT; // registered collators
+        let w in 1..MAX_WINNERS_PER_DRAWING; // winners drawn, every one of them possibly redrawn
+        let r in 0..1_000; // winners of previous drawings redepositing their winnings
 
         // NOTE: We fund 2x gas reserve to have 1x gas reserve to pay out as winnings
-        fund_lottery_account::<T>(Pallet::<T>::gas_reserve().saturating_add(Pallet::<T>::gas_reserve()));
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve().saturating_add(Pallet::<T, I>::gas_reserve()));
+        assert_ok!(Pallet::<T, I>::set_prize_tiers(
+            RawOrigin::Root.into(),
+            vec![PrizeTier { share: Percent::from_percent(100), winners: w }],
+            DuplicateWinnerPolicy::Redraw,
+        ));
 
         let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
         let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
         let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
         // fill collators
-        register_collators::<T>(y);
+        register_collators::<T, I>(y);
         assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
 
-        deposit_prior_users::<T>(x,deposit_amount);
+        fill_ticket_positions::<T, I>(d);
 
         let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
-        assert_ok!(Pallet::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
-        assert_eq!(Pallet::<T>::active_balance_per_user(&caller), deposit_amount);
-        // roll_rounds_and_author::<T>(2);
+        assert_ok!(Pallet::<T, I>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        assert_eq!(Pallet::<T, I>::active_balance_per_user(&caller), deposit_amount);
+        pending_redeposits::<T, I>(r);
+        // let the deposit accrue ticket weight
+        <frame_system::Pallet<T>>::set_block_number(<frame_system::Pallet<T>>::block_number() + 10u32.into());
+        // roll_rounds_and_author::<T, I>(2);
     }: _(RawOrigin::Root)
     verify {
         // someone should have won now
-        let unclaimed_winnings = Pallet::<T>::total_unclaimed_winnings();
+        let unclaimed_winnings = Pallet::<T, I>::total_unclaimed_winnings();
         assert!(!unclaimed_winnings.is_zero());
+        assert!(PendingRedeposits::<T, I>::count().is_zero());
     }
 
     process_matured_withdrawals {
@@ -226,7 +301,7 @@ benchmarks! {
     verify {
     }
     set_min_deposit {
-        assert_ok!(Pallet::<T>::set_min_withdraw(RawOrigin::Root.into(),u32::MAX.into()));
+        assert_ok!(Pallet::<T, I>::set_min_withdraw(RawOrigin::Root.into(),u32::MAX.into()));
     }: _(RawOrigin::Root,u32::MAX.into())
     verify {
     }
@@ -238,6 +313,47 @@ benchmarks! {
     }: _(RawOrigin::Root,u32::MAX.into())
     verify {
     }
+    set_prize_tiers {
+        let t in 0..MAX_WINNERS_PER_DRAWING; // tiers with one winner each
+        let tiers = vec![PrizeTier { share: Percent::from_percent(1), winners: 1 }; t as usize];
+    }: _(RawOrigin::Root, tiers, DuplicateWinnerPolicy::Redraw)
+    verify {
+        assert_eq!(Pallet::<T, I>::prize_tiers().len() as u32, t);
+    }
+    set_staking_strategies {
+        let deposit_strategy = DepositStrategyKind::MaxDiversification { cap: Percent::from_percent(10) };
+    }: _(RawOrigin::Root, deposit_strategy, WithdrawStrategyKind::UnstakeLargestDelegations)
+    verify {
+        assert_eq!(Pallet::<T, I>::deposit_strategy(), deposit_strategy);
+    }
+    set_liquidity_buffer {
+        let target: BalanceOf<T> = u32::MAX.into();
+    }: _(RawOrigin::Root, target, Percent::from_percent(1))
+    verify {
+        assert_eq!(Pallet::<T, I>::liquidity_buffer_target(), target);
+        assert_eq!(Pallet::<T, I>::instant_withdraw_fee(), Percent::from_percent(1));
+    }
+    instant_withdraw {
+        let d in 1..MAX_TICKET_TREE_DEPTH; // depth of the ticket trees updated by the withdrawal
+
+        let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+        let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
+        // the buffer is held unstaked in the pallet next to the gas reserve
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve().saturating_add(deposit_amount));
+        LiquidityBuffer::<T, I>::put(deposit_amount);
+        assert_ok!(Pallet::<T, I>::set_liquidity_buffer(RawOrigin::Root.into(), deposit_amount, Percent::from_percent(1)));
+
+        let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
+        // the first position has a node on every level of the ticket trees
+        assert_ok!(Pallet::<T, I>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        grow_ticket_trees::<T, I>(d);
+        // withdrawals made into the drawing period are credited as well
+        <frame_system::Pallet<T>>::set_block_number(<frame_system::Pallet<T>>::block_number() + 1u32.into());
+    }: _(RawOrigin::Signed(caller.clone()), deposit_amount)
+    verify {
+        assert!(Pallet::<T, I>::active_balance_per_user(caller).is_zero());
+        assert!(Pallet::<T, I>::liquidity_buffer().is_zero());
+    }
     // rebalance_stake {
     // }: _()
     // verify {
@@ -295,6 +411,18 @@ mod tests {
         });
     }
     #[test]
+    fn bench_sponsor() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_sponsor());
+        });
+    }
+    #[test]
+    fn bench_request_sponsor_withdraw() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_request_sponsor_withdraw());
+        });
+    }
+    #[test]
     fn bench_claim_my_winnings() {
         new_test_ext().execute_with(|| {
             assert_ok!(Pallet::<Test>::test_benchmark_claim_my_winnings());
@@ -324,6 +452,31 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_draw_lottery());
         });
     }
+    #[test]
+    fn bench_set_prize_tiers() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_set_prize_tiers());
+        });
+    }
+    #[test]
+    fn bench_set_staking_strategies() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_set_staking_strategies());
+        });
+    }
+    #[test]
+    fn bench_set_liquidity_buffer() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_set_liquidity_buffer());
+        });
+    }
+
+    #[test]
+    fn bench_instant_withdraw() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_instant_withdraw());
+        });
+    }
     // #[test]
     // fn bench_process_matured_withdrawals() {
     //     new_test_ext().execute_with(|| {
@@ -0,0 +1,97 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Drawing history and lifetime statistics of lottery users
+//!
+//! Drawings are recorded under the [`DrawingPeriod`] they end, only the last [`MAX_DRAWING_HISTORY`] are kept.
+//! A user's participation is counted in drawing periods: [`UserStats::active_since`] marks the period their active
+//! balance became non-zero in and every drawing held since then is settled into
+//! [`UserStats::drawings_participated`] once it drops back to zero.
+
+use super::*;
+use pallet_parachain_staking::BalanceOf;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::prelude::*;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+    /// Stores the outcome of the drawing ending the current drawing period and prunes the oldest record
+    pub(crate) fn record_drawing(
+        prize_pool: BalanceOf<T>,
+        winners: Vec<(T::AccountId, BalanceOf<T>)>,
+        participating_funds: BalanceOf<T>,
+    ) {
+        let period = DrawingPeriod::<T, I>::get();
+        DrawingHistory::<T, I>::insert(
+            period,
+            DrawingRecord {
+                drawn_at: <frame_system::Pallet<T>>::block_number(),
+                prize_pool,
+                winners,
+                participating_funds,
+                participating_users: Self::total_users(),
+            },
+        );
+        if let Some(oldest) = period.checked_sub(MAX_DRAWING_HISTORY) {
+            DrawingHistory::<T, I>::remove(oldest);
+        }
+    }
+
+    /// Records a deposit of `amount` by `account`
+    /// Must be called before the deposit is added to [`ActiveBalancePerUser`]
+    pub(crate) fn record_deposit(account: &T::AccountId, amount: BalanceOf<T>) {
+        let is_active = !Self::active_balance_per_user(account).is_zero();
+        UserLifetimeStats::<T, I>::mutate(account, |stats| {
+            stats.total_deposited = stats.total_deposited.saturating_add(amount);
+            if !is_active {
+                stats.active_since = Some(DrawingPeriod::<T, I>::get());
+            }
+        });
+    }
+
+    /// Settles the drawings `account` participated in when its active balance drops to zero
+    pub(crate) fn record_exit(account: &T::AccountId) {
+        UserLifetimeStats::<T, I>::mutate(account, |stats| {
+            // Balances deposited before statistics were tracked count from the first drawing period
+            let active_since = stats.active_since.take().unwrap_or_default();
+            stats.drawings_participated = stats
+                .drawings_participated
+                .saturating_add(DrawingPeriod::<T, I>::get().saturating_sub(active_since));
+        });
+    }
+
+    /// The recorded drawings, oldest first
+    pub fn drawing_history() -> Vec<DrawingRecordOf<T>> {
+        let current = DrawingPeriod::<T, I>::get();
+        (current.saturating_sub(MAX_DRAWING_HISTORY)..current)
+            .filter_map(DrawingHistory::<T, I>::get)
+            .collect()
+    }
+
+    /// Lifetime statistics of `account`, counting drawings held since its active balance last became non-zero
+    pub fn user_stats(account: &T::AccountId) -> UserStats<BalanceOf<T>> {
+        let mut stats = UserLifetimeStats::<T, I>::get(account);
+        // Balances deposited before statistics were tracked count from the first drawing period
+        let active_since = stats
+            .active_since
+            .or_else(|| (!Self::active_balance_per_user(account).is_zero()).then_some(0));
+        if let Some(active_since) = active_since {
+            stats.drawings_participated = stats
+                .drawings_participated
+                .saturating_add(DrawingPeriod::<T, I>::get().saturating_sub(active_since));
+        }
+        stats
+    }
+}
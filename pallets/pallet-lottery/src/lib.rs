@@ -26,12 +26,18 @@
 //! ### Lottery Rules
 //! 1. A drawing is scheduled to happen every [`Config::DrawingInterval`] blocks.
 //! 2. A designated manager can start & stop the drawings as well as rebalance the stake to improve the yield generated through staking
-//! 3. In order to prevent gaming of the lottery drawing mechanism, no modifications to this pallet are allowed [`Config::DrawingFreezeout`] blocks before a drawing
+//! 3. The chance to win is proportional to the user's active balance averaged over the blocks since the previous drawing,
+//!     funds deposited just before a drawing have close to no chance to win
+//! 4. In order to prevent gaming of the lottery drawing mechanism, no modifications to this pallet are allowed [`Config::DrawingFreezeout`] blocks before a drawing
 //!     This is needed e.g. using BABE Randomness, where the randomness will be known a day before the scheduled drawing
-//! 4. Winnings must be claimed manually by the winner but there is no time limit for claiming winnings
-//! 5. Deposits are instantly staked by the pallet
-//! 6. Withdrawals must wait for a timelock imposed by [`pallet_parachain_staking`] and are paid out automatically (via scheduler) in the first lottery drawing after it expires
-//! 7. The [`Config::ManageOrigin`] must at the same time be allowed to use [`frame_support::traits::schedule::Named`] e.g. `ScheduleOrigin` in `pallet_scheduler`
+//! 5. Winnings must be claimed manually by the winner but there is no time limit for claiming winnings
+//!     Winners opting into [`AutoRedeposit`] instead have their winnings deposited at the end of every drawing
+//!     The prize pool goes to a single winner unless split into [`PrizeTiers`], every payout of a tier is drawn independently
+//! 6. Deposits are instantly staked by the pallet
+//!     Sponsored funds are staked alike, their yield raises the prize pool but sponsors hold no tickets and never win
+//! 7. Withdrawals must wait for a timelock imposed by [`pallet_parachain_staking`] and are paid out automatically (via scheduler) in the first lottery drawing after it expires
+//!     Alternatively, withdrawals can be paid instantly from an unstaked [`LiquidityBuffer`] for an [`InstantWithdrawFee`] added to the prize pool
+//! 8. The [`Config::ManageOrigin`] must at the same time be allowed to use [`frame_support::traits::schedule::Named`] e.g. `ScheduleOrigin` in `pallet_scheduler`
 //!
 //! ## Dependencies
 //! 1. To enable fair winner selection, a fair and low-influience randomness provider implementing [`frame_support::traits::Randomness`], e.g. pallet_randomness
@@ -47,6 +53,10 @@
 //! * [`Call::deposit`]: Allows any user to deposit tokens into the lottery
 //! * [`Call::request_withdraw`]: Allows any user to request return of their deposited tokens to own wallet
 //! * [`Call::claim_my_winnings`]: Allows any user to transfer any accrued winnings into their wallet
+//! * [`Call::instant_withdraw`]: Allows any user to withdraw deposited tokens without timelock for a fee, as far as the liquidity buffer allows
+//! * [`Call::sponsor`]: Allows any user to lend tokens whose staking yield is added to the prize pool, without a chance to win
+//! * [`Call::request_sponsor_withdraw`]: Allows a sponsor to request return of their lent tokens to own wallet
+//! * [`Call::set_auto_redeposit`]: Allows any user to have their winnings deposited automatically instead of claiming them
 //!
 //! ### Manager Dispatchable Functions
 //! * [`Call::start_lottery`]: Schedules periodic lottery drawings to occur each [`Config::DrawingInterval`]
@@ -55,24 +65,41 @@
 //! * [`Call::process_matured_withdrawals`]: Immediately transfer funds of all matured withdrawals to their respective owner's wallets
 //! * [`Call::liquidate_lottery`]: Unstakes all lottery funds and schedules [`Call::process_matured_withdrawals`] after the timelock period
 //! * [`Call::rebalance_stake`]: Immediately unstakes overweight collators (with low APY) for later restaking into underweight collators (with high APY)
+//! * [`Call::set_prize_tiers`]: Splits the prize pool of every drawing into tiers with one or more winners each
+//! * [`Call::set_staking_strategies`]: Selects the [`DepositStrategy`] and [`WithdrawStrategy`] used to stake and unstake collators
+//! * [`Call::set_liquidity_buffer`]: Sizes the unstaked buffer paying out instant withdrawals and the fee charged for them
 //!
 //! ### Important state queries callable via RPC
 //! * [`Pallet::next_drawing_at`]: Block number where the next drawing will happen
 //! * [`Pallet::not_in_drawing_freezeout`]: False if deposits/withdrawals are currently frozen
 //! * [`Pallet::current_prize_pool`]: Token amount currently in the pallet the winner would get if the drawing was now
-//! Call these from a frontend as e.g.
+//! * [`Pallet::effective_odds`]: Chance of an account to win a payout if the drawing was now
+//! * [`Pallet::drawing_history`]: Block, prize pool, winners and participation of the last [`MAX_DRAWING_HISTORY`] drawings
+//! * [`Pallet::user_stats`]: Total winnings, total deposits and number of drawings participated in of an account
+//! All queries take the `LotteryId` of the instance to query as optional last parameter, defaulting to lottery `0`,
+//! and fail for unknown ids. Call these from a frontend as e.g.
 //! ```bash
 //!    curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0","id":1,"method":"lottery_next_drawing_at","params": []}'
 //!    curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0","id":1,"method":"lottery_current_prize_pool","params": []}'
-//!    curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0","id":1,"method":"lottery_not_in_drawing_freezeout","params": []}'
+//!    curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0","id":1,"method":"lottery_not_in_drawing_freezeout","params": [1]}'
+//!    curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0","id":1,"method":"lottery_effective_odds","params": ["dmu...", 1]}'
 //! ```
 //!
+//! ### Running several lotteries
+//! The pallet is instantiable: each instance needs a distinct [`Config::LotteryId`], which selects its pot
+//! (id `0` uses the [`Config::LotteryPot`] account, any other id a sub-account of it) and its drawing schedule,
+//! so e.g. a daily and a weekly lottery can run side by side with independent deposits and prize pools.
+//!
 //! Please refer to [`Pallet`] for more documentation on each function.
 //! Furthermore, the storage items containing all relevant information about lottery state can be queried via e.g. the [polkadot.js API](https://polkadot.js.org/docs/api)
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod history;
 mod staking;
+mod tickets;
+
+pub mod migrations;
 
 #[cfg(feature = "rpc")]
 pub mod rpc;
@@ -89,6 +116,11 @@ pub mod weights;
 pub use weights::WeightInfo;
 
 pub use pallet::*;
+pub use staking::{
+    DepositStrategy, EvenSpreadTopApyCollators, MaxDiversification, ReactivateBottomCollators,
+    SplitToUnderallocatedCollators, StakeToRandomCollator, UnstakeInactiveCollators,
+    UnstakeLargestDelegations, UnstakeLeastApyCollators, WithdrawStrategy,
+};
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -113,27 +145,35 @@ pub mod pallet {
     use sp_core::U256;
     use sp_runtime::{
         traits::{AccountIdConversion, CheckedAdd, CheckedSub, Dispatchable, Saturating, Zero},
-        ArithmeticError, DispatchResult,
+        ArithmeticError, DispatchResult, PerThing, Percent,
     };
     use sp_std::prelude::*;
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
-    pub type CallOf<T> = <T as Config>::RuntimeCall;
+    /// Upper bound on the number of payouts in a single drawing, across all prize tiers
+    pub const MAX_WINNERS_PER_DRAWING: u32 = 100;
+    /// Number of times a payout is redrawn under [`DuplicateWinnerPolicy::Redraw`] before it rolls over to the next drawing
+    const MAX_REDRAWS_PER_PAYOUT: u32 = 10;
+    /// Number of past drawings kept in [`DrawingHistory`]
+    pub const MAX_DRAWING_HISTORY: u32 = 100;
+
+    pub type CallOf<T, I = ()> = <T as Config<I>>::RuntimeCall;
 
     #[pallet::config]
-    pub trait Config:
+    pub trait Config<I: 'static = ()>:
         frame_system::Config + pallet_parachain_staking::Config + pallet_farming::Config
     {
         /// The aggregated `RuntimeCall` type.
         type RuntimeCall: Parameter
             + Dispatchable<RuntimeOrigin = Self::RuntimeOrigin>
-            + From<Call<Self>>;
-        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+            + From<Call<Self, I>>;
+        type RuntimeEvent: From<Event<Self, I>>
+            + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// The Scheduler.
         type Scheduler: ScheduleNamed<
             Self::BlockNumber,
-            CallOf<Self>,
+            CallOf<Self, I>,
             Self::PalletsOrigin,
             Hash = Self::Hash,
         >;
@@ -150,7 +190,7 @@ pub mod pallet {
         type EstimateCallFee: frame_support::traits::EstimateCallFee<
                 pallet_parachain_staking::Call<Self>,
                 BalanceOf<Self>,
-            > + frame_support::traits::EstimateCallFee<Call<Self>, BalanceOf<Self>>;
+            > + frame_support::traits::EstimateCallFee<Call<Self, I>, BalanceOf<Self>>;
         /// Origin that can manage lottery parameters and start/stop drawings
         type ManageOrigin: EnsureOrigin<Self::RuntimeOrigin>;
         /// Overarching type of all pallets origins.
@@ -158,6 +198,10 @@ pub mod pallet {
         /// Account Identifier from which the internal Pot is generated.
         #[pallet::constant]
         type LotteryPot: Get<PalletId>;
+        /// Identifier of this lottery among the instances of this pallet sharing the same [`Config::LotteryPot`]
+        /// Every lottery holds its funds in and schedules its drawings under a sub-account of the pot, except for lottery `0` which uses the pot account itself
+        #[pallet::constant]
+        type LotteryId: Get<u32>;
         /// Time in blocks between lottery drawings
         #[pallet::constant]
         type DrawingInterval: Get<Self::BlockNumber>;
@@ -176,7 +220,7 @@ pub mod pallet {
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
-    pub struct Pallet<T>(PhantomData<T>);
+    pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
     // Configurable (constant) storage items
 
@@ -184,15 +228,18 @@ pub mod pallet {
     /// This must be initialized at genesis, otherwise the pallet will run out of gas at the first drawing
     #[pallet::storage]
     #[pallet::getter(fn gas_reserve)]
-    pub(super) type GasReserve<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type GasReserve<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn min_deposit)]
-    pub(super) type MinDeposit<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type MinDeposit<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn min_withdraw)]
-    pub(super) type MinWithdraw<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type MinWithdraw<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     // Dynamic Storage Items
 
@@ -201,31 +248,132 @@ pub mod pallet {
     /// Decremented on withdrawal to user wallet in [`Call::process_matured_withdrawals`]
     #[pallet::storage]
     #[pallet::getter(fn sum_of_deposits)]
-    pub(super) type SumOfDeposits<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type SumOfDeposits<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     /// Total number of token eligible to win in the current drawing cycle
     /// Incremented on [`Call::deposit`]
     /// Decremented on [`Call::request_withdraw`]
     #[pallet::storage]
     #[pallet::getter(fn total_pot)]
-    pub(super) type TotalPot<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type TotalPot<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn total_users)]
-    pub(super) type TotalUsers<T: Config> = StorageValue<_, u32, ValueQuery>;
+    pub(super) type TotalUsers<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn is_rebalancing)]
-    pub(super) type RebalanceInProgress<T: Config> = StorageValue<_, bool, ValueQuery>;
+    pub(super) type RebalanceInProgress<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, bool, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn active_balance_per_user)]
-    pub(super) type ActiveBalancePerUser<T: Config> =
+    pub(super) type ActiveBalancePerUser<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
 
+    /// Principal lent to the lottery by sponsors, staked to add its yield to the prize pool
+    /// Sponsors hold no tickets and can never win, these funds are not part of [`TotalPot`]
+    #[pallet::storage]
+    #[pallet::getter(fn sponsor_balance)]
+    pub(super) type SponsorBalancePerAccount<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Sum of all [`SponsorBalancePerAccount`], included in [`SumOfDeposits`]
+    #[pallet::storage]
+    #[pallet::getter(fn total_sponsored)]
+    pub(super) type TotalSponsored<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Accounts whose unclaimed winnings are turned into an active deposit at the next drawing
+    #[pallet::storage]
+    pub(super) type AutoRedeposit<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Accounts opted into [`AutoRedeposit`] holding [`UnclaimedWinningsByAccount`]
+    /// Inserted when winnings are assigned or an account with winnings opts in
+    /// Drained in the course of a drawing, removed on [`Call::claim_my_winnings`] and opting out
+    #[pallet::storage]
+    pub(super) type PendingRedeposits<T: Config<I>, I: 'static = ()> =
+        CountedStorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Position of each depositor in [`TicketTree`], assigned on their first deposit
+    #[pallet::storage]
+    pub(super) type TicketIndexOf<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, OptionQuery>;
+
+    /// Depositor at each position of [`TicketTree`]
+    #[pallet::storage]
+    pub(super) type TicketHolder<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, T::AccountId, OptionQuery>;
+
+    /// Number of positions allocated in [`TicketTree`]
+    #[pallet::storage]
+    pub(super) type TicketCount<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+    /// Fenwick tree over [`ActiveBalancePerUser`] used to locate a winner in O(log n) reads
+    /// Node `i` holds the sum of the active balances at positions `(i - lowbit(i), i]`
+    /// Incremented on [`Call::deposit`]
+    /// Decremented on [`Call::request_withdraw`]
+    #[pallet::storage]
+    pub(super) type TicketTree<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, BalanceOf<T>, ValueQuery>;
+
+    /// Fenwick tree over `amount * blocks_into_period` of all deposits made in a drawing period
+    /// Incremented on [`Call::deposit`]
+    /// Pruned in `on_idle` once the drawing period ended
+    #[pallet::storage]
+    pub(super) type TicketPenalty<T: Config<I>, I: 'static = ()> =
+        StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, u32, BalanceOf<T>, ValueQuery>;
+
+    /// Fenwick tree over `amount * blocks_into_period` of all withdrawals made in a drawing period
+    /// Incremented on [`Call::request_withdraw`]
+    /// Pruned in `on_idle` once the drawing period ended
+    #[pallet::storage]
+    pub(super) type TicketCredit<T: Config<I>, I: 'static = ()> =
+        StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, u32, BalanceOf<T>, ValueQuery>;
+
+    /// Index of the current drawing period, incremented on every drawing
+    #[pallet::storage]
+    pub(super) type DrawingPeriod<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+    /// Oldest drawing period whose [`TicketPenalty`] and [`TicketCredit`] entries may not be pruned yet
+    /// Advanced in `on_idle` until it reaches [`DrawingPeriod`]
+    #[pallet::storage]
+    pub(super) type StaleTicketPeriod<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, u32, ValueQuery>;
+
+    /// Block the current drawing period started at
+    /// Tickets are weighted by the time their balance was deposited since this block
+    #[pallet::storage]
+    #[pallet::getter(fn drawing_period_start)]
+    pub(super) type DrawingPeriodStart<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    pub struct TicketCheckpoint<BlockNumber, Balance> {
+        /// Drawing period the checkpoint was taken in
+        pub period: u32,
+        /// Block of the last change to the depositor's active balance
+        pub last_update: BlockNumber,
+        /// Time-weighted active balance accrued in `period` up to `last_update`
+        pub accrued: Balance,
+    }
+
+    /// Ticket weight of each depositor as of their last deposit or withdrawal
+    #[pallet::storage]
+    pub(super) type TicketCheckpoints<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        TicketCheckpoint<T::BlockNumber, BalanceOf<T>>,
+        OptionQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn unclaimed_winnings_by_account)]
-    pub(super) type UnclaimedWinningsByAccount<T: Config> =
+    pub(super) type UnclaimedWinningsByAccount<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
 
     /// Free balance in the pallet that belongs to a previous lottery winner
@@ -233,14 +381,16 @@ pub mod pallet {
     /// Decremented on transfer of winnings to ower wallet in [`Call::claim_my_winnings`]
     #[pallet::storage]
     #[pallet::getter(fn total_unclaimed_winnings)]
-    pub(super) type TotalUnclaimedWinnings<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type TotalUnclaimedWinnings<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     /// Free balance in the pallet that was unstaked from a collator and is needed for future withdrawal requests
     /// Incremented on successful unstaking of a collator
     /// Decremented on transfer of funds to withdrawer and on restaking of funds a collator
     #[pallet::storage]
     #[pallet::getter(fn unlocked_unstaking_funds)]
-    pub(super) type UnlockedUnstakingFunds<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type UnlockedUnstakingFunds<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[derive(Clone, Encode, Decode, TypeInfo)]
     pub(super) struct UnstakingCollator<AccountId, BlockNumber> {
@@ -249,7 +399,7 @@ pub mod pallet {
     }
 
     #[pallet::storage]
-    pub(super) type UnstakingCollators<T: Config> =
+    pub(super) type UnstakingCollators<T: Config<I>, I: 'static = ()> =
         StorageValue<_, Vec<UnstakingCollator<T::AccountId, T::BlockNumber>>, ValueQuery>;
 
     /// This is balance unstaked from a collator that is not needed to service user's withdrawal requests
@@ -257,7 +407,29 @@ pub mod pallet {
     /// Decremented on [`Call::request_withdraw`] (no collator unstake) and [`Call::rebalance_stake`] (restaking of surplus funds)
     #[pallet::storage]
     #[pallet::getter(fn surplus_unstaking_balance)]
-    pub(super) type SurplusUnstakingBalance<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type SurplusUnstakingBalance<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Unstaked balance the pallet aims to keep available for [`Call::instant_withdraw`]
+    #[pallet::storage]
+    #[pallet::getter(fn liquidity_buffer_target)]
+    pub(super) type LiquidityBufferTarget<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Free balance in the pallet that is held back from staking to pay out instant withdrawals
+    /// Topped up to [`LiquidityBufferTarget`] from unlocked unstaking funds in the course of a drawing,
+    /// collators are unstaked for any shortfall not covered by funds already unstaking
+    /// Decremented on [`Call::instant_withdraw`]
+    #[pallet::storage]
+    #[pallet::getter(fn liquidity_buffer)]
+    pub(super) type LiquidityBuffer<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Share of an instant withdrawal kept by the pallet and added to the prize pool
+    #[pallet::storage]
+    #[pallet::getter(fn instant_withdraw_fee)]
+    pub(super) type InstantWithdrawFee<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, Percent, ValueQuery>;
 
     #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
     pub struct Request<AccountId, BlockNumber, Balance> {
@@ -268,14 +440,14 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn withdrawal_request_queue)]
-    pub(super) type WithdrawalRequestQueue<T: Config> =
+    pub(super) type WithdrawalRequestQueue<T: Config<I>, I: 'static = ()> =
         StorageValue<_, Vec<Request<T::AccountId, T::BlockNumber, BalanceOf<T>>>, ValueQuery>;
 
     /// Incremented whenever delegating tokens to a collator
     /// Collators are removed from here when their funds are unlocked in [`Call::finish_unstaking_collators`]
     #[pallet::storage]
     #[pallet::getter(fn staked_collators)]
-    pub(super) type StakedCollators<T: Config> =
+    pub(super) type StakedCollators<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
 
     #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -291,19 +463,134 @@ pub mod pallet {
 
     /// Boolean for the minting of a farming token on `deposit` call
     #[pallet::storage]
-    pub(super) type FarmingParameters<T: Config> = StorageValue<_, FarmingParamsOf<T>, ValueQuery>;
+    pub(super) type FarmingParameters<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, FarmingParamsOf<T>, ValueQuery>;
+
+    /// One tier of the prize pool paid out in a drawing
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    pub struct PrizeTier {
+        /// Share of the prize pool allocated to this tier
+        pub share: Percent,
+        /// Number of winners the tier's share is split evenly between
+        pub winners: u32,
+    }
+
+    /// Whether an account can receive more than one payout in the same drawing
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    pub enum DuplicateWinnerPolicy {
+        /// Every payout is drawn independently, an account may win several of them
+        #[default]
+        Allow,
+        /// A payout drawn for an account that already won in this drawing is redrawn
+        Redraw,
+    }
+
+    /// Tiers the prize pool is split into in every drawing
+    /// An empty list pays out the full prize pool to a single winner
+    /// Shares not adding up to 100% leave the remainder in the pot for the next drawing
+    #[pallet::storage]
+    #[pallet::getter(fn prize_tiers)]
+    pub(super) type PrizeTiers<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, Vec<PrizeTier>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn duplicate_winner_policy)]
+    pub(super) type DuplicateWinners<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, DuplicateWinnerPolicy, ValueQuery>;
+
+    /// Strategy distributing deposits to collators once collators that fell out of the active set are reactivated
+    /// Funds the strategy can not place are staked to a random active collator
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    pub enum DepositStrategyKind {
+        /// See [`SplitToUnderallocatedCollators`]
+        #[default]
+        SplitToUnderallocated,
+        /// See [`EvenSpreadTopApyCollators`]
+        EvenSpreadTopApy { collators: u32 },
+        /// See [`MaxDiversification`]
+        MaxDiversification { cap: Percent },
+    }
+
+    /// Strategy selecting collators to unstake once no more inactive collators are left to unstake
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    pub enum WithdrawStrategyKind {
+        /// See [`UnstakeLeastApyCollators`]
+        #[default]
+        UnstakeLeastApy,
+        /// See [`UnstakeLargestDelegations`]
+        UnstakeLargestDelegations,
+    }
+
+    #[pallet::storage]
+    #[pallet::getter(fn deposit_strategy)]
+    pub(super) type SelectedDepositStrategy<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, DepositStrategyKind, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn withdraw_strategy)]
+    pub(super) type SelectedWithdrawStrategy<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, WithdrawStrategyKind, ValueQuery>;
+
+    /// Outcome of a single lottery drawing
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    pub struct DrawingRecord<AccountId, Balance, BlockNumber> {
+        /// Block the drawing was executed in
+        pub drawn_at: BlockNumber,
+        /// Prize pool available to the drawing
+        pub prize_pool: Balance,
+        /// Every payout of the drawing, in the order it was drawn
+        pub winners: Vec<(AccountId, Balance)>,
+        /// Active balance of all depositors at the time of the drawing
+        pub participating_funds: Balance,
+        /// Number of depositors at the time of the drawing
+        pub participating_users: u32,
+    }
+
+    pub type DrawingRecordOf<T> = DrawingRecord<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// Last [`MAX_DRAWING_HISTORY`] drawings, keyed by the [`DrawingPeriod`] they ended
+    #[pallet::storage]
+    pub(super) type DrawingHistory<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, DrawingRecordOf<T>, OptionQuery>;
+
+    /// Lifetime statistics of a lottery user
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    #[derive(Clone, Debug, Default, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    pub struct UserStats<Balance> {
+        /// Sum of all payouts won
+        pub total_won: Balance,
+        /// Sum of all deposits made
+        pub total_deposited: Balance,
+        /// Number of drawings held while the user had an active balance
+        pub drawings_participated: u32,
+        /// Drawing period the user's active balance last became non-zero in, `None` while it is zero
+        pub active_since: Option<u32>,
+    }
+
+    /// Lifetime statistics of every account that ever deposited or won
+    /// `drawings_participated` is settled when the active balance drops to zero, see [`Pallet::user_stats`]
+    #[pallet::storage]
+    pub(super) type UserLifetimeStats<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, UserStats<BalanceOf<T>>, ValueQuery>;
 
     #[pallet::genesis_config]
-    pub struct GenesisConfig<T: Config> {
+    pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
         /// amount of token to keep in the pot for paying gas fees
         pub gas_reserve: BalanceOf<T>,
         pub min_deposit: BalanceOf<T>,
         pub min_withdraw: BalanceOf<T>,
         pub farming_pool_params: FarmingParamsOf<T>,
+        #[serde(skip)]
+        pub phantom: PhantomData<I>,
     }
 
     #[cfg(feature = "std")]
-    impl<T: Config> Default for GenesisConfig<T> {
+    impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
         fn default() -> Self {
             Self {
                 min_deposit: 1u32.into(),
@@ -315,24 +602,25 @@ pub mod pallet {
                     pool_id: 0,
                     currency_id: <T as pallet_farming::Config>::CurrencyId::default(),
                 },
+                phantom: PhantomData,
             }
         }
     }
 
     #[pallet::genesis_build]
-    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+    impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
         #[inline]
         fn build(&self) {
-            GasReserve::<T>::set(self.gas_reserve);
-            MinDeposit::<T>::set(self.min_deposit);
-            MinWithdraw::<T>::set(self.min_withdraw);
-            FarmingParameters::<T>::set(self.farming_pool_params);
+            GasReserve::<T, I>::set(self.gas_reserve);
+            MinDeposit::<T, I>::set(self.min_deposit);
+            MinWithdraw::<T, I>::set(self.min_withdraw);
+            FarmingParameters::<T, I>::set(self.farming_pool_params);
         }
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(fn deposit_event)]
-    pub enum Event<T: Config> {
+    pub enum Event<T: Config<I>, I: 'static = ()> {
         LotteryStarted,
         LotteryStopped,
         LotteryWinner {
@@ -355,10 +643,27 @@ pub mod pallet {
             account: T::AccountId,
             amount: BalanceOf<T>,
         },
+        InstantWithdrawn {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+            fee: BalanceOf<T>,
+        },
+        Sponsored {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        ScheduledSponsorWithdraw {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        WinningsRedeposited {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+        },
     }
 
     #[pallet::error]
-    pub enum Error<T> {
+    pub enum Error<T, I = ()> {
         /// Lottery has not been started
         LotteryNotStarted,
         /// Lottery has already been started
@@ -404,49 +709,45 @@ pub mod pallet {
         CouldNotSchedule,
         /// Fatal: Functionality not yet supported
         NotImplemented,
+        /// Prize tiers exceed the prize pool, have a tier without winners or too many winners in total
+        InvalidPrizeTiers,
+        /// Staking strategy would not stake to any collator
+        InvalidStakingStrategy,
+        /// The liquidity buffer can't cover this instant withdrawal
+        InsufficientLiquidity,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::prune_stale_tickets(remaining_weight)
+        }
     }
 
     #[pallet::call]
-    impl<T: Config> Pallet<T> {
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
         /// Allows any user to deposit tokens into the lottery
         ///
         /// # Arguments
         ///
         /// * `amount` - The amount of tokens to be deposited.
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as Config>::WeightInfo::deposit(Pallet::<T>::total_users(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::deposit(Pallet::<T, I>::ticket_tree_depth(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
         pub fn deposit(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
             let caller_account = ensure_signed(origin)?;
             ensure!(
                 amount >= Self::min_deposit(),
-                Error::<T>::DepositBelowMinAmount
+                Error::<T, I>::DepositBelowMinAmount
             );
             ensure!(
                 Self::not_in_drawing_freezeout(),
-                Error::<T>::TooCloseToDrawing
+                Error::<T, I>::TooCloseToDrawing
             );
             ensure! { // Sanity check: make sure we dont accept deposits that will fail in staking
                 Self::min_deposit() >= <T as pallet_parachain_staking::Config>::MinDelegation::get(),
-                Error::<T>::PalletMisconfigured
+                Error::<T, I>::PalletMisconfigured
             };
 
-            let farming_params = FarmingParameters::<T>::get();
-            if farming_params.mint_farming_token {
-                // mint JUMBO token and put it in farming pool
-                let convert_amount: T::BalanceConversion = amount.into();
-                <T as pallet_farming::Config>::MultiCurrency::deposit(
-                    farming_params.currency_id,
-                    &caller_account,
-                    convert_amount.into(),
-                )?;
-                pallet_farming::Pallet::<T>::deposit_farming(
-                    caller_account.clone(),
-                    farming_params.pool_id,
-                    convert_amount.into(),
-                    None,
-                )?;
-            }
-
             // Transfer funds to pot
             <T as pallet_parachain_staking::Config>::Currency::transfer(
                 &caller_account,
@@ -456,21 +757,10 @@ pub mod pallet {
             )?;
 
             // Attempt to stake them
-            let collator_balance_pairs = Self::calculate_deposit_distribution(amount);
-            ensure!(
-                !collator_balance_pairs.is_empty(),
-                Error::<T>::NoCollatorForDeposit
-            );
-            for (some_collator, balance) in collator_balance_pairs {
-                // TODO: What if the `balance` is below `MinDelegation`a on a new collator? this will fail
-                Self::do_stake_one_collator(some_collator, balance)?;
-            }
+            Self::do_stake_deposit(amount)?;
 
             // Add to active funds
-            ActiveBalancePerUser::<T>::mutate(caller_account.clone(), |balance| *balance += amount);
-            TotalPot::<T>::mutate(|balance| *balance += amount);
-            TotalUsers::<T>::mutate(|users| *users += 1);
-            SumOfDeposits::<T>::mutate(|balance| *balance += amount);
+            Self::do_add_active_balance(&caller_account, amount)?;
             Self::deposit_event(Event::Deposited {
                 account: caller_account,
                 amount,
@@ -502,127 +792,104 @@ pub mod pallet {
         /// * The user has no or not enough active funds
         /// * There are any arithmetic underflows
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::request_withdraw(Pallet::<T>::total_users(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::request_withdraw(Pallet::<T, I>::ticket_tree_depth(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
         pub fn request_withdraw(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
             let caller = ensure_signed(origin)?;
 
             ensure!(
                 amount >= Self::min_withdraw(),
-                Error::<T>::WithdrawBelowMinAmount
+                Error::<T, I>::WithdrawBelowMinAmount
             );
             ensure!(
                 Self::not_in_drawing_freezeout(),
-                Error::<T>::TooCloseToDrawing
+                Error::<T, I>::TooCloseToDrawing
             );
 
-            let farming_params = FarmingParameters::<T>::get();
-            if farming_params.destroy_farming_token {
-                let convert_amount: T::BalanceConversion = amount.into();
-                pallet_farming::Pallet::<T>::withdraw_and_unstake(
-                    caller.clone(),
-                    farming_params.pool_id,
-                    Some(convert_amount.into()),
-                )?;
-                <T as pallet_farming::Config>::MultiCurrency::withdraw(
-                    farming_params.currency_id,
-                    &caller,
-                    convert_amount.into(),
-                )?;
-            }
-
-            let now = <frame_system::Pallet<T>>::block_number();
             log::debug!("Requesting withdraw of {:?} tokens", amount);
             // Ensure user has enough funds active and mark them as offboarding (remove from `ActiveBalancePerUser`)
-            ActiveBalancePerUser::<T>::try_mutate_exists(caller.clone(), |maybe_balance| {
-                match maybe_balance {
-                    None => Err(Error::<T>::NoDepositForAccount),
-                    Some(balance) => {
-                        // Withdraw only what's active
-                        ensure!(*balance >= amount, Error::<T>::WithdrawAboveDeposit);
-                        // Mark funds as offboarding
-                        WithdrawalRequestQueue::<T>::mutate(|withdraw_vec| {
-                            withdraw_vec.push(Request {
-                                user: caller.clone(),
-                                block: now,
-                                balance: amount,
-                            })
-                        });
-                        // store reduced balance
-                        *maybe_balance = match balance
-                            .checked_sub(&amount)
-                            .ok_or(Error::<T>::ArithmeticUnderflow)?
-                        {
-                            new_balance if new_balance.is_zero() => {
-                                // remove user if this was his last remaining funds
-                                TotalUsers::<T>::try_mutate(|users| {
-                                    *users = (*users)
-                                        .checked_sub(1u32)
-                                        .ok_or(Error::<T>::ArithmeticUnderflow)?;
-                                    Ok(())
-                                })?;
-                                None
-                            }
-                            new_balance => Some(new_balance),
-                        };
-                        TotalPot::<T>::try_mutate(|pot| {
-                            *pot = (*pot)
-                                .checked_sub(&amount)
-                                .ok_or(Error::<T>::ArithmeticUnderflow)?;
-                            Ok(())
-                        })?;
-                        Ok(())
-                    }
-                }
-            })?;
+            Self::do_remove_active_balance(&caller, amount)?;
+            Self::do_schedule_withdrawal(&caller, amount)?;
+            Self::deposit_event(Event::ScheduledWithdraw {
+                account: caller,
+                amount,
+            });
+            Ok(())
+        }
 
-            // Unstaking workflow
-            // 1. See if this withdrawal can be serviced with left-over balance from an already unstaking collator, if so deduct remaining balance and schedule the request
-            // 2. If it can't, find the collator with the smallest delegation that is able to handle this withdrawal request and fully unstake it
-            // 3. Add balance overshoot to "remaining balance" to handle further requests from
+        /// Immediately withdraws `amount` from the caller's active funds, paid from the [`LiquidityBuffer`]
+        ///
+        /// Unlike [`Call::request_withdraw`] this is not subject to the staking timelock,
+        /// instead [`InstantWithdrawFee`] of `amount` is kept by the pallet and added to the prize pool
+        ///
+        /// # Arguments
+        ///
+        /// * `amount` - the amount of funds to withdraw, including the fee
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if:
+        /// * `amount` is below the minimum withdraw amount
+        /// * `amount` is larger than the user's total deposit
+        /// * It is too close to the drawing
+        /// * The user has no or not enough active funds
+        /// * The liquidity buffer holds less than `amount`
+        /// * There are any arithmetic underflows
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::instant_withdraw(Pallet::<T, I>::ticket_tree_depth()))]
+        pub fn instant_withdraw(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
 
-            // If the withdrawal fits in the currently unstaking funds, do nothing else
-            SurplusUnstakingBalance::<T>::try_mutate(|remaining_balance| {
-                match (*remaining_balance).checked_sub(&amount){
-                    Some(subtracted) => {
-                        *remaining_balance = subtracted;
-                        Ok(())
-                    }
-                    _ => {
-                        Err("not enough left to handle this request from current unstaking funds")
-                    }
-                }
-            })
-            .or_else(|_| {
-                // Withdrawal needs extra collators to unstake to have enough funds to serve withdrawals, do it
-                let reserve = SurplusUnstakingBalance::<T>::get();
-                let mut remaining_to_withdraw = amount - reserve;
+            ensure!(
+                amount >= Self::min_withdraw(),
+                Error::<T, I>::WithdrawBelowMinAmount
+            );
+            ensure!(
+                Self::not_in_drawing_freezeout(),
+                Error::<T, I>::TooCloseToDrawing
+            );
+            ensure!(
+                amount <= Self::liquidity_buffer(),
+                Error::<T, I>::InsufficientLiquidity
+            );
 
-                // unstake collators as necessary. This updates `SurplusUnstakingBalance`
-                for collator_to_unstake in Self::calculate_withdrawal_distribution(remaining_to_withdraw){
-                    let our_stake = StakedCollators::<T>::get(collator_to_unstake.clone());
-                    remaining_to_withdraw = remaining_to_withdraw.saturating_sub(our_stake);
-                    // The following call updates `SurplusUnstakingBalance` with newly unstaked funds
-                    Self::do_unstake_collator(now,collator_to_unstake)?;
-                }
-                if !remaining_to_withdraw.is_zero() {
-                    return Err("FATAL: Didn't unstake the full requested balance (or more)");
-                }
-                SurplusUnstakingBalance::<T>::try_mutate(|remaining_balance| {
-                    match (*remaining_balance).checked_sub(&amount){
-                        Some(subtracted) => {
-                            *remaining_balance = subtracted;
-                            Ok(())
-                        }
-                        _ => {
-                            Err("not enough unstaking balance to handle request after unstaking additional collators")
-                        }
-                    }
-                })
+            log::debug!("Instantly withdrawing {:?} tokens", amount);
+            Self::do_remove_active_balance(&caller, amount)?;
+            LiquidityBuffer::<T, I>::try_mutate(|buffer| {
+                *buffer = (*buffer)
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                Ok::<(), Error<T, I>>(())
             })?;
-            // END UNSTAKING SECTION
-            Self::deposit_event(Event::ScheduledWithdraw {
+            SumOfDeposits::<T, I>::try_mutate(|sum| {
+                *sum = (*sum)
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+
+            // The fee stays in the pallet as surplus funds and is paid out in the next drawing
+            let fee = Self::instant_withdraw_fee().mul_ceil(amount);
+            let payout = amount.saturating_sub(fee);
+            let all_funds_in_pallet =
+                <T as pallet_parachain_staking::Config>::Currency::total_balance(
+                    &Self::account_id(),
+                );
+            ensure!(
+                // Sanity check: Never pay out funds that would draw on other user's deposits or winnings
+                all_funds_in_pallet.saturating_sub(payout)
+                    >= Self::sum_of_deposits().saturating_add(Self::total_unclaimed_winnings()),
+                Error::<T, I>::PotBalanceTooLow
+            );
+            <T as pallet_parachain_staking::Config>::Currency::transfer(
+                &Self::account_id(),
+                &caller,
+                payout,
+                KeepAlive,
+            )?;
+            Self::deposit_event(Event::InstantWithdrawn {
                 account: caller,
-                amount,
+                amount: payout,
+                fee,
             });
             Ok(())
         }
@@ -633,11 +900,12 @@ pub mod pallet {
         ///
         /// CannotLookup: The caller has no unclaimed winnings.
         #[pallet::call_index(2)]
-        #[pallet::weight(<T as Config>::WeightInfo::claim_my_winnings(pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::claim_my_winnings(pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
         pub fn claim_my_winnings(origin: OriginFor<T>) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            match UnclaimedWinningsByAccount::<T>::take(caller.clone()) {
+            match UnclaimedWinningsByAccount::<T, I>::take(caller.clone()) {
                 Some(winnings) => {
+                    PendingRedeposits::<T, I>::remove(&caller);
                     // Never pay out winnings if it would reduce pallet funds below total number of user's deposits
                     let all_funds_in_pallet =
                         <T as pallet_parachain_staking::Config>::Currency::total_balance(
@@ -646,10 +914,10 @@ pub mod pallet {
                     ensure!(
                         // Sanity check: Never pay out funds that would draw on user deposits
                         all_funds_in_pallet.saturating_sub(winnings) >= Self::sum_of_deposits(),
-                        Error::<T>::PotBalanceTooLow
+                        Error::<T, I>::PotBalanceTooLow
                     );
 
-                    TotalUnclaimedWinnings::<T>::try_mutate(|old| {
+                    TotalUnclaimedWinnings::<T, I>::try_mutate(|old| {
                         *old = (*old)
                             .checked_sub(&winnings)
                             .ok_or(ArithmeticError::Underflow)?;
@@ -693,7 +961,7 @@ pub mod pallet {
         pub fn rebalance_stake(origin: OriginFor<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
             Err(crate::pallet::DispatchError::Other(
-                Error::<T>::NotImplemented.into(),
+                Error::<T, I>::NotImplemented.into(),
             ))
 
             // withdraw from overallocated collators, wait until funds unlock, re-allocate to underallocated collators
@@ -723,26 +991,26 @@ pub mod pallet {
         ///
         /// You can always learn what block the next drawing - if any - will happen by calling [`Self::next_drawing_at`]
         #[pallet::call_index(4)]
-        #[pallet::weight(<T as Config>::WeightInfo::start_lottery())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::start_lottery())]
         pub fn start_lottery(origin: OriginFor<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
             ensure!(
                 Self::next_drawing_at().is_none(),
-                Error::<T>::LotteryIsRunning
+                Error::<T, I>::LotteryIsRunning
             );
             // Pallet has enough funds to pay gas fees for at least the first drawing
             ensure!(
                 Self::surplus_funds() >= Self::gas_reserve(),
-                Error::<T>::PotBalanceBelowGasReserve
+                Error::<T, I>::PotBalanceBelowGasReserve
             );
             // NOTE: If more than gas_reserve is in the pallet, the full excess will be paid out to the winner of the next drawing! This is intended to dope the winning balance with extra rewards
 
-            let drawing_interval = <T as Config>::DrawingInterval::get();
+            let drawing_interval = <T as Config<I>>::DrawingInterval::get();
             ensure!(
                 drawing_interval > 0u32.into(),
-                Error::<T>::PalletMisconfigured
+                Error::<T, I>::PalletMisconfigured
             );
-            let lottery_drawing_call: CallOf<T> = Call::draw_lottery {}.into();
+            let lottery_drawing_call: CallOf<T, I> = Call::<T, I>::draw_lottery {}.into();
             T::Scheduler::schedule_named(
                 Self::lottery_schedule_id(),
                 DispatchTime::After(drawing_interval),
@@ -751,7 +1019,7 @@ pub mod pallet {
                 frame_support::dispatch::RawOrigin::Root.into(),
                 MaybeHashed::Value(lottery_drawing_call),
             )
-            .map_err(|_| Error::<T>::CouldNotSchedule)?;
+            .map_err(|_| Error::<T, I>::CouldNotSchedule)?;
 
             Self::deposit_event(Event::LotteryStarted);
             Ok(())
@@ -769,11 +1037,11 @@ pub mod pallet {
         /// * LotteryNotStarted: Nothing to stop
         ///
         #[pallet::call_index(5)]
-        #[pallet::weight(<T as Config>::WeightInfo::stop_lottery())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::stop_lottery())]
         pub fn stop_lottery(origin: OriginFor<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
             T::Scheduler::cancel_named(Self::lottery_schedule_id())
-                .map_err(|_| Error::<T>::LotteryNotStarted)?;
+                .map_err(|_| Error::<T, I>::LotteryNotStarted)?;
             Self::deposit_event(Event::LotteryStopped);
             Ok(())
         }
@@ -793,7 +1061,7 @@ pub mod pallet {
         /// * PotBalanceTooLow: The balance of the pot is too low.
         /// * NoWinnerFound: Nobody was selected as winner
         #[pallet::call_index(6)]
-        #[pallet::weight(<T as Config>::WeightInfo::draw_lottery(Pallet::<T>::total_users(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::draw_lottery(Pallet::<T, I>::ticket_tree_depth(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32, Pallet::<T, I>::total_winners(), Pallet::<T, I>::redeposits_bound()))]
         pub fn draw_lottery(origin: OriginFor<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
             let now = <frame_system::Pallet<T>>::block_number();
@@ -812,17 +1080,25 @@ pub mod pallet {
                 participating_funds.clone(),
                 winning_claim.clone()
             );
+            let mut winners = vec![];
             // If there's nothing to win or nobody is playing we skip the drawing logic
             if !winning_claim.is_zero() && !participating_funds.is_zero() {
+                let payouts = Self::prize_payouts(winning_claim);
+                let total_payout = payouts.iter().fold(
+                    BalanceOf::<T>::zero(),
+                    |total, (payout_per_winner, winners)| {
+                        total.saturating_add(payout_per_winner.saturating_mul((*winners).into()))
+                    },
+                );
                 ensure!(
                     // Sanity check: Prevent allocating funds as winnings to a user that would have to be paid from user deposits
                     Self::sum_of_deposits()                                 // all users' deposits (staked and unstaking)
                         .saturating_add(Self::total_unclaimed_winnings())   // all prior winnings
-                        .saturating_add(winning_claim)                      // and the current winner's new claim
+                        .saturating_add(total_payout)                       // and the current winners' new claims
                         <= total_funds_in_pallet, // don't exceed funds in the pallet
-                    Error::<T>::PotBalanceTooLow
+                    Error::<T, I>::PotBalanceTooLow
                 );
-                Self::select_winner(winning_claim)?;
+                winners = Self::select_winners(payouts)?;
             } else {
                 log::debug!(
                     "drawing: skipped due to zero winning claim {:?} or participating funds {:?}",
@@ -830,6 +1106,11 @@ pub mod pallet {
                     participating_funds,
                 );
             }
+            Self::record_drawing(winning_claim, winners, participating_funds);
+            // tickets of the next drawing are weighted from now on
+            Self::start_new_drawing_period();
+            // unclaimed winnings of accounts opted into auto-redeposit compete in the next drawing
+            Self::do_redeposit_winnings();
             // unstake, pay out tokens due for withdrawals and restake excess funds
             // At this point, all excess funds except for `gas_reserve` have been reserved for the current winner
             Self::process_matured_withdrawals(origin)?;
@@ -845,7 +1126,7 @@ pub mod pallet {
         /// * BadOrigin: Caller is not ManageOrigin
         /// * errors defined by the do_process_matured_withdrawals function.
         #[pallet::call_index(7)]
-        #[pallet::weight(<T as Config>::WeightInfo::process_matured_withdrawals())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::process_matured_withdrawals())]
         pub fn process_matured_withdrawals(origin: OriginFor<T>) -> DispatchResult {
             log::trace!("process_matured_withdrawals");
             T::ManageOrigin::ensure_origin(origin.clone())?;
@@ -873,11 +1154,11 @@ pub mod pallet {
 
             ensure!(
                 Self::next_drawing_at().is_none(),
-                Error::<T>::LotteryIsRunning
+                Error::<T, I>::LotteryIsRunning
             );
 
             Err(crate::pallet::DispatchError::Other(
-                Error::<T>::NotImplemented.into(),
+                Error::<T, I>::NotImplemented.into(),
             ))
 
             // TODO: Unstake all collators, schedule return of all user deposits
@@ -891,35 +1172,35 @@ pub mod pallet {
             // Ok(())
         }
         #[pallet::call_index(9)]
-        #[pallet::weight(<T as Config>::WeightInfo::set_min_deposit())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_min_deposit())]
         pub fn set_min_deposit(origin: OriginFor<T>, min_deposit: BalanceOf<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
             ensure!(
                 min_deposit >= Self::min_withdraw(),
-                Error::<T>::PalletMisconfigured
+                Error::<T, I>::PalletMisconfigured
             );
-            MinDeposit::<T>::set(min_deposit);
+            MinDeposit::<T, I>::set(min_deposit);
             Ok(())
         }
         #[pallet::call_index(10)]
-        #[pallet::weight(<T as Config>::WeightInfo::set_min_withdraw())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_min_withdraw())]
         pub fn set_min_withdraw(
             origin: OriginFor<T>,
             min_withdraw: BalanceOf<T>,
         ) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
-            MinWithdraw::<T>::set(min_withdraw);
+            MinWithdraw::<T, I>::set(min_withdraw);
             Ok(())
         }
         #[pallet::call_index(11)]
-        #[pallet::weight(<T as Config>::WeightInfo::set_gas_reserve())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_gas_reserve())]
         pub fn set_gas_reserve(origin: OriginFor<T>, gas_reserve: BalanceOf<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
-            GasReserve::<T>::set(gas_reserve);
+            GasReserve::<T, I>::set(gas_reserve);
             Ok(())
         }
         #[pallet::call_index(12)]
-        #[pallet::weight(<T as Config>::WeightInfo::set_gas_reserve())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_gas_reserve())]
         pub fn set_farming_params(
             origin: OriginFor<T>,
             mint_farming_token: bool,
@@ -935,31 +1216,277 @@ pub mod pallet {
                 pool_id,
                 currency_id,
             };
-            FarmingParameters::<T>::set(farming_params);
+            FarmingParameters::<T, I>::set(farming_params);
 
             Ok(())
         }
-    }
 
-    impl<T: Config> Pallet<T> {
-        /// Get a unique, inaccessible account id from the `PotId`.
-        pub(crate) fn account_id() -> T::AccountId {
-            T::LotteryPot::get().into_account_truncating()
-        }
-        /// Get an identifier for scheduling drawings from the `PotId`.
-        fn lottery_schedule_id() -> Vec<u8> {
-            T::LotteryPot::get().0.to_vec()
+        /// Sets the tiers the prize pool is split into in every drawing
+        ///
+        /// Can only be called by the account set as [`Config::ManageOrigin`]
+        ///
+        /// # Arguments
+        ///
+        /// * `tiers` - Share of the prize pool and number of winners of each tier. An empty list pays the full prize pool to a single winner
+        /// * `duplicate_winner_policy` - Whether an account can receive more than one payout in the same drawing
+        ///
+        /// # Errors
+        ///
+        /// * BadOrigin: Caller is not ManageOrigin
+        /// * InvalidPrizeTiers: Shares add up to more than 100%, a tier has no winners or there are more than [`MAX_WINNERS_PER_DRAWING`] winners in total
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_prize_tiers(tiers.len() as u32))]
+        pub fn set_prize_tiers(
+            origin: OriginFor<T>,
+            tiers: Vec<PrizeTier>,
+            duplicate_winner_policy: DuplicateWinnerPolicy,
+        ) -> DispatchResult {
+            T::ManageOrigin::ensure_origin(origin)?;
+            let mut total_share = 0u32;
+            let mut total_winners = 0u32;
+            for tier in tiers.iter() {
+                ensure!(tier.winners > 0, Error::<T, I>::InvalidPrizeTiers);
+                total_share = total_share.saturating_add(tier.share.deconstruct().into());
+                total_winners = total_winners.saturating_add(tier.winners);
+            }
+            ensure!(
+                total_share <= 100 && total_winners <= MAX_WINNERS_PER_DRAWING,
+                Error::<T, I>::InvalidPrizeTiers
+            );
+            PrizeTiers::<T, I>::put(tiers);
+            DuplicateWinners::<T, I>::put(duplicate_winner_policy);
+            Ok(())
         }
-        fn select_winning_balance(
-            max_winning_balance: BalanceOf<T>,
-        ) -> Result<BalanceOf<T>, Error<T>> {
-            const MAX_NUMBER_OF_RESAMPLES: u8 = 3;
-            let mut winning_number = 0; // XXX: This shouldn't need initialization but the compiler doesn't get it
-            for n in 0u8..MAX_NUMBER_OF_RESAMPLES {
-                let random: (T::Hash, BlockNumberFor<T>);
-                #[cfg(feature = "runtime-benchmarks")]
-                {
-                    use rand::{RngCore, SeedableRng};
+
+        /// Selects how deposits are distributed to collators and which collators are unstaked for withdrawals
+        ///
+        /// Can only be called by the account set as [`Config::ManageOrigin`]
+        ///
+        /// # Arguments
+        ///
+        /// * `deposit_strategy` - Strategy staking deposits after collators that fell out of the active set are reactivated
+        /// * `withdraw_strategy` - Strategy unstaking collators after all inactive collators are unstaked
+        ///
+        /// # Errors
+        ///
+        /// * BadOrigin: Caller is not ManageOrigin
+        /// * InvalidStakingStrategy: The deposit strategy spreads to zero collators or caps them at zero
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_staking_strategies())]
+        pub fn set_staking_strategies(
+            origin: OriginFor<T>,
+            deposit_strategy: DepositStrategyKind,
+            withdraw_strategy: WithdrawStrategyKind,
+        ) -> DispatchResult {
+            T::ManageOrigin::ensure_origin(origin)?;
+            match deposit_strategy {
+                DepositStrategyKind::EvenSpreadTopApy { collators } => {
+                    ensure!(collators > 0, Error::<T, I>::InvalidStakingStrategy)
+                }
+                DepositStrategyKind::MaxDiversification { cap } => {
+                    ensure!(!cap.is_zero(), Error::<T, I>::InvalidStakingStrategy)
+                }
+                DepositStrategyKind::SplitToUnderallocated => {}
+            }
+            SelectedDepositStrategy::<T, I>::put(deposit_strategy);
+            SelectedWithdrawStrategy::<T, I>::put(withdraw_strategy);
+            Ok(())
+        }
+
+        /// Lends `amount` to the lottery to raise the prize pool with its staking yield
+        ///
+        /// Sponsored funds are staked like deposits but hold no tickets, so the sponsor can never win
+        /// As they don't change anybody's chance to win, sponsoring is allowed during the drawing freezeout
+        ///
+        /// # Arguments
+        ///
+        /// * `amount` - The amount of tokens to lend
+        ///
+        /// # Errors
+        ///
+        /// * DepositBelowMinAmount: `amount` is below the minimum deposit
+        /// * ArithmeticOverflow: The sponsored funds overflow the balance type
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::sponsor(pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        pub fn sponsor(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(
+                amount >= Self::min_deposit(),
+                Error::<T, I>::DepositBelowMinAmount
+            );
+            ensure! { // Sanity check: make sure we dont accept deposits that will fail in staking
+                Self::min_deposit() >= <T as pallet_parachain_staking::Config>::MinDelegation::get(),
+                Error::<T, I>::PalletMisconfigured
+            };
+
+            <T as pallet_parachain_staking::Config>::Currency::transfer(
+                &caller,
+                &Self::account_id(),
+                amount,
+                KeepAlive,
+            )?;
+            Self::do_stake_deposit(amount)?;
+
+            SponsorBalancePerAccount::<T, I>::try_mutate(caller.clone(), |balance| {
+                *balance = (*balance)
+                    .checked_add(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            TotalSponsored::<T, I>::try_mutate(|total| {
+                *total = (*total)
+                    .checked_add(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            SumOfDeposits::<T, I>::try_mutate(|sum| {
+                *sum = (*sum)
+                    .checked_add(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            Self::deposit_event(Event::Sponsored {
+                account: caller,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Requests return of `amount` of the caller's sponsored funds
+        ///
+        /// Like [`Call::request_withdraw`], the funds are paid out in the first drawing after the staking timelock expired
+        ///
+        /// # Arguments
+        ///
+        /// * `amount` - the amount of sponsored funds to withdraw
+        ///
+        /// # Errors
+        ///
+        /// * WithdrawBelowMinAmount: `amount` is below the minimum withdraw amount
+        /// * NoDepositForAccount: The caller sponsored no funds
+        /// * WithdrawAboveDeposit: `amount` is larger than the caller's sponsored funds
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::request_sponsor_withdraw(pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        pub fn request_sponsor_withdraw(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(
+                amount >= Self::min_withdraw(),
+                Error::<T, I>::WithdrawBelowMinAmount
+            );
+
+            SponsorBalancePerAccount::<T, I>::try_mutate_exists(caller.clone(), |maybe_balance| {
+                let balance = (*maybe_balance).ok_or(Error::<T, I>::NoDepositForAccount)?;
+                ensure!(balance >= amount, Error::<T, I>::WithdrawAboveDeposit);
+                *maybe_balance = Some(balance - amount).filter(|remaining| !remaining.is_zero());
+                Ok::<(), Error<T, I>>(())
+            })?;
+            TotalSponsored::<T, I>::try_mutate(|total| {
+                *total = (*total)
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            Self::do_schedule_withdrawal(&caller, amount)?;
+            Self::deposit_event(Event::ScheduledSponsorWithdraw {
+                account: caller,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Sets whether the caller's winnings are automatically deposited into the lottery
+        ///
+        /// When enabled, all unclaimed winnings of the caller are turned into an active deposit in the course of
+        /// every drawing, as long as they reach the minimum deposit and the drawing is not within the freezeout
+        ///
+        /// # Arguments
+        ///
+        /// * `enabled` - Whether to redeposit winnings instead of keeping them for [`Call::claim_my_winnings`]
+        ///
+        /// # Errors
+        ///
+        /// * NoDepositForAccount: The caller opts in without an active deposit or unclaimed winnings
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 2))]
+        pub fn set_auto_redeposit(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            if enabled {
+                let has_winnings = UnclaimedWinningsByAccount::<T, I>::contains_key(&caller);
+                ensure!(
+                    has_winnings || ActiveBalancePerUser::<T, I>::contains_key(&caller),
+                    Error::<T, I>::NoDepositForAccount
+                );
+                if has_winnings {
+                    PendingRedeposits::<T, I>::insert(&caller, ());
+                }
+                AutoRedeposit::<T, I>::insert(caller, ());
+            } else {
+                PendingRedeposits::<T, I>::remove(&caller);
+                AutoRedeposit::<T, I>::remove(caller);
+            }
+            Ok(())
+        }
+
+        /// Sizes the unstaked liquidity buffer paying out [`Call::instant_withdraw`] and the fee charged for it
+        ///
+        /// Can only be called by the account set as [`Config::ManageOrigin`]
+        /// The buffer is filled up to or shrunk down to `target` when remaining funds are rebalanced after the next drawing
+        ///
+        /// # Arguments
+        ///
+        /// * `target` - Unstaked balance to keep available for instant withdrawals
+        /// * `fee` - Share of every instant withdrawal added to the prize pool
+        ///
+        /// # Errors
+        ///
+        /// * BadOrigin: Caller is not ManageOrigin
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_liquidity_buffer())]
+        pub fn set_liquidity_buffer(
+            origin: OriginFor<T>,
+            target: BalanceOf<T>,
+            fee: Percent,
+        ) -> DispatchResult {
+            T::ManageOrigin::ensure_origin(origin)?;
+            LiquidityBufferTarget::<T, I>::put(target);
+            InstantWithdrawFee::<T, I>::put(fee);
+            Ok(())
+        }
+    }
+
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
+        /// Get a unique, inaccessible account id from the `PotId`.
+        pub(crate) fn account_id() -> T::AccountId {
+            match T::LotteryId::get() {
+                0 => T::LotteryPot::get().into_account_truncating(),
+                lottery_id => T::LotteryPot::get().into_sub_account_truncating(lottery_id),
+            }
+        }
+        /// Get an identifier for scheduling drawings from the `PotId` and `LotteryId`.
+        fn lottery_schedule_id() -> Vec<u8> {
+            let mut schedule_id = T::LotteryPot::get().0.to_vec();
+            match T::LotteryId::get() {
+                0 => {}
+                lottery_id => schedule_id.extend(lottery_id.encode()),
+            }
+            schedule_id
+        }
+        /// Draws a balance in `[0, max_winning_balance)` from the randomness source
+        /// `subject` derives an independent sub-seed so several balances can be drawn from the same randomness
+        fn select_winning_balance(
+            subject: &[u8],
+            max_winning_balance: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, Error<T, I>> {
+            const MAX_NUMBER_OF_RESAMPLES: u8 = 3;
+            let mut winning_number = 0; // XXX: This shouldn't need initialization but the compiler doesn't get it
+            for n in 0u8..MAX_NUMBER_OF_RESAMPLES {
+                let random: (T::Hash, BlockNumberFor<T>);
+                #[cfg(feature = "runtime-benchmarks")]
+                {
+                    use rand::{RngCore, SeedableRng};
                     use sp_runtime::traits::Hash;
                     // XXX: Benchmarking randomness changes per block instead of per epoch
                     let mut rng = rand::rngs::StdRng::seed_from_u64(
@@ -969,21 +1496,21 @@ pub mod pallet {
                     );
                     let mut rnd = [0u8; 32];
                     rng.fill_bytes(&mut rnd);
-                    let randomness = T::Hashing::hash(&rnd);
+                    let randomness = T::Hashing::hash(&[&rnd[..], subject].concat());
                     random = (randomness, <T as frame_system::Config>::BlockNumber::zero());
                     log::debug!("select-winner using randomness {:?}", random);
                 }
                 #[cfg(not(feature = "runtime-benchmarks"))]
                 {
-                    random = T::RandomnessSource::random(&[n; 1]);
+                    random = T::RandomnessSource::random(&[subject, &[n][..]].concat());
                     log::debug!("select-winner using randomness {:?}", random);
                     // TODO: The following check needs a change to pallet randomness but is static,
                     //       so this can be done manually on deployment of the pallet
                     // ensure!(
                     //     random.1 = randomness_established_at_block
-                    //         .saturating_add(<T as Config>::DrawingFreezeout::get())
+                    //         .saturating_add(<T as Config<I>>::DrawingFreezeout::get())
                     //         < <frame_system::Pallet<T>>::block_number(),
-                    //     Error::<T>::PalletMisconfigured
+                    //     Error::<T, I>::PalletMisconfigured
                     // );
                 }
                 let random_hash = random.0;
@@ -1027,7 +1554,7 @@ pub mod pallet {
             }
             // no risk of modulo bias here unless we ran out of retries above
             let winning_balance: BalanceOf<T> = BalanceOf::<T>::try_from(winning_number)
-                .map_err(|_| Error::<T>::ArithmeticOverflow)?
+                .map_err(|_| Error::<T, I>::ArithmeticOverflow)?
                 % max_winning_balance;
             log::debug!(
                 "winning_number: {:?}, winning balance: {:?}",
@@ -1036,43 +1563,333 @@ pub mod pallet {
             );
             Ok(winning_balance)
         }
-        fn select_winner(payout_for_winner: BalanceOf<T>) -> DispatchResult {
-            if payout_for_winner.is_zero() {
-                return Err(Error::<T>::NothingToWin.into());
+        /// Splits `prize_pool` into the payouts of the configured [`PrizeTiers`]
+        /// Returns the amount paid to each winner of a tier together with the tier's number of winners
+        pub(crate) fn prize_payouts(prize_pool: BalanceOf<T>) -> Vec<(BalanceOf<T>, u32)> {
+            let tiers = Self::prize_tiers();
+            if tiers.is_empty() {
+                return vec![(prize_pool, 1)];
             }
-            let participating_funds = Self::total_pot();
-            if participating_funds.is_zero() {
-                return Err(Error::<T>::NobodyPlaying.into());
+            tiers
+                .iter()
+                .map(|tier| {
+                    let tier_prize = tier.share.mul_floor(prize_pool);
+                    (
+                        tier_prize / BalanceOf::<T>::from(tier.winners),
+                        tier.winners,
+                    )
+                })
+                .collect()
+        }
+        /// Number of winners drawn with the configured [`PrizeTiers`]
+        pub(crate) fn total_winners() -> u32 {
+            Self::prize_tiers()
+                .iter()
+                .fold(0u32, |total, tier| total.saturating_add(tier.winners))
+                .max(1)
+        }
+        /// Draws and pays the winner of every payout, returning the winners along with their payout
+        fn select_winners(
+            payouts: Vec<(BalanceOf<T>, u32)>,
+        ) -> Result<Vec<(T::AccountId, BalanceOf<T>)>, DispatchError> {
+            if Self::total_pot().is_zero() {
+                return Err(Error::<T, I>::NobodyPlaying.into());
             }
-            // Match random number to winner. We select a winning **balance** and then just add up accounts in the order they're stored until the sum of balance exceeds the winning amount
-            // IMPORTANT: This order and active balances must be locked to modification after the random seed is created (relay BABE randomness, 2 epochs ago)
-            let winning_balance = Self::select_winning_balance(participating_funds)?;
-            let mut maybe_winner: Option<T::AccountId> = None;
-            let mut count: BalanceOf<T> = 0u32.into();
-            for (account, balance) in ActiveBalancePerUser::<T>::iter() {
-                count += balance;
-                if count >= winning_balance {
-                    maybe_winner = Some(account);
-                    break;
+            let mut winners: Vec<(T::AccountId, BalanceOf<T>)> = vec![];
+            // Odds are the time-weighted active balance over the drawing period
+            let total_weight = Self::total_ticket_weight();
+            if total_weight.is_zero() {
+                log::debug!("drawing: no tickets have accrued any weight yet, prize rolls over");
+                return Ok(winners);
+            }
+            let policy = Self::duplicate_winner_policy();
+            for (tier, (payout_per_winner, number_of_winners)) in payouts.into_iter().enumerate() {
+                if payout_per_winner.is_zero() {
+                    log::debug!("drawing: skipping tier {:?} with nothing to win", tier);
+                    continue;
+                }
+                for winner_index in 0..number_of_winners {
+                    let mut maybe_winner = None;
+                    for redraw in 0..MAX_REDRAWS_PER_PAYOUT {
+                        // Every payout is drawn with its own sub-seed of the drawing's randomness
+                        let subject = (tier as u32, winner_index, redraw).encode();
+                        let winning_weight = Self::select_winning_balance(&subject, total_weight)?;
+                        let candidate = Self::select_winner(winning_weight)?;
+                        if policy == DuplicateWinnerPolicy::Redraw
+                            && winners.iter().any(|(winner, _)| *winner == candidate)
+                        {
+                            continue;
+                        }
+                        maybe_winner = Some(candidate);
+                        break;
+                    }
+                    match maybe_winner {
+                        Some(winner) => {
+                            Self::assign_winnings(winner.clone(), payout_per_winner)?;
+                            winners.push((winner, payout_per_winner));
+                        }
+                        None => log::warn!(
+                            "No new winner found after {:?} redraws, {:?} rolls over to the next drawing",
+                            MAX_REDRAWS_PER_PAYOUT,
+                            payout_per_winner
+                        ),
+                    }
                 }
             }
+            Ok(winners)
+        }
+        fn select_winner(winning_weight: BalanceOf<T>) -> Result<T::AccountId, DispatchError> {
+            // Match random number to winner. We select a winning **ticket weight** and look up the depositor whose tickets cover it
+            // IMPORTANT: Ticket positions and active balances must be locked to modification after the random seed is created (relay BABE randomness, 2 epochs ago)
             // Should be impossible: If no winner was selected, return Error
-            ensure!(maybe_winner.is_some(), Error::<T>::NoWinnerFound);
-            let winner = maybe_winner.expect("we checked a winner exists before. qed");
+            Self::find_ticket_holder(winning_weight)
+                .ok_or_else(|| Error::<T, I>::NoWinnerFound.into())
+        }
+        /// Stakes a freshly deposited `amount` using the selected deposit strategy
+        fn do_stake_deposit(amount: BalanceOf<T>) -> DispatchResult {
+            let collator_balance_pairs = Self::calculate_deposit_distribution(amount);
+            ensure!(
+                !collator_balance_pairs.is_empty(),
+                Error::<T, I>::NoCollatorForDeposit
+            );
+            for (some_collator, balance) in collator_balance_pairs {
+                // TODO: What if the `balance` is below `MinDelegation`a on a new collator? this will fail
+                Self::do_stake_one_collator(some_collator, balance)?;
+            }
+            Ok(())
+        }
+        /// Queues the withdrawal of `amount` to `account`, unstaking collators as needed to serve it
+        fn do_schedule_withdrawal(account: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            let now = <frame_system::Pallet<T>>::block_number();
+            WithdrawalRequestQueue::<T, I>::mutate(|withdraw_vec| {
+                withdraw_vec.push(Request {
+                    user: account.clone(),
+                    block: now,
+                    balance: amount,
+                })
+            });
+
+            // Unstaking workflow
+            // 1. See if this withdrawal can be serviced with left-over balance from an already unstaking collator, if so deduct remaining balance and schedule the request
+            // 2. If it can't, find the collator with the smallest delegation that is able to handle this withdrawal request and fully unstake it
+            // 3. Add balance overshoot to "remaining balance" to handle further requests from
+
+            // If the withdrawal fits in the currently unstaking funds, do nothing else
+            SurplusUnstakingBalance::<T, I>::try_mutate(|remaining_balance| {
+                match (*remaining_balance).checked_sub(&amount){
+                    Some(subtracted) => {
+                        *remaining_balance = subtracted;
+                        Ok(())
+                    }
+                    _ => {
+                        Err("not enough left to handle this request from current unstaking funds")
+                    }
+                }
+            })
+            .or_else(|_| {
+                // Withdrawal needs extra collators to unstake to have enough funds to serve withdrawals, do it
+                let reserve = SurplusUnstakingBalance::<T, I>::get();
+                let mut remaining_to_withdraw = amount - reserve;
+
+                // unstake collators as necessary. This updates `SurplusUnstakingBalance`
+                for collator_to_unstake in Self::calculate_withdrawal_distribution(remaining_to_withdraw){
+                    let our_stake = StakedCollators::<T, I>::get(collator_to_unstake.clone());
+                    remaining_to_withdraw = remaining_to_withdraw.saturating_sub(our_stake);
+                    // The following call updates `SurplusUnstakingBalance` with newly unstaked funds
+                    Self::do_unstake_collator(now,collator_to_unstake)?;
+                }
+                if !remaining_to_withdraw.is_zero() {
+                    return Err("FATAL: Didn't unstake the full requested balance (or more)");
+                }
+                SurplusUnstakingBalance::<T, I>::try_mutate(|remaining_balance| {
+                    match (*remaining_balance).checked_sub(&amount){
+                        Some(subtracted) => {
+                            *remaining_balance = subtracted;
+                            Ok(())
+                        }
+                        _ => {
+                            Err("not enough unstaking balance to handle request after unstaking additional collators")
+                        }
+                    }
+                })
+            })?;
+            // END UNSTAKING SECTION
+            Ok(())
+        }
+        /// Adds the staked `amount` to the active funds of `account`, minting farming tokens for it
+        fn do_add_active_balance(account: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            let farming_params = FarmingParameters::<T, I>::get();
+            if farming_params.mint_farming_token {
+                // mint JUMBO token and put it in farming pool
+                let convert_amount: T::BalanceConversion = amount.into();
+                <T as pallet_farming::Config>::MultiCurrency::deposit(
+                    farming_params.currency_id,
+                    account,
+                    convert_amount.into(),
+                )?;
+                pallet_farming::Pallet::<T>::deposit_farming(
+                    account.clone(),
+                    farming_params.pool_id,
+                    convert_amount.into(),
+                    None,
+                )?;
+            }
+
+            Self::add_tickets(account, amount);
+            Self::record_deposit(account, amount);
+            ActiveBalancePerUser::<T, I>::mutate(account.clone(), |balance| *balance += amount);
+            TotalPot::<T, I>::mutate(|balance| *balance += amount);
+            TotalUsers::<T, I>::mutate(|users| *users += 1);
+            SumOfDeposits::<T, I>::mutate(|balance| *balance += amount);
+            Ok(())
+        }
+        /// Converts the unclaimed winnings of every account in [`PendingRedeposits`] into an active deposit
+        /// Winnings below the minimum deposit stay unclaimed until later winnings add up to it
+        fn do_redeposit_winnings() {
+            let now = <frame_system::Pallet<T>>::block_number();
+            // While the scheduled drawing executes, the scheduler still reports it as the next one
+            let in_freezeout = match Self::next_drawing_at() {
+                Some(drawing) if drawing > now => !Self::not_in_drawing_freezeout(),
+                _ => false,
+            };
+            if in_freezeout {
+                log::debug!("Not redepositing winnings within the drawing freezeout");
+                return;
+            }
+            // A failed redeposit must not fail the drawing, its winnings stay claimable instead
+            let result = frame_support::storage::with_storage_layer(
+                || -> Result<Vec<(T::AccountId, BalanceOf<T>)>, DispatchError> {
+                    let mut redeposited = vec![];
+                    let mut total = BalanceOf::<T>::zero();
+                    for (account, ()) in PendingRedeposits::<T, I>::drain() {
+                        let winnings = match Self::unclaimed_winnings_by_account(&account) {
+                            Some(winnings) if winnings >= Self::min_deposit() => winnings,
+                            _ => continue,
+                        };
+                        let credited =
+                            frame_support::storage::with_storage_layer(|| -> DispatchResult {
+                                UnclaimedWinningsByAccount::<T, I>::remove(&account);
+                                TotalUnclaimedWinnings::<T, I>::try_mutate(
+                                    |unclaimed| -> DispatchResult {
+                                        *unclaimed = (*unclaimed)
+                                            .checked_sub(&winnings)
+                                            .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                                        Ok(())
+                                    },
+                                )?;
+                                Self::do_add_active_balance(&account, winnings)
+                            });
+                        match credited {
+                            Ok(()) => {
+                                total = total.saturating_add(winnings);
+                                redeposited.push((account, winnings));
+                            }
+                            Err(e) => {
+                                log::warn!("Could not redeposit winnings of {:?}: {:?}", account, e)
+                            }
+                        }
+                    }
+                    // All redeposited winnings are staked at once
+                    if !total.is_zero() {
+                        Self::do_stake_deposit(total)?;
+                    }
+                    Ok(redeposited)
+                },
+            );
+            match result {
+                Ok(redeposited) => {
+                    for (account, amount) in redeposited {
+                        Self::deposit_event(Event::WinningsRedeposited { account, amount });
+                    }
+                }
+                Err(e) => log::warn!("Could not stake redeposited winnings: {:?}", e),
+            }
+        }
+        /// Upper bound on the accounts [`Self::do_redeposit_winnings`] processes in a drawing
+        pub(crate) fn redeposits_bound() -> u32 {
+            PendingRedeposits::<T, I>::count().saturating_add(Self::total_winners())
+        }
+        /// Removes `amount` from the active funds of `caller`, burning farming tokens minted for it
+        fn do_remove_active_balance(caller: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            let farming_params = FarmingParameters::<T, I>::get();
+            if farming_params.destroy_farming_token {
+                let convert_amount: T::BalanceConversion = amount.into();
+                pallet_farming::Pallet::<T>::withdraw_and_unstake(
+                    caller.clone(),
+                    farming_params.pool_id,
+                    Some(convert_amount.into()),
+                )?;
+                <T as pallet_farming::Config>::MultiCurrency::withdraw(
+                    farming_params.currency_id,
+                    caller,
+                    convert_amount.into(),
+                )?;
+            }
+
+            ActiveBalancePerUser::<T, I>::try_mutate_exists(caller.clone(), |maybe_balance| {
+                match maybe_balance {
+                    None => Err(Error::<T, I>::NoDepositForAccount),
+                    Some(balance) => {
+                        // Withdraw only what's active
+                        ensure!(*balance >= amount, Error::<T, I>::WithdrawAboveDeposit);
+                        Self::remove_tickets(caller, amount);
+                        // store reduced balance
+                        *maybe_balance = match balance
+                            .checked_sub(&amount)
+                            .ok_or(Error::<T, I>::ArithmeticUnderflow)?
+                        {
+                            new_balance if new_balance.is_zero() => {
+                                Self::record_exit(caller);
+                                // remove user if this was his last remaining funds
+                                TotalUsers::<T, I>::try_mutate(|users| {
+                                    *users = (*users)
+                                        .checked_sub(1u32)
+                                        .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                                    Ok(())
+                                })?;
+                                None
+                            }
+                            new_balance => Some(new_balance),
+                        };
+                        TotalPot::<T, I>::try_mutate(|pot| {
+                            *pot = (*pot)
+                                .checked_sub(&amount)
+                                .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                            Ok(())
+                        })?;
+                        Ok(())
+                    }
+                }
+            })?;
+            Ok(())
+        }
+        fn assign_winnings(
+            winner: T::AccountId,
+            payout_for_winner: BalanceOf<T>,
+        ) -> DispatchResult {
+            if payout_for_winner.is_zero() {
+                return Err(Error::<T, I>::NothingToWin.into());
+            }
             // Allow winner to manually claim their winnings later
-            UnclaimedWinningsByAccount::<T>::mutate(winner.clone(), |maybe_balance| {
+            UnclaimedWinningsByAccount::<T, I>::mutate(winner.clone(), |maybe_balance| {
                 *maybe_balance = Some(
                     maybe_balance
                         .unwrap_or_else(|| 0u32.into())
                         .saturating_add(payout_for_winner),
                 );
             });
-            TotalUnclaimedWinnings::<T>::try_mutate(|old| {
+            if AutoRedeposit::<T, I>::contains_key(&winner) {
+                PendingRedeposits::<T, I>::insert(&winner, ());
+            }
+            TotalUnclaimedWinnings::<T, I>::try_mutate(|old| {
                 *old = (*old)
                     .checked_add(&payout_for_winner)
                     .ok_or(ArithmeticError::Overflow)?;
                 Ok::<(), ArithmeticError>(())
             })?;
+            UserLifetimeStats::<T, I>::mutate(&winner, |stats| {
+                stats.total_won = stats.total_won.saturating_add(payout_for_winner)
+            });
             log::debug!(
                 "winning of {:?} added to claim for account {:?}",
                 payout_for_winner,
@@ -1089,7 +1906,7 @@ pub mod pallet {
         /// This is infallible, if any step fails we just leave the collator in the request queue
         fn finish_unstaking_collators() {
             let now = <frame_system::Pallet<T>>::block_number();
-            let mut unstaking = UnstakingCollators::<T>::get();
+            let mut unstaking = UnstakingCollators::<T, I>::get();
             let original_len = unstaking.len();
             if unstaking.is_empty() {
                 return;
@@ -1097,7 +1914,7 @@ pub mod pallet {
             // Unstake what we can (false), leave the rest (true)
             unstaking.retain(|collator|{
                     // Leave collators that are not finished unstaking alone
-                    if collator.since + <T as Config>::UnstakeLockTime::get() > now {
+                    if collator.since + <T as Config<I>>::UnstakeLockTime::get() > now {
                         return true;
                     };
                     // Recover funds locked in the collator
@@ -1134,8 +1951,8 @@ pub mod pallet {
                         Ok(_) => {
                             // collator was unstaked, its funds are now "free balance", we track it so it won't be given to the next winner
                             log::debug!("Unstaked {:?} from collator {:?}",balance_to_unstake,collator.account.clone());
-                            <UnlockedUnstakingFunds<T>>::mutate(|unlocked| *unlocked = (*unlocked).saturating_add(balance_to_unstake));
-                            <StakedCollators<T>>::remove(collator.account.clone());
+                            <UnlockedUnstakingFunds<T, I>>::mutate(|unlocked| *unlocked = (*unlocked).saturating_add(balance_to_unstake));
+                            <StakedCollators<T, I>>::remove(collator.account.clone());
                             // don't retain this collator in the unstaking collators vec
                             false
                         },
@@ -1147,7 +1964,7 @@ pub mod pallet {
                     original_len - unstaking.len(),
                     original_len
                 );
-                UnstakingCollators::<T>::put(unstaking);
+                UnstakingCollators::<T, I>::put(unstaking);
             }
         }
 
@@ -1162,13 +1979,18 @@ pub mod pallet {
             // - is funds that were previously unstaked
             // - is surplus funds (we may have some from `finish_unstaking_collators`)
             // NOTE: Funds tracked in `surplus_unstaking_balance` might still be partially stake locked
-            let outstanding_balance_to_withdraw = <WithdrawalRequestQueue<T>>::get()
+            // - isn't held back in the liquidity buffer for instant withdrawals
+            Self::do_shrink_liquidity_buffer()?;
+            let outstanding_balance_to_withdraw = <WithdrawalRequestQueue<T, I>>::get()
                 .iter()
                 .map(|request| request.balance)
                 .reduce(|acc, balance| acc + balance)
                 .unwrap_or_else(|| 0u32.into());
             let restakable_balance =
                 Self::unlocked_unstaking_funds().saturating_sub(outstanding_balance_to_withdraw);
+            let restakable_balance = restakable_balance
+                .saturating_sub(Self::do_fill_liquidity_buffer(restakable_balance)?);
+            Self::do_unstake_liquidity_shortfall()?;
             if restakable_balance < Self::min_deposit() {
                 log::debug!(
                     "Restakable balance of {:?} is below staking minimum of {:?}. Not restaking",
@@ -1192,21 +2014,92 @@ pub mod pallet {
                     collator
                 );
             }
-            SurplusUnstakingBalance::<T>::try_mutate(|bal| -> DispatchResult {
+            SurplusUnstakingBalance::<T, I>::try_mutate(|bal| -> DispatchResult {
                 *bal = (*bal)
                     .checked_sub(&restakable_balance)
-                    .ok_or(Error::<T>::ArithmeticUnderflow)?;
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
                 Ok(())
             })?;
-            UnlockedUnstakingFunds::<T>::try_mutate(|unlocked| -> DispatchResult {
+            UnlockedUnstakingFunds::<T, I>::try_mutate(|unlocked| -> DispatchResult {
                 *unlocked = (*unlocked)
                     .checked_sub(&restakable_balance)
-                    .ok_or(Error::<T>::ArithmeticUnderflow)?;
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
                 Ok(())
             })?;
             Ok(())
         }
 
+        /// Returns the part of the liquidity buffer exceeding its target to the unstaked funds for restaking
+        fn do_shrink_liquidity_buffer() -> DispatchResult {
+            let excess = Self::liquidity_buffer().saturating_sub(Self::liquidity_buffer_target());
+            if excess.is_zero() {
+                return Ok(());
+            }
+            LiquidityBuffer::<T, I>::mutate(|buffer| *buffer = (*buffer).saturating_sub(excess));
+            SurplusUnstakingBalance::<T, I>::try_mutate(|bal| -> DispatchResult {
+                *bal = (*bal)
+                    .checked_add(&excess)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok(())
+            })?;
+            UnlockedUnstakingFunds::<T, I>::try_mutate(|unlocked| -> DispatchResult {
+                *unlocked = (*unlocked)
+                    .checked_add(&excess)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok(())
+            })
+        }
+
+        /// Moves up to `available` unlocked unstaking funds into the liquidity buffer until it reaches its target
+        /// Returns the amount moved
+        fn do_fill_liquidity_buffer(
+            available: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let top_up = Self::liquidity_buffer_target()
+                .saturating_sub(Self::liquidity_buffer())
+                .min(available);
+            if top_up.is_zero() {
+                return Ok(top_up);
+            }
+            SurplusUnstakingBalance::<T, I>::try_mutate(|bal| -> DispatchResult {
+                *bal = (*bal)
+                    .checked_sub(&top_up)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                Ok(())
+            })?;
+            UnlockedUnstakingFunds::<T, I>::try_mutate(|unlocked| -> DispatchResult {
+                *unlocked = (*unlocked)
+                    .checked_sub(&top_up)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                Ok(())
+            })?;
+            LiquidityBuffer::<T, I>::mutate(|buffer| *buffer = (*buffer).saturating_add(top_up));
+            log::debug!("Moved {:?} into the liquidity buffer", top_up);
+            Ok(top_up)
+        }
+
+        /// Unstakes collators to cover the part of the liquidity buffer target that neither the buffer
+        /// nor the funds still unstaking will fill once they unlock
+        /// Must be called after [`Self::do_fill_liquidity_buffer`] moved all unlocked surplus funds it could
+        fn do_unstake_liquidity_shortfall() -> DispatchResult {
+            let shortfall = Self::liquidity_buffer_target()
+                .saturating_sub(Self::liquidity_buffer())
+                .saturating_sub(Self::surplus_unstaking_balance());
+            if shortfall.is_zero() {
+                return Ok(());
+            }
+            log::debug!(
+                "Unstaking collators for a liquidity shortfall of {:?}",
+                shortfall
+            );
+            let now = <frame_system::Pallet<T>>::block_number();
+            // The following calls update `SurplusUnstakingBalance` with newly unstaked funds
+            for collator in Self::calculate_withdrawal_distribution(shortfall) {
+                Self::do_unstake_collator(now, collator)?;
+            }
+            Ok(())
+        }
+
         /// This fn schedules a single shot payout of all matured withdrawals
         /// Main usage: Automatic execution in the course of a drawing
         /// It can also be manually invoke by T::ManageOrigin to reprocess withdrawals that
@@ -1215,7 +2108,7 @@ pub mod pallet {
         #[named]
         fn do_process_matured_withdrawals() -> DispatchResult {
             log::trace!(function_name!());
-            if <WithdrawalRequestQueue<T>>::get().is_empty() {
+            if <WithdrawalRequestQueue<T, I>>::get().is_empty() {
                 return Ok(()); // nothing to do
             }
             let now = <frame_system::Pallet<T>>::block_number();
@@ -1224,7 +2117,7 @@ pub mod pallet {
                 Self::unlocked_unstaking_funds()
             );
             // Pay down the list from top (oldest) to bottom until we've paid out everyone or run out of available funds
-            <WithdrawalRequestQueue<T>>::mutate(|request_vec| -> Result<(), DispatchError> {
+            <WithdrawalRequestQueue<T, I>>::mutate(|request_vec| -> Result<(), DispatchError> {
                 let mut left_overs: Vec<Request<_, _, _>> = Vec::new();
                 for request in request_vec.iter() {
                     let funds_available_to_withdraw = Self::unlocked_unstaking_funds();
@@ -1234,7 +2127,7 @@ pub mod pallet {
                         continue;
                     }
                     // Don't pay anyone still timelocked
-                    if request.block + <T as Config>::UnstakeLockTime::get() > now {
+                    if request.block + <T as Config<I>>::UnstakeLockTime::get() > now {
                         left_overs.push((*request).clone());
                         continue;
                     }
@@ -1246,7 +2139,9 @@ pub mod pallet {
                         continue;
                     }
                     // we know we can pay this out, do it
-                    <SumOfDeposits<T>>::mutate(|sum| *sum = (*sum).saturating_sub(request.balance));
+                    <SumOfDeposits<T, I>>::mutate(|sum| {
+                        *sum = (*sum).saturating_sub(request.balance)
+                    });
                     log::debug!(
                         "Transferring {:?} to {:?}",
                         request.balance.clone(),
@@ -1258,10 +2153,10 @@ pub mod pallet {
                         request.balance,
                         KeepAlive,
                     )?;
-                    <UnlockedUnstakingFunds<T>>::try_mutate(|funds| -> DispatchResult {
+                    <UnlockedUnstakingFunds<T, I>>::try_mutate(|funds| -> DispatchResult {
                         *funds = (*funds)
                             .checked_sub(&request.balance)
-                            .ok_or(Error::<T>::ArithmeticUnderflow)?;
+                            .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
                         Ok(())
                     })?;
                     Self::deposit_event(Event::Withdrawn {
@@ -1286,7 +2181,7 @@ pub mod pallet {
         }
     }
 
-    impl<T: Config> Pallet<T> {
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
         // public getters for lottery state
         /// Returns the block the next drawing will execute, if any
         pub fn next_drawing_at() -> Option<T::BlockNumber> {
@@ -1307,10 +2202,13 @@ pub mod pallet {
             // waiting to serve a pending withdrawal in the next cycle.
             // These free funds must not be touched until then, so we don't consider this balance a surplus
             let unlocked = Self::unlocked_unstaking_funds();
+            // The liquidity buffer is held back to pay out instant withdrawals of user deposits
+            let buffer = Self::liquidity_buffer();
 
             non_staked_funds
                 .saturating_sub(unclaimed)
                 .saturating_sub(unlocked)
+                .saturating_sub(buffer)
         }
         /// funds in the lottery pallet that are not needed/reserved for anything and can be paid to the next winner
         pub fn current_prize_pool() -> BalanceOf<T> {
@@ -1325,7 +2223,7 @@ pub mod pallet {
                 }
                 Some(drawing) => {
                     let now = <frame_system::Pallet<T>>::block_number();
-                    now < drawing.saturating_sub(<T as Config>::DrawingFreezeout::get())
+                    now < drawing.saturating_sub(<T as Config<I>>::DrawingFreezeout::get())
                 }
             }
         }
@@ -0,0 +1,89 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations for the lottery pallet.
+
+use super::*;
+use core::marker::PhantomData;
+use frame_support::{
+    pallet_prelude::Weight,
+    traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Storage migration building the `TicketTree` winner lookup from the
+/// existing `ActiveBalancePerUser` entries
+pub struct BuildTicketTree<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for BuildTicketTree<T, I> {
+    fn on_runtime_upgrade() -> Weight {
+        let storage_version = Pallet::<T, I>::on_chain_storage_version();
+        if storage_version < 2 {
+            log::info!(target: "lottery", "Start to build the ticket tree for pallet-lottery.");
+            let mut users: u64 = 0;
+            for (account, balance) in ActiveBalancePerUser::<T, I>::iter() {
+                Pallet::<T, I>::add_ticket_balance(&account, balance);
+                users += 1;
+            }
+            // Existing deposits start accruing ticket weight from the upgrade on
+            DrawingPeriodStart::<T, I>::put(<frame_system::Pallet<T>>::block_number());
+            StorageVersion::new(2).put::<Pallet<T, I>>();
+            // Every insertion touches at most one node per level of the tree
+            let depth = (64 - users.leading_zeros()) as u64;
+            T::DbWeight::get()
+                .reads(users.saturating_mul(depth.saturating_mul(2) + 3) + 1)
+                .saturating_add(T::DbWeight::get().writes(users.saturating_mul(depth + 4) + 2))
+        } else {
+            log::info!("✅ no migration for pallet-lottery.");
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+        let storage_version = Pallet::<T, I>::on_chain_storage_version();
+        if storage_version >= 2 {
+            return Err(DispatchError::Other(
+                "Storage version is >= 2, the migration won't be executed.",
+            ));
+        }
+        if TicketCount::<T, I>::get() != 0 {
+            return Err(DispatchError::Other("Ticket tree is already populated."));
+        }
+        Ok(Vec::new())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+        let storage_version = Pallet::<T, I>::on_chain_storage_version();
+        if storage_version < 2 {
+            return Err(DispatchError::Other(
+                "Storage version is < 2, the migration was not executed.",
+            ));
+        }
+        let all_tickets = Pallet::<T, I>::ticket_prefix_sum(TicketCount::<T, I>::get());
+        if all_tickets != Pallet::<T, I>::total_pot() {
+            return Err(DispatchError::Other(
+                "Ticket tree does not add up to the total pot.",
+            ));
+        }
+        log::info!("✅ Storage migration for pallet-lottery has been executed successfully.");
+        Ok(())
+    }
+}
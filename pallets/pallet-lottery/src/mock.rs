@@ -44,7 +44,7 @@ use sp_core::H256;
 
 use sp_runtime::{
     traits::{BlakeTwo256, Hash, IdentityLookup},
-    Perbill, Percent,
+    FixedU128, Perbill, Percent,
 };
 use xcm::{
     prelude::{Junctions, Parachain, X1},
@@ -87,6 +87,7 @@ construct_runtime!(
         BlockAuthor: block_author::{Pallet, Storage},
         CollatorSelection: manta_collator_selection::{Pallet, Call, Storage, Config<T>, Event<T>},
         Lottery: pallet_lottery::{Pallet, Call, Storage, Event<T>, Config<T>},
+        DailyLottery: pallet_lottery::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>},
         Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
         Assets: pallet_assets::{Pallet, Storage, Config<T>, Event<T>},
         AssetManager: pallet_asset_manager::{Pallet, Call, Storage, Event<T>},
@@ -464,6 +465,7 @@ impl pallet_asset_manager::Config for Test {
 parameter_types! {
     pub const FarmingKeeperPalletId: PalletId = PalletId(*b"bf/fmkpr");
     pub const FarmingRewardIssuerPalletId: PalletId = PalletId(*b"bf/fmrir");
+    pub FarmingMaxBoost: FixedU128 = FixedU128::from_rational(5, 2);
     pub const TreasuryAccount: AccountId = TREASURY_ACCOUNT;
 }
 
@@ -481,6 +483,16 @@ impl pallet_farming::Config for Test {
     type TreasuryAccount = TreasuryAccount;
     type Keeper = FarmingKeeperPalletId;
     type RewardIssuer = FarmingRewardIssuerPalletId;
+    type MaxIncentives = ConstU32<10>;
+    type VoteEscrowCurrency = Balances;
+    type MaxLockBlocks = ConstU32<100>;
+    type MaxBoost = FarmingMaxBoost;
+    type EpochLength = ConstU32<10>;
+    type MaxPoolVotes = ConstU32<3>;
+    type MaxVotedPools = ConstU32<5>;
+    type MaxAllowlist = ConstU32<5>;
+    type MaxChargeRefunds = ConstU32<5>;
+    type PoolGuard = ();
     type WeightInfo = ();
 }
 
@@ -497,6 +509,8 @@ parameter_types! {
     pub DrawingFreezeout: BlockNumber = 5;
     /// Time in blocks until a collator is done unstaking
     pub UnstakeLockTime: BlockNumber = LeaveDelayRounds::get() * DefaultBlocksPerRound::get();
+    /// Time in blocks between drawings of the second lottery
+    pub DailyDrawingInterval: BlockNumber = 10;
 }
 
 use frame_support::traits::Currency;
@@ -524,6 +538,16 @@ impl frame_support::traits::EstimateCallFee<pallet::Call<Test>, BalanceOf<Test>>
         3 * KMA
     }
 }
+impl frame_support::traits::EstimateCallFee<pallet::Call<Test, pallet::Instance1>, BalanceOf<Test>>
+    for MockEstimateFee
+{
+    fn estimate_call_fee(
+        _call: &pallet::Call<Test, pallet::Instance1>,
+        _post_info: frame_support::dispatch::PostDispatchInfo,
+    ) -> BalanceOf<Test> {
+        3 * KMA
+    }
+}
 impl Config for Test {
     type RuntimeCall = RuntimeCall;
     type RuntimeEvent = RuntimeEvent;
@@ -533,12 +557,29 @@ impl Config for Test {
     type ManageOrigin = frame_system::EnsureRoot<AccountId>;
     type PalletsOrigin = OriginCaller;
     type LotteryPot = LotteryPotId;
+    type LotteryId = ConstU32<0>;
     type DrawingInterval = DrawingInterval;
     type DrawingFreezeout = DrawingFreezeout;
     type UnstakeLockTime = UnstakeLockTime;
     type BalanceConversion = Balance;
     type WeightInfo = ();
 }
+impl Config<pallet::Instance1> for Test {
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type Scheduler = Scheduler;
+    type EstimateCallFee = MockEstimateFee;
+    type RandomnessSource = TestRandomness<Test>;
+    type ManageOrigin = frame_system::EnsureRoot<AccountId>;
+    type PalletsOrigin = OriginCaller;
+    type LotteryPot = LotteryPotId;
+    type LotteryId = ConstU32<1>;
+    type DrawingInterval = DailyDrawingInterval;
+    type DrawingFreezeout = DrawingFreezeout;
+    type UnstakeLockTime = UnstakeLockTime;
+    type BalanceConversion = Balance;
+    type WeightInfo = ();
+}
 
 use frame_support::traits::{ValidatorRegistration, ValidatorSet};
 
@@ -647,9 +688,19 @@ impl ExtBuilder {
             min_withdraw: 5_000 * KMA,
             gas_reserve: 10_000 * KMA,
             farming_pool_params: farming_params,
+            phantom: PhantomData,
         }
         .assimilate_storage(&mut t)
         .expect("pallet_lottery's storage can be assimilated");
+        pallet_lottery::GenesisConfig::<Test, pallet::Instance1> {
+            min_deposit: 5_000 * KMA,
+            min_withdraw: 5_000 * KMA,
+            gas_reserve: 1_000 * KMA,
+            farming_pool_params: FarmingParamsOf::<Test>::default(),
+            phantom: PhantomData,
+        }
+        .assimilate_storage(&mut t)
+        .expect("second pallet_lottery instance's storage can be assimilated");
         pallet_asset_manager::GenesisConfig::<Test> {
             start_id: <MantaAssetConfig as AssetConfig<Test>>::StartNonNativeAssetId::get(),
         }
@@ -723,7 +774,9 @@ fn init_jumbo_farming() {
         0, // after_block_to_start
         0, // withdraw_limit_time
         0, // claim_limit_time
-        5  // withdraw_limit_count
+        5, // withdraw_limit_count
+        None,
+        pallet_farming::EmissionCurve::Constant
     ));
 
     let pool_id = 0;
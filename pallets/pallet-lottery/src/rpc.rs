@@ -16,30 +16,76 @@
 
 //! Lottery RPC Interfaces
 
-use crate::runtime::LotteryApi;
+use crate::{runtime::LotteryApi, DrawingRecord, UserStats};
+use codec::Codec;
 use core::marker::PhantomData;
 use jsonrpsee::{
-    core::{async_trait, RpcResult},
+    core::{async_trait, Error as JsonRpseeError, RpcResult},
     proc_macros::rpc,
     types::error::{CallError, ErrorObject},
 };
-use sp_api::ProvideRuntimeApi;
+use manta_primitives::types::{Balance, BlockNumber};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_runtime::traits::Block;
+use sp_runtime::{traits::Block, Perquintill};
 use sp_std::sync::Arc;
 
 pub const LOTTERY_ERROR: i32 = 777;
 
+/// Lottery queried when no `lottery_id` is given, the only lottery of runtimes before version 2 of [`LotteryApi`]
+pub const DEFAULT_LOTTERY_ID: u32 = 0;
+
+/// Error returned for a `lottery_id` the runtime has no lottery for
+fn unknown_lottery(lottery_id: u32) -> JsonRpseeError {
+    CallError::Custom(ErrorObject::owned(
+        LOTTERY_ERROR,
+        "No lottery with this id",
+        Some(format!("{lottery_id}")),
+    ))
+    .into()
+}
+
+/// Error returned when the runtime API call itself fails
+fn api_error(message: &'static str, err: impl core::fmt::Debug) -> JsonRpseeError {
+    CallError::Custom(ErrorObject::owned(
+        LOTTERY_ERROR,
+        message,
+        Some(format!("{err:?}")),
+    ))
+    .into()
+}
+
+/// All queries take an optional trailing `lottery_id`, querying [`DEFAULT_LOTTERY_ID`] when omitted
 #[rpc(server)]
-pub trait LotteryRpc {
+pub trait LotteryRpc<AccountId>
+where
+    AccountId: Send + Sync + 'static,
+{
     #[method(name = "lottery_not_in_drawing_freezeout", blocking)]
-    fn not_in_drawing_freezeout(&self) -> RpcResult<bool>;
+    fn not_in_drawing_freezeout(&self, lottery_id: Option<u32>) -> RpcResult<bool>;
 
     #[method(name = "lottery_current_prize_pool", blocking)]
-    fn current_prize_pool(&self) -> RpcResult<u128>;
+    fn current_prize_pool(&self, lottery_id: Option<u32>) -> RpcResult<u128>;
 
     #[method(name = "lottery_next_drawing_at", blocking)]
-    fn next_drawing_at(&self) -> RpcResult<Option<u128>>;
+    fn next_drawing_at(&self, lottery_id: Option<u32>) -> RpcResult<Option<u128>>;
+
+    #[method(name = "lottery_effective_odds", blocking)]
+    fn effective_odds(&self, account: AccountId, lottery_id: Option<u32>)
+        -> RpcResult<Perquintill>;
+
+    #[method(name = "lottery_drawing_history", blocking)]
+    fn drawing_history(
+        &self,
+        lottery_id: Option<u32>,
+    ) -> RpcResult<Vec<DrawingRecord<AccountId, Balance, BlockNumber>>>;
+
+    #[method(name = "lottery_user_stats", blocking)]
+    fn user_stats(
+        &self,
+        account: AccountId,
+        lottery_id: Option<u32>,
+    ) -> RpcResult<UserStats<Balance>>;
 }
 
 /// Lottery RPC API Implementation
@@ -62,52 +108,122 @@ impl<B, C> Lottery<B, C> {
     }
 }
 
+impl<B, C> Lottery<B, C>
+where
+    B: Block,
+    C: 'static + ProvideRuntimeApi<B> + HeaderBackend<B>,
+{
+    /// Returns whether the runtime at `at` only serves the queries of [`DEFAULT_LOTTERY_ID`]
+    fn is_single_lottery_runtime<AccountId>(&self, at: B::Hash) -> RpcResult<bool>
+    where
+        C::Api: LotteryApi<B, AccountId>,
+        AccountId: Codec,
+    {
+        self.client
+            .runtime_api()
+            .api_version::<dyn LotteryApi<B, AccountId>>(at)
+            .map_err(|err| api_error("Unable to fetch lottery API version", err))
+            .map(|version| version.map_or(false, |version| version < 2))
+    }
+}
+
 #[async_trait]
-impl<B, C> LotteryRpcServer for Lottery<B, C>
+impl<B, C, AccountId> LotteryRpcServer<AccountId> for Lottery<B, C>
 where
     B: Block,
     C: 'static + ProvideRuntimeApi<B> + HeaderBackend<B>,
-    C::Api: LotteryApi<B>,
+    C::Api: LotteryApi<B, AccountId>,
+    AccountId: Codec + Send + Sync + 'static,
 {
     #[inline]
-    fn not_in_drawing_freezeout(&self) -> RpcResult<bool> {
+    fn not_in_drawing_freezeout(&self, lottery_id: Option<u32>) -> RpcResult<bool> {
+        let lottery_id = lottery_id.unwrap_or(DEFAULT_LOTTERY_ID);
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+        let result = if self.is_single_lottery_runtime::<AccountId>(at)? {
+            #[allow(deprecated)]
+            let result = api.not_in_drawing_freezeout_before_version_2(at);
+            result.map(|result| (lottery_id == DEFAULT_LOTTERY_ID).then_some(result))
+        } else {
+            api.not_in_drawing_freezeout(at, lottery_id)
+        };
+        result
+            .map_err(|err| api_error("Unable to compute drawing freezeout", err))
+            .and_then(|result| result.ok_or_else(|| unknown_lottery(lottery_id)))
+    }
+
+    #[inline]
+    fn current_prize_pool(&self, lottery_id: Option<u32>) -> RpcResult<u128> {
+        let lottery_id = lottery_id.unwrap_or(DEFAULT_LOTTERY_ID);
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+        let result = if self.is_single_lottery_runtime::<AccountId>(at)? {
+            #[allow(deprecated)]
+            let result = api.current_prize_pool_before_version_2(at);
+            result.map(|result| (lottery_id == DEFAULT_LOTTERY_ID).then_some(result))
+        } else {
+            api.current_prize_pool(at, lottery_id)
+        };
+        result
+            .map_err(|err| api_error("Unable to compute current prize pool", err))
+            .and_then(|result| result.ok_or_else(|| unknown_lottery(lottery_id)))
+    }
+
+    #[inline]
+    fn next_drawing_at(&self, lottery_id: Option<u32>) -> RpcResult<Option<u128>> {
+        let lottery_id = lottery_id.unwrap_or(DEFAULT_LOTTERY_ID);
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+        let result = if self.is_single_lottery_runtime::<AccountId>(at)? {
+            #[allow(deprecated)]
+            let result = api.next_drawing_at_before_version_2(at);
+            result.map(|result| (lottery_id == DEFAULT_LOTTERY_ID).then_some(result))
+        } else {
+            api.next_drawing_at(at, lottery_id)
+        };
+        result
+            .map_err(|err| api_error("Unable to compute next drawing", err))
+            .and_then(|result| result.ok_or_else(|| unknown_lottery(lottery_id)))
+    }
+
+    #[inline]
+    fn effective_odds(
+        &self,
+        account: AccountId,
+        lottery_id: Option<u32>,
+    ) -> RpcResult<Perquintill> {
+        let lottery_id = lottery_id.unwrap_or(DEFAULT_LOTTERY_ID);
         let api = self.client.runtime_api();
         let at = self.client.info().best_hash;
-        api.not_in_drawing_freezeout(at).map_err(|err| {
-            CallError::Custom(ErrorObject::owned(
-                LOTTERY_ERROR,
-                "Unable to compute drawing freezeout",
-                Some(format!("{err:?}")),
-            ))
-            .into()
-        })
+        api.effective_odds(at, lottery_id, account)
+            .map_err(|err| api_error("Unable to compute effective odds", err))
+            .and_then(|result| result.ok_or_else(|| unknown_lottery(lottery_id)))
     }
 
     #[inline]
-    fn current_prize_pool(&self) -> RpcResult<u128> {
+    fn drawing_history(
+        &self,
+        lottery_id: Option<u32>,
+    ) -> RpcResult<Vec<DrawingRecord<AccountId, Balance, BlockNumber>>> {
+        let lottery_id = lottery_id.unwrap_or(DEFAULT_LOTTERY_ID);
         let api = self.client.runtime_api();
         let at = self.client.info().best_hash;
-        api.current_prize_pool(at).map_err(|err| {
-            CallError::Custom(ErrorObject::owned(
-                LOTTERY_ERROR,
-                "Unable to compute current prize pool",
-                Some(format!("{err:?}")),
-            ))
-            .into()
-        })
+        api.drawing_history(at, lottery_id)
+            .map_err(|err| api_error("Unable to fetch drawing history", err))
+            .and_then(|result| result.ok_or_else(|| unknown_lottery(lottery_id)))
     }
 
     #[inline]
-    fn next_drawing_at(&self) -> RpcResult<Option<u128>> {
+    fn user_stats(
+        &self,
+        account: AccountId,
+        lottery_id: Option<u32>,
+    ) -> RpcResult<UserStats<Balance>> {
+        let lottery_id = lottery_id.unwrap_or(DEFAULT_LOTTERY_ID);
         let api = self.client.runtime_api();
         let at = self.client.info().best_hash;
-        api.next_drawing_at(at).map_err(|err| {
-            CallError::Custom(ErrorObject::owned(
-                LOTTERY_ERROR,
-                "Unable to compute next drawing",
-                Some(format!("{err:?}")),
-            ))
-            .into()
-        })
+        api.user_stats(at, lottery_id, account)
+            .map_err(|err| api_error("Unable to fetch user stats", err))
+            .and_then(|result| result.ok_or_else(|| unknown_lottery(lottery_id)))
     }
 }
@@ -14,10 +14,32 @@
 // You should have received a copy of the GNU General Public License
 // along with Manta.  If not, see <http://www.gnu.org/licenses/>.
 
+use crate::{DrawingRecord, UserStats};
+use codec::Codec;
+use manta_primitives::types::{Balance, BlockNumber};
+use sp_runtime::Perquintill;
+use sp_std::vec::Vec;
+
 sp_api::decl_runtime_apis! {
-    pub trait LotteryApi {
+    /// Queries of the lottery with the given `lottery_id`, returning `None` if the runtime has no such lottery
+    ///
+    /// Version 1 only served the lottery of the runtime, its queries are kept below for nodes talking to older runtimes
+    #[api_version(2)]
+    pub trait LotteryApi<AccountId> where
+        AccountId: Codec,
+    {
+        #[changed_in(2)]
         fn not_in_drawing_freezeout() -> bool;
+        #[changed_in(2)]
         fn current_prize_pool() -> u128;
+        #[changed_in(2)]
         fn next_drawing_at() -> Option<u128>;
+
+        fn not_in_drawing_freezeout(lottery_id: u32) -> Option<bool>;
+        fn current_prize_pool(lottery_id: u32) -> Option<u128>;
+        fn next_drawing_at(lottery_id: u32) -> Option<Option<u128>>;
+        fn effective_odds(lottery_id: u32, account: AccountId) -> Option<Perquintill>;
+        fn drawing_history(lottery_id: u32) -> Option<Vec<DrawingRecord<AccountId, Balance, BlockNumber>>>;
+        fn user_stats(lottery_id: u32, account: AccountId) -> Option<UserStats<Balance>>;
     }
 }
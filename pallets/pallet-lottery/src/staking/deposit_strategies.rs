@@ -19,250 +19,408 @@ use super::*;
 use frame_support::traits::Randomness;
 use pallet_parachain_staking::BalanceOf;
 use sp_runtime::{
-    traits::{Saturating, Zero},
+    traits::{SaturatedConversion, Saturating, Zero},
     Percent,
 };
 use sp_std::{vec, vec::Vec};
 
-#[named]
-pub(super) fn reactivate_bottom_collators<T: Config>(
-    active_collators: &[T::AccountId],
-    new_deposit: BalanceOf<T>,
-) -> Vec<(T::AccountId, BalanceOf<T>)> {
-    log::trace!(function_name!());
+/// first concern: If we fell out of the active set on one or more collators, we need to get back into it
+pub struct ReactivateBottomCollators;
 
-    let mut deposits: Vec<(T::AccountId, BalanceOf<T>)> = vec![];
-    let mut remaining_deposit = new_deposit;
+impl<T: Config<I>, I: 'static> DepositStrategy<T, I> for ReactivateBottomCollators {
+    #[named]
+    fn distribute(
+        &self,
+        active_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        log::trace!(function_name!());
 
-    // We only consider collators we're already staked to that are also currently active (and not being unstaked)
-    for collator in StakedCollators::<T>::iter_keys().filter(|coll| active_collators.contains(coll))
-    {
-        let staked = StakedCollators::<T>::get(collator.clone());
-        let info = pallet_parachain_staking::Pallet::<T>::candidate_info(collator.clone())
-            .expect("is active collator, therefore it has collator info. qed");
-        if staked < info.lowest_top_delegation_amount {
-            // TODO: Small optimization: sort collators ascending by missing amount so we get the largest amount of collators active before running out of funds
-            let this_deposit = core::cmp::min(
-                remaining_deposit,
-                info.lowest_top_delegation_amount - staked + 1u32.into(),
-            );
-            // Ensure we don't try to stake a smaller than allowed delegation to a collator
-            if remaining_deposit.saturating_sub(this_deposit) < crate::Pallet::<T>::min_deposit() {
-                deposits.push((collator, remaining_deposit)); // put the full remaining balance in this collator
-                break;
-            } else {
-                deposits.push((collator, this_deposit)); // put just what's needed to get back into the top delegators
-                remaining_deposit = remaining_deposit.saturating_sub(this_deposit);
+        let mut deposits: Vec<(T::AccountId, BalanceOf<T>)> = vec![];
+        let mut remaining_deposit = new_deposit;
+
+        // We only consider collators we're already staked to that are also currently active (and not being unstaked)
+        for collator in
+            StakedCollators::<T, I>::iter_keys().filter(|coll| active_collators.contains(coll))
+        {
+            let staked = StakedCollators::<T, I>::get(collator.clone());
+            let info = pallet_parachain_staking::Pallet::<T>::candidate_info(collator.clone())
+                .expect("is active collator, therefore it has collator info. qed");
+            if staked < info.lowest_top_delegation_amount {
+                // TODO: Small optimization: sort collators ascending by missing amount so we get the largest amount of collators active before running out of funds
+                let this_deposit = core::cmp::min(
+                    remaining_deposit,
+                    info.lowest_top_delegation_amount - staked + 1u32.into(),
+                );
+                // Ensure we don't try to stake a smaller than allowed delegation to a collator
+                if remaining_deposit.saturating_sub(this_deposit)
+                    < crate::Pallet::<T, I>::min_deposit()
+                {
+                    deposits.push((collator, remaining_deposit)); // put the full remaining balance in this collator
+                    break;
+                } else {
+                    deposits.push((collator, this_deposit)); // put just what's needed to get back into the top delegators
+                    remaining_deposit = remaining_deposit.saturating_sub(this_deposit);
+                }
             }
         }
+        deposits
     }
-    deposits
 }
 
 /// second concern: We want to maximize staking APY earned, so we want to balance the staking pools with our deposits while conserving gas
-pub(super) fn split_to_underallocated_collators<T: Config>(
-    active_collators: &[T::AccountId],
-    new_deposit: BalanceOf<T>,
-) -> Vec<(T::AccountId, BalanceOf<T>)> {
-    let mut deposits: Vec<(T::AccountId, BalanceOf<T>)> = vec![];
-    let mut remaining_deposit = new_deposit;
+pub struct SplitToUnderallocatedCollators;
 
-    if active_collators.len().is_zero() || new_deposit.is_zero() {
-        return deposits;
-    }
-    // We only consider active collators for deposits
-    // TODO: Small optimization: Also consider points / pointsAwarded to not stake to collators missing blocks
-    let mut collators_and_counted_balances: Vec<_> = active_collators
-        .iter()
-        .cloned()
-        .map(|collator| {
-            (
-                collator.clone(),
-                pallet_parachain_staking::Pallet::<T>::candidate_info(collator)
-                    .expect("is active collator, therefore it has collator info. qed")
-                    .total_counted,
-            )
-        })
-        .collect();
-    // sort ascending by counted stake
-    collators_and_counted_balances.sort_by(|a, b| a.1.cmp(&b.1));
-    debug_assert!(
-        collators_and_counted_balances.len() == 1
-            || pallet_parachain_staking::Pallet::<T>::candidate_info(
-                collators_and_counted_balances[0].0.clone()
-            )
-            .unwrap()
-            .total_counted
-                <= pallet_parachain_staking::Pallet::<T>::candidate_info(
-                    collators_and_counted_balances[1].0.clone()
+impl<T: Config<I>, I: 'static> DepositStrategy<T, I> for SplitToUnderallocatedCollators {
+    fn distribute(
+        &self,
+        active_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        let mut deposits: Vec<(T::AccountId, BalanceOf<T>)> = vec![];
+        let mut remaining_deposit = new_deposit;
+
+        if active_collators.len().is_zero() || new_deposit.is_zero() {
+            return deposits;
+        }
+        // We only consider active collators for deposits
+        // TODO: Small optimization: Also consider points / pointsAwarded to not stake to collators missing blocks
+        let mut collators_and_counted_balances: Vec<_> = active_collators
+            .iter()
+            .cloned()
+            .map(|collator| {
+                (
+                    collator.clone(),
+                    pallet_parachain_staking::Pallet::<T>::candidate_info(collator)
+                        .expect("is active collator, therefore it has collator info. qed")
+                        .total_counted,
+                )
+            })
+            .collect();
+        // sort ascending by counted stake
+        collators_and_counted_balances.sort_by(|a, b| a.1.cmp(&b.1));
+        debug_assert!(
+            collators_and_counted_balances.len() == 1
+                || pallet_parachain_staking::Pallet::<T>::candidate_info(
+                    collators_and_counted_balances[0].0.clone()
                 )
                 .unwrap()
                 .total_counted
-    );
+                    <= pallet_parachain_staking::Pallet::<T>::candidate_info(
+                        collators_and_counted_balances[1].0.clone()
+                    )
+                    .unwrap()
+                    .total_counted
+        );
 
-    let median_collator_balance =
-        collators_and_counted_balances[collators_and_counted_balances.len() / 2].1;
+        let median_collator_balance =
+            collators_and_counted_balances[collators_and_counted_balances.len() / 2].1;
 
-    // build collator => deviation from median map
-    let mut underallocated_collators: Vec<_> =
-        collators_and_counted_balances[..collators_and_counted_balances.len() / 2].to_vec();
-    underallocated_collators = underallocated_collators
-        .into_iter()
-        .filter_map(|(collator, balance)| {
-            let underallocation = median_collator_balance.saturating_sub(balance);
-            if !underallocation.is_zero() {
-                Some((collator, underallocation))
-            } else {
-                None
-            }
-        })
-        .collect();
-    // After this calculation, underallocated_collators is in descending order of underallocation
+        // build collator => deviation from median map
+        let mut underallocated_collators: Vec<_> =
+            collators_and_counted_balances[..collators_and_counted_balances.len() / 2].to_vec();
+        underallocated_collators = underallocated_collators
+            .into_iter()
+            .filter_map(|(collator, balance)| {
+                let underallocation = median_collator_balance.saturating_sub(balance);
+                if !underallocation.is_zero() {
+                    Some((collator, underallocation))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        // After this calculation, underallocated_collators is in descending order of underallocation
 
-    // take up to 4 collators with the highest deficit ( stopping at median )
-    let num_collators_to_take = core::cmp::min(4, underallocated_collators.len());
-    underallocated_collators = underallocated_collators[..num_collators_to_take].to_vec();
+        // take up to 4 collators with the highest deficit ( stopping at median )
+        let num_collators_to_take = core::cmp::min(4, underallocated_collators.len());
+        underallocated_collators = underallocated_collators[..num_collators_to_take].to_vec();
 
-    debug_assert!(
-        underallocated_collators.is_empty()
-            || pallet_parachain_staking::Pallet::<T>::candidate_info(
-                underallocated_collators[0].0.clone()
-            )
-            .unwrap()
-            .total_counted
-                <= median_collator_balance
-    );
-    debug_assert!(
-        underallocated_collators.len() < 2
-            || pallet_parachain_staking::Pallet::<T>::candidate_info(
-                underallocated_collators[0].0.clone()
-            )
-            .unwrap()
-            .total_counted
-                <= pallet_parachain_staking::Pallet::<T>::candidate_info(
-                    underallocated_collators[1].0.clone()
+        debug_assert!(
+            underallocated_collators.is_empty()
+                || pallet_parachain_staking::Pallet::<T>::candidate_info(
+                    underallocated_collators[0].0.clone()
+                )
+                .unwrap()
+                .total_counted
+                    <= median_collator_balance
+        );
+        debug_assert!(
+            underallocated_collators.len() < 2
+                || pallet_parachain_staking::Pallet::<T>::candidate_info(
+                    underallocated_collators[0].0.clone()
                 )
                 .unwrap()
                 .total_counted
-    );
-    debug_assert!(
-        underallocated_collators.len() < 2
-            || underallocated_collators[0].1 >= underallocated_collators[1].1
-    );
-    log::debug!(
-        "Total Underallocated collators: {:?}",
-        underallocated_collators.len()
-    );
-    if !underallocated_collators.is_empty() {
-        let total_underallocation = underallocated_collators
+                    <= pallet_parachain_staking::Pallet::<T>::candidate_info(
+                        underallocated_collators[1].0.clone()
+                    )
+                    .unwrap()
+                    .total_counted
+        );
+        debug_assert!(
+            underallocated_collators.len() < 2
+                || underallocated_collators[0].1 >= underallocated_collators[1].1
+        );
+        log::debug!(
+            "Total Underallocated collators: {:?}",
+            underallocated_collators.len()
+        );
+        if !underallocated_collators.is_empty() {
+            let total_underallocation = underallocated_collators
                 .iter()
                 .cloned()
                 .map(|a| a.1)
                 .reduce(|acc, balance| acc + balance)
                 .expect("reduce returns None on empty iterator. we checked that `underallocated_collators` is not empty. qed");
-        log::debug!(
-            "Underallocated tokens {:?} on selected collators: {:?}",
-            total_underallocation,
-            underallocated_collators
-        );
-        for (account, tokens_to_reach_median) in underallocated_collators.clone() {
-            // If a proportional deposit is over the min deposit and can get us into the top balance, deposit it, if not just skip it
-            let info = pallet_parachain_staking::Pallet::<T>::candidate_info(account.clone())
-                .expect("is active collator, therefor it has collator info. qed");
-            let collator_proportion =
-                Percent::from_rational(tokens_to_reach_median, total_underallocation);
-            let to_reach_mean = collator_proportion.mul_ceil(new_deposit);
-            let to_deposit = to_reach_mean.min(remaining_deposit);
-            let our_stake = StakedCollators::<T>::get(account.clone());
-            if to_deposit > crate::Pallet::<T>::min_deposit()
-                && to_deposit + our_stake > info.lowest_top_delegation_amount
-            {
-                let this_deposit = core::cmp::min(to_deposit, remaining_deposit);
-                deposits.push((account.clone(), this_deposit));
-                remaining_deposit -= this_deposit;
-                log::debug!(
-                    "Selected collator {:?} for deposit of {:?} token",
-                    account.clone(),
-                    to_deposit
-                );
-            };
-            if remaining_deposit < crate::Pallet::<T>::min_deposit() {
-                break;
-            }
-        }
-    }
-    // if we had to skip a collator above due to not getting into the top deposit or because the deposit was below minimum
-    // we just lump the rest into the collator with the lowest stake
-    if !remaining_deposit.is_zero() {
-        if !deposits.is_empty() {
-            let mut last_new_deposit = deposits.pop().expect(
-                "we checked that deposits is not empty, therefore pop will return Some. qed",
-            );
-            last_new_deposit.1 += remaining_deposit;
-            remaining_deposit.set_zero();
-            deposits.push(last_new_deposit);
-        } else if !underallocated_collators.is_empty() {
-            // i.e. no collator could be staked above but underallocated ones exist
-            let deposit = (
+            log::debug!(
+                "Underallocated tokens {:?} on selected collators: {:?}",
+                total_underallocation,
                 underallocated_collators
-                    .first()
-                    .expect("underallocated_collators is not empty. qed")
-                    .clone()
-                    .0,
-                remaining_deposit,
             );
-            remaining_deposit.set_zero();
-            deposits.push(deposit);
+            for (account, tokens_to_reach_median) in underallocated_collators.clone() {
+                // If a proportional deposit is over the min deposit and can get us into the top balance, deposit it, if not just skip it
+                let info = pallet_parachain_staking::Pallet::<T>::candidate_info(account.clone())
+                    .expect("is active collator, therefor it has collator info. qed");
+                let collator_proportion =
+                    Percent::from_rational(tokens_to_reach_median, total_underallocation);
+                let to_reach_mean = collator_proportion.mul_ceil(new_deposit);
+                let to_deposit = to_reach_mean.min(remaining_deposit);
+                let our_stake = StakedCollators::<T, I>::get(account.clone());
+                if to_deposit > crate::Pallet::<T, I>::min_deposit()
+                    && to_deposit + our_stake > info.lowest_top_delegation_amount
+                {
+                    let this_deposit = core::cmp::min(to_deposit, remaining_deposit);
+                    deposits.push((account.clone(), this_deposit));
+                    remaining_deposit -= this_deposit;
+                    log::debug!(
+                        "Selected collator {:?} for deposit of {:?} token",
+                        account.clone(),
+                        to_deposit
+                    );
+                };
+                if remaining_deposit < crate::Pallet::<T, I>::min_deposit() {
+                    break;
+                }
+            }
         }
+        // if we had to skip a collator above due to not getting into the top deposit or because the deposit was below minimum
+        // we just lump the rest into the collator with the lowest stake
         if !remaining_deposit.is_zero() {
-            log::error!("FATAL: Have {:?} tokens left over after depositing. active collators {:?}, underallocated collators {:?}, deposits {:?}",
+            if !deposits.is_empty() {
+                let mut last_new_deposit = deposits.pop().expect(
+                    "we checked that deposits is not empty, therefore pop will return Some. qed",
+                );
+                last_new_deposit.1 += remaining_deposit;
+                remaining_deposit.set_zero();
+                deposits.push(last_new_deposit);
+            } else if !underallocated_collators.is_empty() {
+                // i.e. no collator could be staked above but underallocated ones exist
+                let deposit = (
+                    underallocated_collators
+                        .first()
+                        .expect("underallocated_collators is not empty. qed")
+                        .clone()
+                        .0,
+                    remaining_deposit,
+                );
+                remaining_deposit.set_zero();
+                deposits.push(deposit);
+            }
+            if !remaining_deposit.is_zero() {
+                log::error!("FATAL: Have {:?} tokens left over after depositing. active collators {:?}, underallocated collators {:?}, deposits {:?}",
             remaining_deposit,
             active_collators.len(),
             underallocated_collators.len(),
             deposits.len()
         );
+            }
         }
+        deposits
     }
-    deposits
 }
 
 /// fallback: just assign to a random active collator ( choose a different collator for each invocation )
-pub(crate) fn stake_to_random_collator<T: Config>(
-    active_collators: &[T::AccountId],
-    new_deposit: BalanceOf<T>,
-) -> Option<(T::AccountId, BalanceOf<T>)> {
-    use sp_runtime::traits::SaturatedConversion;
-    if active_collators.len().is_zero() || new_deposit.is_zero() {
-        return None;
+pub struct StakeToRandomCollator;
+
+impl<T: Config<I>, I: 'static> DepositStrategy<T, I> for StakeToRandomCollator {
+    fn distribute(
+        &self,
+        active_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        if active_collators.len().is_zero() || new_deposit.is_zero() {
+            return vec![];
+        }
+
+        let block_number = <frame_system::Pallet<T>>::block_number().saturated_into::<u128>();
+        let extrinsic_index = <frame_system::Pallet<T>>::extrinsic_index().unwrap_or_default();
+        let nonce: u128 = block_number ^ extrinsic_index as u128;
+        let randomness_output: sp_core::U256;
+        #[cfg(feature = "runtime-benchmarks")]
+        {
+            use rand::{Rng, SeedableRng};
+            let mut rng = rand::rngs::StdRng::seed_from_u64(nonce as u64);
+            randomness_output = rng.gen::<u128>().into();
+        }
+        #[cfg(not(feature = "runtime-benchmarks"))]
+        {
+            randomness_output = sp_core::U256::from_big_endian(
+                T::RandomnessSource::random(&nonce.to_be_bytes()).0.as_ref(),
+            );
+        }
+        // NOTE: The following line introduces modulo bias, but since this is just a fallback it is accepted
+        let random_index: usize = randomness_output.low_u64() as usize % active_collators.len();
+        if let Some(random_collator) = active_collators.get(random_index) {
+            log::warn!(
+                "Staking {:?} randomly to {:?}",
+                new_deposit,
+                random_collator
+            );
+            vec![(random_collator.clone(), new_deposit)]
+        } else {
+            log::error!("Could not stake {:?} randomly", new_deposit);
+            vec![]
+        }
     }
+}
+
+/// Spreads the deposit evenly across the `collators` active collators with the highest APY, i.e. the lowest counted stake
+pub struct EvenSpreadTopApyCollators {
+    pub collators: u32,
+}
 
-    let block_number = <frame_system::Pallet<T>>::block_number().saturated_into::<u128>();
-    let extrinsic_index = <frame_system::Pallet<T>>::extrinsic_index().unwrap_or_default();
-    let nonce: u128 = block_number ^ extrinsic_index as u128;
-    let randomness_output: sp_core::U256;
-    #[cfg(feature = "runtime-benchmarks")]
-    {
-        use rand::{Rng, SeedableRng};
-        let mut rng = rand::rngs::StdRng::seed_from_u64(nonce as u64);
-        randomness_output = rng.gen::<u128>().into();
+impl<T: Config<I>, I: 'static> DepositStrategy<T, I> for EvenSpreadTopApyCollators {
+    fn distribute(
+        &self,
+        active_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        let min_deposit = crate::Pallet::<T, I>::min_deposit();
+        if active_collators.is_empty() || new_deposit < min_deposit || min_deposit.is_zero() {
+            return vec![];
+        }
+        let mut apy_ordered_collators: Vec<_> = active_collators
+            .iter()
+            .cloned()
+            .map(|collator| {
+                (
+                    collator.clone(),
+                    pallet_parachain_staking::Pallet::<T>::candidate_info(collator)
+                        .expect("is active collator, therefore it has collator info. qed")
+                        .total_counted,
+                )
+            })
+            .collect();
+        // sort ascending by counted stake, the collators with the least stake pay the highest APY
+        apy_ordered_collators.sort_by(|a, b| a.1.cmp(&b.1));
+
+        // Ensure every collator receives at least the minimum deposit
+        let affordable_collators = (new_deposit / min_deposit).saturated_into::<u32>();
+        let num_collators = self
+            .collators
+            .min(affordable_collators)
+            .min(apy_ordered_collators.len() as u32);
+        if num_collators.is_zero() {
+            return vec![];
+        }
+        let deposit_per_collator = new_deposit / BalanceOf::<T>::from(num_collators);
+        let mut deposits: Vec<(T::AccountId, BalanceOf<T>)> = apy_ordered_collators
+            [..num_collators as usize]
+            .iter()
+            .map(|(collator, _)| (collator.clone(), deposit_per_collator))
+            .collect();
+        // the division remainder goes to the collator with the highest APY
+        if let Some(first) = deposits.first_mut() {
+            first.1 += new_deposit - deposit_per_collator * BalanceOf::<T>::from(num_collators);
+        }
+        log::debug!("Spreading deposit evenly: {:?}", deposits);
+        deposits
     }
-    #[cfg(not(feature = "runtime-benchmarks"))]
-    {
-        randomness_output = sp_core::U256::from_big_endian(
-            T::RandomnessSource::random(&nonce.to_be_bytes()).0.as_ref(),
+}
+
+/// Stakes to the collators holding the least of our stake first, filling each up to `cap` of all funds staked by the lottery
+pub struct MaxDiversification {
+    pub cap: Percent,
+}
+
+impl<T: Config<I>, I: 'static> DepositStrategy<T, I> for MaxDiversification {
+    fn distribute(
+        &self,
+        active_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        let mut deposits: Vec<(T::AccountId, BalanceOf<T>)> = vec![];
+        let min_deposit = crate::Pallet::<T, I>::min_deposit();
+        if active_collators.is_empty() || new_deposit < min_deposit {
+            return deposits;
+        }
+        let total_staked = StakedCollators::<T, I>::iter_values()
+            .fold(new_deposit, |total, staked| total.saturating_add(staked));
+        let stake_cap = self.cap.mul_floor(total_staked);
+
+        let mut collators_by_our_stake: Vec<_> = active_collators
+            .iter()
+            .cloned()
+            .map(|collator| {
+                let our_stake = StakedCollators::<T, I>::get(&collator);
+                (collator, our_stake)
+            })
+            .collect();
+        // sort ascending by our stake
+        collators_by_our_stake.sort_by(|a, b| a.1.cmp(&b.1));
+
+        let mut remaining_deposit = new_deposit;
+        for (collator, our_stake) in collators_by_our_stake {
+            let mut this_deposit = stake_cap.saturating_sub(our_stake).min(remaining_deposit);
+            // Never leave a remainder that is too small to be staked on its own
+            let left_over = remaining_deposit - this_deposit;
+            if !left_over.is_zero() && left_over < min_deposit {
+                this_deposit = remaining_deposit.saturating_sub(min_deposit);
+            }
+            if this_deposit < min_deposit {
+                continue;
+            }
+            deposits.push((collator, this_deposit));
+            remaining_deposit -= this_deposit;
+            if remaining_deposit.is_zero() {
+                break;
+            }
+        }
+        log::debug!(
+            "Diversified deposits: {:?}, {:?} exceeding the cap of {:?}",
+            deposits,
+            remaining_deposit,
+            stake_cap
         );
+        deposits
     }
-    // NOTE: The following line introduces modulo bias, but since this is just a fallback it is accepted
-    let random_index: usize = randomness_output.low_u64() as usize % active_collators.len();
-    if let Some(random_collator) = active_collators.get(random_index) {
-        log::warn!(
-            "Staking {:?} randomly to {:?}",
-            new_deposit,
-            random_collator
-        );
-        Some((random_collator.clone(), new_deposit))
-    } else {
-        log::error!("Could not stake {:?} randomly", new_deposit);
-        None
+}
+
+impl<T: Config<I>, I: 'static> DepositStrategy<T, I> for DepositStrategyKind {
+    fn distribute(
+        &self,
+        active_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        match *self {
+            DepositStrategyKind::SplitToUnderallocated => DepositStrategy::<T, I>::distribute(
+                &SplitToUnderallocatedCollators,
+                active_collators,
+                new_deposit,
+            ),
+            DepositStrategyKind::EvenSpreadTopApy { collators } => {
+                DepositStrategy::<T, I>::distribute(
+                    &EvenSpreadTopApyCollators { collators },
+                    active_collators,
+                    new_deposit,
+                )
+            }
+            DepositStrategyKind::MaxDiversification { cap } => DepositStrategy::<T, I>::distribute(
+                &MaxDiversification { cap },
+                active_collators,
+                new_deposit,
+            ),
+        }
     }
 }
@@ -17,6 +17,14 @@
 mod deposit_strategies;
 mod withdraw_strategies;
 
+pub use deposit_strategies::{
+    EvenSpreadTopApyCollators, MaxDiversification, ReactivateBottomCollators,
+    SplitToUnderallocatedCollators, StakeToRandomCollator,
+};
+pub use withdraw_strategies::{
+    UnstakeInactiveCollators, UnstakeLargestDelegations, UnstakeLeastApyCollators,
+};
+
 use super::*;
 use frame_support::{dispatch::RawOrigin, ensure, traits::EstimateCallFee};
 use pallet_parachain_staking::BalanceOf;
@@ -26,7 +34,28 @@ use sp_runtime::{
 };
 use sp_std::{vec, vec::Vec};
 
-impl<T: Config> Pallet<T> {
+/// Selects the collators new deposits are staked to
+pub trait DepositStrategy<T: Config<I>, I: 'static = ()> {
+    /// Assigns up to `new_deposit` tokens to some of the `active_collators`
+    /// Tokens left unassigned are handed to the next strategy in line
+    fn distribute(
+        &self,
+        active_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)>;
+}
+
+/// Selects the collators to fully unstake to service a withdrawal
+pub trait WithdrawStrategy<T: Config<I>, I: 'static = ()> {
+    /// Returns collators to unstake and the total we have staked to them, which may fall short of `withdrawal_amount`
+    fn select(
+        &self,
+        eligible_collators: &[T::AccountId],
+        withdrawal_amount: BalanceOf<T>,
+    ) -> (Vec<T::AccountId>, BalanceOf<T>);
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
     #[named]
     /// distributes a given amount of tokens to zero or more collators for staking
     /// if it can't distribute all tokens for some reason, it returns an empty vec
@@ -55,7 +84,7 @@ impl<T: Config> Pallet<T> {
             log::error!("FATAL: ParachainStaking returned no active collators"); // NOTE: guaranteed by ParachainStaking to not happen
             return vec![];
         }
-        let collators_we_are_unstaking_from = UnstakingCollators::<T>::get()
+        let collators_we_are_unstaking_from = UnstakingCollators::<T, I>::get()
             .iter()
             .cloned()
             .map(|uc| uc.account)
@@ -78,7 +107,8 @@ impl<T: Config> Pallet<T> {
             .collect::<Vec<_>>();
 
         // first concern: If we fell out of the active set on one or more collators, we need to get back into it
-        deposits.append(&mut deposit_strategies::reactivate_bottom_collators::<T>(
+        deposits.append(&mut DepositStrategy::<T, I>::distribute(
+            &ReactivateBottomCollators,
             deposit_eligible_collators.as_slice(),
             new_deposit,
         ));
@@ -103,13 +133,13 @@ impl<T: Config> Pallet<T> {
             return deposits;
         }
 
-        // second concern: We want to maximize staking APY earned, so we want to balance the staking pools with our deposits while conserving gas
-        deposits.append(
-            &mut deposit_strategies::split_to_underallocated_collators::<T>(
-                deposit_eligible_collators.as_slice(),
-                remaining_deposit,
-            ),
-        );
+        // second concern: Distribute according to the strategy selected by governance, by default we want to maximize
+        // staking APY earned, so we want to balance the staking pools with our deposits while conserving gas
+        deposits.append(&mut DepositStrategy::<T, I>::distribute(
+            &Self::deposit_strategy(),
+            deposit_eligible_collators.as_slice(),
+            remaining_deposit,
+        ));
         remaining_deposit -= deposits
             .iter()
             .map(|deposit| deposit.1)
@@ -121,11 +151,13 @@ impl<T: Config> Pallet<T> {
                 "Failed to distribute {:?} tokens by strategy",
                 remaining_deposit
             );
-            if let Some(deposit) = deposit_strategies::stake_to_random_collator::<T>(
+            let mut random_deposit = DepositStrategy::<T, I>::distribute(
+                &StakeToRandomCollator,
                 deposit_eligible_collators.as_slice(),
                 remaining_deposit,
-            ) {
-                deposits.push(deposit);
+            );
+            if !random_deposit.is_empty() {
+                deposits.append(&mut random_deposit);
                 remaining_deposit = new_deposit
                     - deposits
                         .iter()
@@ -160,8 +192,8 @@ impl<T: Config> Pallet<T> {
         let mut remaining_balance = withdrawal_amount;
 
         // Only unstake collators we're staked to **and not already unstaking from**
-        let staked_collators: Vec<_> = StakedCollators::<T>::iter_keys().collect();
-        let collators_we_are_unstaking_from: Vec<_> = UnstakingCollators::<T>::get()
+        let staked_collators: Vec<_> = StakedCollators::<T, I>::iter_keys().collect();
+        let collators_we_are_unstaking_from: Vec<_> = UnstakingCollators::<T, I>::get()
             .iter()
             .cloned()
             .map(|uc| uc.account)
@@ -176,7 +208,8 @@ impl<T: Config> Pallet<T> {
             return vec![];
         }
         // first concern: If there are inactive collators we are staked with, prefer these
-        let (mut collators, balance_unstaked) = withdraw_strategies::unstake_inactive_collators::<T>(
+        let (mut collators, balance_unstaked) = WithdrawStrategy::<T, I>::select(
+            &UnstakeInactiveCollators,
             &withdrawal_eligible_collators,
             remaining_balance,
         );
@@ -186,12 +219,14 @@ impl<T: Config> Pallet<T> {
             return withdrawals;
         }
         // If we have balance to withdraw left over, we have to unstake some healthy collator.
-        // Unstake starting from the highest overallocated collator ( since that yields the lowest APY ) going down until request is satisfied
-        let (mut collators, balance_unstaked) = withdraw_strategies::unstake_least_apy_collators::<T>(
-            &withdrawal_eligible_collators
-                .into_iter()
-                .filter(|collator| !withdrawals.contains(collator))
-                .collect(),
+        // By default unstake starting from the highest overallocated collator ( since that yields the lowest APY ) going down until request is satisfied
+        let remaining_eligible_collators: Vec<_> = withdrawal_eligible_collators
+            .into_iter()
+            .filter(|collator| !withdrawals.contains(collator))
+            .collect();
+        let (mut collators, balance_unstaked) = WithdrawStrategy::<T, I>::select(
+            &Self::withdraw_strategy(),
+            &remaining_eligible_collators,
             remaining_balance,
         );
         withdrawals.append(&mut collators);
@@ -223,23 +258,23 @@ impl<T: Config> Pallet<T> {
         // - newly deposited funds > min_deposit
         // - unstaked-but-not-needed-for-withdrawals funds > min_deposit
         if amount < Self::min_deposit() {
-            return Err(Error::<T>::DepositBelowMinAmount.into());
+            return Err(Error::<T, I>::DepositBelowMinAmount.into());
         }
         if amount > Self::surplus_funds() && amount > Self::unlocked_unstaking_funds() {
             // we can't handle this withdrawal from new deposits or unstaked funds
-            return Err(Error::<T>::PotBalanceTooLowToStake.into());
+            return Err(Error::<T, I>::PotBalanceTooLowToStake.into());
         }
         // collator exists
         let candidate_delegation_count;
         if let Some(info) = pallet_parachain_staking::Pallet::<T>::candidate_info(&collator) {
             candidate_delegation_count = info.delegation_count;
         } else {
-            return Err(Error::<T>::NoCollatorForStake.into());
+            return Err(Error::<T, I>::NoCollatorForStake.into());
         };
-        let delegation_count = StakedCollators::<T>::iter_keys().count() as u32;
+        let delegation_count = StakedCollators::<T, I>::iter_keys().count() as u32;
 
         // If we're already delegated to this collator, we must call `delegate_more`
-        if StakedCollators::<T>::get(&collator).is_zero() {
+        if StakedCollators::<T, I>::get(&collator).is_zero() {
             // Ensure the pallet has enough gas to pay for this
             let fee_estimate: BalanceOf<T> = T::EstimateCallFee::estimate_call_fee(
                 &pallet_parachain_staking::Call::delegate {
@@ -252,7 +287,7 @@ impl<T: Config> Pallet<T> {
             );
             ensure!(
                 Self::surplus_funds() > fee_estimate,
-                Error::<T>::PotBalanceTooLowToPayTxFee
+                Error::<T, I>::PotBalanceTooLowToPayTxFee
             );
             pallet_parachain_staking::Pallet::<T>::delegate(
                 RawOrigin::Signed(Self::account_id()).into(),
@@ -281,7 +316,7 @@ impl<T: Config> Pallet<T> {
             );
             ensure!(
                 Self::surplus_funds() > fee_estimate,
-                Error::<T>::PotBalanceTooLowToPayTxFee
+                Error::<T, I>::PotBalanceTooLowToPayTxFee
             );
             pallet_parachain_staking::Pallet::<T>::delegator_bond_more(
                 RawOrigin::Signed(Self::account_id()).into(),
@@ -298,7 +333,7 @@ impl<T: Config> Pallet<T> {
                 e.error
             })?;
         }
-        StakedCollators::<T>::mutate(&collator, |balance| *balance += amount);
+        StakedCollators::<T, I>::mutate(&collator, |balance| *balance += amount);
 
         log::debug!("Delegated {:?} tokens to {:?}", amount, collator);
         Ok(())
@@ -310,10 +345,10 @@ impl<T: Config> Pallet<T> {
         some_collator: T::AccountId,
     ) -> DispatchResult {
         log::trace!(function_name!());
-        let delegated_amount_to_be_unstaked = StakedCollators::<T>::get(some_collator.clone());
+        let delegated_amount_to_be_unstaked = StakedCollators::<T, I>::get(some_collator.clone());
         if delegated_amount_to_be_unstaked.is_zero() {
             log::error!("requested to unstake a collator that isn't staked");
-            return Err(Error::<T>::NoCollatorForWithdrawal.into());
+            return Err(Error::<T, I>::NoCollatorForWithdrawal.into());
         };
         log::debug!(
             "Unstaking collator {:?} with balance {:?}",
@@ -329,7 +364,7 @@ impl<T: Config> Pallet<T> {
         );
         ensure!(
             Self::surplus_funds() > fee_estimate,
-            Error::<T>::PotBalanceTooLowToPayTxFee
+            Error::<T, I>::PotBalanceTooLowToPayTxFee
         );
         // unstake from parachain staking
         // NOTE: All funds that were delegated here will no longer produce staking rewards
@@ -340,10 +375,10 @@ impl<T: Config> Pallet<T> {
         .map_err(|e| e.error)?;
 
         // Update bookkeeping
-        SurplusUnstakingBalance::<T>::mutate(|bal| {
+        SurplusUnstakingBalance::<T, I>::mutate(|bal| {
             *bal = (*bal).saturating_add(delegated_amount_to_be_unstaked);
         });
-        UnstakingCollators::<T>::mutate(|collators| {
+        UnstakingCollators::<T, I>::mutate(|collators| {
             collators.push(UnstakingCollator {
                 account: some_collator.clone(),
                 since: now,
@@ -19,81 +19,152 @@ use pallet_parachain_staking::BalanceOf;
 use sp_runtime::traits::{Saturating, Zero};
 use sp_std::{vec, vec::Vec};
 
-pub(super) fn unstake_inactive_collators<T: Config>(
-    eligible_collators: &Vec<T::AccountId>,
-    withdrawal_amount: BalanceOf<T>,
-) -> (Vec<T::AccountId>, BalanceOf<T>) {
-    let mut withdrawals = vec![];
-    let mut unstaked = 0u32.into();
+/// first concern: If there are inactive collators we are staked with, prefer these
+pub struct UnstakeInactiveCollators;
 
-    if eligible_collators.len().is_zero() || withdrawal_amount.is_zero() {
-        return (withdrawals, 0u32.into());
-    }
-    // first concern: If there are inactive collators we are staked with, prefer these
-    let round_info = pallet_parachain_staking::Pallet::<T>::round();
-    let selected = pallet_parachain_staking::Pallet::<T>::selected_candidates();
-    let inactive_eligible_collators = eligible_collators.iter().filter(
+impl<T: Config<I>, I: 'static> WithdrawStrategy<T, I> for UnstakeInactiveCollators {
+    fn select(
+        &self,
+        eligible_collators: &[T::AccountId],
+        withdrawal_amount: BalanceOf<T>,
+    ) -> (Vec<T::AccountId>, BalanceOf<T>) {
+        let mut withdrawals = vec![];
+        let mut unstaked = 0u32.into();
+
+        if eligible_collators.len().is_zero() || withdrawal_amount.is_zero() {
+            return (withdrawals, 0u32.into());
+        }
+        // first concern: If there are inactive collators we are staked with, prefer these
+        let round_info = pallet_parachain_staking::Pallet::<T>::round();
+        let selected = pallet_parachain_staking::Pallet::<T>::selected_candidates();
+        let inactive_eligible_collators = eligible_collators.iter().filter(
             |collator|{
                 // no longer selected for block rewards
                 !selected.contains(collator) ||
                 // did not receive any points last round unless this is the first round
                 (round_info.current > 1 && pallet_parachain_staking::Pallet::<T>::awarded_pts(round_info.current-1,collator).is_zero())
             });
-    // since these collators are inactive, we just unstake in any order until we have satisfied the withdrawal request
-    for collator in inactive_eligible_collators {
-        let our_stake = StakedCollators::<T>::get(collator);
-        log::debug!("Unstaking {:?} from inactive {:?}", our_stake, collator);
-        unstaked += our_stake;
-        withdrawals.push(collator.clone());
-        if unstaked >= withdrawal_amount {
+        // since these collators are inactive, we just unstake in any order until we have satisfied the withdrawal request
+        for collator in inactive_eligible_collators {
+            let our_stake = StakedCollators::<T, I>::get(collator);
+            log::debug!("Unstaking {:?} from inactive {:?}", our_stake, collator);
+            unstaked += our_stake;
+            withdrawals.push(collator.clone());
+            if unstaked >= withdrawal_amount {
+                return (withdrawals, unstaked);
+            }
+        }
+        log::debug!(
+            "Remaining after inactive: {:?}",
+            withdrawal_amount.saturating_sub(unstaked)
+        );
+        (withdrawals, unstaked)
+    }
+}
+
+/// Unstakes the active collators with the lowest APY, i.e. the highest counted stake, first
+pub struct UnstakeLeastApyCollators;
+
+impl<T: Config<I>, I: 'static> WithdrawStrategy<T, I> for UnstakeLeastApyCollators {
+    fn select(
+        &self,
+        eligible_collators: &[T::AccountId],
+        withdrawal_amount: BalanceOf<T>,
+    ) -> (Vec<T::AccountId>, BalanceOf<T>) {
+        // If we have balance to withdraw left over, we have to unstake some healthy collator.
+        // Unstake starting from the highest overallocated collator ( since that yields the lowest APY ) going down until request is satisfied
+        let mut withdrawals = vec![];
+        let mut unstaked = 0u32.into();
+
+        if eligible_collators.len().is_zero() || withdrawal_amount.is_zero() {
             return (withdrawals, unstaked);
         }
+
+        let selected = pallet_parachain_staking::Pallet::<T>::selected_candidates();
+        let mut apy_ordered_active_collators_we_are_staked_with: Vec<_> = eligible_collators
+            .iter()
+            .filter(|collator| selected.contains(collator))
+            .cloned()
+            .collect();
+        apy_ordered_active_collators_we_are_staked_with.sort_by(|a, b| {
+            let ainfo = pallet_parachain_staking::Pallet::<T>::candidate_info(a.clone())
+                .expect("is a selected collator, therefore it has collator info. qed");
+            let binfo = pallet_parachain_staking::Pallet::<T>::candidate_info(b.clone())
+                .expect("is a selected collator, therefore it has collator info. qed");
+            binfo.total_counted.cmp(&ainfo.total_counted)
+        });
+        log::debug!(
+            "Active collators: {:?}",
+            apy_ordered_active_collators_we_are_staked_with.len()
+        );
+        for c in apy_ordered_active_collators_we_are_staked_with {
+            let our_stake = StakedCollators::<T, I>::get(c.clone());
+            log::debug!("Unstaking {:?} from active {:?}", our_stake, c);
+            withdrawals.push(c);
+            unstaked += our_stake;
+            if unstaked >= withdrawal_amount {
+                break;
+            }
+        }
+        (withdrawals, unstaked)
     }
-    log::debug!(
-        "Remaining after inactive: {:?}",
-        withdrawal_amount.saturating_sub(unstaked)
-    );
-    (withdrawals, unstaked)
 }
 
-pub(super) fn unstake_least_apy_collators<T: Config>(
-    eligible_collators: &Vec<T::AccountId>,
-    withdrawal_amount: BalanceOf<T>,
-) -> (Vec<T::AccountId>, BalanceOf<T>) {
-    // If we have balance to withdraw left over, we have to unstake some healthy collator.
-    // Unstake starting from the highest overallocated collator ( since that yields the lowest APY ) going down until request is satisfied
-    let mut withdrawals = vec![];
-    let mut unstaked = 0u32.into();
+/// Unstakes the collators holding the most of our stake first, undoing the largest concentrations of our funds
+pub struct UnstakeLargestDelegations;
+
+impl<T: Config<I>, I: 'static> WithdrawStrategy<T, I> for UnstakeLargestDelegations {
+    fn select(
+        &self,
+        eligible_collators: &[T::AccountId],
+        withdrawal_amount: BalanceOf<T>,
+    ) -> (Vec<T::AccountId>, BalanceOf<T>) {
+        let mut withdrawals = vec![];
+        let mut unstaked = 0u32.into();
+
+        if eligible_collators.is_empty() || withdrawal_amount.is_zero() {
+            return (withdrawals, unstaked);
+        }
 
-    if eligible_collators.len().is_zero() || withdrawal_amount.is_zero() {
-        return (withdrawals, unstaked);
+        let mut collators_by_our_stake: Vec<_> = eligible_collators
+            .iter()
+            .cloned()
+            .map(|collator| {
+                let our_stake = StakedCollators::<T, I>::get(&collator);
+                (collator, our_stake)
+            })
+            .collect();
+        // sort descending by our stake
+        collators_by_our_stake.sort_by(|a, b| b.1.cmp(&a.1));
+        for (collator, our_stake) in collators_by_our_stake {
+            log::debug!("Unstaking {:?} from {:?}", our_stake, collator);
+            withdrawals.push(collator);
+            unstaked += our_stake;
+            if unstaked >= withdrawal_amount {
+                break;
+            }
+        }
+        (withdrawals, unstaked)
     }
+}
 
-    let selected = pallet_parachain_staking::Pallet::<T>::selected_candidates();
-    let mut apy_ordered_active_collators_we_are_staked_with: Vec<_> = eligible_collators
-        .iter()
-        .filter(|collator| selected.contains(collator))
-        .cloned()
-        .collect();
-    apy_ordered_active_collators_we_are_staked_with.sort_by(|a, b| {
-        let ainfo = pallet_parachain_staking::Pallet::<T>::candidate_info(a.clone())
-            .expect("is a selected collator, therefore it has collator info. qed");
-        let binfo = pallet_parachain_staking::Pallet::<T>::candidate_info(b.clone())
-            .expect("is a selected collator, therefore it has collator info. qed");
-        binfo.total_counted.cmp(&ainfo.total_counted)
-    });
-    log::debug!(
-        "Active collators: {:?}",
-        apy_ordered_active_collators_we_are_staked_with.len()
-    );
-    for c in apy_ordered_active_collators_we_are_staked_with {
-        let our_stake = StakedCollators::<T>::get(c.clone());
-        log::debug!("Unstaking {:?} from active {:?}", our_stake, c);
-        withdrawals.push(c);
-        unstaked += our_stake;
-        if unstaked >= withdrawal_amount {
-            break;
+impl<T: Config<I>, I: 'static> WithdrawStrategy<T, I> for WithdrawStrategyKind {
+    fn select(
+        &self,
+        eligible_collators: &[T::AccountId],
+        withdrawal_amount: BalanceOf<T>,
+    ) -> (Vec<T::AccountId>, BalanceOf<T>) {
+        match self {
+            WithdrawStrategyKind::UnstakeLeastApy => WithdrawStrategy::<T, I>::select(
+                &UnstakeLeastApyCollators,
+                eligible_collators,
+                withdrawal_amount,
+            ),
+            WithdrawStrategyKind::UnstakeLargestDelegations => WithdrawStrategy::<T, I>::select(
+                &UnstakeLargestDelegations,
+                eligible_collators,
+                withdrawal_amount,
+            ),
         }
     }
-    (withdrawals, unstaked)
 }
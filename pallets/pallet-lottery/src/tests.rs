@@ -18,16 +18,21 @@ use crate::{
     assert_last_event,
     mock::{
         roll_one_block, roll_to, roll_to_round_begin, roll_to_round_end, AccountId, Assets,
-        Balance, Balances, ExtBuilder, Farming, Lottery, ParachainStaking, RuntimeOrigin as Origin,
-        System, Test, ALICE, BOB, CHARLIE, DAVE, EVE, INIT_JUMBO_AMOUNT, INIT_V_MANTA_AMOUNT,
-        JUMBO_ID, POOL_ID, V_MANTA_ID,
+        Balance, Balances, DailyLottery, ExtBuilder, Farming, Lottery, ParachainStaking,
+        RuntimeOrigin as Origin, System, Test, ALICE, BOB, CHARLIE, DAVE, EVE, INIT_JUMBO_AMOUNT,
+        INIT_V_MANTA_AMOUNT, JUMBO_ID, POOL_ID, V_MANTA_ID,
     },
-    Config, Error, FarmingParameters,
+    Config, DepositStrategyKind, DuplicateWinnerPolicy, Error, FarmingParameters, PrizeTier,
+    WithdrawStrategyKind,
 };
 
-use frame_support::{assert_noop, assert_ok, traits::Currency};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, Hooks, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+};
 use frame_system::RawOrigin;
-use sp_runtime::TokenError;
+use sp_runtime::{Percent, Perquintill, TokenError};
 
 const UNIT: Balance = 1_000_000_000_000;
 const HIGH_BALANCE: Balance = 1_000_000_000 * UNIT;
@@ -1141,3 +1146,1064 @@ fn fails_withdrawing_more_than_vmanta() {
             assert_eq!(0, Assets::balance(V_MANTA_ID, CHARLIE));
         });
 }
+
+fn count_winner_events() -> usize {
+    System::events()
+        .iter()
+        .filter(|record| {
+            matches!(
+                record.event,
+                crate::mock::RuntimeEvent::Lottery(crate::Event::LotteryWinner { .. })
+            )
+        })
+        .count()
+}
+
+#[test]
+fn setting_invalid_prize_tiers_should_not_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Lottery::set_prize_tiers(Origin::signed(ALICE), vec![], DuplicateWinnerPolicy::Allow),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Lottery::set_prize_tiers(
+                RawOrigin::Root.into(),
+                vec![
+                    PrizeTier {
+                        share: Percent::from_percent(60),
+                        winners: 1
+                    },
+                    PrizeTier {
+                        share: Percent::from_percent(50),
+                        winners: 2
+                    }
+                ],
+                DuplicateWinnerPolicy::Allow
+            ),
+            Error::<Test>::InvalidPrizeTiers
+        );
+        assert_noop!(
+            Lottery::set_prize_tiers(
+                RawOrigin::Root.into(),
+                vec![PrizeTier {
+                    share: Percent::from_percent(50),
+                    winners: 0
+                }],
+                DuplicateWinnerPolicy::Allow
+            ),
+            Error::<Test>::InvalidPrizeTiers
+        );
+        assert_noop!(
+            Lottery::set_prize_tiers(
+                RawOrigin::Root.into(),
+                vec![PrizeTier {
+                    share: Percent::from_percent(100),
+                    winners: crate::MAX_WINNERS_PER_DRAWING + 1
+                }],
+                DuplicateWinnerPolicy::Allow
+            ),
+            Error::<Test>::InvalidPrizeTiers
+        );
+    });
+}
+
+#[test]
+fn prize_tiers_pay_every_winner_of_every_tier() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .build()
+        .execute_with(|| {
+            <Test as pallet_parachain_staking::Config>::Currency::make_free_balance_be(
+                &Lottery::account_id(),
+                Lottery::gas_reserve(),
+            );
+            const NUMBER_OF_USERS: u32 = 20;
+            let deposit_amount = Lottery::min_deposit();
+            for user in 0..NUMBER_OF_USERS {
+                let (depositor, _) = crate::mock::from_bench::create_funded_user::<Test>(
+                    "depositor",
+                    user,
+                    deposit_amount,
+                );
+                assert_ok!(Lottery::deposit(
+                    RawOrigin::Signed(depositor).into(),
+                    deposit_amount
+                ));
+            }
+            assert_ok!(Lottery::set_prize_tiers(
+                RawOrigin::Root.into(),
+                vec![
+                    PrizeTier {
+                        share: Percent::from_percent(50),
+                        winners: 1
+                    },
+                    PrizeTier {
+                        share: Percent::from_percent(30),
+                        winners: 3
+                    },
+                    PrizeTier {
+                        share: Percent::from_percent(20),
+                        winners: 10
+                    },
+                ],
+                DuplicateWinnerPolicy::Allow
+            ));
+            assert_eq!(
+                Lottery::prize_payouts(1_000),
+                vec![(500, 1), (100, 3), (20, 10)]
+            );
+            // deposits need to be held for a block to have a chance to win
+            roll_one_block();
+            // simulate accrued staking rewards
+            assert_ok!(Balances::deposit_into_existing(
+                &Lottery::account_id(),
+                1_000
+            ));
+            assert_eq!(Lottery::current_prize_pool(), 1_000);
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(Lottery::total_unclaimed_winnings(), 1_000);
+            assert_eq!(count_winner_events(), 14);
+        });
+}
+
+#[test]
+fn redraw_policy_pays_each_account_at_most_once_per_drawing() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .build()
+        .execute_with(|| {
+            <Test as pallet_parachain_staking::Config>::Currency::make_free_balance_be(
+                &Lottery::account_id(),
+                Lottery::gas_reserve(),
+            );
+            assert_ok!(Lottery::deposit(
+                Origin::signed(ALICE),
+                Lottery::min_deposit()
+            ));
+            roll_one_block();
+            let three_winners = vec![PrizeTier {
+                share: Percent::from_percent(90),
+                winners: 3,
+            }];
+
+            // ALICE is the only player and wins every payout
+            assert_ok!(Lottery::set_prize_tiers(
+                RawOrigin::Root.into(),
+                three_winners.clone(),
+                DuplicateWinnerPolicy::Allow
+            ));
+            assert_ok!(Balances::deposit_into_existing(
+                &Lottery::account_id(),
+                1_000
+            ));
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(count_winner_events(), 3);
+            assert_eq!(Lottery::unclaimed_winnings_by_account(ALICE), Some(900));
+            // the 10% not assigned to any tier stays in the pot
+            assert_eq!(Lottery::current_prize_pool(), 100);
+
+            // ALICE can only win one payout, the rest rolls over
+            roll_one_block();
+            assert_ok!(Lottery::set_prize_tiers(
+                RawOrigin::Root.into(),
+                three_winners,
+                DuplicateWinnerPolicy::Redraw
+            ));
+            assert_ok!(Balances::deposit_into_existing(&Lottery::account_id(), 900));
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(count_winner_events(), 1);
+            assert_eq!(Lottery::unclaimed_winnings_by_account(ALICE), Some(1_200));
+            assert_eq!(Lottery::current_prize_pool(), 700);
+        });
+}
+
+#[test]
+fn ticket_tree_locates_the_holder_of_every_ticket() {
+    ExtBuilder::default().build().execute_with(|| {
+        // one block into the drawing period, every ticket weighs its balance
+        for (account, balance) in [(ALICE, 10), (BOB, 0), (CHARLIE, 20), (DAVE, 5), (EVE, 15)] {
+            Lottery::add_ticket_balance(&account, balance);
+        }
+        assert_eq!(crate::TicketCount::<Test>::get(), 5);
+        assert_eq!(Lottery::ticket_prefix_sum(5), 50);
+        assert_eq!(Lottery::total_ticket_weight(), 50);
+        assert_eq!(Lottery::find_ticket_holder(0), Some(ALICE));
+        assert_eq!(Lottery::find_ticket_holder(9), Some(ALICE));
+        assert_eq!(Lottery::find_ticket_holder(10), Some(CHARLIE));
+        assert_eq!(Lottery::find_ticket_holder(29), Some(CHARLIE));
+        assert_eq!(Lottery::find_ticket_holder(30), Some(DAVE));
+        assert_eq!(Lottery::find_ticket_holder(35), Some(EVE));
+        assert_eq!(Lottery::find_ticket_holder(49), Some(EVE));
+        assert_eq!(Lottery::find_ticket_holder(50), None);
+
+        // CHARLIE keeps the weight accrued before withdrawing
+        System::set_block_number(2);
+        Lottery::remove_tickets(&CHARLIE, 20);
+        System::set_block_number(3);
+        assert_eq!(Lottery::ticket_prefix_sum(5), 30);
+        assert_eq!(Lottery::total_ticket_weight(), 130);
+        assert_eq!(Lottery::find_ticket_holder(29), Some(ALICE));
+        assert_eq!(Lottery::find_ticket_holder(30), Some(CHARLIE));
+        assert_eq!(Lottery::find_ticket_holder(69), Some(CHARLIE));
+        assert_eq!(Lottery::find_ticket_holder(70), Some(DAVE));
+        assert_eq!(Lottery::find_ticket_holder(130), None);
+
+        // a deposit only gains weight for the blocks it is held
+        Lottery::add_tickets(&BOB, 100);
+        assert_eq!(Lottery::total_ticket_weight(), 130);
+        assert_eq!(Lottery::find_ticket_holder(30), Some(CHARLIE));
+        System::set_block_number(4);
+        assert_eq!(Lottery::total_ticket_weight(), 260);
+        assert_eq!(Lottery::find_ticket_holder(39), Some(ALICE));
+        assert_eq!(Lottery::find_ticket_holder(40), Some(BOB));
+        assert_eq!(Lottery::find_ticket_holder(139), Some(BOB));
+        assert_eq!(Lottery::find_ticket_holder(140), Some(CHARLIE));
+
+        // weights restart with the next drawing period, positions are kept
+        Lottery::start_new_drawing_period();
+        assert_eq!(Lottery::total_ticket_weight(), 0);
+        System::set_block_number(5);
+        assert_eq!(crate::TicketCount::<Test>::get(), 5);
+        assert_eq!(Lottery::total_ticket_weight(), 130);
+        assert_eq!(Lottery::find_ticket_holder(10), Some(BOB));
+        assert_eq!(Lottery::find_ticket_holder(110), Some(DAVE));
+    });
+}
+
+#[test]
+fn stale_ticket_periods_are_pruned_on_idle() {
+    ExtBuilder::default().build().execute_with(|| {
+        Lottery::add_ticket_balance(&ALICE, 10);
+        System::set_block_number(2);
+        Lottery::add_tickets(&BOB, 20);
+        Lottery::remove_tickets(&ALICE, 5);
+        assert!(crate::TicketPenalty::<Test>::iter_prefix(0)
+            .next()
+            .is_some());
+        assert!(crate::TicketCredit::<Test>::iter_prefix(0).next().is_some());
+
+        // ending the period leaves its trees in storage until there is spare block weight
+        Lottery::start_new_drawing_period();
+        assert!(crate::TicketPenalty::<Test>::iter_prefix(0)
+            .next()
+            .is_some());
+        assert_eq!(crate::StaleTicketPeriod::<Test>::get(), 0);
+
+        Lottery::on_idle(2, Weight::MAX);
+        assert!(crate::TicketPenalty::<Test>::iter_prefix(0)
+            .next()
+            .is_none());
+        assert!(crate::TicketCredit::<Test>::iter_prefix(0).next().is_none());
+        assert_eq!(crate::StaleTicketPeriod::<Test>::get(), 1);
+
+        // the current period is never pruned
+        System::set_block_number(3);
+        Lottery::add_tickets(&CHARLIE, 30);
+        Lottery::on_idle(3, Weight::MAX);
+        assert!(crate::TicketPenalty::<Test>::iter_prefix(1)
+            .next()
+            .is_some());
+        assert_eq!(crate::StaleTicketPeriod::<Test>::get(), 1);
+    });
+}
+
+#[test]
+fn depositing_and_withdrawing_updates_ticket_weight() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_eq!(Lottery::ticket_prefix_sum(1), balance);
+            // nothing is held yet in the block of the deposit
+            assert_eq!(Lottery::ticket_weight(&ALICE), 0);
+            assert_eq!(Lottery::effective_odds(&ALICE), Perquintill::zero());
+            assert_eq!(Lottery::find_ticket_holder(0), None);
+
+            roll_one_block();
+            assert_eq!(Lottery::ticket_weight(&ALICE), balance);
+            assert_eq!(Lottery::effective_odds(&ALICE), Perquintill::one());
+            assert_eq!(Lottery::find_ticket_holder(balance - 1), Some(ALICE));
+            assert_eq!(Lottery::find_ticket_holder(balance), None);
+
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(ALICE),
+                balance / 2
+            ));
+            assert_eq!(Lottery::ticket_prefix_sum(1), balance / 2);
+            assert_eq!(Lottery::ticket_weight(&ALICE), balance);
+            roll_one_block();
+            assert_eq!(Lottery::ticket_weight(&ALICE), balance + balance / 2);
+            assert_eq!(Lottery::total_ticket_weight(), balance + balance / 2);
+            assert_eq!(
+                Lottery::find_ticket_holder(balance + balance / 2 - 1),
+                Some(ALICE)
+            );
+            assert_eq!(Lottery::find_ticket_holder(balance + balance / 2), None);
+        });
+}
+
+#[test]
+fn time_weighted_odds_favour_longer_deposits() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            let deposit = Lottery::min_deposit();
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), deposit));
+            roll_to(System::block_number() + 9);
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), deposit));
+            roll_one_block();
+            assert_eq!(Lottery::ticket_weight(&ALICE), 10 * deposit);
+            assert_eq!(Lottery::ticket_weight(&CHARLIE), deposit);
+            assert_eq!(
+                Lottery::effective_odds(&ALICE),
+                Perquintill::from_rational(10u64, 11u64)
+            );
+            assert_eq!(
+                Lottery::effective_odds(&CHARLIE),
+                Perquintill::from_rational(1u64, 11u64)
+            );
+
+            // topping up right before the drawing does not change the odds
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), 100 * deposit));
+            assert_eq!(
+                Lottery::effective_odds(&ALICE),
+                Perquintill::from_rational(10u64, 11u64)
+            );
+
+            // after the drawing, odds follow the balances held since
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(Lottery::effective_odds(&ALICE), Perquintill::zero());
+            roll_one_block();
+            assert_eq!(
+                Lottery::effective_odds(&ALICE),
+                Perquintill::from_rational(1u64, 102u64)
+            );
+            assert_eq!(
+                Lottery::effective_odds(&CHARLIE),
+                Perquintill::from_rational(101u64, 102u64)
+            );
+        });
+}
+
+#[test]
+fn ticket_tree_migration_covers_existing_deposits() {
+    ExtBuilder::default().build().execute_with(|| {
+        StorageVersion::new(1).put::<Lottery>();
+        let deposits = [(ALICE, 10), (BOB, 20), (CHARLIE, 30)];
+        for (account, balance) in deposits {
+            crate::ActiveBalancePerUser::<Test>::insert(account, balance);
+        }
+        crate::TotalPot::<Test>::put(60);
+
+        crate::migrations::BuildTicketTree::<Test>::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get::<Lottery>(), 2);
+        assert_eq!(crate::TicketCount::<Test>::get(), 3);
+        assert_eq!(Lottery::ticket_prefix_sum(3), Lottery::total_pot());
+        // existing deposits accrue weight from the upgrade on
+        assert_eq!(Lottery::total_ticket_weight(), 0);
+        System::set_block_number(System::block_number() + 1);
+        assert_eq!(Lottery::total_ticket_weight(), Lottery::total_pot());
+        for (account, balance) in deposits {
+            let index = crate::TicketIndexOf::<Test>::get(account).unwrap();
+            assert_eq!(
+                Lottery::ticket_prefix_sum(index) - Lottery::ticket_prefix_sum(index - 1),
+                balance
+            );
+        }
+
+        // running the migration again must not count deposits twice
+        crate::migrations::BuildTicketTree::<Test>::on_runtime_upgrade();
+        assert_eq!(Lottery::ticket_prefix_sum(3), Lottery::total_pot());
+    });
+}
+
+#[test]
+fn drawings_are_recorded_with_their_winners() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .build()
+        .execute_with(|| {
+            <Test as pallet_parachain_staking::Config>::Currency::make_free_balance_be(
+                &Lottery::account_id(),
+                Lottery::gas_reserve(),
+            );
+            let deposit = Lottery::min_deposit();
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), deposit));
+            assert_eq!(Lottery::user_stats(&ALICE).total_deposited, deposit);
+            assert_eq!(Lottery::user_stats(&ALICE).drawings_participated, 0);
+            assert!(Lottery::drawing_history().is_empty());
+
+            roll_one_block();
+            assert_ok!(Balances::deposit_into_existing(
+                &Lottery::account_id(),
+                1_000
+            ));
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(
+                Lottery::drawing_history(),
+                vec![crate::DrawingRecord {
+                    drawn_at: System::block_number(),
+                    prize_pool: 1_000,
+                    winners: vec![(ALICE, 1_000)],
+                    participating_funds: deposit,
+                    participating_users: 1,
+                }]
+            );
+            assert_eq!(Lottery::user_stats(&ALICE).total_won, 1_000);
+            assert_eq!(Lottery::user_stats(&ALICE).drawings_participated, 1);
+
+            // drawings without a prize are recorded as well
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), deposit));
+            roll_one_block();
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            let history = Lottery::drawing_history();
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[1].prize_pool, 0);
+            assert!(history[1].winners.is_empty());
+            assert_eq!(history[1].participating_funds, 2 * deposit);
+            assert_eq!(Lottery::user_stats(&ALICE).drawings_participated, 2);
+            assert_eq!(Lottery::user_stats(&CHARLIE).drawings_participated, 1);
+
+            // ALICE stops participating once the active balance is withdrawn
+            assert_ok!(Lottery::request_withdraw(Origin::signed(ALICE), deposit));
+            roll_one_block();
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(Lottery::user_stats(&ALICE).drawings_participated, 2);
+            assert_eq!(Lottery::user_stats(&ALICE).total_deposited, deposit);
+            assert_eq!(Lottery::user_stats(&CHARLIE).drawings_participated, 2);
+            assert_eq!(Lottery::drawing_history().len(), 3);
+        });
+}
+
+#[test]
+fn drawing_history_keeps_only_the_latest_drawings() {
+    ExtBuilder::default().build().execute_with(|| {
+        <Test as pallet_parachain_staking::Config>::Currency::make_free_balance_be(
+            &Lottery::account_id(),
+            Lottery::gas_reserve(),
+        );
+        const DRAWINGS: u32 = crate::MAX_DRAWING_HISTORY + 5;
+        for _ in 0..DRAWINGS {
+            roll_one_block();
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+        }
+        let history = Lottery::drawing_history();
+        assert_eq!(history.len() as u32, crate::MAX_DRAWING_HISTORY);
+        assert_eq!(
+            history[0].drawn_at,
+            System::block_number() - crate::MAX_DRAWING_HISTORY + 1
+        );
+        assert_eq!(history.last().unwrap().drawn_at, System::block_number());
+        assert_eq!(
+            crate::DrawingHistory::<Test>::iter().count() as u32,
+            crate::MAX_DRAWING_HISTORY
+        );
+    });
+}
+
+#[test]
+fn setting_invalid_staking_strategies_should_not_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Lottery::set_staking_strategies(
+                Origin::signed(ALICE),
+                DepositStrategyKind::SplitToUnderallocated,
+                WithdrawStrategyKind::UnstakeLeastApy
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Lottery::set_staking_strategies(
+                RawOrigin::Root.into(),
+                DepositStrategyKind::EvenSpreadTopApy { collators: 0 },
+                WithdrawStrategyKind::UnstakeLeastApy
+            ),
+            Error::<Test>::InvalidStakingStrategy
+        );
+        assert_noop!(
+            Lottery::set_staking_strategies(
+                RawOrigin::Root.into(),
+                DepositStrategyKind::MaxDiversification {
+                    cap: Percent::from_percent(0)
+                },
+                WithdrawStrategyKind::UnstakeLeastApy
+            ),
+            Error::<Test>::InvalidStakingStrategy
+        );
+        assert_ok!(Lottery::set_staking_strategies(
+            RawOrigin::Root.into(),
+            DepositStrategyKind::MaxDiversification {
+                cap: Percent::from_percent(25)
+            },
+            WithdrawStrategyKind::UnstakeLargestDelegations
+        ));
+        assert_eq!(
+            Lottery::deposit_strategy(),
+            DepositStrategyKind::MaxDiversification {
+                cap: Percent::from_percent(25)
+            }
+        );
+        assert_eq!(
+            Lottery::withdraw_strategy(),
+            WithdrawStrategyKind::UnstakeLargestDelegations
+        );
+    });
+}
+
+#[test]
+fn even_spread_strategy_stakes_to_top_apy_collators() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+            (DAVE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![
+            (BOB, balance),
+            (CHARLIE, 2 * balance),
+            (DAVE, balance),
+        ])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::set_staking_strategies(
+                RawOrigin::Root.into(),
+                DepositStrategyKind::EvenSpreadTopApy { collators: 2 },
+                WithdrawStrategyKind::UnstakeLeastApy
+            ));
+            let deposit = 10 * Lottery::min_deposit() + 1;
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), deposit));
+            // CHARLIE has the most stake and therefore the lowest APY
+            assert_eq!(Lottery::staked_collators(CHARLIE), 0);
+            assert_eq!(
+                Lottery::staked_collators(BOB) + Lottery::staked_collators(DAVE),
+                deposit
+            );
+            assert!(Lottery::staked_collators(BOB).abs_diff(Lottery::staked_collators(DAVE)) <= 1);
+
+            // a deposit too small to be split is staked to a single collator
+            assert_ok!(Lottery::deposit(
+                Origin::signed(ALICE),
+                Lottery::min_deposit()
+            ));
+            assert_eq!(Lottery::staked_collators(CHARLIE), 0);
+            assert_eq!(Lottery::total_pot(), deposit + Lottery::min_deposit());
+        });
+}
+
+#[test]
+fn max_diversification_strategy_caps_stake_per_collator() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+            (DAVE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![
+            (BOB, 2 * balance),
+            (CHARLIE, balance),
+            (DAVE, balance),
+        ])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::set_staking_strategies(
+                RawOrigin::Root.into(),
+                DepositStrategyKind::MaxDiversification {
+                    cap: Percent::from_percent(50)
+                },
+                WithdrawStrategyKind::UnstakeLargestDelegations
+            ));
+            let deposit = 10 * Lottery::min_deposit();
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), deposit));
+            assert_eq!(crate::StakedCollators::<Test>::iter().count(), 2);
+            for (_, staked) in crate::StakedCollators::<Test>::iter() {
+                assert_eq!(staked, deposit / 2);
+            }
+            // the collator we did not stake to yet is filled up to the cap first
+            let (unstaked_collator, _) = [BOB, CHARLIE, DAVE]
+                .iter()
+                .map(|collator| (*collator, Lottery::staked_collators(collator)))
+                .find(|(_, staked)| *staked == 0)
+                .unwrap();
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), deposit));
+            assert_eq!(Lottery::staked_collators(unstaked_collator), deposit);
+
+            // withdrawals unstake our largest delegation first, not the collator with the lowest APY
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(ALICE),
+                Lottery::min_withdraw()
+            ));
+            let unstaking = crate::UnstakingCollators::<Test>::get();
+            assert_eq!(unstaking.len(), 1);
+            assert_eq!(unstaking[0].account, unstaked_collator);
+        });
+}
+
+#[test]
+fn instant_withdraw_without_liquidity_should_not_work() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Lottery::set_liquidity_buffer(
+                    Origin::signed(ALICE),
+                    balance,
+                    Percent::from_percent(1)
+                ),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            // The buffer is only filled once unstaked funds are rebalanced
+            assert_ok!(Lottery::set_liquidity_buffer(
+                RawOrigin::Root.into(),
+                balance,
+                Percent::from_percent(1)
+            ));
+            assert_eq!(0, Lottery::liquidity_buffer());
+            assert_noop!(
+                Lottery::instant_withdraw(Origin::signed(ALICE), balance),
+                Error::<Test>::InsufficientLiquidity
+            );
+            assert_noop!(
+                Lottery::instant_withdraw(Origin::signed(ALICE), 1),
+                Error::<Test>::WithdrawBelowMinAmount
+            );
+        });
+}
+
+#[test]
+fn instant_withdraw_pays_from_liquidity_buffer_and_adds_fee_to_prize_pool() {
+    let balance = 500_000_000 * UNIT;
+    let half_balance = 250_000_000 * UNIT;
+    let quarter_balance = 125_000_000 * UNIT;
+    let fee = 12_500_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::set_liquidity_buffer(
+                RawOrigin::Root.into(),
+                quarter_balance,
+                Percent::from_percent(10)
+            ));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(ALICE),
+                half_balance
+            ));
+
+            pallet_parachain_staking::AwardedPts::<Test>::insert(2, BOB, 20);
+            roll_to_round_begin(3);
+            // the unstaked half is paid out, the rest fills the buffer before being restaked
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(quarter_balance, Lottery::liquidity_buffer());
+            assert_eq!(quarter_balance, Lottery::staked_collators(BOB));
+            assert_eq!(0, Lottery::unlocked_unstaking_funds());
+            assert_eq!(0, Lottery::surplus_unstaking_balance());
+            assert_eq!(half_balance, Lottery::sum_of_deposits());
+
+            let alice_balance = Balances::free_balance(ALICE);
+            let prize_pool = Lottery::current_prize_pool();
+            assert_noop!(
+                Lottery::instant_withdraw(Origin::signed(ALICE), half_balance),
+                Error::<Test>::InsufficientLiquidity
+            );
+            assert_ok!(Lottery::instant_withdraw(
+                Origin::signed(ALICE),
+                quarter_balance
+            ));
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::InstantWithdrawn {
+                    account: ALICE,
+                    amount: quarter_balance - fee,
+                    fee,
+                }
+            ));
+            assert_eq!(
+                alice_balance + quarter_balance - fee,
+                Balances::free_balance(ALICE)
+            );
+            assert_eq!(prize_pool + fee, Lottery::current_prize_pool());
+            assert_eq!(0, Lottery::liquidity_buffer());
+            assert_eq!(quarter_balance, Lottery::active_balance_per_user(ALICE));
+            assert_eq!(quarter_balance, Lottery::total_pot());
+            assert_eq!(quarter_balance, Lottery::sum_of_deposits());
+        });
+}
+
+#[test]
+fn lowering_liquidity_buffer_target_restakes_the_excess() {
+    let balance = 500_000_000 * UNIT;
+    let half_balance = 250_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::set_liquidity_buffer(
+                RawOrigin::Root.into(),
+                half_balance,
+                Percent::from_percent(1)
+            ));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(ALICE),
+                half_balance
+            ));
+            pallet_parachain_staking::AwardedPts::<Test>::insert(2, BOB, 20);
+            roll_to_round_begin(3);
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(half_balance, Lottery::liquidity_buffer());
+            assert_eq!(0, Lottery::staked_collators(BOB));
+
+            assert_ok!(Lottery::set_liquidity_buffer(
+                RawOrigin::Root.into(),
+                0,
+                Percent::from_percent(1)
+            ));
+            assert_ok!(Lottery::process_matured_withdrawals(RawOrigin::Root.into()));
+            assert_eq!(0, Lottery::liquidity_buffer());
+            assert_eq!(half_balance, Lottery::staked_collators(BOB));
+            assert_eq!(0, Lottery::unlocked_unstaking_funds());
+            assert_eq!(0, Lottery::surplus_unstaking_balance());
+        });
+}
+
+#[test]
+fn liquidity_buffer_shortfall_unstakes_collators() {
+    let balance = 500_000_000 * UNIT;
+    let quarter_balance = 125_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::set_liquidity_buffer(
+                RawOrigin::Root.into(),
+                quarter_balance,
+                Percent::from_percent(1)
+            ));
+            // no unstaked funds are around to fill the buffer, so a collator is unstaked for it
+            assert_ok!(Lottery::process_matured_withdrawals(RawOrigin::Root.into()));
+            assert_eq!(0, Lottery::liquidity_buffer());
+            assert_eq!(balance, Lottery::surplus_unstaking_balance());
+            let unstaking = crate::UnstakingCollators::<Test>::get();
+            assert_eq!(unstaking.len(), 1);
+            assert_eq!(unstaking[0].account, BOB);
+
+            // funds already unstaking cover the shortfall, nothing else is unstaked
+            assert_ok!(Lottery::process_matured_withdrawals(RawOrigin::Root.into()));
+            assert_eq!(1, crate::UnstakingCollators::<Test>::get().len());
+
+            // once unlocked the buffer is filled and the rest restaked
+            roll_to_round_begin(3);
+            assert_ok!(Lottery::process_matured_withdrawals(RawOrigin::Root.into()));
+            assert_eq!(quarter_balance, Lottery::liquidity_buffer());
+            assert_eq!(balance - quarter_balance, Lottery::staked_collators(BOB));
+            assert_eq!(0, Lottery::surplus_unstaking_balance());
+            assert_eq!(0, Lottery::unlocked_unstaking_funds());
+        });
+}
+
+#[test]
+fn sponsors_are_staked_but_never_win() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Lottery::sponsor(Origin::signed(CHARLIE), 1),
+                Error::<Test>::DepositBelowMinAmount
+            );
+            assert_ok!(Lottery::sponsor(Origin::signed(CHARLIE), balance));
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::Sponsored {
+                    account: CHARLIE,
+                    amount: balance,
+                }
+            ));
+            assert_eq!(balance, Lottery::staked_collators(BOB));
+            assert_eq!(balance, Lottery::sponsor_balance(CHARLIE));
+            assert_eq!(balance, Lottery::total_sponsored());
+            assert_eq!(balance, Lottery::sum_of_deposits());
+            assert_eq!(0, Lottery::total_pot());
+            assert_eq!(0, Lottery::total_users());
+
+            // Nobody but the sponsor is playing, the prize rolls over
+            roll_one_block();
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(Lottery::unclaimed_winnings_by_account(CHARLIE), None);
+            assert_eq!(0, Lottery::total_unclaimed_winnings());
+
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_eq!(2 * balance, Lottery::staked_collators(BOB));
+            assert_eq!(balance, Lottery::total_pot());
+            assert_eq!(2 * balance, Lottery::sum_of_deposits());
+            roll_one_block();
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert!(Lottery::unclaimed_winnings_by_account(ALICE).is_some());
+            assert_eq!(Lottery::unclaimed_winnings_by_account(CHARLIE), None);
+        });
+}
+
+#[test]
+fn sponsors_withdraw_through_the_withdrawal_queue() {
+    let balance = 500_000_000 * UNIT;
+    let half_balance = 250_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(BOB, HIGH_BALANCE), (CHARLIE, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::sponsor(Origin::signed(CHARLIE), balance));
+            let charlie_post_sponsor_balance = Balances::free_balance(CHARLIE);
+            assert_noop!(
+                Lottery::request_sponsor_withdraw(Origin::signed(ALICE), half_balance),
+                Error::<Test>::NoDepositForAccount
+            );
+            assert_noop!(
+                Lottery::request_sponsor_withdraw(Origin::signed(CHARLIE), 2 * balance),
+                Error::<Test>::WithdrawAboveDeposit
+            );
+            // Sponsored funds are not part of the active funds
+            assert_noop!(
+                Lottery::request_withdraw(Origin::signed(CHARLIE), half_balance),
+                Error::<Test>::NoDepositForAccount
+            );
+            assert_ok!(Lottery::request_sponsor_withdraw(
+                Origin::signed(CHARLIE),
+                half_balance
+            ));
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::ScheduledSponsorWithdraw {
+                    account: CHARLIE,
+                    amount: half_balance,
+                }
+            ));
+            assert_eq!(half_balance, Lottery::sponsor_balance(CHARLIE));
+            assert_eq!(half_balance, Lottery::total_sponsored());
+            assert_eq!(half_balance, Lottery::surplus_unstaking_balance());
+            assert_eq!(1, Lottery::withdrawal_request_queue().len());
+
+            pallet_parachain_staking::AwardedPts::<Test>::insert(2, BOB, 20);
+            roll_to_round_begin(3);
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(
+                charlie_post_sponsor_balance + half_balance,
+                Balances::free_balance(CHARLIE)
+            );
+            assert!(Lottery::withdrawal_request_queue().is_empty());
+            assert_eq!(half_balance, Lottery::staked_collators(BOB));
+            assert_eq!(half_balance, Lottery::sum_of_deposits());
+        });
+}
+
+#[test]
+fn auto_redeposit_turns_winnings_into_deposit() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::set_auto_redeposit(Origin::signed(ALICE), true));
+            roll_one_block();
+            let prize = Lottery::current_prize_pool();
+            assert!(prize >= Lottery::min_deposit());
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::WinningsRedeposited {
+                    account: ALICE,
+                    amount: prize,
+                }
+            ));
+            assert_eq!(Lottery::unclaimed_winnings_by_account(ALICE), None);
+            assert_eq!(0, Lottery::total_unclaimed_winnings());
+            assert_eq!(balance + prize, Lottery::active_balance_per_user(ALICE));
+            assert_eq!(balance + prize, Lottery::total_pot());
+            assert_eq!(balance + prize, Lottery::sum_of_deposits());
+            assert_eq!(balance + prize, Lottery::staked_collators(BOB));
+
+            // Opting out keeps the next winnings claimable
+            assert_ok!(Lottery::set_auto_redeposit(Origin::signed(ALICE), false));
+            let _ = Balances::deposit_creating(&Lottery::account_id(), balance);
+            roll_one_block();
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert!(Lottery::unclaimed_winnings_by_account(ALICE).is_some());
+            assert_eq!(balance + prize, Lottery::active_balance_per_user(ALICE));
+        });
+}
+
+#[test]
+fn auto_redeposit_only_tracks_opted_in_winners() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            // only depositors or winners can opt in
+            assert_noop!(
+                Lottery::set_auto_redeposit(Origin::signed(ALICE), true),
+                Error::<Test>::NoDepositForAccount
+            );
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::set_auto_redeposit(Origin::signed(ALICE), true));
+            assert_eq!(0, crate::PendingRedeposits::<Test>::count());
+
+            // winnings are not redeposited while the lottery is in its freezeout
+            assert_ok!(Lottery::start_lottery(RawOrigin::Root.into()));
+            roll_to(
+                Lottery::next_drawing_at().unwrap() - <Test as Config>::DrawingFreezeout::get(),
+            );
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert!(crate::PendingRedeposits::<Test>::contains_key(ALICE));
+
+            // claiming leaves nothing to redeposit
+            assert_ok!(Lottery::claim_my_winnings(Origin::signed(ALICE)));
+            assert_eq!(0, crate::PendingRedeposits::<Test>::count());
+        });
+}
+
+#[test]
+fn auto_redeposit_keeps_winnings_below_min_deposit() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        // The prize pool is what's left above the gas reserve of 10_000 UNIT
+        .with_funded_lottery_account(12_000 * UNIT)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::set_auto_redeposit(Origin::signed(ALICE), true));
+            roll_one_block();
+            let prize = Lottery::current_prize_pool();
+            assert!(prize > 0 && prize < Lottery::min_deposit());
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(Lottery::unclaimed_winnings_by_account(ALICE), Some(prize));
+            assert_eq!(balance, Lottery::active_balance_per_user(ALICE));
+            assert_eq!(balance, Lottery::sum_of_deposits());
+        });
+}
+
+#[test]
+fn auto_redeposit_is_skipped_within_drawing_freezeout() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::set_auto_redeposit(Origin::signed(ALICE), true));
+            assert_ok!(Lottery::start_lottery(RawOrigin::Root.into()));
+            roll_to(
+                Lottery::next_drawing_at().unwrap() - <Test as Config>::DrawingFreezeout::get(),
+            );
+            assert!(!Lottery::not_in_drawing_freezeout());
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert!(Lottery::unclaimed_winnings_by_account(ALICE).is_some());
+            assert_eq!(balance, Lottery::active_balance_per_user(ALICE));
+        });
+}
+
+#[test]
+fn lottery_instances_keep_separate_pots_and_schedules() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ne!(Lottery::account_id(), DailyLottery::account_id());
+            assert_eq!(10_000 * UNIT, Lottery::gas_reserve());
+            assert_eq!(1_000 * UNIT, DailyLottery::gas_reserve());
+            assert_noop!(
+                DailyLottery::start_lottery(RawOrigin::Root.into()),
+                Error::<Test, crate::Instance1>::PotBalanceBelowGasReserve
+            );
+            let _ = Balances::deposit_creating(&DailyLottery::account_id(), HIGH_BALANCE);
+
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(DailyLottery::deposit(Origin::signed(CHARLIE), balance));
+            assert_eq!(balance, Lottery::total_pot());
+            assert_eq!(balance, DailyLottery::total_pot());
+            assert_eq!(0, Lottery::active_balance_per_user(CHARLIE));
+            assert_eq!(0, DailyLottery::active_balance_per_user(ALICE));
+            assert_eq!(balance, Lottery::staked_collators(BOB));
+            assert_eq!(balance, DailyLottery::staked_collators(BOB));
+
+            assert_ok!(Lottery::start_lottery(RawOrigin::Root.into()));
+            assert_ok!(DailyLottery::start_lottery(RawOrigin::Root.into()));
+            // Both drawings are scheduled from the same block, each with its own interval
+            assert_eq!(
+                Lottery::next_drawing_at().unwrap() - DailyLottery::next_drawing_at().unwrap(),
+                <Test as Config>::DrawingInterval::get()
+                    - <Test as Config<crate::Instance1>>::DrawingInterval::get()
+            );
+            assert_ok!(DailyLottery::stop_lottery(RawOrigin::Root.into()));
+            assert!(DailyLottery::next_drawing_at().is_none());
+            assert!(Lottery::next_drawing_at().is_some());
+
+            roll_one_block();
+            assert_ok!(DailyLottery::draw_lottery(RawOrigin::Root.into()));
+            assert!(DailyLottery::unclaimed_winnings_by_account(CHARLIE).is_some());
+            assert_eq!(Lottery::unclaimed_winnings_by_account(CHARLIE), None);
+            assert_eq!(0, Lottery::total_unclaimed_winnings());
+        });
+}
@@ -0,0 +1,314 @@
+// Copyright 2020-2023 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Time-weighted lottery tickets
+//!
+//! A depositor's chance to win is their active balance integrated over the blocks of the current drawing period.
+//! With `L` blocks elapsed since the period started, that integral is `balance * L - penalty + credit` where
+//! * `penalty` adds up `amount * blocks_into_period` of every deposit made during the period
+//! * `credit` adds up `amount * blocks_into_period` of every withdrawal made during the period
+//!
+//! Every depositor is assigned a fixed position on their first deposit and the three terms are kept in Fenwick trees
+//! over these positions ([`TicketTree`], [`TicketPenalty`], [`TicketCredit`]). Node `i` of a tree holds the sum of
+//! positions `(i - lowbit(i), i]`, so updating a depositor and locating the holder of a winning ticket both take
+//! O(log n) storage reads. Penalty and credit trees are keyed by [`DrawingPeriod`] and pruned in `on_idle` after it ends.
+
+use super::*;
+use frame_support::{traits::Get, weights::Weight};
+use pallet_parachain_staking::BalanceOf;
+use sp_runtime::{
+    traits::{SaturatedConversion, Saturating, Zero},
+    Perquintill,
+};
+
+/// Lowest set bit of `i`, i.e. the number of positions covered by node `i`
+fn lowbit(i: u32) -> u32 {
+    i & i.wrapping_neg()
+}
+
+/// The Fenwick trees making up the ticket weight of all depositors
+#[derive(Clone, Copy)]
+enum Tree {
+    Balance,
+    Penalty(u32),
+    Credit(u32),
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+    fn tree_node(tree: Tree, index: u32) -> BalanceOf<T> {
+        match tree {
+            Tree::Balance => TicketTree::<T, I>::get(index),
+            Tree::Penalty(period) => TicketPenalty::<T, I>::get(period, index),
+            Tree::Credit(period) => TicketCredit::<T, I>::get(period, index),
+        }
+    }
+
+    fn set_tree_node(tree: Tree, index: u32, value: BalanceOf<T>) {
+        match tree {
+            Tree::Balance => TicketTree::<T, I>::insert(index, value),
+            Tree::Penalty(period) => TicketPenalty::<T, I>::insert(period, index, value),
+            Tree::Credit(period) => TicketCredit::<T, I>::insert(period, index, value),
+        }
+    }
+
+    /// Sum of the positions `[1, index]` of `tree`
+    fn tree_prefix_sum(tree: Tree, mut index: u32) -> BalanceOf<T> {
+        let mut sum = BalanceOf::<T>::zero();
+        while index > 0 {
+            sum = sum.saturating_add(Self::tree_node(tree, index));
+            index -= lowbit(index);
+        }
+        sum
+    }
+
+    /// Applies `update` to every node of `tree` covering position `index`
+    fn update_tree(tree: Tree, mut index: u32, update: impl Fn(BalanceOf<T>) -> BalanceOf<T>) {
+        let count = TicketCount::<T, I>::get();
+        while index <= count {
+            Self::set_tree_node(tree, index, update(Self::tree_node(tree, index)));
+            index = match index.checked_add(lowbit(index)) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+    }
+
+    /// Returns the tree position of `account`, allocating a new one at the end of the trees if needed
+    fn ticket_index(account: &T::AccountId) -> u32 {
+        if let Some(index) = TicketIndexOf::<T, I>::get(account) {
+            return index;
+        }
+        let index = TicketCount::<T, I>::get().saturating_add(1);
+        let period = DrawingPeriod::<T, I>::get();
+        // The new node covers `(index - lowbit(index), index]`, all positions but its own already exist
+        for tree in [Tree::Balance, Tree::Penalty(period), Tree::Credit(period)] {
+            let covered = Self::tree_prefix_sum(tree, index - 1)
+                .saturating_sub(Self::tree_prefix_sum(tree, index - lowbit(index)));
+            if !covered.is_zero() {
+                Self::set_tree_node(tree, index, covered);
+            }
+        }
+        TicketCount::<T, I>::put(index);
+        TicketIndexOf::<T, I>::insert(account, index);
+        TicketHolder::<T, I>::insert(index, account);
+        index
+    }
+
+    /// Number of tree nodes on the path of a position, counting the position the next depositor would be given
+    pub(crate) fn ticket_tree_depth() -> u32 {
+        u32::BITS - TicketCount::<T, I>::get().saturating_add(1).leading_zeros()
+    }
+
+    /// Blocks elapsed since the current drawing period started, as a balance multiplier
+    fn blocks_into_period() -> BalanceOf<T> {
+        let now = <frame_system::Pallet<T>>::block_number();
+        now.saturating_sub(Self::drawing_period_start())
+            .saturated_into::<u32>()
+            .into()
+    }
+
+    /// Sum of the active balances at positions `[1, index]`
+    pub(crate) fn ticket_prefix_sum(index: u32) -> BalanceOf<T> {
+        Self::tree_prefix_sum(Tree::Balance, index)
+    }
+
+    /// Adds `amount` to the active balance of `account` in the ticket tree, counting it as held since the period started
+    pub(crate) fn add_ticket_balance(account: &T::AccountId, amount: BalanceOf<T>) {
+        let index = Self::ticket_index(account);
+        Self::update_tree(Tree::Balance, index, |node| node.saturating_add(amount));
+    }
+
+    /// Records a deposit of `amount` by `account`
+    /// Must be called before the deposit is added to [`ActiveBalancePerUser`]
+    pub(crate) fn add_tickets(account: &T::AccountId, amount: BalanceOf<T>) {
+        Self::checkpoint_tickets(account);
+        let index = Self::ticket_index(account);
+        let penalty = amount.saturating_mul(Self::blocks_into_period());
+        Self::update_tree(Tree::Balance, index, |node| node.saturating_add(amount));
+        if !penalty.is_zero() {
+            let period = DrawingPeriod::<T, I>::get();
+            Self::update_tree(Tree::Penalty(period), index, |node| {
+                node.saturating_add(penalty)
+            });
+        }
+    }
+
+    /// Records a withdrawal of `amount` by `account`
+    /// Must be called before the withdrawal is removed from [`ActiveBalancePerUser`]
+    pub(crate) fn remove_tickets(account: &T::AccountId, amount: BalanceOf<T>) {
+        let index = match TicketIndexOf::<T, I>::get(account) {
+            Some(index) => index,
+            None => {
+                log::error!("FATAL: Removing tickets of {:?} who holds none", account);
+                return;
+            }
+        };
+        Self::checkpoint_tickets(account);
+        let credit = amount.saturating_mul(Self::blocks_into_period());
+        Self::update_tree(Tree::Balance, index, |node| node.saturating_sub(amount));
+        if !credit.is_zero() {
+            let period = DrawingPeriod::<T, I>::get();
+            Self::update_tree(Tree::Credit(period), index, |node| {
+                node.saturating_add(credit)
+            });
+        }
+    }
+
+    /// Stores the ticket weight `account` accrued so far, before its active balance changes
+    fn checkpoint_tickets(account: &T::AccountId) {
+        let checkpoint = TicketCheckpoint {
+            period: DrawingPeriod::<T, I>::get(),
+            last_update: <frame_system::Pallet<T>>::block_number(),
+            accrued: Self::ticket_weight(account),
+        };
+        TicketCheckpoints::<T, I>::insert(account, checkpoint);
+    }
+
+    /// Ends the current drawing period, its penalties and credits are left to [`Self::prune_stale_tickets`]
+    pub(crate) fn start_new_drawing_period() {
+        let period = DrawingPeriod::<T, I>::get();
+        DrawingPeriod::<T, I>::put(period.wrapping_add(1));
+        DrawingPeriodStart::<T, I>::put(<frame_system::Pallet<T>>::block_number());
+    }
+
+    /// Removes penalties and credits of ended drawing periods within `remaining_weight`, returning the weight used
+    pub(crate) fn prune_stale_tickets(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        // Every removed node is read and deleted
+        let remove = db_weight.reads_writes(1, 1);
+        let mut used = db_weight.reads_writes(2, 1);
+        if remaining_weight.any_lt(used.saturating_add(remove)) {
+            return Weight::zero();
+        }
+        let current = DrawingPeriod::<T, I>::get();
+        let mut period = StaleTicketPeriod::<T, I>::get();
+        if period == current {
+            return db_weight.reads(2);
+        }
+        // Each prefix is cleared at most once per block, so no cursor has to be carried over
+        let removal_limit = |used: Weight| -> u32 {
+            remaining_weight
+                .saturating_sub(used)
+                .ref_time()
+                .checked_div(remove.ref_time())
+                .unwrap_or(u64::MAX)
+                .saturated_into()
+        };
+        while period != current {
+            let limit = removal_limit(used);
+            if limit.is_zero() {
+                break;
+            }
+            let penalties = TicketPenalty::<T, I>::clear_prefix(period, limit, None);
+            used = used.saturating_add(
+                db_weight.reads_writes(penalties.loops.into(), penalties.unique.into()),
+            );
+            if penalties.maybe_cursor.is_some() {
+                break;
+            }
+            let limit = removal_limit(used);
+            if limit.is_zero() {
+                break;
+            }
+            let credits = TicketCredit::<T, I>::clear_prefix(period, limit, None);
+            used = used.saturating_add(
+                db_weight.reads_writes(credits.loops.into(), credits.unique.into()),
+            );
+            if credits.maybe_cursor.is_some() {
+                break;
+            }
+            period = period.wrapping_add(1);
+        }
+        StaleTicketPeriod::<T, I>::put(period);
+        used
+    }
+
+    /// Ticket weight of all positions `[1, index]` at `blocks_into_period`
+    fn weight_prefix_sum(index: u32, blocks_into_period: BalanceOf<T>) -> BalanceOf<T> {
+        let period = DrawingPeriod::<T, I>::get();
+        Self::tree_prefix_sum(Tree::Balance, index)
+            .saturating_mul(blocks_into_period)
+            .saturating_add(Self::tree_prefix_sum(Tree::Credit(period), index))
+            .saturating_sub(Self::tree_prefix_sum(Tree::Penalty(period), index))
+    }
+
+    /// Sum of the ticket weight of all depositors in the current drawing period
+    pub(crate) fn total_ticket_weight() -> BalanceOf<T> {
+        Self::weight_prefix_sum(TicketCount::<T, I>::get(), Self::blocks_into_period())
+    }
+
+    /// Time-weighted active balance of `account` in the current drawing period
+    pub(crate) fn ticket_weight(account: &T::AccountId) -> BalanceOf<T> {
+        let balance = Self::active_balance_per_user(account);
+        match TicketCheckpoints::<T, I>::get(account) {
+            Some(checkpoint) if checkpoint.period == DrawingPeriod::<T, I>::get() => {
+                let now = <frame_system::Pallet<T>>::block_number();
+                let blocks_since_checkpoint: BalanceOf<T> = now
+                    .saturating_sub(checkpoint.last_update)
+                    .saturated_into::<u32>()
+                    .into();
+                checkpoint
+                    .accrued
+                    .saturating_add(balance.saturating_mul(blocks_since_checkpoint))
+            }
+            _ => balance.saturating_mul(Self::blocks_into_period()),
+        }
+    }
+
+    /// Returns the account holding the ticket at `winning_weight`, i.e. the first position whose prefix weight exceeds it
+    pub(crate) fn find_ticket_holder(winning_weight: BalanceOf<T>) -> Option<T::AccountId> {
+        let count = TicketCount::<T, I>::get();
+        if count.is_zero() {
+            return None;
+        }
+        let period = DrawingPeriod::<T, I>::get();
+        let blocks_into_period = Self::blocks_into_period();
+        let mut position = 0u32;
+        let mut remaining = winning_weight;
+        let mut step = 1u32 << (31 - count.leading_zeros());
+        while !step.is_zero() {
+            let next = position.saturating_add(step);
+            if next <= count {
+                let node = Self::tree_node(Tree::Balance, next)
+                    .saturating_mul(blocks_into_period)
+                    .saturating_add(Self::tree_node(Tree::Credit(period), next))
+                    .saturating_sub(Self::tree_node(Tree::Penalty(period), next));
+                if node <= remaining {
+                    position = next;
+                    remaining -= node;
+                }
+            }
+            step >>= 1;
+        }
+        if position >= count {
+            // `winning_weight` is not below the weight of all tickets
+            return None;
+        }
+        TicketHolder::<T, I>::get(position + 1)
+    }
+
+    /// Chance of `account` to win a single payout if the drawing happened now
+    pub fn effective_odds(account: &T::AccountId) -> Perquintill {
+        let total_weight = Self::total_ticket_weight();
+        if total_weight.is_zero() {
+            return Perquintill::zero();
+        }
+        Perquintill::from_rational(
+            Self::ticket_weight(account).saturated_into::<u128>(),
+            total_weight.saturated_into::<u128>(),
+        )
+    }
+}
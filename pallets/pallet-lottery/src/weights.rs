@@ -45,16 +45,22 @@ use core::marker::PhantomData;
 
 /// Weight functions needed for pallet_lottery.
 pub trait WeightInfo {
-	fn deposit(x: u32, y: u32, ) -> Weight;
-	fn request_withdraw(x: u32, y: u32, ) -> Weight;
+	fn deposit(d: u32, y: u32, ) -> Weight;
+	fn request_withdraw(d: u32, y: u32, ) -> Weight;
+	fn sponsor(y: u32, ) -> Weight;
+	fn request_sponsor_withdraw(y: u32, ) -> Weight;
 	fn claim_my_winnings(y: u32, ) -> Weight;
 	fn start_lottery() -> Weight;
 	fn stop_lottery() -> Weight;
-	fn draw_lottery(x: u32, y: u32, ) -> Weight;
+	fn draw_lottery(d: u32, y: u32, w: u32, r: u32, ) -> Weight;
 	fn process_matured_withdrawals() -> Weight;
 	fn set_min_deposit() -> Weight;
 	fn set_min_withdraw() -> Weight;
 	fn set_gas_reserve() -> Weight;
+	fn set_prize_tiers(t: u32, ) -> Weight;
+	fn set_staking_strategies() -> Weight;
+	fn set_liquidity_buffer() -> Weight;
+	fn instant_withdraw(d: u32, ) -> Weight;
 }
 
 /// Weights for pallet_lottery using the Substrate node and recommended hardware.
@@ -98,27 +104,45 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: ParachainStaking Total (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
 	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketPenalty (r:20 w:20)
+	/// Proof Skipped: Lottery TicketPenalty (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Lottery TotalPot (r:1 w:1)
 	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery TotalUsers (r:1 w:1)
 	/// Proof Skipped: Lottery TotalUsers (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery SumOfDeposits (r:1 w:1)
 	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn deposit(x: u32, y: u32, ) -> Weight {
+	fn deposit(d: u32, y: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `3459 + x * (1 ±0) + y * (72 ±0)`
-		//  Estimated: `8716 + x * (2 ±0) + y * (79 ±0)`
+		//  Measured:  `3459 + d * (32 ±0) + y * (72 ±0)`
+		//  Estimated: `8716 + d * (5046 ±0) + y * (79 ±0)`
 		// Minimum execution time: 109_295_000 picoseconds.
 		Weight::from_parts(129_547_079, 8716)
 			// Standard Error: 583
-			.saturating_add(Weight::from_parts(58_735, 0).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(822_290, 0).saturating_mul(d.into()))
 			// Standard Error: 9_193
 			.saturating_add(Weight::from_parts(96_358, 0).saturating_mul(y.into()))
-			.saturating_add(T::DbWeight::get().reads(24_u64))
-			.saturating_add(T::DbWeight::get().writes(13_u64))
-			.saturating_add(Weight::from_parts(0, 2).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(30_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
 			.saturating_add(Weight::from_parts(0, 79).saturating_mul(y.into()))
 	}
 	/// Storage: Lottery MinWithdraw (r:1 w:0)
@@ -129,6 +153,22 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
 	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:20)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:1)
 	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery TotalUsers (r:1 w:1)
@@ -155,21 +195,123 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:1)
 	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn request_withdraw(x: u32, y: u32, ) -> Weight {
+	fn request_withdraw(d: u32, y: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `2216 + x * (1 ±0)`
-		//  Estimated: `7719 + x * (1 ±0) + y * (5 ±0)`
+		//  Measured:  `2216 + d * (32 ±0)`
+		//  Estimated: `7719 + d * (5046 ±0) + y * (5 ±0)`
 		// Minimum execution time: 50_976_000 picoseconds.
 		Weight::from_parts(56_643_077, 7719)
 			// Standard Error: 244
-			.saturating_add(Weight::from_parts(32_928, 0).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(460_992, 0).saturating_mul(d.into()))
+			// Standard Error: 3_856
+			.saturating_add(Weight::from_parts(4_322, 0).saturating_mul(y.into()))
+			.saturating_add(T::DbWeight::get().reads(24_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
+	}
+	/// Storage: Lottery MinDeposit (r:1 w:0)
+	/// Proof Skipped: Lottery MinDeposit (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking SelectedCandidates (r:1 w:0)
+	/// Proof Skipped: ParachainStaking SelectedCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnstakingCollators (r:1 w:0)
+	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery StakedCollators (r:2 w:1)
+	/// Proof Skipped: Lottery StakedCollators (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking CandidateInfo (r:1 w:1)
+	/// Proof Skipped: ParachainStaking CandidateInfo (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking DelegatorState (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegatorState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnlockedUnstakingFunds (r:1 w:0)
+	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: TransactionPayment NextFeeMultiplier (r:1 w:0)
+	/// Proof: TransactionPayment NextFeeMultiplier (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:0)
+	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: Balances Freezes (r:1 w:0)
+	/// Proof: Balances Freezes (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking TopDelegations (r:1 w:1)
+	/// Proof Skipped: ParachainStaking TopDelegations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking CandidatePool (r:1 w:1)
+	/// Proof Skipped: ParachainStaking CandidatePool (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking Total (r:1 w:1)
+	/// Proof Skipped: ParachainStaking Total (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SponsorBalancePerAccount (r:1 w:1)
+	/// Proof Skipped: Lottery SponsorBalancePerAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalSponsored (r:1 w:1)
+	/// Proof Skipped: Lottery TotalSponsored (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SumOfDeposits (r:1 w:1)
+	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `y` is `[0, 63]`.
+	fn sponsor(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3459 + y * (72 ±0)`
+		//  Estimated: `8716 + y * (79 ±0)`
+		// Minimum execution time: 92_900_000 picoseconds.
+		Weight::from_parts(110_115_017, 8716)
+			// Standard Error: 9_193
+			.saturating_add(Weight::from_parts(96_358, 0).saturating_mul(y.into()))
+			.saturating_add(T::DbWeight::get().reads(23_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+			.saturating_add(Weight::from_parts(0, 79).saturating_mul(y.into()))
+	}
+	/// Storage: Lottery MinWithdraw (r:1 w:0)
+	/// Proof Skipped: Lottery MinWithdraw (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SponsorBalancePerAccount (r:1 w:1)
+	/// Proof Skipped: Lottery SponsorBalancePerAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalSponsored (r:1 w:1)
+	/// Proof Skipped: Lottery TotalSponsored (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:1)
+	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SurplusUnstakingBalance (r:1 w:1)
+	/// Proof Skipped: Lottery SurplusUnstakingBalance (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery StakedCollators (r:2 w:0)
+	/// Proof Skipped: Lottery StakedCollators (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UnstakingCollators (r:1 w:1)
+	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking SelectedCandidates (r:1 w:0)
+	/// Proof Skipped: ParachainStaking SelectedCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: TransactionPayment NextFeeMultiplier (r:1 w:0)
+	/// Proof: TransactionPayment NextFeeMultiplier (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DelegatorState (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegatorState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnlockedUnstakingFunds (r:1 w:0)
+	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `y` is `[0, 63]`.
+	fn request_sponsor_withdraw(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2216`
+		//  Estimated: `7719 + y * (5 ±0)`
+		// Minimum execution time: 43_329_000 picoseconds.
+		Weight::from_parts(48_146_615, 7719)
 			// Standard Error: 3_856
 			.saturating_add(Weight::from_parts(4_322, 0).saturating_mul(y.into()))
-			.saturating_add(T::DbWeight::get().reads(18_u64))
-			.saturating_add(T::DbWeight::get().writes(8_u64))
-			.saturating_add(Weight::from_parts(0, 1).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
 	}
 	/// Storage: Lottery UnclaimedWinningsByAccount (r:1 w:1)
@@ -243,29 +385,66 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery SumOfDeposits (r:1 w:0)
 	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
-	/// Storage: Lottery ActiveBalancePerUser (r:516 w:0)
-	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Lottery UnclaimedWinningsByAccount (r:1 w:1)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:1)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:1)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:0)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:0)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketPenalty (r:20 w:0)
+	/// Proof Skipped: Lottery TicketPenalty (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketHolder (r:1 w:0)
+	/// Proof Skipped: Lottery TicketHolder (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery PrizeTiers (r:1 w:0)
+	/// Proof Skipped: Lottery PrizeTiers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DuplicateWinners (r:1 w:0)
+	/// Proof Skipped: Lottery DuplicateWinners (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:100 w:100)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UnclaimedWinningsByAccount (r:100 w:100)
 	/// Proof Skipped: Lottery UnclaimedWinningsByAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingHistory (r:0 w:2)
+	/// Proof Skipped: Lottery DrawingHistory (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery PendingRedeposits (r:1001 w:1000)
+	/// Proof Skipped: Lottery PendingRedeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery CounterForPendingRedeposits (r:1 w:1)
+	/// Proof Skipped: Lottery CounterForPendingRedeposits (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery UnstakingCollators (r:1 w:0)
 	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:0)
 	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery MinDeposit (r:1 w:0)
 	/// Proof Skipped: Lottery MinDeposit (max_values: Some(1), max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn draw_lottery(x: u32, _y: u32, ) -> Weight {
+	/// The range of component `w` is `[1, 100]`.
+	/// The range of component `r` is `[0, 1000]`.
+	fn draw_lottery(d: u32, _y: u32, w: u32, r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `1665 + x * (30 ±0)`
-		//  Estimated: `259887 + x * (1112 ±0)`
+		//  Measured:  `1665 + d * (30 ±0) + w * (64 ±0) + r * (48 ±0)`
+		//  Estimated: `259887 + d * (7569 ±0) + w * (2603 ±0) + r * (2624 ±0)`
 		// Minimum execution time: 35_998_000 picoseconds.
 		Weight::from_parts(334_839_510, 259887)
 			// Standard Error: 27_525
-			.saturating_add(Weight::from_parts(1_187_025, 0).saturating_mul(x.into()))
-			.saturating_add(T::DbWeight::get().reads(112_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
-			.saturating_add(Weight::from_parts(0, 1112).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(16_618_350, 0).saturating_mul(d.into()))
+			// Standard Error: 61_233
+			.saturating_add(Weight::from_parts(4_918_412, 0).saturating_mul(w.into()))
+			// Standard Error: 54_107
+			.saturating_add(Weight::from_parts(9_846_231, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(116_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(w.into())))
+			.saturating_add(T::DbWeight::get().reads((9_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(w.into())))
+			.saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 7569).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(w.into()))
+			.saturating_add(Weight::from_parts(0, 2624).saturating_mul(r.into()))
 	}
 	/// Storage: Lottery UnstakingCollators (r:1 w:0)
 	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
@@ -316,6 +495,98 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(1_233_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: Lottery PrizeTiers (r:0 w:1)
+	/// Proof Skipped: Lottery PrizeTiers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DuplicateWinners (r:0 w:1)
+	/// Proof Skipped: Lottery DuplicateWinners (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `t` is `[0, 100]`.
+	fn set_prize_tiers(t: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_873_000 picoseconds.
+		Weight::from_parts(2_114_506, 0)
+			// Standard Error: 212
+			.saturating_add(Weight::from_parts(9_347, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery SelectedDepositStrategy (r:0 w:1)
+	/// Proof Skipped: Lottery SelectedDepositStrategy (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SelectedWithdrawStrategy (r:0 w:1)
+	/// Proof Skipped: Lottery SelectedWithdrawStrategy (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_staking_strategies() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_984_000 picoseconds.
+		Weight::from_parts(2_115_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery LiquidityBufferTarget (r:0 w:1)
+	/// Proof Skipped: Lottery LiquidityBufferTarget (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery InstantWithdrawFee (r:0 w:1)
+	/// Proof Skipped: Lottery InstantWithdrawFee (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_liquidity_buffer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_962_000 picoseconds.
+		Weight::from_parts(2_103_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery MinWithdraw (r:1 w:0)
+	/// Proof Skipped: Lottery MinWithdraw (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery LiquidityBuffer (r:1 w:1)
+	/// Proof Skipped: Lottery LiquidityBuffer (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
+	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:20)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalPot (r:1 w:1)
+	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUsers (r:1 w:1)
+	/// Proof Skipped: Lottery TotalUsers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SumOfDeposits (r:1 w:1)
+	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery InstantWithdrawFee (r:1 w:0)
+	/// Proof Skipped: Lottery InstantWithdrawFee (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `d` is `[1, 20]`.
+	fn instant_withdraw(d: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1874 + d * (32 ±0)`
+		//  Estimated: `7146 + d * (5046 ±0)`
+		// Minimum execution time: 61_842_000 picoseconds.
+		Weight::from_parts(66_310_574, 7146)
+			// Standard Error: 412
+			.saturating_add(Weight::from_parts(818_576, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(19_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -358,27 +629,45 @@ impl WeightInfo for () {
 	/// Proof Skipped: ParachainStaking Total (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
 	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketPenalty (r:20 w:20)
+	/// Proof Skipped: Lottery TicketPenalty (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Lottery TotalPot (r:1 w:1)
 	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery TotalUsers (r:1 w:1)
 	/// Proof Skipped: Lottery TotalUsers (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery SumOfDeposits (r:1 w:1)
 	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn deposit(x: u32, y: u32, ) -> Weight {
+	fn deposit(d: u32, y: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `3459 + x * (1 ±0) + y * (72 ±0)`
-		//  Estimated: `8716 + x * (2 ±0) + y * (79 ±0)`
+		//  Measured:  `3459 + d * (32 ±0) + y * (72 ±0)`
+		//  Estimated: `8716 + d * (5046 ±0) + y * (79 ±0)`
 		// Minimum execution time: 109_295_000 picoseconds.
 		Weight::from_parts(129_547_079, 8716)
 			// Standard Error: 583
-			.saturating_add(Weight::from_parts(58_735, 0).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(822_290, 0).saturating_mul(d.into()))
 			// Standard Error: 9_193
 			.saturating_add(Weight::from_parts(96_358, 0).saturating_mul(y.into()))
-			.saturating_add(RocksDbWeight::get().reads(24_u64))
-			.saturating_add(RocksDbWeight::get().writes(13_u64))
-			.saturating_add(Weight::from_parts(0, 2).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(30_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
 			.saturating_add(Weight::from_parts(0, 79).saturating_mul(y.into()))
 	}
 	/// Storage: Lottery MinWithdraw (r:1 w:0)
@@ -389,6 +678,22 @@ impl WeightInfo for () {
 	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
 	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:20)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:1)
 	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery TotalUsers (r:1 w:1)
@@ -415,21 +720,123 @@ impl WeightInfo for () {
 	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:1)
 	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn request_withdraw(x: u32, y: u32, ) -> Weight {
+	fn request_withdraw(d: u32, y: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `2216 + x * (1 ±0)`
-		//  Estimated: `7719 + x * (1 ±0) + y * (5 ±0)`
+		//  Measured:  `2216 + d * (32 ±0)`
+		//  Estimated: `7719 + d * (5046 ±0) + y * (5 ±0)`
 		// Minimum execution time: 50_976_000 picoseconds.
 		Weight::from_parts(56_643_077, 7719)
 			// Standard Error: 244
-			.saturating_add(Weight::from_parts(32_928, 0).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(460_992, 0).saturating_mul(d.into()))
+			// Standard Error: 3_856
+			.saturating_add(Weight::from_parts(4_322, 0).saturating_mul(y.into()))
+			.saturating_add(RocksDbWeight::get().reads(24_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
+	}
+	/// Storage: Lottery MinDeposit (r:1 w:0)
+	/// Proof Skipped: Lottery MinDeposit (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking SelectedCandidates (r:1 w:0)
+	/// Proof Skipped: ParachainStaking SelectedCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnstakingCollators (r:1 w:0)
+	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery StakedCollators (r:2 w:1)
+	/// Proof Skipped: Lottery StakedCollators (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking CandidateInfo (r:1 w:1)
+	/// Proof Skipped: ParachainStaking CandidateInfo (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking DelegatorState (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegatorState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnlockedUnstakingFunds (r:1 w:0)
+	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: TransactionPayment NextFeeMultiplier (r:1 w:0)
+	/// Proof: TransactionPayment NextFeeMultiplier (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:0)
+	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: Balances Freezes (r:1 w:0)
+	/// Proof: Balances Freezes (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking TopDelegations (r:1 w:1)
+	/// Proof Skipped: ParachainStaking TopDelegations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking CandidatePool (r:1 w:1)
+	/// Proof Skipped: ParachainStaking CandidatePool (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking Total (r:1 w:1)
+	/// Proof Skipped: ParachainStaking Total (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SponsorBalancePerAccount (r:1 w:1)
+	/// Proof Skipped: Lottery SponsorBalancePerAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalSponsored (r:1 w:1)
+	/// Proof Skipped: Lottery TotalSponsored (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SumOfDeposits (r:1 w:1)
+	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `y` is `[0, 63]`.
+	fn sponsor(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3459 + y * (72 ±0)`
+		//  Estimated: `8716 + y * (79 ±0)`
+		// Minimum execution time: 92_900_000 picoseconds.
+		Weight::from_parts(110_115_017, 8716)
+			// Standard Error: 9_193
+			.saturating_add(Weight::from_parts(96_358, 0).saturating_mul(y.into()))
+			.saturating_add(RocksDbWeight::get().reads(23_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+			.saturating_add(Weight::from_parts(0, 79).saturating_mul(y.into()))
+	}
+	/// Storage: Lottery MinWithdraw (r:1 w:0)
+	/// Proof Skipped: Lottery MinWithdraw (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SponsorBalancePerAccount (r:1 w:1)
+	/// Proof Skipped: Lottery SponsorBalancePerAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalSponsored (r:1 w:1)
+	/// Proof Skipped: Lottery TotalSponsored (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:1)
+	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SurplusUnstakingBalance (r:1 w:1)
+	/// Proof Skipped: Lottery SurplusUnstakingBalance (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery StakedCollators (r:2 w:0)
+	/// Proof Skipped: Lottery StakedCollators (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UnstakingCollators (r:1 w:1)
+	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking SelectedCandidates (r:1 w:0)
+	/// Proof Skipped: ParachainStaking SelectedCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: TransactionPayment NextFeeMultiplier (r:1 w:0)
+	/// Proof: TransactionPayment NextFeeMultiplier (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DelegatorState (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegatorState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnlockedUnstakingFunds (r:1 w:0)
+	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `y` is `[0, 63]`.
+	fn request_sponsor_withdraw(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2216`
+		//  Estimated: `7719 + y * (5 ±0)`
+		// Minimum execution time: 43_329_000 picoseconds.
+		Weight::from_parts(48_146_615, 7719)
 			// Standard Error: 3_856
 			.saturating_add(Weight::from_parts(4_322, 0).saturating_mul(y.into()))
-			.saturating_add(RocksDbWeight::get().reads(18_u64))
-			.saturating_add(RocksDbWeight::get().writes(8_u64))
-			.saturating_add(Weight::from_parts(0, 1).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
 	}
 	/// Storage: Lottery UnclaimedWinningsByAccount (r:1 w:1)
@@ -503,29 +910,66 @@ impl WeightInfo for () {
 	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery SumOfDeposits (r:1 w:0)
 	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
-	/// Storage: Lottery ActiveBalancePerUser (r:516 w:0)
-	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Lottery UnclaimedWinningsByAccount (r:1 w:1)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:1)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:1)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:0)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:0)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketPenalty (r:20 w:0)
+	/// Proof Skipped: Lottery TicketPenalty (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketHolder (r:1 w:0)
+	/// Proof Skipped: Lottery TicketHolder (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery PrizeTiers (r:1 w:0)
+	/// Proof Skipped: Lottery PrizeTiers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DuplicateWinners (r:1 w:0)
+	/// Proof Skipped: Lottery DuplicateWinners (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:100 w:100)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UnclaimedWinningsByAccount (r:100 w:100)
 	/// Proof Skipped: Lottery UnclaimedWinningsByAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingHistory (r:0 w:2)
+	/// Proof Skipped: Lottery DrawingHistory (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery PendingRedeposits (r:1001 w:1000)
+	/// Proof Skipped: Lottery PendingRedeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery CounterForPendingRedeposits (r:1 w:1)
+	/// Proof Skipped: Lottery CounterForPendingRedeposits (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery UnstakingCollators (r:1 w:0)
 	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:0)
 	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery MinDeposit (r:1 w:0)
 	/// Proof Skipped: Lottery MinDeposit (max_values: Some(1), max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn draw_lottery(x: u32, _y: u32, ) -> Weight {
+	/// The range of component `w` is `[1, 100]`.
+	/// The range of component `r` is `[0, 1000]`.
+	fn draw_lottery(d: u32, _y: u32, w: u32, r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `1665 + x * (30 ±0)`
-		//  Estimated: `259887 + x * (1112 ±0)`
+		//  Measured:  `1665 + d * (30 ±0) + w * (64 ±0) + r * (48 ±0)`
+		//  Estimated: `259887 + d * (7569 ±0) + w * (2603 ±0) + r * (2624 ±0)`
 		// Minimum execution time: 35_998_000 picoseconds.
 		Weight::from_parts(334_839_510, 259887)
 			// Standard Error: 27_525
-			.saturating_add(Weight::from_parts(1_187_025, 0).saturating_mul(x.into()))
-			.saturating_add(RocksDbWeight::get().reads(112_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
-			.saturating_add(Weight::from_parts(0, 1112).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(16_618_350, 0).saturating_mul(d.into()))
+			// Standard Error: 61_233
+			.saturating_add(Weight::from_parts(4_918_412, 0).saturating_mul(w.into()))
+			// Standard Error: 54_107
+			.saturating_add(Weight::from_parts(9_846_231, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(116_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(w.into())))
+			.saturating_add(RocksDbWeight::get().reads((9_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(w.into())))
+			.saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 7569).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(w.into()))
+			.saturating_add(Weight::from_parts(0, 2624).saturating_mul(r.into()))
 	}
 	/// Storage: Lottery UnstakingCollators (r:1 w:0)
 	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
@@ -576,4 +1020,96 @@ impl WeightInfo for () {
 		Weight::from_parts(1_233_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: Lottery PrizeTiers (r:0 w:1)
+	/// Proof Skipped: Lottery PrizeTiers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DuplicateWinners (r:0 w:1)
+	/// Proof Skipped: Lottery DuplicateWinners (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `t` is `[0, 100]`.
+	fn set_prize_tiers(t: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_873_000 picoseconds.
+		Weight::from_parts(2_114_506, 0)
+			// Standard Error: 212
+			.saturating_add(Weight::from_parts(9_347, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery SelectedDepositStrategy (r:0 w:1)
+	/// Proof Skipped: Lottery SelectedDepositStrategy (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SelectedWithdrawStrategy (r:0 w:1)
+	/// Proof Skipped: Lottery SelectedWithdrawStrategy (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_staking_strategies() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_984_000 picoseconds.
+		Weight::from_parts(2_115_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery LiquidityBufferTarget (r:0 w:1)
+	/// Proof Skipped: Lottery LiquidityBufferTarget (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery InstantWithdrawFee (r:0 w:1)
+	/// Proof Skipped: Lottery InstantWithdrawFee (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_liquidity_buffer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_962_000 picoseconds.
+		Weight::from_parts(2_103_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery MinWithdraw (r:1 w:0)
+	/// Proof Skipped: Lottery MinWithdraw (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery LiquidityBuffer (r:1 w:1)
+	/// Proof Skipped: Lottery LiquidityBuffer (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
+	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:20)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalPot (r:1 w:1)
+	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUsers (r:1 w:1)
+	/// Proof Skipped: Lottery TotalUsers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SumOfDeposits (r:1 w:1)
+	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery InstantWithdrawFee (r:1 w:0)
+	/// Proof Skipped: Lottery InstantWithdrawFee (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `d` is `[1, 20]`.
+	fn instant_withdraw(d: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1874 + d * (32 ±0)`
+		//  Estimated: `7146 + d * (5046 ±0)`
+		// Minimum execution time: 61_842_000 picoseconds.
+		Weight::from_parts(66_310_574, 7146)
+			// Standard Error: 412
+			.saturating_add(Weight::from_parts(818_576, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
+	}
 }
@@ -2590,13 +2590,12 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
     {
         assert_eq!(
             crate::RuntimeCall::get_call_names("NameService").len(),
-            5,
+            19,
             "Please update new extrinsic here."
         );
         // register
         let call = crate::RuntimeCall::NameService(pallet_name_service::Call::register {
-            username: "test".as_bytes().to_vec(),
-            registrant: ALICE.clone().into(),
+            commitment: Default::default(),
         });
         let (dispatch_info, call_len) = get_call_details(&call);
         calamari_runtime_calls.push(("pallet_name_service", "register", dispatch_info, call_len));
@@ -2605,6 +2604,7 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
         let call = crate::RuntimeCall::NameService(pallet_name_service::Call::accept_register {
             username: "test".as_bytes().to_vec(),
             registrant: ALICE.clone().into(),
+            salt: [0u8; 32],
         });
         let (dispatch_info, call_len) = get_call_details(&call);
         calamari_runtime_calls.push((
@@ -2630,8 +2630,7 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
         // cancel_pending_register
         let call =
             crate::RuntimeCall::NameService(pallet_name_service::Call::cancel_pending_register {
-                username: "test".as_bytes().to_vec(),
-                registrant: ALICE.clone().into(),
+                commitment: Default::default(),
             });
         let (dispatch_info, call_len) = get_call_details(&call);
         calamari_runtime_calls.push((
@@ -2653,13 +2652,181 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
             dispatch_info,
             call_len,
         ));
+
+        // renew
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::renew {
+            username: "test".as_bytes().to_vec(),
+            periods: 1,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_name_service", "renew", dispatch_info, call_len));
+
+        // transfer_name
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::transfer_name {
+            username: "test".as_bytes().to_vec(),
+            new_owner: ALICE.clone().into(),
+            new_controller: ALICE.clone(),
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_name_service",
+            "transfer_name",
+            dispatch_info,
+            call_len,
+        ));
+
+        // accept_transfer
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::accept_transfer {
+            username: "test".as_bytes().to_vec(),
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_name_service",
+            "accept_transfer",
+            dispatch_info,
+            call_len,
+        ));
+
+        // list_name
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::list_name {
+            username: "test".as_bytes().to_vec(),
+            price: 1_000_000_000_000,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_name_service", "list_name", dispatch_info, call_len));
+
+        // unlist_name
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::unlist_name {
+            username: "test".as_bytes().to_vec(),
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_name_service",
+            "unlist_name",
+            dispatch_info,
+            call_len,
+        ));
+
+        // buy_name
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::buy_name {
+            username: "test".as_bytes().to_vec(),
+            new_owner: ALICE.clone().into(),
+            max_price: 1_000_000_000_000,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_name_service", "buy_name", dispatch_info, call_len));
+
+        // set_records
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::set_records {
+            username: "test".as_bytes().to_vec(),
+            records: pallet_name_service::Records {
+                account: Some(ALICE.clone()),
+                evm_address: Some([1u8; 20]),
+                chain_addresses: vec![(60, vec![1u8; 20])],
+                texts: vec![(b"url".to_vec(), b"https://manta.network".to_vec())],
+            },
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_name_service",
+            "set_records",
+            dispatch_info,
+            call_len,
+        ));
+
+        // clear_records
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::clear_records {
+            username: "test".as_bytes().to_vec(),
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_name_service",
+            "clear_records",
+            dispatch_info,
+            call_len,
+        ));
+
+        // create_subname
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::create_subname {
+            parent: "test".as_bytes().to_vec(),
+            label: "alice".as_bytes().to_vec(),
+            owner: ALICE.clone().into(),
+            controller: ALICE.clone(),
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_name_service",
+            "create_subname",
+            dispatch_info,
+            call_len,
+        ));
+
+        // revoke_subname
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::revoke_subname {
+            username: "alice.test".as_bytes().to_vec(),
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_name_service",
+            "revoke_subname",
+            dispatch_info,
+            call_len,
+        ));
+
+        // set_subname_price
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::set_subname_price {
+            parent: "test".as_bytes().to_vec(),
+            price: Some(1_000_000_000_000),
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_name_service",
+            "set_subname_price",
+            dispatch_info,
+            call_len,
+        ));
+
+        // buy_subname
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::buy_subname {
+            parent: "test".as_bytes().to_vec(),
+            label: "alice".as_bytes().to_vec(),
+            owner: ALICE.clone().into(),
+            max_price: 1_000_000_000_000,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_name_service",
+            "buy_subname",
+            dispatch_info,
+            call_len,
+        ));
+
+        // sweep_fees
+        let call = crate::RuntimeCall::NameService(pallet_name_service::Call::sweep_fees {});
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_name_service", "sweep_fees", dispatch_info, call_len));
+
+        // assign_legacy_controller
+        let call =
+            crate::RuntimeCall::NameService(pallet_name_service::Call::assign_legacy_controller {
+                controllers: vec![("test".as_bytes().to_vec(), ALICE.clone())]
+                    .try_into()
+                    .unwrap(),
+            });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_name_service",
+            "assign_legacy_controller",
+            dispatch_info,
+            call_len,
+        ));
     }
 
     // pallet_farming
     {
         assert_eq!(
             crate::RuntimeCall::get_call_names("Farming").len(),
-            14,
+            27,
             "Please update new extrinsic here."
         );
         // create_farming_pool
@@ -2675,6 +2842,8 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
             withdraw_limit_time: 200,
             claim_limit_time: 300,
             withdraw_limit_count: 6,
+            end_block: None,
+            emission_curve: pallet_farming::EmissionCurve::Constant,
         });
         let (dispatch_info, call_len) = get_call_details(&call);
         calamari_runtime_calls.push((
@@ -2750,6 +2919,7 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
             claim_limit_time: Some(300),
             withdraw_limit_count: Some(6),
             gauge_init: None,
+            emission_init: None,
         });
         let (dispatch_info, call_len) = get_call_details(&call);
         calamari_runtime_calls.push(("pallet_farming", "reset_pool", dispatch_info, call_len));
@@ -2785,13 +2955,175 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
             dispatch_info,
             call_len,
         ));
+
+        // refund_charge
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::refund_charge { pool_id: 1 });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming", "refund_charge", dispatch_info, call_len));
+
+        // add_incentive
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::add_incentive {
+            pool_id: 1,
+            reward_currency: 1,
+            reward_per_block: 100,
+            start_block: 100,
+            end_block: 200,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming", "add_incentive", dispatch_info, call_len));
+
+        // withdraw_incentive
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::withdraw_incentive {
+            pool_id: 1,
+            incentive_id: 1,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_farming",
+            "withdraw_incentive",
+            dispatch_info,
+            call_len,
+        ));
+
+        // ve_lock
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::ve_lock {
+            amount: 1000,
+            unlock_block: 100,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming", "ve_lock", dispatch_info, call_len));
+
+        // ve_unlock
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::ve_unlock {});
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming", "ve_unlock", dispatch_info, call_len));
+
+        // update_boost
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::update_boost {
+            pool_id: 1,
+            who: ALICE,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming", "update_boost", dispatch_info, call_len));
+
+        // vote
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::vote {
+            votes: vec![(1, Perbill::from_percent(50))],
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming", "vote", dispatch_info, call_len));
+
+        // set_vote_emission
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::set_vote_emission {
+            rewards: vec![(1, 1000)],
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_farming",
+            "set_vote_emission",
+            dispatch_info,
+            call_len,
+        ));
+
+        // set_vesting
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::set_vesting {
+            pool_id: 1,
+            vesting_blocks: Some(100),
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming", "set_vesting", dispatch_info, call_len));
+
+        // claim_vested
+        let call =
+            crate::RuntimeCall::Farming(pallet_farming::Call::claim_vested { reward_currency: 1 });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming", "claim_vested", dispatch_info, call_len));
+
+        // set_emergency_withdraw
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::set_emergency_withdraw {
+            pool_id: 1,
+            enabled: true,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_farming",
+            "set_emergency_withdraw",
+            dispatch_info,
+            call_len,
+        ));
+
+        // emergency_withdraw
+        let call =
+            crate::RuntimeCall::Farming(pallet_farming::Call::emergency_withdraw { pool_id: 1 });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_farming",
+            "emergency_withdraw",
+            dispatch_info,
+            call_len,
+        ));
+
+        // set_charge_allowlist
+        let call = crate::RuntimeCall::Farming(pallet_farming::Call::set_charge_allowlist {
+            pool_id: 1,
+            allowlist: Some(vec![ALICE.clone()]),
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_farming",
+            "set_charge_allowlist",
+            dispatch_info,
+            call_len,
+        ));
+    }
+
+    // pallet_farming_vault
+    {
+        assert_eq!(
+            crate::RuntimeCall::get_call_names("FarmingVault").len(),
+            4,
+            "Please update new extrinsic here."
+        );
+        // create_vault
+        let call = crate::RuntimeCall::FarmingVault(pallet_farming_vault::Call::create_vault {
+            pool_id: 1,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push((
+            "pallet_farming_vault",
+            "create_vault",
+            dispatch_info,
+            call_len,
+        ));
+
+        // deposit
+        let call = crate::RuntimeCall::FarmingVault(pallet_farming_vault::Call::deposit {
+            vault_id: 1,
+            lp_amount: 100,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming_vault", "deposit", dispatch_info, call_len));
+
+        // withdraw
+        let call = crate::RuntimeCall::FarmingVault(pallet_farming_vault::Call::withdraw {
+            vault_id: 1,
+            shares: 100,
+        });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming_vault", "withdraw", dispatch_info, call_len));
+
+        // compound
+        let call =
+            crate::RuntimeCall::FarmingVault(pallet_farming_vault::Call::compound { vault_id: 1 });
+        let (dispatch_info, call_len) = get_call_details(&call);
+        calamari_runtime_calls.push(("pallet_farming_vault", "compound", dispatch_info, call_len));
     }
 
     // pallet_lottery
     {
         assert_eq!(
             crate::RuntimeCall::get_call_names("Lottery").len(),
-            13,
+            20,
             "Please update new extrinsic here."
         );
         t.execute_with(|| {
@@ -2929,6 +3261,91 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
                 dispatch_info,
                 call_len,
             ));
+
+            // set_prize_tiers
+            let call = crate::RuntimeCall::Lottery(pallet_lottery::Call::set_prize_tiers {
+                tiers: vec![pallet_lottery::PrizeTier {
+                    share: Percent::from_percent(100),
+                    winners: 1,
+                }],
+                duplicate_winner_policy: pallet_lottery::DuplicateWinnerPolicy::Allow,
+            });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "set_prize_tiers",
+                dispatch_info,
+                call_len,
+            ));
+
+            // set_staking_strategies
+            let call = crate::RuntimeCall::Lottery(pallet_lottery::Call::set_staking_strategies {
+                deposit_strategy: pallet_lottery::DepositStrategyKind::EvenSpreadTopApy {
+                    collators: 4,
+                },
+                withdraw_strategy: pallet_lottery::WithdrawStrategyKind::UnstakeLargestDelegations,
+            });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "set_staking_strategies",
+                dispatch_info,
+                call_len,
+            ));
+
+            // set_liquidity_buffer
+            let call = crate::RuntimeCall::Lottery(pallet_lottery::Call::set_liquidity_buffer {
+                target: 1_000,
+                fee: Percent::from_percent(1),
+            });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "set_liquidity_buffer",
+                dispatch_info,
+                call_len,
+            ));
+
+            // instant_withdraw
+            let call =
+                crate::RuntimeCall::Lottery(pallet_lottery::Call::instant_withdraw { amount: 10 });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "instant_withdraw",
+                dispatch_info,
+                call_len,
+            ));
+
+            // sponsor
+            let call = crate::RuntimeCall::Lottery(pallet_lottery::Call::sponsor { amount: 10 });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push(("pallet_lottery", "sponsor", dispatch_info, call_len));
+
+            // request_sponsor_withdraw
+            let call =
+                crate::RuntimeCall::Lottery(pallet_lottery::Call::request_sponsor_withdraw {
+                    amount: 10,
+                });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "request_sponsor_withdraw",
+                dispatch_info,
+                call_len,
+            ));
+
+            // set_auto_redeposit
+            let call = crate::RuntimeCall::Lottery(pallet_lottery::Call::set_auto_redeposit {
+                enabled: true,
+            });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "set_auto_redeposit",
+                dispatch_info,
+                call_len,
+            ));
         });
     }
 
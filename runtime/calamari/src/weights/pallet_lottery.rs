@@ -45,16 +45,22 @@ use core::marker::PhantomData;
 
 /// Weight functions needed for pallet_lottery.
 pub trait WeightInfo {
-	fn deposit(x: u32, y: u32, ) -> Weight;
-	fn request_withdraw(x: u32, y: u32, ) -> Weight;
+	fn deposit(d: u32, y: u32, ) -> Weight;
+	fn request_withdraw(d: u32, y: u32, ) -> Weight;
+	fn sponsor(y: u32, ) -> Weight;
+	fn request_sponsor_withdraw(y: u32, ) -> Weight;
 	fn claim_my_winnings(y: u32, ) -> Weight;
 	fn start_lottery() -> Weight;
 	fn stop_lottery() -> Weight;
-	fn draw_lottery(x: u32, y: u32, ) -> Weight;
+	fn draw_lottery(d: u32, y: u32, w: u32, r: u32, ) -> Weight;
 	fn process_matured_withdrawals() -> Weight;
 	fn set_min_deposit() -> Weight;
 	fn set_min_withdraw() -> Weight;
 	fn set_gas_reserve() -> Weight;
+	fn set_prize_tiers(t: u32, ) -> Weight;
+	fn set_staking_strategies() -> Weight;
+	fn set_liquidity_buffer() -> Weight;
+	fn instant_withdraw(d: u32, ) -> Weight;
 }
 
 /// Weights for pallet_lottery using the Substrate node and recommended hardware.
@@ -98,27 +104,45 @@ impl<T: frame_system::Config> pallet_lottery::WeightInfo for SubstrateWeight<T>
 	/// Proof Skipped: ParachainStaking Total (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
 	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketPenalty (r:20 w:20)
+	/// Proof Skipped: Lottery TicketPenalty (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Lottery TotalPot (r:1 w:1)
 	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery TotalUsers (r:1 w:1)
 	/// Proof Skipped: Lottery TotalUsers (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery SumOfDeposits (r:1 w:1)
 	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn deposit(x: u32, y: u32, ) -> Weight {
+	fn deposit(d: u32, y: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `3459 + x * (1 ±0) + y * (72 ±0)`
-		//  Estimated: `8716 + x * (2 ±0) + y * (79 ±0)`
+		//  Measured:  `3459 + d * (32 ±0) + y * (72 ±0)`
+		//  Estimated: `8716 + d * (5046 ±0) + y * (79 ±0)`
 		// Minimum execution time: 219_382_000 picoseconds.
 		Weight::from_parts(254_137_415, 8716)
 			// Standard Error: 1_320
-			.saturating_add(Weight::from_parts(203_207, 0).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(2_844_898, 0).saturating_mul(d.into()))
 			// Standard Error: 20_803
 			.saturating_add(Weight::from_parts(26_611, 0).saturating_mul(y.into()))
-			.saturating_add(T::DbWeight::get().reads(24_u64))
-			.saturating_add(T::DbWeight::get().writes(13_u64))
-			.saturating_add(Weight::from_parts(0, 2).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(30_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
 			.saturating_add(Weight::from_parts(0, 79).saturating_mul(y.into()))
 	}
 	/// Storage: Lottery MinWithdraw (r:1 w:0)
@@ -129,6 +153,22 @@ impl<T: frame_system::Config> pallet_lottery::WeightInfo for SubstrateWeight<T>
 	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
 	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:20)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:1)
 	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery TotalUsers (r:1 w:1)
@@ -155,19 +195,119 @@ impl<T: frame_system::Config> pallet_lottery::WeightInfo for SubstrateWeight<T>
 	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:1)
 	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn request_withdraw(x: u32, y: u32, ) -> Weight {
+	fn request_withdraw(d: u32, y: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `2216 + x * (1 ±0)`
-		//  Estimated: `7719 + x * (1 ±0) + y * (5 ±0)`
+		//  Measured:  `2216 + d * (32 ±0)`
+		//  Estimated: `7719 + d * (5046 ±0) + y * (5 ±0)`
 		// Minimum execution time: 106_439_000 picoseconds.
 		Weight::from_parts(109_754_175, 7719)
 			// Standard Error: 315
-			.saturating_add(Weight::from_parts(94_367, 0).saturating_mul(x.into()))
-			.saturating_add(T::DbWeight::get().reads(18_u64))
-			.saturating_add(T::DbWeight::get().writes(8_u64))
-			.saturating_add(Weight::from_parts(0, 1).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(1_321_138, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(24_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
+	}
+	/// Storage: Lottery MinDeposit (r:1 w:0)
+	/// Proof Skipped: Lottery MinDeposit (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking SelectedCandidates (r:1 w:0)
+	/// Proof Skipped: ParachainStaking SelectedCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnstakingCollators (r:1 w:0)
+	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery StakedCollators (r:2 w:1)
+	/// Proof Skipped: Lottery StakedCollators (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking CandidateInfo (r:1 w:1)
+	/// Proof Skipped: ParachainStaking CandidateInfo (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking DelegatorState (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegatorState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnlockedUnstakingFunds (r:1 w:0)
+	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: TransactionPayment NextFeeMultiplier (r:1 w:0)
+	/// Proof: TransactionPayment NextFeeMultiplier (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:0)
+	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: Balances Freezes (r:1 w:0)
+	/// Proof: Balances Freezes (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking TopDelegations (r:1 w:1)
+	/// Proof Skipped: ParachainStaking TopDelegations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking CandidatePool (r:1 w:1)
+	/// Proof Skipped: ParachainStaking CandidatePool (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking Total (r:1 w:1)
+	/// Proof Skipped: ParachainStaking Total (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SponsorBalancePerAccount (r:1 w:1)
+	/// Proof Skipped: Lottery SponsorBalancePerAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalSponsored (r:1 w:1)
+	/// Proof Skipped: Lottery TotalSponsored (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SumOfDeposits (r:1 w:1)
+	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `y` is `[0, 63]`.
+	fn sponsor(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3459 + y * (72 ±0)`
+		//  Estimated: `8716 + y * (79 ±0)`
+		// Minimum execution time: 186_474_000 picoseconds.
+		Weight::from_parts(216_016_802, 8716)
+			// Standard Error: 20_803
+			.saturating_add(Weight::from_parts(26_611, 0).saturating_mul(y.into()))
+			.saturating_add(T::DbWeight::get().reads(23_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+			.saturating_add(Weight::from_parts(0, 79).saturating_mul(y.into()))
+	}
+	/// Storage: Lottery MinWithdraw (r:1 w:0)
+	/// Proof Skipped: Lottery MinWithdraw (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SponsorBalancePerAccount (r:1 w:1)
+	/// Proof Skipped: Lottery SponsorBalancePerAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalSponsored (r:1 w:1)
+	/// Proof Skipped: Lottery TotalSponsored (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:1)
+	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SurplusUnstakingBalance (r:1 w:1)
+	/// Proof Skipped: Lottery SurplusUnstakingBalance (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery StakedCollators (r:2 w:0)
+	/// Proof Skipped: Lottery StakedCollators (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UnstakingCollators (r:1 w:1)
+	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking SelectedCandidates (r:1 w:0)
+	/// Proof Skipped: ParachainStaking SelectedCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: TransactionPayment NextFeeMultiplier (r:1 w:0)
+	/// Proof: TransactionPayment NextFeeMultiplier (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DelegatorState (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegatorState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnlockedUnstakingFunds (r:1 w:0)
+	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `y` is `[0, 63]`.
+	fn request_sponsor_withdraw(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2216`
+		//  Estimated: `7719 + y * (5 ±0)`
+		// Minimum execution time: 90_473_000 picoseconds.
+		Weight::from_parts(93_291_048, 7719)
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
 	}
 	/// Storage: Lottery UnclaimedWinningsByAccount (r:1 w:1)
@@ -241,29 +381,66 @@ impl<T: frame_system::Config> pallet_lottery::WeightInfo for SubstrateWeight<T>
 	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery SumOfDeposits (r:1 w:0)
 	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
-	/// Storage: Lottery ActiveBalancePerUser (r:516 w:0)
-	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Lottery UnclaimedWinningsByAccount (r:1 w:1)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:1)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:1)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:0)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:0)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketPenalty (r:20 w:0)
+	/// Proof Skipped: Lottery TicketPenalty (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketHolder (r:1 w:0)
+	/// Proof Skipped: Lottery TicketHolder (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery PrizeTiers (r:1 w:0)
+	/// Proof Skipped: Lottery PrizeTiers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DuplicateWinners (r:1 w:0)
+	/// Proof Skipped: Lottery DuplicateWinners (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:100 w:100)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UnclaimedWinningsByAccount (r:100 w:100)
 	/// Proof Skipped: Lottery UnclaimedWinningsByAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingHistory (r:0 w:2)
+	/// Proof Skipped: Lottery DrawingHistory (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery PendingRedeposits (r:1001 w:1000)
+	/// Proof Skipped: Lottery PendingRedeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery CounterForPendingRedeposits (r:1 w:1)
+	/// Proof Skipped: Lottery CounterForPendingRedeposits (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery UnstakingCollators (r:1 w:0)
 	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:0)
 	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery MinDeposit (r:1 w:0)
 	/// Proof Skipped: Lottery MinDeposit (max_values: Some(1), max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn draw_lottery(x: u32, _y: u32, ) -> Weight {
+	/// The range of component `w` is `[1, 100]`.
+	/// The range of component `r` is `[0, 1000]`.
+	fn draw_lottery(d: u32, _y: u32, w: u32, r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `1665 + x * (30 ±0)`
-		//  Estimated: `259887 + x * (1112 ±0)`
+		//  Measured:  `1665 + d * (30 ±0) + w * (64 ±0) + r * (48 ±0)`
+		//  Estimated: `259887 + d * (7569 ±0) + w * (2603 ±0) + r * (2624 ±0)`
 		// Minimum execution time: 75_132_000 picoseconds.
 		Weight::from_parts(480_159_574, 259887)
 			// Standard Error: 42_267
-			.saturating_add(Weight::from_parts(1_932_706, 0).saturating_mul(x.into()))
-			.saturating_add(T::DbWeight::get().reads(112_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
-			.saturating_add(Weight::from_parts(0, 1112).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(27_057_884, 0).saturating_mul(d.into()))
+			// Standard Error: 98_418
+			.saturating_add(Weight::from_parts(7_912_305, 0).saturating_mul(w.into()))
+			// Standard Error: 61_932
+			.saturating_add(Weight::from_parts(12_405_877, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(116_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(w.into())))
+			.saturating_add(T::DbWeight::get().reads((9_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(w.into())))
+			.saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 7569).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(w.into()))
+			.saturating_add(Weight::from_parts(0, 2624).saturating_mul(r.into()))
 	}
 	/// Storage: Lottery UnstakingCollators (r:1 w:0)
 	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
@@ -314,6 +491,98 @@ impl<T: frame_system::Config> pallet_lottery::WeightInfo for SubstrateWeight<T>
 		Weight::from_parts(3_201_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: Lottery PrizeTiers (r:0 w:1)
+	/// Proof Skipped: Lottery PrizeTiers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DuplicateWinners (r:0 w:1)
+	/// Proof Skipped: Lottery DuplicateWinners (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `t` is `[0, 100]`.
+	fn set_prize_tiers(t: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_812_000 picoseconds.
+		Weight::from_parts(4_305_112, 0)
+			// Standard Error: 212
+			.saturating_add(Weight::from_parts(18_922, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery SelectedDepositStrategy (r:0 w:1)
+	/// Proof Skipped: Lottery SelectedDepositStrategy (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SelectedWithdrawStrategy (r:0 w:1)
+	/// Proof Skipped: Lottery SelectedWithdrawStrategy (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_staking_strategies() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_984_000 picoseconds.
+		Weight::from_parts(2_115_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery LiquidityBufferTarget (r:0 w:1)
+	/// Proof Skipped: Lottery LiquidityBufferTarget (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery InstantWithdrawFee (r:0 w:1)
+	/// Proof Skipped: Lottery InstantWithdrawFee (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_liquidity_buffer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_962_000 picoseconds.
+		Weight::from_parts(2_103_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery MinWithdraw (r:1 w:0)
+	/// Proof Skipped: Lottery MinWithdraw (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery LiquidityBuffer (r:1 w:1)
+	/// Proof Skipped: Lottery LiquidityBuffer (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
+	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:20)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalPot (r:1 w:1)
+	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUsers (r:1 w:1)
+	/// Proof Skipped: Lottery TotalUsers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SumOfDeposits (r:1 w:1)
+	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery InstantWithdrawFee (r:1 w:0)
+	/// Proof Skipped: Lottery InstantWithdrawFee (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `d` is `[1, 20]`.
+	fn instant_withdraw(d: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1874 + d * (32 ±0)`
+		//  Estimated: `7146 + d * (5046 ±0)`
+		// Minimum execution time: 63_105_000 picoseconds.
+		Weight::from_parts(67_942_118, 7146)
+			// Standard Error: 436
+			.saturating_add(Weight::from_parts(848_122, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(19_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -356,27 +625,45 @@ impl WeightInfo for () {
 	/// Proof Skipped: ParachainStaking Total (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
 	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketPenalty (r:20 w:20)
+	/// Proof Skipped: Lottery TicketPenalty (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Lottery TotalPot (r:1 w:1)
 	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery TotalUsers (r:1 w:1)
 	/// Proof Skipped: Lottery TotalUsers (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery SumOfDeposits (r:1 w:1)
 	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn deposit(x: u32, y: u32, ) -> Weight {
+	fn deposit(d: u32, y: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `3459 + x * (1 ±0) + y * (72 ±0)`
-		//  Estimated: `8716 + x * (2 ±0) + y * (79 ±0)`
+		//  Measured:  `3459 + d * (32 ±0) + y * (72 ±0)`
+		//  Estimated: `8716 + d * (5046 ±0) + y * (79 ±0)`
 		// Minimum execution time: 219_382_000 picoseconds.
 		Weight::from_parts(254_137_415, 8716)
 			// Standard Error: 1_320
-			.saturating_add(Weight::from_parts(203_207, 0).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(2_844_898, 0).saturating_mul(d.into()))
 			// Standard Error: 20_803
 			.saturating_add(Weight::from_parts(26_611, 0).saturating_mul(y.into()))
-			.saturating_add(RocksDbWeight::get().reads(24_u64))
-			.saturating_add(RocksDbWeight::get().writes(13_u64))
-			.saturating_add(Weight::from_parts(0, 2).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(30_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
 			.saturating_add(Weight::from_parts(0, 79).saturating_mul(y.into()))
 	}
 	/// Storage: Lottery MinWithdraw (r:1 w:0)
@@ -387,6 +674,22 @@ impl WeightInfo for () {
 	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
 	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:20)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:1)
 	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery TotalUsers (r:1 w:1)
@@ -413,19 +716,119 @@ impl WeightInfo for () {
 	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:1)
 	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn request_withdraw(x: u32, y: u32, ) -> Weight {
+	fn request_withdraw(d: u32, y: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `2216 + x * (1 ±0)`
-		//  Estimated: `7719 + x * (1 ±0) + y * (5 ±0)`
+		//  Measured:  `2216 + d * (32 ±0)`
+		//  Estimated: `7719 + d * (5046 ±0) + y * (5 ±0)`
 		// Minimum execution time: 106_439_000 picoseconds.
 		Weight::from_parts(109_754_175, 7719)
 			// Standard Error: 315
-			.saturating_add(Weight::from_parts(94_367, 0).saturating_mul(x.into()))
-			.saturating_add(RocksDbWeight::get().reads(18_u64))
-			.saturating_add(RocksDbWeight::get().writes(8_u64))
-			.saturating_add(Weight::from_parts(0, 1).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(1_321_138, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(24_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
+	}
+	/// Storage: Lottery MinDeposit (r:1 w:0)
+	/// Proof Skipped: Lottery MinDeposit (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking SelectedCandidates (r:1 w:0)
+	/// Proof Skipped: ParachainStaking SelectedCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnstakingCollators (r:1 w:0)
+	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery StakedCollators (r:2 w:1)
+	/// Proof Skipped: Lottery StakedCollators (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking CandidateInfo (r:1 w:1)
+	/// Proof Skipped: ParachainStaking CandidateInfo (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking DelegatorState (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegatorState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnlockedUnstakingFunds (r:1 w:0)
+	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: TransactionPayment NextFeeMultiplier (r:1 w:0)
+	/// Proof: TransactionPayment NextFeeMultiplier (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:0)
+	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: Balances Freezes (r:1 w:0)
+	/// Proof: Balances Freezes (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking TopDelegations (r:1 w:1)
+	/// Proof Skipped: ParachainStaking TopDelegations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParachainStaking CandidatePool (r:1 w:1)
+	/// Proof Skipped: ParachainStaking CandidatePool (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking Total (r:1 w:1)
+	/// Proof Skipped: ParachainStaking Total (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SponsorBalancePerAccount (r:1 w:1)
+	/// Proof Skipped: Lottery SponsorBalancePerAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalSponsored (r:1 w:1)
+	/// Proof Skipped: Lottery TotalSponsored (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SumOfDeposits (r:1 w:1)
+	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `y` is `[0, 63]`.
+	fn sponsor(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3459 + y * (72 ±0)`
+		//  Estimated: `8716 + y * (79 ±0)`
+		// Minimum execution time: 186_474_000 picoseconds.
+		Weight::from_parts(216_016_802, 8716)
+			// Standard Error: 20_803
+			.saturating_add(Weight::from_parts(26_611, 0).saturating_mul(y.into()))
+			.saturating_add(RocksDbWeight::get().reads(23_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+			.saturating_add(Weight::from_parts(0, 79).saturating_mul(y.into()))
+	}
+	/// Storage: Lottery MinWithdraw (r:1 w:0)
+	/// Proof Skipped: Lottery MinWithdraw (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SponsorBalancePerAccount (r:1 w:1)
+	/// Proof Skipped: Lottery SponsorBalancePerAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalSponsored (r:1 w:1)
+	/// Proof Skipped: Lottery TotalSponsored (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:1)
+	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SurplusUnstakingBalance (r:1 w:1)
+	/// Proof Skipped: Lottery SurplusUnstakingBalance (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery StakedCollators (r:2 w:0)
+	/// Proof Skipped: Lottery StakedCollators (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UnstakingCollators (r:1 w:1)
+	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking SelectedCandidates (r:1 w:0)
+	/// Proof Skipped: ParachainStaking SelectedCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: TransactionPayment NextFeeMultiplier (r:1 w:0)
+	/// Proof: TransactionPayment NextFeeMultiplier (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DelegatorState (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegatorState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UnlockedUnstakingFunds (r:1 w:0)
+	/// Proof Skipped: Lottery UnlockedUnstakingFunds (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking DelegationScheduledRequests (r:1 w:1)
+	/// Proof Skipped: ParachainStaking DelegationScheduledRequests (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `y` is `[0, 63]`.
+	fn request_sponsor_withdraw(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2216`
+		//  Estimated: `7719 + y * (5 ±0)`
+		// Minimum execution time: 90_473_000 picoseconds.
+		Weight::from_parts(93_291_048, 7719)
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
 	}
 	/// Storage: Lottery UnclaimedWinningsByAccount (r:1 w:1)
@@ -499,29 +902,66 @@ impl WeightInfo for () {
 	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery SumOfDeposits (r:1 w:0)
 	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
-	/// Storage: Lottery ActiveBalancePerUser (r:516 w:0)
-	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Lottery UnclaimedWinningsByAccount (r:1 w:1)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:1)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:1)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:0)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:0)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketPenalty (r:20 w:0)
+	/// Proof Skipped: Lottery TicketPenalty (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketHolder (r:1 w:0)
+	/// Proof Skipped: Lottery TicketHolder (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery PrizeTiers (r:1 w:0)
+	/// Proof Skipped: Lottery PrizeTiers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DuplicateWinners (r:1 w:0)
+	/// Proof Skipped: Lottery DuplicateWinners (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:100 w:100)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UnclaimedWinningsByAccount (r:100 w:100)
 	/// Proof Skipped: Lottery UnclaimedWinningsByAccount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingHistory (r:0 w:2)
+	/// Proof Skipped: Lottery DrawingHistory (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery PendingRedeposits (r:1001 w:1000)
+	/// Proof Skipped: Lottery PendingRedeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery CounterForPendingRedeposits (r:1 w:1)
+	/// Proof Skipped: Lottery CounterForPendingRedeposits (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery UnstakingCollators (r:1 w:0)
 	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery WithdrawalRequestQueue (r:1 w:0)
 	/// Proof Skipped: Lottery WithdrawalRequestQueue (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Lottery MinDeposit (r:1 w:0)
 	/// Proof Skipped: Lottery MinDeposit (max_values: Some(1), max_size: None, mode: Measured)
-	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `d` is `[1, 20]`.
 	/// The range of component `y` is `[0, 63]`.
-	fn draw_lottery(x: u32, _y: u32, ) -> Weight {
+	/// The range of component `w` is `[1, 100]`.
+	/// The range of component `r` is `[0, 1000]`.
+	fn draw_lottery(d: u32, _y: u32, w: u32, r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `1665 + x * (30 ±0)`
-		//  Estimated: `259887 + x * (1112 ±0)`
+		//  Measured:  `1665 + d * (30 ±0) + w * (64 ±0) + r * (48 ±0)`
+		//  Estimated: `259887 + d * (7569 ±0) + w * (2603 ±0) + r * (2624 ±0)`
 		// Minimum execution time: 75_132_000 picoseconds.
 		Weight::from_parts(480_159_574, 259887)
 			// Standard Error: 42_267
-			.saturating_add(Weight::from_parts(1_932_706, 0).saturating_mul(x.into()))
-			.saturating_add(RocksDbWeight::get().reads(112_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
-			.saturating_add(Weight::from_parts(0, 1112).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(27_057_884, 0).saturating_mul(d.into()))
+			// Standard Error: 98_418
+			.saturating_add(Weight::from_parts(7_912_305, 0).saturating_mul(w.into()))
+			// Standard Error: 61_932
+			.saturating_add(Weight::from_parts(12_405_877, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(116_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(w.into())))
+			.saturating_add(RocksDbWeight::get().reads((9_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(w.into())))
+			.saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 7569).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(w.into()))
+			.saturating_add(Weight::from_parts(0, 2624).saturating_mul(r.into()))
 	}
 	/// Storage: Lottery UnstakingCollators (r:1 w:0)
 	/// Proof Skipped: Lottery UnstakingCollators (max_values: Some(1), max_size: None, mode: Measured)
@@ -572,4 +1012,96 @@ impl WeightInfo for () {
 		Weight::from_parts(3_201_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: Lottery PrizeTiers (r:0 w:1)
+	/// Proof Skipped: Lottery PrizeTiers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DuplicateWinners (r:0 w:1)
+	/// Proof Skipped: Lottery DuplicateWinners (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `t` is `[0, 100]`.
+	fn set_prize_tiers(t: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_812_000 picoseconds.
+		Weight::from_parts(4_305_112, 0)
+			// Standard Error: 212
+			.saturating_add(Weight::from_parts(18_922, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery SelectedDepositStrategy (r:0 w:1)
+	/// Proof Skipped: Lottery SelectedDepositStrategy (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SelectedWithdrawStrategy (r:0 w:1)
+	/// Proof Skipped: Lottery SelectedWithdrawStrategy (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_staking_strategies() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_984_000 picoseconds.
+		Weight::from_parts(2_115_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery LiquidityBufferTarget (r:0 w:1)
+	/// Proof Skipped: Lottery LiquidityBufferTarget (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery InstantWithdrawFee (r:0 w:1)
+	/// Proof Skipped: Lottery InstantWithdrawFee (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_liquidity_buffer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_962_000 picoseconds.
+		Weight::from_parts(2_103_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Lottery MinWithdraw (r:1 w:0)
+	/// Proof Skipped: Lottery MinWithdraw (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Lottery LiquidityBuffer (r:1 w:1)
+	/// Proof Skipped: Lottery LiquidityBuffer (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery ActiveBalancePerUser (r:1 w:1)
+	/// Proof Skipped: Lottery ActiveBalancePerUser (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery FarmingParameters (r:1 w:0)
+	/// Proof Skipped: Lottery FarmingParameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketIndexOf (r:1 w:0)
+	/// Proof Skipped: Lottery TicketIndexOf (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCheckpoints (r:1 w:1)
+	/// Proof Skipped: Lottery TicketCheckpoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery UserLifetimeStats (r:1 w:1)
+	/// Proof Skipped: Lottery UserLifetimeStats (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriod (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriod (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery DrawingPeriodStart (r:1 w:0)
+	/// Proof Skipped: Lottery DrawingPeriodStart (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCount (r:1 w:0)
+	/// Proof Skipped: Lottery TicketCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TicketTree (r:20 w:20)
+	/// Proof Skipped: Lottery TicketTree (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TicketCredit (r:20 w:20)
+	/// Proof Skipped: Lottery TicketCredit (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Lottery TotalPot (r:1 w:1)
+	/// Proof Skipped: Lottery TotalPot (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery TotalUsers (r:1 w:1)
+	/// Proof Skipped: Lottery TotalUsers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery SumOfDeposits (r:1 w:1)
+	/// Proof Skipped: Lottery SumOfDeposits (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Lottery InstantWithdrawFee (r:1 w:0)
+	/// Proof Skipped: Lottery InstantWithdrawFee (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Lottery TotalUnclaimedWinnings (r:1 w:0)
+	/// Proof Skipped: Lottery TotalUnclaimedWinnings (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `d` is `[1, 20]`.
+	fn instant_withdraw(d: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1874 + d * (32 ±0)`
+		//  Estimated: `7146 + d * (5046 ±0)`
+		// Minimum execution time: 63_105_000 picoseconds.
+		Weight::from_parts(67_942_118, 7146)
+			// Standard Error: 436
+			.saturating_add(Weight::from_parts(848_122, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 5046).saturating_mul(d.into()))
+	}
 }
\ No newline at end of file
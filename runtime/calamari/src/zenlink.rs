@@ -18,13 +18,15 @@ use super::{AssetManager, Balances, ParachainInfo, Runtime, RuntimeEvent, Zenlin
 use crate::assets_config::CalamariConcreteFungibleLedger;
 use frame_support::{parameter_types, traits::ExistenceRequirement, PalletId};
 use manta_primitives::{
-    assets::{AssetIdLpMap, FungibleLedger},
-    types::CalamariAssetId,
+    assets::{AssetIdLpMap, AssetRegistryMetadata, AssetStorageMetadata, FungibleLedger},
+    types::{Balance, CalamariAssetId},
 };
-use sp_runtime::{traits::Zero, DispatchError};
+use pallet_farming_vault::{LiquidityPool, ShareAssetRegistry};
+use sp_runtime::{traits::Saturating, AccountId32, DispatchError};
+use sp_std::vec;
 use zenlink_protocol::{
-    AssetBalance, AssetId as ZenlinkAssetId, GenerateLpAssetId, LocalAssetHandler,
-    ZenlinkMultiAssets, LOCAL,
+    AssetBalance, AssetId as ZenlinkAssetId, ExportZenlink, GenerateLpAssetId, LocalAssetHandler,
+    ZenlinkMultiAssets, LOCAL, NATIVE,
 };
 
 // Normal Coin AMM
@@ -165,6 +167,170 @@ impl LocalAssetHandler<sp_runtime::AccountId32> for LocalAssetAdaptor {
     }
 }
 
+/// Swaps and adds liquidity for the farming vaults through Zenlink.
+pub struct ZenlinkLiquidityPool;
+
+impl ZenlinkLiquidityPool {
+    fn zenlink_asset_id(asset_id: CalamariAssetId) -> ZenlinkAssetId {
+        // Notice: Manta native asset id is 1, but Zenlink native asset id is 0.
+        if asset_id == MantaNativeAssetId::get() {
+            ZenlinkAssetId {
+                chain_id: SelfParaId::get(),
+                asset_type: NATIVE,
+                asset_index: ZenlinkNativeAssetId::get(),
+            }
+        } else {
+            ZenlinkAssetId {
+                chain_id: SelfParaId::get(),
+                asset_type: LOCAL,
+                asset_index: asset_id as u64,
+            }
+        }
+    }
+}
+
+impl LiquidityPool<AccountId32, CalamariAssetId, Balance> for ZenlinkLiquidityPool {
+    fn lp_pair(lp_token: CalamariAssetId) -> Option<(CalamariAssetId, CalamariAssetId)> {
+        AssetManager::lp_to_asset_id_pair(lp_token)
+    }
+
+    fn quote(asset_in: CalamariAssetId, asset_out: CalamariAssetId, amount_in: Balance) -> Balance {
+        ZenlinkProtocol::supply_out_amount(
+            amount_in,
+            vec![
+                Self::zenlink_asset_id(asset_in),
+                Self::zenlink_asset_id(asset_out),
+            ],
+        )
+    }
+
+    fn swap_exact_in(
+        who: &AccountId32,
+        asset_in: CalamariAssetId,
+        asset_out: CalamariAssetId,
+        amount_in: Balance,
+        amount_out_min: Balance,
+    ) -> Result<Balance, DispatchError> {
+        let balance = <CalamariConcreteFungibleLedger as FungibleLedger>::balance(asset_out, who);
+        <ZenlinkProtocol as ExportZenlink<AccountId32, ZenlinkAssetId>>::inner_swap_exact_assets_for_assets(
+            who,
+            amount_in,
+            amount_out_min,
+            &[
+                Self::zenlink_asset_id(asset_in),
+                Self::zenlink_asset_id(asset_out),
+            ],
+            who,
+        )?;
+        Ok(
+            <CalamariConcreteFungibleLedger as FungibleLedger>::balance(asset_out, who)
+                .saturating_sub(balance),
+        )
+    }
+
+    fn add_liquidity(
+        who: &AccountId32,
+        asset_0: CalamariAssetId,
+        asset_1: CalamariAssetId,
+        amount_0: Balance,
+        amount_1: Balance,
+        amount_0_min: Balance,
+        amount_1_min: Balance,
+    ) -> Result<Balance, DispatchError> {
+        let lp_token = <AssetManager as AssetIdLpMap>::lp_asset_id(&asset_0, &asset_1)
+            .ok_or(DispatchError::Other("unknown lp asset"))?;
+        let balance = <CalamariConcreteFungibleLedger as FungibleLedger>::balance(lp_token, who);
+        <ZenlinkProtocol as ExportZenlink<AccountId32, ZenlinkAssetId>>::inner_add_liquidity(
+            who,
+            Self::zenlink_asset_id(asset_0),
+            Self::zenlink_asset_id(asset_1),
+            amount_0,
+            amount_1,
+            amount_0_min,
+            amount_1_min,
+        )?;
+        Ok(
+            <CalamariConcreteFungibleLedger as FungibleLedger>::balance(lp_token, who)
+                .saturating_sub(balance),
+        )
+    }
+}
+
+/// Registers the share asset of a farming vault based on the metadata of its LP asset.
+pub struct VaultShareAssetRegistry;
+impl ShareAssetRegistry<CalamariAssetId> for VaultShareAssetRegistry {
+    fn register_share_asset(lp_token: CalamariAssetId) -> Result<CalamariAssetId, DispatchError> {
+        let lp_metadata = AssetManager::asset_id_metadata(lp_token)
+            .ok_or(DispatchError::Other("unknown lp asset"))?;
+        let metadata = AssetRegistryMetadata {
+            metadata: AssetStorageMetadata {
+                name: [&b"Vault "[..], &lp_metadata.metadata.name].concat(),
+                symbol: [&b"v"[..], &lp_metadata.metadata.symbol].concat(),
+                decimals: lp_metadata.metadata.decimals,
+                is_frozen: false,
+            },
+            min_balance: lp_metadata.min_balance,
+            is_sufficient: lp_metadata.is_sufficient,
+        };
+        AssetManager::do_register_asset(None, &metadata)
+    }
+}
+
+/// Creates Zenlink pairs with liquidity for the farming vault benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct VaultBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_farming_vault::BenchmarkHelper<AccountId32, CalamariAssetId, Balance>
+    for VaultBenchmarkHelper
+{
+    fn create_lp_pair(who: &AccountId32, amount: Balance) -> CalamariAssetId {
+        let (metadata_0, location_0) = mock_benchmark::mock_asset("VaultAsset0", 100);
+        let (metadata_1, location_1) = mock_benchmark::mock_asset("VaultAsset1", 101);
+        let (lp_metadata, _) = mock_benchmark::mock_asset("VaultLPAsset01", 102);
+        let asset_0 = AssetManager::do_register_asset(Some(&location_0), &metadata_0)
+            .expect("the benchmark asset is new");
+        let asset_1 = AssetManager::do_register_asset(Some(&location_1), &metadata_1)
+            .expect("the benchmark asset is new");
+        // Registered right after the assets of the pair, like the mock LP generation expects
+        AssetManager::register_lp_asset(
+            super::RuntimeOrigin::root(),
+            asset_0,
+            asset_1,
+            lp_metadata,
+        )
+        .expect("both assets are registered");
+        let lp_token = <AssetManager as AssetIdLpMap>::lp_asset_id(&asset_0, &asset_1)
+            .expect("the lp asset is registered");
+
+        let zenlink_asset_0 = ZenlinkLiquidityPool::zenlink_asset_id(asset_0);
+        let zenlink_asset_1 = ZenlinkLiquidityPool::zenlink_asset_id(asset_1);
+        ZenlinkProtocol::create_pair(
+            super::RuntimeOrigin::root(),
+            zenlink_asset_0,
+            zenlink_asset_1,
+        )
+        .expect("the pair is new");
+        for asset_id in [asset_0, asset_1] {
+            <CalamariConcreteFungibleLedger as FungibleLedger>::deposit_minting(
+                asset_id, who, amount,
+            )
+            .expect("the benchmark asset is sufficient");
+        }
+        ZenlinkProtocol::add_liquidity(
+            super::RuntimeOrigin::signed(who.clone()),
+            zenlink_asset_0,
+            zenlink_asset_1,
+            amount,
+            amount,
+            0,
+            0,
+            manta_primitives::types::BlockNumber::MAX,
+        )
+        .expect("the pair is trading");
+        lp_token
+    }
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 mod mock_benchmark {
     use super::super::*;
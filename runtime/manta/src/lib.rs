@@ -30,9 +30,9 @@ use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
     create_runtime_str, generic, impl_opaque_keys,
-    traits::{AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT},
+    traits::{AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, Convert},
     transaction_validity::{TransactionSource, TransactionValidity},
-    ApplyExtrinsicResult, Perbill, Percent, Permill,
+    ApplyExtrinsicResult, FixedU128, Perbill, Percent, Permill,
 };
 
 use sp_std::{cmp::Ordering, prelude::*};
@@ -278,6 +278,7 @@ impl Contains<RuntimeCall> for MantaFilter {
             | RuntimeCall::TransactionPause(_)
             | RuntimeCall::ZenlinkProtocol(_)
             | RuntimeCall::Farming(_)
+            | RuntimeCall::FarmingVault(_)
             | RuntimeCall::PolkadotXcm(pallet_xcm::Call::send {..})
             | RuntimeCall::AssetManager(pallet_asset_manager::Call::update_outgoing_filtered_assets {..})
             | RuntimeCall::Utility(_) => true,
@@ -408,6 +409,8 @@ parameter_types! {
     pub DrawingFreezeout: BlockNumber = prod_or_fast!(1 * DAYS, 1 * MINUTES);
     /// Time in blocks until a collator is done unstaking
     pub UnstakeLockTime: BlockNumber = LeaveDelayRounds::get() * DefaultBlocksPerRound::get();
+    /// Identifier of the lottery, it uses the `LotteryPotId` account itself
+    pub const LotteryId: u32 = 0;
 }
 impl pallet_lottery::Config for Runtime {
     type RuntimeCall = RuntimeCall;
@@ -418,6 +421,7 @@ impl pallet_lottery::Config for Runtime {
     type ManageOrigin = EnsureRootOrMoreThanHalfCouncil;
     type PalletsOrigin = OriginCaller;
     type LotteryPot = LotteryPotId;
+    type LotteryId = LotteryId;
     type DrawingInterval = DrawingInterval;
     type DrawingFreezeout = DrawingFreezeout;
     type UnstakeLockTime = UnstakeLockTime;
@@ -863,6 +867,9 @@ parameter_types! {
     pub const FarmingKeeperPalletId: PalletId = PalletId(*b"mt/fmkpr");
     pub const FarmingRewardIssuerPalletId: PalletId = PalletId(*b"mt/fmrir");
     pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
+    pub const FarmingMaxLockBlocks: BlockNumber = 4 * 365 * DAYS;
+    pub FarmingMaxBoost: FixedU128 = FixedU128::from_rational(5, 2);
+    pub const FarmingEpochLength: BlockNumber = 7 * DAYS;
 }
 
 /// Zenlink protocol Asset adaptor for orml_traits::MultiCurrency.
@@ -879,20 +886,94 @@ impl pallet_farming::Config for Runtime {
     type TreasuryAccount = TreasuryAccount;
     type Keeper = FarmingKeeperPalletId;
     type RewardIssuer = FarmingRewardIssuerPalletId;
+    type MaxIncentives = ConstU32<10>;
+    type VoteEscrowCurrency = Balances;
+    type MaxLockBlocks = FarmingMaxLockBlocks;
+    type MaxBoost = FarmingMaxBoost;
+    type EpochLength = FarmingEpochLength;
+    type MaxPoolVotes = ConstU32<10>;
+    type MaxVotedPools = ConstU32<50>;
+    type MaxAllowlist = ConstU32<20>;
+    type MaxChargeRefunds = ConstU32<10>;
+    type PoolGuard = FarmingVault;
     type WeightInfo = weights::pallet_farming::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const FarmingVaultPalletId: PalletId = PalletId(*b"mt/fmvlt");
+    pub const FarmingVaultCompoundInterval: BlockNumber = 6 * HOURS;
+    pub const FarmingVaultMaxCompoundsPerBlock: u32 = 10;
+    pub const FarmingVaultMaxPriceDeviation: Perbill = Perbill::from_percent(3);
+}
+
+impl pallet_farming_vault::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type LiquidityPool = zenlink::ZenlinkLiquidityPool;
+    type ShareAssetRegistry = zenlink::VaultShareAssetRegistry;
+    type ControlOrigin = EitherOfDiverse<
+        EnsureRoot<AccountId>,
+        pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 2, 3>,
+    >;
+    type PalletId = FarmingVaultPalletId;
+    type CompoundInterval = FarmingVaultCompoundInterval;
+    type MaxCompoundsPerBlock = FarmingVaultMaxCompoundsPerBlock;
+    type MaxPriceDeviation = FarmingVaultMaxPriceDeviation;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = zenlink::VaultBenchmarkHelper;
+    type WeightInfo = pallet_farming_vault::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
     pub const NameServicePalletId: PalletId = NAME_SERVICE_PALLET_ID;
+    pub const NameServiceCommitmentLifetime: BlockNumber = 1 * DAYS;
+    pub const NameServiceCommitmentRefund: Percent = Percent::from_percent(80);
+    pub const NameServiceRegistrationPeriod: BlockNumber = 365 * DAYS;
+    pub const NameServiceGracePeriod: BlockNumber = 30 * DAYS;
+    pub const NameServiceRecordDepositBase: Balance = deposit(1, 0);
+    pub const NameServiceRecordDepositPerByte: Balance = deposit(0, 1);
+    pub const NameServiceMaxRecords: u32 = 16;
+    pub const NameServiceMaxRecordLength: u32 = 128;
+    pub const NameServiceMaxSubnames: u32 = 100;
+    pub const NameServiceMaxLegacyControllers: u32 = 100;
+    /// Registrants of the names registered before controllers were recorded, as found in the
+    /// chain history for the upgrade. Names missing here are assigned by the council with
+    /// `assign_legacy_controller`.
+    pub NameServiceLegacyControllers: Vec<(pallet_name_service::UserName, AccountId)> = Vec::new();
+}
+
+/// Yearly name price, three and four letter names are premium.
+pub struct NameServicePrice;
+impl Convert<u32, Balance> for NameServicePrice {
+    fn convert(len: u32) -> Balance {
+        let base = 15 * MANTA;
+        match len {
+            0..=3 => 20 * base,
+            4 => 5 * base,
+            _ => base,
+        }
+    }
 }
 
 impl pallet_name_service::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type Currency = Balances;
     type PalletId = NameServicePalletId;
+    type ControlOrigin = EnsureRootOrMoreThanHalfCouncil;
+    type TreasuryAccount = TreasuryAccount;
     type RegisterWaitingPeriod = ConstU32<2>;
-    /// Register pricing around 5$ with estimated MANTA/USD
+    /// Register deposit around 5$ with estimated MANTA/USD
     type RegisterPrice = ConstU128<{ 15 * MANTA }>;
+    type CommitmentLifetime = NameServiceCommitmentLifetime;
+    type CommitmentRefund = NameServiceCommitmentRefund;
+    type RegistrationPeriod = NameServiceRegistrationPeriod;
+    type GracePeriod = NameServiceGracePeriod;
+    type NamePrice = NameServicePrice;
+    type RecordDepositBase = NameServiceRecordDepositBase;
+    type RecordDepositPerByte = NameServiceRecordDepositPerByte;
+    type MaxRecords = NameServiceMaxRecords;
+    type MaxRecordLength = NameServiceMaxRecordLength;
+    type MaxSubnames = NameServiceMaxSubnames;
+    type MaxLegacyControllers = NameServiceMaxLegacyControllers;
     type WeightInfo = weights::pallet_name_service::SubstrateWeight<Runtime>;
 }
 
@@ -985,6 +1066,7 @@ construct_runtime!(
 
         ZenlinkProtocol: zenlink_protocol::{Pallet, Call, Storage, Event<T>} = 51,
         Farming: pallet_farming::{Pallet, Call, Storage, Event<T>} = 54,
+        FarmingVault: pallet_farming_vault::{Pallet, Call, Storage, Event<T>} = 55,
 
         // Lottery
         Randomness: pallet_randomness::{Pallet, Call, Storage, Inherent} = 70,
@@ -1019,7 +1101,17 @@ pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, RuntimeCall, Si
 
 /// Types for runtime upgrading.
 /// Each type should implement trait `OnRuntimeUpgrade`.
-pub type OnRuntimeUpgradeHooks = (migrations::asset_manager::XcmV1ToV3<Runtime>,);
+pub type OnRuntimeUpgradeHooks = (
+    migrations::asset_manager::XcmV1ToV3<Runtime>,
+    pallet_lottery::migrations::BuildTicketTree<Runtime>,
+    pallet_farming::migrations::LazyRewardAccrual<Runtime>,
+    pallet_farming::migrations::EmissionSchedules<Runtime>,
+    pallet_farming::migrations::ThirdPartyIncentives<Runtime>,
+    pallet_name_service::migrations::AssignControllers<Runtime, NameServiceLegacyControllers>,
+    pallet_name_service::migrations::CommitReveal<Runtime>,
+    pallet_name_service::migrations::NameExpiries<Runtime>,
+    pallet_name_service::migrations::ReservedDeposits<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
     Runtime,
@@ -1074,6 +1166,7 @@ mod benches {
         // we disable zenlink in this release, and will fix it in next release
         // [zenlink_protocol, ZenlinkProtocol]
         [pallet_farming, Farming]
+        [pallet_farming_vault, FarmingVault]
         // Nimbus pallets
         [pallet_author_inherent, AuthorInherent]
     );
@@ -1219,16 +1312,33 @@ impl_runtime_apis! {
         }
     }
 
-    impl pallet_lottery::runtime::LotteryApi<Block> for Runtime {
-        fn not_in_drawing_freezeout(
-        ) -> bool {
-            Lottery::not_in_drawing_freezeout()
+    impl pallet_lottery::runtime::LotteryApi<Block, AccountId> for Runtime {
+        fn not_in_drawing_freezeout(lottery_id: u32) -> Option<bool> {
+            (lottery_id == LotteryId::get()).then(Lottery::not_in_drawing_freezeout)
+        }
+        fn current_prize_pool(lottery_id: u32) -> Option<u128> {
+            (lottery_id == LotteryId::get()).then(Lottery::current_prize_pool)
+        }
+        fn next_drawing_at(lottery_id: u32) -> Option<Option<u128>> {
+            (lottery_id == LotteryId::get()).then(|| Lottery::next_drawing_at().map(|x| x as u128))
+        }
+        fn effective_odds(lottery_id: u32, account: AccountId) -> Option<sp_runtime::Perquintill> {
+            (lottery_id == LotteryId::get()).then(|| Lottery::effective_odds(&account))
         }
-        fn current_prize_pool() -> u128 {
-            Lottery::current_prize_pool()
+        fn drawing_history(lottery_id: u32) -> Option<Vec<pallet_lottery::DrawingRecord<AccountId, Balance, BlockNumber>>> {
+            (lottery_id == LotteryId::get()).then(Lottery::drawing_history)
+        }
+        fn user_stats(lottery_id: u32, account: AccountId) -> Option<pallet_lottery::UserStats<Balance>> {
+            (lottery_id == LotteryId::get()).then(|| Lottery::user_stats(&account))
+        }
+    }
+
+    impl pallet_name_service::runtime::NameServiceApi<Block, AccountId> for Runtime {
+        fn resolve(username: pallet_name_service::UserName) -> Option<pallet_name_service::Records<AccountId>> {
+            NameService::resolve(&username)
         }
-        fn next_drawing_at() -> Option<u128> {
-            Lottery::next_drawing_at().map(|x| x as u128)
+        fn reverse(owner: pallet_name_service::ZkAddressType) -> Option<pallet_name_service::UserName> {
+            NameService::reverse(&owner)
         }
     }
 
@@ -1353,6 +1463,7 @@ impl_runtime_apis! {
         }
     }
 
+    #[api_version(2)]
     impl pallet_farming_rpc_runtime_api::FarmingRuntimeApi<Block, AccountId, MantaAssetId, PoolId> for Runtime {
         fn get_farming_rewards(who: AccountId, pid: PoolId) -> Vec<(MantaAssetId, Balance)> {
             Farming::get_farming_rewards(&who, pid).unwrap_or_default()
@@ -1361,6 +1472,22 @@ impl_runtime_apis! {
         fn get_gauge_rewards(who: AccountId, pid: PoolId) -> Vec<(MantaAssetId, Balance)> {
             Farming::get_gauge_rewards(&who, pid).unwrap_or_default()
         }
+
+        fn get_vesting_rewards(who: AccountId) -> Vec<(MantaAssetId, Balance, Balance)> {
+            Farming::get_vesting_rewards(&who)
+        }
+
+        fn get_pools() -> Vec<pallet_farming::PoolData<Balance, MantaAssetId, BlockNumber>> {
+            Farming::get_pools()
+        }
+
+        fn get_positions(who: AccountId) -> Vec<pallet_farming::PositionData<Balance, MantaAssetId, BlockNumber>> {
+            Farming::get_positions(&who)
+        }
+
+        fn get_pool_apr(pid: PoolId, prices: Vec<(MantaAssetId, FixedU128)>) -> Option<FixedU128> {
+            Farming::get_pool_apr(pid, prices, 365 * DAYS)
+        }
     }
 
     #[cfg(feature = "try-runtime")]
@@ -49,7 +49,21 @@ pub trait WeightInfo {
 	fn accept_register() -> Weight;
 	fn set_primary_name() -> Weight;
 	fn cancel_pending_register() -> Weight;
-	fn remove_register() -> Weight;
+	fn remove_register(s: u32, ) -> Weight;
+	fn renew() -> Weight;
+	fn transfer_name() -> Weight;
+	fn accept_transfer() -> Weight;
+	fn list_name() -> Weight;
+	fn unlist_name() -> Weight;
+	fn buy_name() -> Weight;
+	fn set_records() -> Weight;
+	fn clear_records() -> Weight;
+	fn create_subname() -> Weight;
+	fn revoke_subname() -> Weight;
+	fn set_subname_price() -> Weight;
+	fn buy_subname() -> Weight;
+	fn sweep_fees() -> Weight;
+	fn assign_legacy_controller(n: u32, ) -> Weight;
 }
 
 /// Weights for pallet_name_service using the Substrate node and recommended hardware.
@@ -74,6 +88,8 @@ impl<T: frame_system::Config> pallet_name_service::WeightInfo for SubstrateWeigh
 	/// Proof Skipped: NameService PendingRegister (max_values: None, max_size: None, mode: Measured)
 	/// Storage: NameService UsernameRecords (r:0 w:1)
 	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Deposits (r:0 w:1)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
 	fn accept_register() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `145`
@@ -81,7 +97,7 @@ impl<T: frame_system::Config> pallet_name_service::WeightInfo for SubstrateWeigh
 		// Minimum execution time: 20_132_000 picoseconds.
 		Weight::from_parts(20_461_000, 3610)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	/// Storage: NameService UsernameRecords (r:1 w:0)
 	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
@@ -111,15 +127,290 @@ impl<T: frame_system::Config> pallet_name_service::WeightInfo for SubstrateWeigh
 	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
 	/// Storage: NameService PrimaryRecords (r:1 w:0)
 	/// Proof Skipped: NameService PrimaryRecords (max_values: None, max_size: None, mode: Measured)
-	fn remove_register() -> Weight {
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Subnames (r:1 w:1)
+	/// Proof Skipped: NameService Subnames (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService SubnamePrices (r:0 w:1)
+	/// Proof Skipped: NameService SubnamePrices (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Deposits (r:1 w:1)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService LegacyDeposits (r:1 w:1)
+	/// Proof Skipped: NameService LegacyDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService LegacyDepositTotal (r:1 w:1)
+	/// Proof Skipped: NameService LegacyDepositTotal (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `s` is `[0, 100]`.
+	fn remove_register(s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `111`
+		//  Measured:  `111 + s * (98 ±0)`
 		//  Estimated: `3576`
 		// Minimum execution time: 18_783_000 picoseconds.
 		Weight::from_parts(19_144_000, 3576)
+			// Standard Error: 4_716
+			.saturating_add(Weight::from_parts(9_847_311, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(s.into())))
+	}
+	/// Storage: NameService Expiries (r:1 w:1)
+	/// Proof Skipped: NameService Expiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn renew() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `3593`
+		// Minimum execution time: 58_575_000 picoseconds.
+		Weight::from_parts(59_414_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: NameService UsernameRecords (r:1 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService PrimaryRecords (r:2 w:2)
+	/// Proof Skipped: NameService PrimaryRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService PendingTransfers (r:0 w:1)
+	/// Proof Skipped: NameService PendingTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Listings (r:0 w:1)
+	/// Proof Skipped: NameService Listings (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Deposits (r:1 w:1)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
+	fn transfer_name() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `111`
+		//  Estimated: `3576`
+		// Minimum execution time: 24_715_000 picoseconds.
+		Weight::from_parts(25_480_000, 3576)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: NameService PendingTransfers (r:1 w:1)
+	/// Proof Skipped: NameService PendingTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService UsernameRecords (r:1 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:0 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService PrimaryRecords (r:2 w:2)
+	/// Proof Skipped: NameService PrimaryRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Listings (r:0 w:1)
+	/// Proof Skipped: NameService Listings (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Deposits (r:1 w:1)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
+	fn accept_transfer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `145`
+		//  Estimated: `3610`
+		// Minimum execution time: 25_724_000 picoseconds.
+		Weight::from_parts(26_520_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: NameService UsernameRecords (r:1 w:0)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Listings (r:0 w:1)
+	/// Proof Skipped: NameService Listings (max_values: None, max_size: None, mode: Measured)
+	fn list_name() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `111`
+		//  Estimated: `3576`
+		// Minimum execution time: 21_184_000 picoseconds.
+		Weight::from_parts(21_840_000, 3576)
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Listings (r:1 w:1)
+	/// Proof Skipped: NameService Listings (max_values: None, max_size: None, mode: Measured)
+	fn unlist_name() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `111`
+		//  Estimated: `3576`
+		// Minimum execution time: 20_428_000 picoseconds.
+		Weight::from_parts(21_060_000, 3576)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: NameService Listings (r:1 w:1)
+	/// Proof Skipped: NameService Listings (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService UsernameRecords (r:1 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: NameService PrimaryRecords (r:2 w:2)
+	/// Proof Skipped: NameService PrimaryRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService PendingTransfers (r:0 w:1)
+	/// Proof Skipped: NameService PendingTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Deposits (r:1 w:1)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
+	fn buy_name() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2731`
+		//  Estimated: `6196`
+		// Minimum execution time: 57_753_000 picoseconds.
+		Weight::from_parts(59_540_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: NameService UsernameRecords (r:1 w:0)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Expiries (r:1 w:0)
+	/// Proof Skipped: NameService Expiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn set_records() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1240`
+		//  Estimated: `4705`
+		// Minimum execution time: 24_516_000 picoseconds.
+		Weight::from_parts(25_300_000, 4705)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn clear_records() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1203`
+		//  Estimated: `4668`
+		// Minimum execution time: 19_870_000 picoseconds.
+		Weight::from_parts(20_500_000, 4668)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: NameService Controllers (r:1 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService UsernameRecords (r:2 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Expiries (r:1 w:0)
+	/// Proof Skipped: NameService Expiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Subnames (r:1 w:1)
+	/// Proof Skipped: NameService Subnames (max_values: None, max_size: None, mode: Measured)
+	fn create_subname() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2083`
+		//  Estimated: `5548`
+		// Minimum execution time: 14_372_000 picoseconds.
+		Weight::from_parts(14_900_000, 5548)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: NameService Controllers (r:1 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Subnames (r:1 w:1)
+	/// Proof Skipped: NameService Subnames (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService UsernameRecords (r:1 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService PrimaryRecords (r:1 w:0)
+	/// Proof Skipped: NameService PrimaryRecords (max_values: None, max_size: None, mode: Measured)
+	fn revoke_subname() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2215`
+		//  Estimated: `5680`
+		// Minimum execution time: 16_905_000 picoseconds.
+		Weight::from_parts(17_400_000, 5680)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Expiries (r:1 w:0)
+	/// Proof Skipped: NameService Expiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService SubnamePrices (r:0 w:1)
+	/// Proof Skipped: NameService SubnamePrices (max_values: None, max_size: None, mode: Measured)
+	fn set_subname_price() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `111`
+		//  Estimated: `3576`
+		// Minimum execution time: 8_021_000 picoseconds.
+		Weight::from_parts(8_300_000, 3576)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: NameService SubnamePrices (r:1 w:0)
+	/// Proof Skipped: NameService SubnamePrices (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: NameService UsernameRecords (r:2 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Expiries (r:1 w:0)
+	/// Proof Skipped: NameService Expiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Subnames (r:1 w:1)
+	/// Proof Skipped: NameService Subnames (max_values: None, max_size: None, mode: Measured)
+	fn buy_subname() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2217`
+		//  Estimated: `6196`
+		// Minimum execution time: 27_648_000 picoseconds.
+		Weight::from_parts(28_400_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: NameService LegacyDepositTotal (r:1 w:0)
+	/// Proof Skipped: NameService LegacyDepositTotal (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn sweep_fees() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `206`
+		//  Estimated: `6196`
+		// Minimum execution time: 31_466_000 picoseconds.
+		Weight::from_parts(32_100_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: NameService UsernameRecords (r:100 w:0)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:100 w:100)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService LegacyDeposits (r:100 w:100)
+	/// Proof Skipped: NameService LegacyDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService LegacyDepositTotal (r:1 w:1)
+	/// Proof Skipped: NameService LegacyDepositTotal (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:200 w:200)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: NameService Deposits (r:0 w:100)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 100]`.
+	fn assign_legacy_controller(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `248 + n * (172 ±0)`
+		//  Estimated: `3593 + n * (5206 ±0)`
+		// Minimum execution time: 41_218_000 picoseconds.
+		Weight::from_parts(5_870_000, 3593)
+			// Standard Error: 9_512
+			.saturating_add(Weight::from_parts(36_482_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5206).saturating_mul(n.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -143,6 +434,8 @@ impl WeightInfo for () {
 	/// Proof Skipped: NameService PendingRegister (max_values: None, max_size: None, mode: Measured)
 	/// Storage: NameService UsernameRecords (r:0 w:1)
 	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Deposits (r:0 w:1)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
 	fn accept_register() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `145`
@@ -150,7 +443,7 @@ impl WeightInfo for () {
 		// Minimum execution time: 20_132_000 picoseconds.
 		Weight::from_parts(20_461_000, 3610)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	/// Storage: NameService UsernameRecords (r:1 w:0)
 	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
@@ -180,13 +473,288 @@ impl WeightInfo for () {
 	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
 	/// Storage: NameService PrimaryRecords (r:1 w:0)
 	/// Proof Skipped: NameService PrimaryRecords (max_values: None, max_size: None, mode: Measured)
-	fn remove_register() -> Weight {
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Subnames (r:1 w:1)
+	/// Proof Skipped: NameService Subnames (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService SubnamePrices (r:0 w:1)
+	/// Proof Skipped: NameService SubnamePrices (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Deposits (r:1 w:1)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService LegacyDeposits (r:1 w:1)
+	/// Proof Skipped: NameService LegacyDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService LegacyDepositTotal (r:1 w:1)
+	/// Proof Skipped: NameService LegacyDepositTotal (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `s` is `[0, 100]`.
+	fn remove_register(s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `111`
+		//  Measured:  `111 + s * (98 ±0)`
 		//  Estimated: `3576`
 		// Minimum execution time: 18_783_000 picoseconds.
 		Weight::from_parts(19_144_000, 3576)
+			// Standard Error: 4_716
+			.saturating_add(Weight::from_parts(9_847_311, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(s.into())))
+	}
+	/// Storage: NameService Expiries (r:1 w:1)
+	/// Proof Skipped: NameService Expiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn renew() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `3593`
+		// Minimum execution time: 58_575_000 picoseconds.
+		Weight::from_parts(59_414_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: NameService UsernameRecords (r:1 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService PrimaryRecords (r:2 w:2)
+	/// Proof Skipped: NameService PrimaryRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService PendingTransfers (r:0 w:1)
+	/// Proof Skipped: NameService PendingTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Listings (r:0 w:1)
+	/// Proof Skipped: NameService Listings (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Deposits (r:1 w:1)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
+	fn transfer_name() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `111`
+		//  Estimated: `3576`
+		// Minimum execution time: 24_715_000 picoseconds.
+		Weight::from_parts(25_480_000, 3576)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: NameService PendingTransfers (r:1 w:1)
+	/// Proof Skipped: NameService PendingTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService UsernameRecords (r:1 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:0 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService PrimaryRecords (r:2 w:2)
+	/// Proof Skipped: NameService PrimaryRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Listings (r:0 w:1)
+	/// Proof Skipped: NameService Listings (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Deposits (r:1 w:1)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
+	fn accept_transfer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `145`
+		//  Estimated: `3610`
+		// Minimum execution time: 25_724_000 picoseconds.
+		Weight::from_parts(26_520_000, 3610)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: NameService UsernameRecords (r:1 w:0)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Listings (r:0 w:1)
+	/// Proof Skipped: NameService Listings (max_values: None, max_size: None, mode: Measured)
+	fn list_name() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `111`
+		//  Estimated: `3576`
+		// Minimum execution time: 21_184_000 picoseconds.
+		Weight::from_parts(21_840_000, 3576)
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Listings (r:1 w:1)
+	/// Proof Skipped: NameService Listings (max_values: None, max_size: None, mode: Measured)
+	fn unlist_name() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `111`
+		//  Estimated: `3576`
+		// Minimum execution time: 20_428_000 picoseconds.
+		Weight::from_parts(21_060_000, 3576)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: NameService Listings (r:1 w:1)
+	/// Proof Skipped: NameService Listings (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService UsernameRecords (r:1 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: NameService PrimaryRecords (r:2 w:2)
+	/// Proof Skipped: NameService PrimaryRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService PendingTransfers (r:0 w:1)
+	/// Proof Skipped: NameService PendingTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Deposits (r:1 w:1)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
+	fn buy_name() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2731`
+		//  Estimated: `6196`
+		// Minimum execution time: 57_753_000 picoseconds.
+		Weight::from_parts(59_540_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: NameService UsernameRecords (r:1 w:0)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Expiries (r:1 w:0)
+	/// Proof Skipped: NameService Expiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn set_records() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1240`
+		//  Estimated: `4705`
+		// Minimum execution time: 24_516_000 picoseconds.
+		Weight::from_parts(25_300_000, 4705)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn clear_records() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1203`
+		//  Estimated: `4668`
+		// Minimum execution time: 19_870_000 picoseconds.
+		Weight::from_parts(20_500_000, 4668)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: NameService Controllers (r:1 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService UsernameRecords (r:2 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Expiries (r:1 w:0)
+	/// Proof Skipped: NameService Expiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Subnames (r:1 w:1)
+	/// Proof Skipped: NameService Subnames (max_values: None, max_size: None, mode: Measured)
+	fn create_subname() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2083`
+		//  Estimated: `5548`
+		// Minimum execution time: 14_372_000 picoseconds.
+		Weight::from_parts(14_900_000, 5548)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: NameService Controllers (r:1 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Subnames (r:1 w:1)
+	/// Proof Skipped: NameService Subnames (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService UsernameRecords (r:1 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Resolvers (r:1 w:1)
+	/// Proof Skipped: NameService Resolvers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService PrimaryRecords (r:1 w:0)
+	/// Proof Skipped: NameService PrimaryRecords (max_values: None, max_size: None, mode: Measured)
+	fn revoke_subname() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2215`
+		//  Estimated: `5680`
+		// Minimum execution time: 16_905_000 picoseconds.
+		Weight::from_parts(17_400_000, 5680)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: NameService Controllers (r:1 w:0)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Expiries (r:1 w:0)
+	/// Proof Skipped: NameService Expiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService SubnamePrices (r:0 w:1)
+	/// Proof Skipped: NameService SubnamePrices (max_values: None, max_size: None, mode: Measured)
+	fn set_subname_price() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `111`
+		//  Estimated: `3576`
+		// Minimum execution time: 8_021_000 picoseconds.
+		Weight::from_parts(8_300_000, 3576)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: NameService SubnamePrices (r:1 w:0)
+	/// Proof Skipped: NameService SubnamePrices (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:1 w:1)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: NameService UsernameRecords (r:2 w:1)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Expiries (r:1 w:0)
+	/// Proof Skipped: NameService Expiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Subnames (r:1 w:1)
+	/// Proof Skipped: NameService Subnames (max_values: None, max_size: None, mode: Measured)
+	fn buy_subname() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2217`
+		//  Estimated: `6196`
+		// Minimum execution time: 27_648_000 picoseconds.
+		Weight::from_parts(28_400_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: NameService LegacyDepositTotal (r:1 w:0)
+	/// Proof Skipped: NameService LegacyDepositTotal (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn sweep_fees() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `206`
+		//  Estimated: `6196`
+		// Minimum execution time: 31_466_000 picoseconds.
+		Weight::from_parts(32_100_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: NameService UsernameRecords (r:100 w:0)
+	/// Proof Skipped: NameService UsernameRecords (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService Controllers (r:100 w:100)
+	/// Proof Skipped: NameService Controllers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService LegacyDeposits (r:100 w:100)
+	/// Proof Skipped: NameService LegacyDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: NameService LegacyDepositTotal (r:1 w:1)
+	/// Proof Skipped: NameService LegacyDepositTotal (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:200 w:200)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: NameService Deposits (r:0 w:100)
+	/// Proof Skipped: NameService Deposits (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 100]`.
+	fn assign_legacy_controller(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `248 + n * (172 ±0)`
+		//  Estimated: `3593 + n * (5206 ±0)`
+		// Minimum execution time: 41_218_000 picoseconds.
+		Weight::from_parts(5_870_000, 3593)
+			// Standard Error: 9_512
+			.saturating_add(Weight::from_parts(36_482_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5206).saturating_mul(n.into()))
+	}
 }